@@ -0,0 +1,361 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::common::rand::RngSeed;
+use crate::common::results::{Outcome, Summarize};
+use crate::common::search::Searchable;
+
+/// Playouts longer than this are given up on and counted as unfinished
+pub const MAX_PLAYOUT_LENGTH: usize = 10_000;
+
+/// How often a player won the playouts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WinProbability<P> {
+    pub player: P,
+    pub wins: u32,
+    pub draws: u32,
+    /// The share of the finished playouts the player won
+    pub probability: f64,
+    /// The 95% confidence interval of the probability, from the Wilson score interval
+    pub low: f64,
+    pub high: f64,
+}
+
+/// The results of running playouts from a position, see
+/// [`estimate_win_probability`](fn@estimate_win_probability)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WinEstimate<P> {
+    pub playouts: u32,
+    /// Playouts that hit [`MAX_PLAYOUT_LENGTH`](constant@MAX_PLAYOUT_LENGTH) without finishing,
+    /// they aren't counted in the probabilities
+    pub unfinished: u32,
+    /// Every player in the finished playouts, in the order the game lists them
+    pub players: Vec<WinProbability<P>>,
+}
+
+impl<P: PartialEq> WinEstimate<P> {
+    /// The estimate for a player, `None` if they weren't in any finished playouts
+    pub fn player(&self, player: &P) -> Option<&WinProbability<P>> {
+        self.players
+            .iter()
+            .find(|estimate| &estimate.player == player)
+    }
+}
+
+/// Estimates each player's chances of winning by playing the game out from the position with
+/// uniformly random moves, for evaluation bars and the like. The same seed always gives the same
+/// estimate
+/// ```
+/// use lib_table_top::common::ai::estimate_win_probability;
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// // P1 has two in a row and it's their turn
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col2, Row2))).unwrap();
+///
+/// let estimate = estimate_win_probability(&game, 1000, RngSeed([0; 32]));
+/// let p1 = estimate.player(&P1).unwrap();
+/// let p2 = estimate.player(&P2).unwrap();
+///
+/// assert_eq!(estimate.playouts, 1000);
+/// assert!(p1.probability > p2.probability);
+/// assert!(p1.low < p1.probability && p1.probability < p1.high);
+/// ```
+pub fn estimate_win_probability<G>(game: &G, playouts: u32, seed: RngSeed) -> WinEstimate<G::Player>
+where
+    G: Searchable + Summarize,
+    G::Player: Clone + PartialEq,
+{
+    estimate_win_probability_with(game, playouts, seed, |_game, actions, rng| {
+        actions
+            .choose(rng)
+            .cloned()
+            .expect("Games in progress have legal actions")
+    })
+}
+
+/// [`estimate_win_probability`](fn@estimate_win_probability) with a policy that picks the moves
+/// in the playouts, given the game, its legal actions, and a random number generator. Stronger
+/// policies give estimates closer to how the game would really go
+pub fn estimate_win_probability_with<G>(
+    game: &G,
+    playouts: u32,
+    seed: RngSeed,
+    mut policy: impl FnMut(&G, &[G::Action], &mut ChaCha20Rng) -> G::Action,
+) -> WinEstimate<G::Player>
+where
+    G: Searchable + Summarize,
+    G::Player: Clone + PartialEq,
+{
+    let mut rng = seed.into_rng();
+    let mut tallies: Vec<(G::Player, u32, u32)> = Vec::new();
+    let mut unfinished = 0;
+
+    for _ in 0..playouts {
+        let mut state = game.clone();
+        let mut result = state.game_result();
+
+        for _ in 0..MAX_PLAYOUT_LENGTH {
+            if result.is_some() {
+                break;
+            }
+
+            let actions = state.legal_actions();
+            if actions.is_empty() {
+                break;
+            }
+            let action = policy(&state, &actions, &mut rng);
+            state = state.play(action);
+            result = state.game_result();
+        }
+
+        let result = match result {
+            Some(result) => result,
+            None => {
+                unfinished += 1;
+                continue;
+            }
+        };
+
+        for player_result in result.players {
+            let index = match tallies
+                .iter()
+                .position(|(player, _, _)| player == &player_result.player)
+            {
+                Some(index) => index,
+                None => {
+                    tallies.push((player_result.player, 0, 0));
+                    tallies.len() - 1
+                }
+            };
+
+            match player_result.outcome {
+                Outcome::Win => tallies[index].1 += 1,
+                Outcome::Draw => tallies[index].2 += 1,
+                Outcome::Loss => {}
+            }
+        }
+    }
+
+    let finished = playouts - unfinished;
+    let players = tallies
+        .into_iter()
+        .map(|(player, wins, draws)| {
+            let (probability, low, high) = wilson_interval(wins, finished);
+
+            WinProbability {
+                player,
+                wins,
+                draws,
+                probability,
+                low,
+                high,
+            }
+        })
+        .collect();
+
+    WinEstimate {
+        playouts,
+        unfinished,
+        players,
+    }
+}
+
+/// The share of successes with its 95% Wilson score interval, which stays sensible for shares
+/// near 0 and 1 where the normal approximation doesn't
+fn wilson_interval(successes: u32, trials: u32) -> (f64, f64, f64) {
+    if trials == 0 {
+        return (0.0, 0.0, 1.0);
+    }
+
+    let z: f64 = 1.96;
+    let n = f64::from(trials);
+    let p = f64::from(successes) / n;
+    let denominator = 1.0 + z * z / n;
+    let center = (p + z * z / (2.0 * n)) / denominator;
+    let margin = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+
+    (p, (center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Bots that score the actions they're choosing between, so how they choose can be tuned
+pub trait Bot<G: Searchable> {
+    /// The legal actions with how good the bot thinks each one is for the player to move, in the
+    /// same units as [`Searchable::evaluate`](Searchable::evaluate)
+    fn evaluate_actions(&self, game: &G) -> Vec<(G::Action, i32)>;
+
+    /// The best scored action, the first one on ties, `None` if there aren't any
+    fn choose(&self, game: &G) -> Option<G::Action> {
+        self.evaluate_actions(game)
+            .into_iter()
+            .fold(
+                None,
+                |best: Option<(G::Action, i32)>, (action, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((action, score)),
+                },
+            )
+            .map(|(action, _)| action)
+    }
+}
+
+/// Scores each action by the evaluation of the position it leads to, looking no further
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Greedy;
+
+impl<G: Searchable> Bot<G> for Greedy {
+    fn evaluate_actions(&self, game: &G) -> Vec<(G::Action, i32)> {
+        game.legal_actions()
+            .into_iter()
+            .map(|action| {
+                // The evaluation is for the player to move next, which is the opponent
+                let score = -game.play(action.clone()).evaluate();
+                (action, score)
+            })
+            .collect()
+    }
+}
+
+/// Wraps a bot so it sometimes plays a worse move, for opponents that are strong but not
+/// perfect. Actions are picked with a softmax over the bot's scores, the temperature is in the
+/// same units as the scores, so with a temperature of 10 a move 10 points worse is picked about
+/// a third as often as the best one. A temperature of 0 always plays the bot's choice. The
+/// same seed always makes the same choices
+/// ```
+/// use lib_table_top::common::ai::{Bot, Greedy, NoisyPolicy};
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col2, Row2))).unwrap();
+///
+/// // A little noise never misses a win
+/// let mut noisy = NoisyPolicy::new(Greedy, RngSeed([0; 32]), 100.0);
+/// for _ in 0..20 {
+///     assert_eq!(noisy.choose(&game), Some((P1, (Col0, Row2))));
+/// }
+///
+/// // But picks between moves that look alike
+/// let mut noisy = NoisyPolicy::new(Greedy, RngSeed([0; 32]), 100.0);
+/// let choices: Vec<_> = (0..20).map(|_| noisy.choose(&GameState::new())).collect();
+/// assert!(choices.iter().any(|choice| choice != &choices[0]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct NoisyPolicy<B> {
+    bot: B,
+    temperature: f64,
+    rng: ChaCha20Rng,
+}
+
+impl<B> NoisyPolicy<B> {
+    pub fn new(bot: B, seed: RngSeed, temperature: f64) -> Self {
+        Self {
+            bot,
+            temperature: temperature.max(0.0),
+            rng: seed.into_rng(),
+        }
+    }
+
+    /// The wrapped bot
+    pub fn bot(&self) -> &B {
+        &self.bot
+    }
+
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Picks an action from the bot's scores, `None` if there aren't any
+    pub fn choose<G>(&mut self, game: &G) -> Option<G::Action>
+    where
+        G: Searchable,
+        B: Bot<G>,
+    {
+        if self.temperature == 0.0 {
+            return self.bot.choose(game);
+        }
+
+        let candidates = self.bot.evaluate_actions(game);
+        let best = candidates.iter().map(|(_, score)| *score).max()?;
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|(_, score)| (f64::from(score - best) / self.temperature).exp())
+            .collect();
+
+        let mut pick = self.rng.gen::<f64>() * weights.iter().sum::<f64>();
+        for ((action, _), weight) in candidates.iter().zip(&weights) {
+            if pick < *weight {
+                return Some(action.clone());
+            }
+            pick -= weight;
+        }
+
+        // Rounding can leave a sliver past the last weight
+        candidates.last().map(|(action, _)| action.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::marooned::GameState;
+
+    #[test]
+    fn test_the_wilson_interval() {
+        let (p, low, high) = wilson_interval(50, 100);
+        assert_eq!(p, 0.5);
+        assert!((low - 0.4038).abs() < 0.001);
+        assert!((high - 0.5962).abs() < 0.001);
+
+        let (p, low, high) = wilson_interval(0, 10);
+        assert_eq!((p, low), (0.0, 0.0));
+        assert!(high > 0.2);
+    }
+
+    #[test]
+    fn test_noise_is_reproducible() {
+        let game: GameState = Default::default();
+        let choices = |temperature| {
+            let mut noisy = NoisyPolicy::new(Greedy, RngSeed([5; 32]), temperature);
+            (0..50)
+                .map(|_| noisy.choose(&game).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(choices(3.0), choices(3.0));
+        assert!(choices(0.0)
+            .iter()
+            .all(|&action| Greedy.choose(&game) == Some(action)));
+        assert!(choices(3.0)
+            .iter()
+            .any(|&action| Greedy.choose(&game) != Some(action)));
+    }
+
+    #[test]
+    fn test_estimates_are_reproducible_and_add_up() {
+        let game: GameState = Default::default();
+        let estimate = estimate_win_probability(&game, 200, RngSeed([3; 32]));
+
+        assert_eq!(
+            estimate,
+            estimate_win_probability(&game, 200, RngSeed([3; 32]))
+        );
+        assert_eq!(estimate.unfinished, 0);
+        assert_eq!(estimate.players.len(), 2);
+
+        let total: u32 = estimate
+            .players
+            .iter()
+            .map(|player| player.wins * 2 + player.draws)
+            .sum();
+        assert_eq!(total, 400);
+    }
+}
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::common::replay::{Replay, Replayable};
+use crate::common::search::{search_with_budget, SearchResult, Searchable};
+
+/// An engine's opinion of a position, from the perspective of the player to move
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evaluation<A> {
+    pub score: i32,
+    pub depth: u32,
+    /// The action the engine would have taken, `None` if the game was already over
+    pub best_action: Option<A>,
+    /// The line of play the engine expected, starting with the best action
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub principal_variation: Vec<A>,
+}
+
+impl<A> From<SearchResult<A>> for Evaluation<A> {
+    fn from(result: SearchResult<A>) -> Self {
+        Self {
+            score: result.score,
+            depth: result.depth,
+            best_action: result.best_action,
+            principal_variation: result.principal_variation,
+        }
+    }
+}
+
+/// How likely a policy was to pick an action
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionProbability<A> {
+    pub action: A,
+    pub probability: f64,
+}
+
+/// An action from a game's history along with any analysis of the position it was taken in
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnnotatedAction<A> {
+    pub action: A,
+    #[serde(default = "Option::default", skip_serializing_if = "Option::is_none")]
+    pub evaluation: Option<Evaluation<A>>,
+    /// The probabilities a policy gave the actions available in the position
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub probabilities: Vec<ActionProbability<A>>,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+}
+
+impl<A: PartialEq> AnnotatedAction<A> {
+    /// Whether the action taken was the one the engine thought was best, `None` without an
+    /// evaluation
+    pub fn was_best(&self) -> Option<bool> {
+        let evaluation = self.evaluation.as_ref()?;
+        Some(evaluation.best_action.as_ref() == Some(&self.action))
+    }
+
+    /// The probability the policy gave the action taken, `None` if it wasn't given one
+    pub fn probability(&self) -> Option<f64> {
+        self.probabilities
+            .iter()
+            .find(|probability| probability.action == self.action)
+            .map(|probability| probability.probability)
+    }
+}
+
+/// The history of a game with room for evaluations, policy probabilities, and comments on each
+/// action, for post game review. Entry `n` is the action taken at turn `n`, and its analysis is
+/// of the position before the action was taken
+/// ```
+/// use lib_table_top::common::analysis::AnnotatedGame;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap();
+///
+/// let mut annotated = AnnotatedGame::new(&game);
+/// annotated.comment(1, "Taking the center is the only good reply");
+///
+/// assert_eq!(annotated.actions.len(), 2);
+/// assert_eq!(annotated.actions[1].action, (P2, (Col1, Row1)));
+/// assert_eq!(annotated.actions[1].comments.len(), 1);
+/// assert_eq!(annotated.actions[0].was_best(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnnotatedGame<A> {
+    pub actions: Vec<AnnotatedAction<A>>,
+}
+
+impl<A: Clone + PartialEq> AnnotatedGame<A> {
+    /// The game's history without any analysis yet
+    pub fn new<G: Replayable<Action = A>>(game: &G) -> Self {
+        let actions = game
+            .actions()
+            .into_iter()
+            .map(|action| AnnotatedAction {
+                action,
+                evaluation: None,
+                probabilities: Vec::new(),
+                comments: Vec::new(),
+            })
+            .collect();
+
+        Self { actions }
+    }
+
+    /// Adds a comment to the action at a turn, returns `false` if there's no action at that turn
+    pub fn comment(&mut self, turn: usize, comment: impl Into<String>) -> bool {
+        match self.actions.get_mut(turn) {
+            Some(action) => {
+                action.comments.push(comment.into());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the evaluation of the position at a turn, returns `false` if there's no action at
+    /// that turn
+    pub fn evaluate(&mut self, turn: usize, evaluation: Evaluation<A>) -> bool {
+        match self.actions.get_mut(turn) {
+            Some(action) => {
+                action.evaluation = Some(evaluation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the policy probabilities of the position at a turn, returns `false` if there's no
+    /// action at that turn
+    pub fn set_probabilities(
+        &mut self,
+        turn: usize,
+        probabilities: Vec<ActionProbability<A>>,
+    ) -> bool {
+        match self.actions.get_mut(turn) {
+            Some(action) => {
+                action.probabilities = probabilities;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Replays a game and searches every position in it, giving each search the budget
+/// ```
+/// use lib_table_top::common::analysis::analyze;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+/// use std::time::Duration;
+///
+/// // P1 could have won by taking the bottom left corner, but didn't
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row0))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap()
+///   .apply_action((P1, (Col2, Row2))).unwrap();
+///
+/// let analyzed = analyze(&game, Duration::from_millis(50)).unwrap();
+/// let missed_win = &analyzed.actions[4];
+///
+/// assert_eq!(missed_win.was_best(), Some(false));
+/// assert_eq!(
+///   missed_win.evaluation.as_ref().unwrap().best_action,
+///   Some((P1, (Col0, Row2)))
+/// );
+/// ```
+pub fn analyze<G, A>(game: &G, budget: Duration) -> Result<AnnotatedGame<A>, G::Error>
+where
+    G: Replayable<Action = A> + Searchable<Action = A>,
+    A: Clone + PartialEq,
+{
+    let mut annotated = AnnotatedGame::new(game);
+    let mut state = game.initial();
+
+    for entry in annotated.actions.iter_mut() {
+        entry.evaluation = Some(search_with_budget(&state, budget).into());
+        state = state.apply(entry.action.clone())?;
+    }
+
+    Ok(annotated)
+}
+
+/// An action that lost more than the threshold, scores are from the perspective of the player
+/// who took it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blunder<A> {
+    pub turn: usize,
+    pub action: A,
+    /// What the evaluator would have played instead
+    pub best_action: Option<A>,
+    /// The score of the position before the action
+    pub before: i32,
+    /// The score of the position after the action
+    pub after: i32,
+}
+
+impl<A> Blunder<A> {
+    /// How much the action cost the player who took it
+    pub fn loss(&self) -> i32 {
+        self.before - self.after
+    }
+}
+
+/// The blunders found in a game, see [`blunder_check`](fn@blunder_check)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlunderReport<A> {
+    pub threshold: i32,
+    /// The number of actions that were checked
+    pub actions_checked: usize,
+    pub blunders: Vec<Blunder<A>>,
+}
+
+/// Evaluates every position of a replay and flags the actions whose score dropped by more than
+/// the threshold for the player who took them. The evaluator scores positions for the player to
+/// move, like the search framework does, so the position after an action is scored for the
+/// opponent and negated
+/// ```
+/// use lib_table_top::common::analysis::blunder_check;
+/// use lib_table_top::common::replay::Replay;
+/// use lib_table_top::common::search::search_with_budget;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+/// use std::time::Duration;
+///
+/// // Answering a corner with an edge loses for P2, then P1 missed the win in the left col and
+/// // left P2 a win instead
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row0))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap()
+///   .apply_action((P1, (Col2, Row2))).unwrap();
+///
+/// let replay = Replay::new(&game).unwrap();
+/// let report = blunder_check(
+///   &replay,
+///   |game| search_with_budget(game, Duration::from_millis(50)).into(),
+///   100,
+/// );
+///
+/// assert_eq!(report.actions_checked, 5);
+/// let turns: Vec<usize> = report.blunders.iter().map(|blunder| blunder.turn).collect();
+/// assert_eq!(turns, vec![1, 4]);
+/// assert_eq!(report.blunders[1].best_action, Some((P1, (Col0, Row2))));
+/// assert!(report.blunders[1].loss() > 100);
+/// ```
+pub fn blunder_check<G, A>(
+    replay: &Replay<G>,
+    mut evaluator: impl FnMut(&G) -> Evaluation<A>,
+    threshold: i32,
+) -> BlunderReport<A>
+where
+    G: Replayable<Action = A>,
+    A: Clone,
+{
+    let evaluations: Vec<Evaluation<A>> = (0..=replay.len())
+        .filter_map(|turn| replay.state(turn))
+        .map(&mut evaluator)
+        .collect();
+
+    let blunders = replay
+        .actions()
+        .iter()
+        .zip(evaluations.windows(2))
+        .enumerate()
+        .map(|(turn, (action, evaluations))| Blunder {
+            turn,
+            action: action.clone(),
+            best_action: evaluations[0].best_action.clone(),
+            before: evaluations[0].score,
+            after: -evaluations[1].score,
+        })
+        .filter(|blunder| blunder.loss() > threshold)
+        .collect();
+
+    BlunderReport {
+        threshold,
+        actions_checked: replay.len(),
+        blunders,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::tic_tac_toe::{Col::*, GameState, Player::*, Row::*};
+    use serde_json::json;
+
+    #[test]
+    fn test_serializing_annotations() {
+        let game = GameState::new().apply_action((P1, (Col1, Row1))).unwrap();
+        let mut annotated = AnnotatedGame::new(&game);
+
+        assert!(annotated.set_probabilities(
+            0,
+            vec![ActionProbability {
+                action: (P1, (Col1, Row1)),
+                probability: 0.5
+            }]
+        ));
+        assert!(!annotated.comment(1, "There's no second action"));
+
+        let serialized = serde_json::to_value(&annotated).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "actions": [{
+                    "action": ["P1", [1, 1]],
+                    "probabilities": [{"action": ["P1", [1, 1]], "probability": 0.5}]
+                }]
+            })
+        );
+        assert_eq!(annotated.actions[0].probability(), Some(0.5));
+
+        let deserialized: AnnotatedGame<_> = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, annotated);
+    }
+}
@@ -0,0 +1,579 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// What a player can do when it's their turn to bet
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BetAction {
+    Fold,
+    Check,
+    /// Matches the bet, going all in if the stack is too short
+    Call,
+    /// Bets or raises so the player has this much in for the betting round
+    RaiseTo(u64),
+    /// Puts in the whole stack. It only reopens the betting if it's at least a full raise
+    AllIn,
+}
+
+#[derive(Clone, Debug, Error, Hash, PartialEq, Eq)]
+pub enum BettingError<P: Debug> {
+    #[error("{:?} isn't at the table", player)]
+    UnknownPlayer { player: P },
+    #[error("{:?} has already folded", player)]
+    PlayerFolded { player: P },
+    #[error("{:?} is all in and can't bet any more", player)]
+    PlayerAllIn { player: P },
+    #[error("{:?} can't check with {:?} to call", player, to_call)]
+    CannotCheck { player: P, to_call: u64 },
+    #[error("{:?} has nothing to call", player)]
+    NothingToCall { player: P },
+    #[error("{:?} needs {:?} but only has {:?}", player, needed, available)]
+    NotEnoughChips {
+        player: P,
+        needed: u64,
+        available: u64,
+    },
+    #[error("{:?} can't raise to {:?}, the minimum is {:?}", player, to, minimum)]
+    RaiseTooSmall { player: P, to: u64, minimum: u64 },
+    #[error(
+        "{:?} can't raise, nobody made a full raise since they last acted",
+        player
+    )]
+    RaisingNotReopened { player: P },
+    #[error("The pots can't be paid without a ranking for {:?}", players)]
+    UnrankedPlayers { players: Vec<P> },
+}
+
+use BettingError::*;
+
+impl<P: Debug + Serialize> BettingError<P> {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::common::betting::BettingError;
+    ///
+    /// assert_eq!(BettingError::NothingToCall { player: 1 }.code(), "nothing_to_call");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnknownPlayer { .. } => "unknown_player",
+            PlayerFolded { .. } => "player_folded",
+            PlayerAllIn { .. } => "player_all_in",
+            CannotCheck { .. } => "cannot_check",
+            NothingToCall { .. } => "nothing_to_call",
+            NotEnoughChips { .. } => "not_enough_chips",
+            RaiseTooSmall { .. } => "raise_too_small",
+            RaisingNotReopened { .. } => "raising_not_reopened",
+            UnrankedPlayers { .. } => "unranked_players",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            UnknownPlayer { player }
+            | PlayerFolded { player }
+            | PlayerAllIn { player }
+            | NothingToCall { player }
+            | RaisingNotReopened { player } => json!({ "player": player }),
+            CannotCheck { player, to_call } => json!({ "player": player, "to_call": to_call }),
+            NotEnoughChips {
+                player,
+                needed,
+                available,
+            } => json!({ "player": player, "needed": needed, "available": available }),
+            RaiseTooSmall {
+                player,
+                to,
+                minimum,
+            } => json!({ "player": player, "to": to, "minimum": minimum }),
+            UnrankedPlayers { players } => json!({ "players": players }),
+        }
+    }
+}
+
+/// Chips that can be won by some of the players, the main pot is first
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pot<P> {
+    pub amount: u64,
+    /// The players who haven't folded and put in enough to win it, in seat order
+    pub eligible: Vec<P>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+struct Seat<P> {
+    player: P,
+    stack: u64,
+    /// Put in during the betting round
+    round: u64,
+    /// Put in during the hand, including antes and the betting round
+    total: u64,
+    folded: bool,
+    /// The full bet the player faced when they last acted, `None` if they haven't this round
+    acted_at: Option<u64>,
+}
+
+/// Keeps the chips of a no limit betting game: blinds and antes, checking that bets and raises
+/// are allowed, building side pots when players go all in, and paying the pots out. Turn order
+/// is left to the game, and chips are never made or lost, so [`total`](PotManager::total)
+/// is the same from the first blind to the payout
+/// ```
+/// use lib_table_top::common::betting::{BetAction::*, BettingError, Pot, PotManager};
+///
+/// let table = PotManager::new(vec![("ann", 50), ("bob", 200), ("cat", 200)], 10)
+///     .post_blind("bob", 5).unwrap()
+///     .post_blind("cat", 10).unwrap();
+///
+/// assert_eq!(
+///     table.apply("ann", RaiseTo(15)),
+///     Err(BettingError::RaiseTooSmall { player: "ann", to: 15, minimum: 20 })
+/// );
+/// let table = table
+///     .apply("ann", AllIn).unwrap()
+///     .apply("bob", Call).unwrap()
+///     .apply("cat", RaiseTo(120)).unwrap()
+///     .apply("bob", Call).unwrap();
+/// assert!(table.is_round_complete());
+///
+/// assert_eq!(
+///     table.pots(),
+///     vec![
+///         Pot { amount: 150, eligible: vec!["ann", "bob", "cat"] },
+///         Pot { amount: 140, eligible: vec!["bob", "cat"] },
+///     ]
+/// );
+///
+/// // Ann has the best hand, and Bob beats Cat for the side pot
+/// let payouts = table.payouts(&[vec!["ann"], vec!["bob"], vec!["cat"]]).unwrap();
+/// assert_eq!(payouts[&"ann"], 150);
+/// assert_eq!(payouts[&"bob"], 140);
+/// assert_eq!(table.total(), 450);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PotManager<P> {
+    seats: Vec<Seat<P>>,
+    big_blind: u64,
+    /// The most anyone has put in this betting round
+    current_bet: u64,
+    /// The size of the last full bet or raise, the smallest the next raise can be
+    min_raise: u64,
+    /// The bet as of the last full bet or raise, short all ins don't change it
+    full_bet: u64,
+}
+
+impl<P: Copy + Debug + Ord> PotManager<P> {
+    /// Seats the players with their stacks, in the order they act after the blinds. The big
+    /// blind is the smallest bet
+    pub fn new(stacks: impl IntoIterator<Item = (P, u64)>, big_blind: u64) -> Self {
+        Self {
+            seats: stacks
+                .into_iter()
+                .map(|(player, stack)| Seat {
+                    player,
+                    stack,
+                    round: 0,
+                    total: 0,
+                    folded: false,
+                    acted_at: None,
+                })
+                .collect(),
+            big_blind,
+            current_bet: 0,
+            min_raise: big_blind,
+            full_bet: 0,
+        }
+    }
+
+    fn seat(&self, player: P) -> Result<usize, BettingError<P>> {
+        self.seats
+            .iter()
+            .position(|seat| seat.player == player)
+            .ok_or(UnknownPlayer { player })
+    }
+
+    fn commit(&mut self, index: usize, amount: u64) {
+        let seat = &mut self.seats[index];
+        seat.stack -= amount;
+        seat.round += amount;
+        seat.total += amount;
+    }
+
+    /// Everyone puts in an ante, or what they have if it's less. Antes go in the pot without
+    /// counting toward the bet
+    pub fn post_ante(&self, ante: u64) -> Self {
+        let mut table = self.clone();
+
+        for seat in &mut table.seats {
+            let amount = ante.min(seat.stack);
+            seat.stack -= amount;
+            seat.total += amount;
+        }
+
+        table
+    }
+
+    /// Puts in a blind, or what the player has if it's less. Posting a blind isn't acting, so
+    /// the big blind still gets to raise when everyone calls
+    pub fn post_blind(&self, player: P, amount: u64) -> Result<Self, BettingError<P>> {
+        let index = self.seat(player)?;
+        let mut table = self.clone();
+
+        table.commit(index, amount.min(self.seats[index].stack));
+        let round = table.seats[index].round;
+        table.current_bet = table.current_bet.max(round);
+        table.full_bet = table.full_bet.max(round);
+        Ok(table)
+    }
+
+    /// The chips a player needs to put in to call
+    pub fn to_call(&self, player: P) -> Result<u64, BettingError<P>> {
+        let index = self.seat(player)?;
+        Ok(self.current_bet.saturating_sub(self.seats[index].round))
+    }
+
+    /// The smallest a bet or raise can go to, unless it's all in
+    pub fn min_raise_to(&self) -> u64 {
+        self.current_bet + self.min_raise
+    }
+
+    /// The chips a player has in front of them
+    pub fn stack(&self, player: P) -> Result<u64, BettingError<P>> {
+        Ok(self.seats[self.seat(player)?].stack)
+    }
+
+    /// Every chip at the table, in the stacks and the pots
+    pub fn total(&self) -> u64 {
+        self.seats.iter().map(|seat| seat.stack + seat.total).sum()
+    }
+
+    /// Takes a player's turn to bet
+    pub fn apply(&self, player: P, action: BetAction) -> Result<Self, BettingError<P>> {
+        let index = self.seat(player)?;
+        let seat = &self.seats[index];
+
+        if seat.folded {
+            return Err(PlayerFolded { player });
+        }
+
+        if seat.stack == 0 {
+            return Err(PlayerAllIn { player });
+        }
+
+        let to_call = self.current_bet.saturating_sub(seat.round);
+        let available = seat.round + seat.stack;
+        let reopened = match seat.acted_at {
+            Some(full_bet) => full_bet < self.full_bet,
+            None => true,
+        };
+
+        let mut table = self.clone();
+
+        match action {
+            BetAction::Fold => table.seats[index].folded = true,
+            BetAction::Check if to_call > 0 => return Err(CannotCheck { player, to_call }),
+            BetAction::Check => {}
+            BetAction::Call if to_call == 0 => return Err(NothingToCall { player }),
+            BetAction::Call => table.commit(index, to_call.min(seat.stack)),
+            BetAction::RaiseTo(_) | BetAction::AllIn => {
+                let to = match action {
+                    BetAction::RaiseTo(to) => to,
+                    _ => available,
+                };
+
+                if to > available {
+                    return Err(NotEnoughChips {
+                        player,
+                        needed: to - seat.round,
+                        available: seat.stack,
+                    });
+                }
+
+                if to > self.current_bet && !reopened {
+                    return Err(RaisingNotReopened { player });
+                }
+
+                if to < self.min_raise_to() && to < available {
+                    return Err(RaiseTooSmall {
+                        player,
+                        to,
+                        minimum: self.min_raise_to(),
+                    });
+                }
+
+                table.raise_to(index, to);
+            }
+        }
+
+        table.seats[index].acted_at = Some(table.full_bet);
+        Ok(table)
+    }
+
+    fn raise_to(&mut self, index: usize, to: u64) {
+        let round = self.seats[index].round;
+        self.commit(index, to.saturating_sub(round));
+
+        if to > self.current_bet {
+            let raise = to - self.current_bet;
+            if raise >= self.min_raise {
+                self.min_raise = raise;
+                self.full_bet = to;
+            }
+            self.current_bet = to;
+        }
+    }
+
+    /// Whether everyone who can still bet has acted and matched the bet
+    pub fn is_round_complete(&self) -> bool {
+        if self.seats.iter().filter(|seat| !seat.folded).count() <= 1 {
+            return true;
+        }
+
+        let active: Vec<&Seat<P>> = self
+            .seats
+            .iter()
+            .filter(|seat| !seat.folded && seat.stack > 0)
+            .collect();
+
+        match active.as_slice() {
+            [] => true,
+            [seat] if seat.round >= self.current_bet => true,
+            _ => active
+                .iter()
+                .all(|seat| seat.acted_at.is_some() && seat.round == self.current_bet),
+        }
+    }
+
+    /// Starts the next betting round, the chips bet so far stay in the pots
+    pub fn end_round(&self) -> Self {
+        let mut table = self.clone();
+
+        for seat in &mut table.seats {
+            seat.round = 0;
+            seat.acted_at = None;
+        }
+        table.current_bet = 0;
+        table.min_raise = table.big_blind;
+        table.full_bet = 0;
+        table
+    }
+
+    /// The main pot and the side pots. Each all in caps a pot at what they put in, and a bet
+    /// nobody called is a pot only the bettor can win, so it goes back to them
+    pub fn pots(&self) -> Vec<Pot<P>> {
+        let mut levels: Vec<u64> = self
+            .seats
+            .iter()
+            .filter(|seat| !seat.folded && seat.total > 0)
+            .map(|seat| seat.total)
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut pots: Vec<Pot<P>> = Vec::new();
+        let mut previous = 0;
+
+        for (index, &level) in levels.iter().enumerate() {
+            let last = index + 1 == levels.len();
+            let amount = self
+                .seats
+                .iter()
+                .map(|seat| {
+                    let capped = if last {
+                        seat.total
+                    } else {
+                        seat.total.min(level)
+                    };
+                    capped.saturating_sub(previous)
+                })
+                .sum();
+            let eligible: Vec<P> = self
+                .seats
+                .iter()
+                .filter(|seat| !seat.folded && seat.total >= level)
+                .map(|seat| seat.player)
+                .collect();
+            previous = level;
+
+            match pots.last_mut() {
+                Some(pot) if pot.eligible == eligible => pot.amount += amount,
+                _ if amount > 0 => pots.push(Pot { amount, eligible }),
+                _ => {}
+            }
+        }
+
+        pots
+    }
+
+    /// What each player wins, given the players still in from best hand to worst with ties
+    /// grouped together. Split pots go evenly to the tied players and odd chips go to them in
+    /// seat order. A pot with one player left needs no ranking
+    pub fn payouts(&self, ranking: &[Vec<P>]) -> Result<BTreeMap<P, u64>, BettingError<P>> {
+        let mut payouts = BTreeMap::new();
+
+        for pot in self.pots() {
+            let winners: Vec<P> = match pot.eligible.as_slice() {
+                [player] => vec![*player],
+                eligible => ranking
+                    .iter()
+                    .map(|group| {
+                        eligible
+                            .iter()
+                            .copied()
+                            .filter(|player| group.contains(player))
+                            .collect::<Vec<P>>()
+                    })
+                    .find(|winners| !winners.is_empty())
+                    .ok_or_else(|| UnrankedPlayers {
+                        players: eligible.to_vec(),
+                    })?,
+            };
+
+            let share = pot.amount / winners.len() as u64;
+            let odd = pot.amount as usize % winners.len();
+            for (index, &winner) in winners.iter().enumerate() {
+                let extra = if index < odd { 1 } else { 0 };
+                *payouts.entry(winner).or_insert(0) += share + extra;
+            }
+        }
+
+        Ok(payouts)
+    }
+
+    /// Pays out the pots and clears the hand, ready for the next blinds
+    pub fn settle(&self, ranking: &[Vec<P>]) -> Result<Self, BettingError<P>> {
+        let payouts = self.payouts(ranking)?;
+        let mut table = self.end_round();
+
+        for seat in &mut table.seats {
+            seat.stack += payouts.get(&seat.player).copied().unwrap_or(0);
+            seat.total = 0;
+            seat.folded = false;
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BetAction::*;
+
+    fn table() -> PotManager<u8> {
+        PotManager::new(vec![(1, 100), (2, 100), (3, 100), (4, 30)], 10)
+            .post_ante(1)
+            .post_blind(1, 5)
+            .unwrap()
+            .post_blind(2, 10)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_short_all_ins_dont_reopen_the_betting() {
+        let table = table()
+            .apply(3, RaiseTo(20))
+            .unwrap()
+            .apply(4, AllIn)
+            .unwrap();
+        assert_eq!(table.min_raise_to(), 39);
+
+        let table = table.apply(1, Fold).unwrap().apply(2, Call).unwrap();
+        assert_eq!(
+            table.apply(3, RaiseTo(60)),
+            Err(RaisingNotReopened { player: 3 })
+        );
+        assert!(!table.is_round_complete());
+
+        let table = table.apply(3, Call).unwrap();
+        assert!(table.is_round_complete());
+        assert_eq!(table.apply(1, Check), Err(PlayerFolded { player: 1 }));
+        assert_eq!(table.apply(4, Call), Err(PlayerAllIn { player: 4 }));
+
+        let table = table.end_round();
+        assert_eq!(table.apply(2, Call), Err(NothingToCall { player: 2 }));
+        let table = table.apply(2, RaiseTo(10)).unwrap();
+        assert_eq!(
+            table.apply(3, Check),
+            Err(CannotCheck {
+                player: 3,
+                to_call: 10
+            })
+        );
+        assert_eq!(
+            table.apply(3, RaiseTo(200)),
+            Err(NotEnoughChips {
+                player: 3,
+                needed: 200,
+                available: 70
+            })
+        );
+    }
+
+    #[test]
+    fn test_side_pots_pay_the_best_eligible_hands() {
+        let table = table()
+            .apply(3, RaiseTo(20))
+            .unwrap()
+            .apply(4, AllIn)
+            .unwrap()
+            .apply(1, Fold)
+            .unwrap()
+            .apply(2, Call)
+            .unwrap()
+            .apply(3, Call)
+            .unwrap()
+            .end_round()
+            .apply(2, RaiseTo(50))
+            .unwrap()
+            .apply(3, AllIn)
+            .unwrap()
+            .apply(2, Fold)
+            .unwrap();
+
+        assert_eq!(
+            table.pots(),
+            vec![
+                Pot {
+                    amount: 6 + 3 * 30,
+                    eligible: vec![3, 4]
+                },
+                Pot {
+                    amount: 50 + 70,
+                    eligible: vec![3]
+                },
+            ]
+        );
+
+        // Player 4 wins the main pot, and the side pot only player 3 can win goes to them
+        let settled = table.settle(&[vec![4], vec![3]]).unwrap();
+        assert_eq!(settled.stack(4), Ok(96));
+        assert_eq!(settled.stack(3), Ok(120));
+        assert_eq!(settled.total(), 330);
+        assert_eq!(settled.pots(), vec![]);
+    }
+
+    #[test]
+    fn test_split_pots_give_odd_chips_in_seat_order() {
+        let table = PotManager::new(vec![(1, 10), (2, 10), (3, 10)], 2)
+            .post_ante(1)
+            .apply(1, Fold)
+            .unwrap()
+            .apply(2, Check)
+            .unwrap()
+            .apply(3, Check)
+            .unwrap();
+
+        assert_eq!(
+            table.payouts(&[vec![3, 2]]),
+            Ok(vec![(2, 2), (3, 1)].into_iter().collect())
+        );
+        assert_eq!(
+            table.payouts(&[vec![1]]),
+            Err(UnrankedPlayers {
+                players: vec![2, 3]
+            })
+        );
+        assert_eq!(table.apply(5, Check), Err(UnknownPlayer { player: 5 }));
+    }
+}
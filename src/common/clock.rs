@@ -0,0 +1,168 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// What happens when a player runs out of time to act
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeoutPolicy {
+    /// The player loses the game
+    Forfeit,
+    /// A random valid action is played for them
+    RandomMove,
+    /// Their turn is passed, for games where that's allowed, otherwise they forfeit
+    SkipTurn,
+    /// The game's safe default action is played for them, if the game has one, otherwise they
+    /// forfeit
+    DefaultAction,
+}
+
+/// What the runtime should do about a player who ran out of time
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeoutOutcome<A> {
+    Forfeit,
+    Play(A),
+    SkipTurn,
+}
+
+/// The game specific parts of handling timeouts
+pub trait TimeoutHooks {
+    type Player: Copy;
+    type Action: Clone;
+
+    /// The actions the player could take right now
+    fn timeout_actions(&self, player: Self::Player) -> Vec<Self::Action>;
+
+    /// A conservative action to take for the player, like drawing a card
+    fn default_action(&self, _player: Self::Player) -> Option<Self::Action> {
+        None
+    }
+
+    /// Whether the player's turn can be passed without them acting
+    fn can_skip_turn(&self, _player: Self::Player) -> bool {
+        false
+    }
+}
+
+impl TimeoutPolicy {
+    /// Decides what to do about a player who ran out of time, falling back to a forfeit when the
+    /// policy can't be followed
+    /// ```
+    /// use lib_table_top::common::clock::{TimeoutOutcome, TimeoutPolicy};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+    /// let mut rng = RngSeed([0; 32]).into_rng();
+    ///
+    /// let outcome = TimeoutPolicy::DefaultAction.resolve(&game, P1, &mut rng);
+    /// assert_eq!(outcome, TimeoutOutcome::Play(game.player_view(P1).suggest_action().unwrap()));
+    ///
+    /// // Crazy eights doesn't let players pass, and it's not P2's turn anyway
+    /// assert_eq!(TimeoutPolicy::SkipTurn.resolve(&game, P1, &mut rng), TimeoutOutcome::Forfeit);
+    /// assert_eq!(TimeoutPolicy::RandomMove.resolve(&game, P2, &mut rng), TimeoutOutcome::Forfeit);
+    /// ```
+    pub fn resolve<G: TimeoutHooks>(
+        &self,
+        game: &G,
+        player: G::Player,
+        rng: &mut impl Rng,
+    ) -> TimeoutOutcome<G::Action> {
+        let action = match self {
+            TimeoutPolicy::Forfeit => None,
+            TimeoutPolicy::RandomMove => game.timeout_actions(player).choose(rng).cloned(),
+            TimeoutPolicy::SkipTurn if game.can_skip_turn(player) => {
+                return TimeoutOutcome::SkipTurn
+            }
+            TimeoutPolicy::SkipTurn => None,
+            TimeoutPolicy::DefaultAction => game.default_action(player),
+        };
+
+        action.map_or(TimeoutOutcome::Forfeit, TimeoutOutcome::Play)
+    }
+}
+
+/// The time the player to move has to act, started when the game begins waiting on them. Games
+/// don't keep time themselves, so the host starts a new clock every turn
+/// ```
+/// use lib_table_top::common::clock::TurnClock;
+/// use std::time::{Duration, SystemTime};
+///
+/// let started = SystemTime::UNIX_EPOCH;
+/// let clock = TurnClock::start(Duration::from_secs(30), started);
+///
+/// assert_eq!(clock.deadline(), started + Duration::from_secs(30));
+/// assert_eq!(clock.time_remaining(started + Duration::from_secs(10)), Duration::from_secs(20));
+/// assert_eq!(clock.time_remaining(started + Duration::from_secs(40)), Duration::from_secs(0));
+/// assert!(clock.is_expired(started + Duration::from_secs(30)));
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnClock {
+    pub started: SystemTime,
+    pub limit: Duration,
+}
+
+impl TurnClock {
+    pub fn start(limit: Duration, now: SystemTime) -> Self {
+        Self {
+            started: now,
+            limit,
+        }
+    }
+
+    /// When the player runs out of time
+    pub fn deadline(&self) -> SystemTime {
+        self.started + self.limit
+    }
+
+    /// How long the player has left, zero once the deadline has passed
+    pub fn time_remaining(&self, now: SystemTime) -> Duration {
+        self.deadline()
+            .duration_since(now)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+    }
+
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.deadline()
+    }
+}
+
+/// Views that can carry the countdown of the player to move, so clients can render it straight
+/// from the view
+pub trait ShowsClock: Sized {
+    /// Sets the view's `deadline` and `time_remaining`
+    fn set_countdown(&mut self, deadline: SystemTime, time_remaining: Duration);
+
+    /// Fills in the countdown from a clock as of `now`
+    fn with_clock(mut self, clock: &TurnClock, now: SystemTime) -> Self {
+        self.set_countdown(clock.deadline(), clock.time_remaining(now));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::rand::RngSeed;
+    use crate::games::marooned::{GameState, Player::*};
+
+    #[test]
+    fn test_random_moves_are_valid() {
+        let game: GameState = Default::default();
+        let outcome =
+            TimeoutPolicy::RandomMove.resolve(&game, P1, &mut RngSeed([0; 32]).into_rng());
+
+        match outcome {
+            TimeoutOutcome::Play(action) => assert!(game.apply_action(action).is_ok()),
+            outcome => panic!("Expected a random move, got {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn test_games_without_defaults_forfeit() {
+        let game: GameState = Default::default();
+        let outcome =
+            TimeoutPolicy::DefaultAction.resolve(&game, P1, &mut RngSeed([0; 32]).into_rng());
+        assert_eq!(outcome, TimeoutOutcome::Forfeit);
+    }
+}
@@ -0,0 +1,146 @@
+use thiserror::Error;
+
+/// The things that can go wrong reading a square's label
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum CoordsError {
+    /// Labels are letters for the column followed by a number for the row, like "c7"
+    #[error("{:?} isn't a square label like \"a1\"", label)]
+    InvalidLabel { label: String },
+    /// The label is fine but the square isn't on the board
+    #[error(
+        "{:?} isn't on a board with {:?} cols and {:?} rows",
+        label,
+        cols,
+        rows
+    )]
+    OffBoard { label: String, cols: u8, rows: u8 },
+}
+
+use CoordsError::*;
+
+/// The letters for a column, "a" through "z", then "aa", "ab", and so on like a spreadsheet
+/// ```
+/// use lib_table_top::common::coords::col_label;
+///
+/// assert_eq!(col_label(0), "a");
+/// assert_eq!(col_label(25), "z");
+/// assert_eq!(col_label(26), "aa");
+/// assert_eq!(col_label(255), "iv");
+/// ```
+pub fn col_label(col: u8) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = usize::from(col) + 1;
+
+    while remaining > 0 {
+        letters.push(b'a' + ((remaining - 1) % 26) as u8);
+        remaining = (remaining - 1) / 26;
+    }
+
+    letters.iter().rev().map(|&letter| letter as char).collect()
+}
+
+/// The label for a square, the column letters followed by the row counting from 1, so the
+/// square at col 0 and row 0 is "a1"
+/// ```
+/// use lib_table_top::common::coords::label;
+///
+/// assert_eq!(label(0, 0), "a1");
+/// assert_eq!(label(2, 6), "c7");
+/// assert_eq!(label(27, 99), "ab100");
+/// ```
+pub fn label(col: u8, row: u8) -> String {
+    format!("{}{}", col_label(col), usize::from(row) + 1)
+}
+
+/// Reads a square's label, ignoring case, and checks that the square is on a board of the
+/// given size. Returns the (col, row) indices
+/// ```
+/// use lib_table_top::common::coords::{parse_label, CoordsError};
+///
+/// assert_eq!(parse_label("a1", 3, 3), Ok((0, 0)));
+/// assert_eq!(parse_label("C7", 8, 8), Ok((2, 6)));
+/// assert_eq!(
+///   parse_label("d1", 3, 3),
+///   Err(CoordsError::OffBoard { label: "d1".to_string(), cols: 3, rows: 3 })
+/// );
+/// assert_eq!(
+///   parse_label("1a", 3, 3),
+///   Err(CoordsError::InvalidLabel { label: "1a".to_string() })
+/// );
+/// ```
+pub fn parse_label(label: &str, cols: u8, rows: u8) -> Result<(u8, u8), CoordsError> {
+    let invalid = || InvalidLabel {
+        label: label.to_string(),
+    };
+    let off_board = || OffBoard {
+        label: label.to_string(),
+        cols,
+        rows,
+    };
+
+    let split = label
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .ok_or_else(invalid)?;
+    let (letters, digits) = label.split_at(split);
+
+    if letters.is_empty() || digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let col = letters.bytes().try_fold(0usize, |col, letter| {
+        let value = usize::from(letter.to_ascii_lowercase() - b'a') + 1;
+        col.checked_mul(26)?.checked_add(value)
+    });
+    let row: Option<usize> = digits.parse().ok();
+
+    match (col, row) {
+        (Some(col), Some(row)) if row > 0 => {
+            if col > usize::from(cols) || row > usize::from(rows) {
+                Err(off_board())
+            } else {
+                Ok(((col - 1) as u8, (row - 1) as u8))
+            }
+        }
+        (Some(_), Some(_)) => Err(invalid()),
+        _ => Err(off_board()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_label_round_trips() {
+        // The biggest board has 255 cols and rows, so the last index is 254
+        for col in 0..u8::MAX {
+            for &row in &[0, 1, 9, 10, u8::MAX - 1] {
+                let label = label(col, row);
+                assert_eq!(parse_label(&label, u8::MAX, u8::MAX), Ok((col, row)));
+                assert_eq!(
+                    parse_label(&label.to_uppercase(), u8::MAX, u8::MAX),
+                    Ok((col, row))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_malformed_labels() {
+        for &label in &["", "a", "7", "a0", "a-1", "a1b", "é1"] {
+            assert_eq!(
+                parse_label(label, 8, 8),
+                Err(InvalidLabel {
+                    label: label.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_labels_are_off_the_board() {
+        for &label in &["zzzzzzzzzzzzzzzzzz1", "a99999999999999999999999"] {
+            assert!(matches!(parse_label(label, 8, 8), Err(OffBoard { .. })));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::{Card, DealId, StandardDeck, STANDARD_DECK};
+use crate::common::rand::RngSeed;
+
+/// How evenly a shuffle spread the cards over the positions in the deck. A fair shuffle puts
+/// every card in every position equally often, so the counts should be close to the number of
+/// shuffles divided by 52
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub shuffles: usize,
+    /// Pearson's chi-squared statistic over every card and position pair
+    pub chi_squared: f64,
+    pub degrees_of_freedom: usize,
+    /// The statistic a fair shuffle stays under 99.9% of the time
+    pub critical_value: f64,
+    /// The card whose positions were the furthest from even, with its share of `chi_squared`
+    pub worst_card: Card,
+    pub worst_card_chi_squared: f64,
+}
+
+impl AuditReport {
+    /// Whether the shuffle looks fair. A fair shuffle fails one audit in a thousand, so fails are
+    /// worth running again with a different number of shuffles before drawing conclusions
+    pub fn passed(&self) -> bool {
+        self.chi_squared <= self.critical_value
+    }
+}
+
+/// Shuffles a deck many times with the seeds of `DealId(0)`, `DealId(1)`, and so on, counting how
+/// often each card lands in each position, then runs a chi-squared test for uniformity. Audits are
+/// reproducible, the same shuffle routine and number of shuffles always gives the same report
+/// ```
+/// use lib_table_top::common::deck::audit::positional_uniformity;
+/// use lib_table_top::common::deck::{Deck, StandardDeck};
+///
+/// let report = positional_uniformity(5_000, StandardDeck::shuffled);
+/// assert!(report.passed());
+/// assert_eq!(report.degrees_of_freedom, 51 * 51);
+/// ```
+pub fn positional_uniformity(
+    shuffles: usize,
+    shuffle: impl Fn(RngSeed) -> StandardDeck,
+) -> AuditReport {
+    let mut counts = [[0usize; 52]; 52];
+
+    for deal in 0..shuffles {
+        let deck = shuffle(DealId(deal as u64).seed());
+
+        for (position, card) in deck.iter().enumerate() {
+            let index = STANDARD_DECK
+                .iter()
+                .position(|standard| standard == card)
+                .expect("Decks only have standard cards");
+            counts[index][position] += 1;
+        }
+    }
+
+    let expected = shuffles as f64 / 52.0;
+    let card_chi_squared: Vec<f64> = counts
+        .iter()
+        .map(|positions| {
+            positions
+                .iter()
+                .map(|&count| (count as f64 - expected).powi(2) / expected)
+                .sum()
+        })
+        .collect();
+
+    let (worst, &worst_card_chi_squared) = card_chi_squared
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .expect("There are always 52 cards");
+
+    // Both the cards and the positions add up to the number of shuffles, which takes away a
+    // degree of freedom from each
+    let degrees_of_freedom = 51 * 51;
+
+    AuditReport {
+        shuffles,
+        chi_squared: card_chi_squared.iter().sum(),
+        degrees_of_freedom,
+        critical_value: chi_squared_critical_value(degrees_of_freedom),
+        worst_card: STANDARD_DECK[worst],
+        worst_card_chi_squared,
+    }
+}
+
+/// The 99.9th percentile of the chi-squared distribution, using the Wilson-Hilferty
+/// approximation, which is very close for the thousands of degrees of freedom used here
+fn chi_squared_critical_value(degrees_of_freedom: usize) -> f64 {
+    // The 99.9th percentile of the standard normal distribution
+    let z = 3.090_232;
+    let k = degrees_of_freedom as f64;
+    let spread = 2.0 / (9.0 * k);
+
+    k * (1.0 - spread + z * spread.sqrt()).powi(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_the_critical_value_approximation() {
+        // From a chi-squared table
+        assert!((chi_squared_critical_value(100) - 149.449).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_biased_shuffles_fail() {
+        // Never moves the first card
+        let biased = |seed: RngSeed| {
+            let mut deck = STANDARD_DECK;
+            deck[1..].shuffle(&mut seed.into_rng());
+            deck
+        };
+        let report = positional_uniformity(2_000, biased);
+
+        assert!(!report.passed());
+        assert_eq!(report.worst_card, STANDARD_DECK[0]);
+    }
+}
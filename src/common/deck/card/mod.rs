@@ -2,14 +2,28 @@ use serde::{Deserialize, Serialize};
 mod rank;
 mod suit;
 
-pub use rank::Rank;
+pub use rank::{Rank, RankOrder};
 pub use suit::{Color, Suit};
 
+use std::cmp::Ordering;
 use std::fmt;
 
+/// A card from a standard deck. The derived ordering compares ranks with aces low and then
+/// suits, which is only meant for keeping cards in sets and maps. Anything players see or rules
+/// depend on should say how it orders cards with [`cmp_by`](Card::cmp_by)
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Hash, Ord, Serialize, Deserialize)]
 pub struct Card(pub Rank, pub Suit);
 
+/// The ways to sort cards, see [`Card::cmp_by`](Card::cmp_by). Suits go in the order of
+/// `Suit::ALL`, clubs, diamonds, hearts, then spades
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Grouped by suit, then by rank within each suit
+    SuitThenRank(RankOrder),
+    /// By rank, with cards of the same rank ordered by suit
+    RankThenSuit(RankOrder),
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?} of {:?}", self.rank(), self.suit())
@@ -27,6 +41,47 @@ impl Card {
     pub fn rank(&self) -> Rank {
         self.0
     }
+
+    /// Compares two cards in a sort order
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, RankOrder, SortOrder, Suit::*};
+    /// use std::cmp::Ordering;
+    ///
+    /// let ace = Card(Ace, Clubs);
+    /// let king = Card(King, Spades);
+    ///
+    /// assert_eq!(ace.cmp_by(&king, SortOrder::SuitThenRank(RankOrder::AceHigh)), Ordering::Less);
+    /// assert_eq!(ace.cmp_by(&king, SortOrder::RankThenSuit(RankOrder::AceHigh)), Ordering::Greater);
+    /// assert_eq!(ace.cmp_by(&king, SortOrder::RankThenSuit(RankOrder::AceLow)), Ordering::Less);
+    /// ```
+    pub fn cmp_by(&self, other: &Self, order: SortOrder) -> Ordering {
+        match order {
+            SortOrder::SuitThenRank(rank_order) => self.suit().cmp(&other.suit()).then_with(|| {
+                self.rank()
+                    .power(rank_order)
+                    .cmp(&other.rank().power(rank_order))
+            }),
+            SortOrder::RankThenSuit(rank_order) => self
+                .rank()
+                .power(rank_order)
+                .cmp(&other.rank().power(rank_order))
+                .then_with(|| self.suit().cmp(&other.suit())),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Card {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        (any::<Rank>(), any::<Suit>())
+            .prop_map(|(rank, suit)| Card(rank, suit))
+            .boxed()
+    }
 }
 
 #[cfg(test)]
@@ -49,4 +104,21 @@ mod tests {
             assert_eq!(displayed, *expected);
         }
     }
+
+    #[test]
+    fn test_sort_orders() {
+        let sort = |order| {
+            let mut deck = crate::common::deck::STANDARD_DECK.to_vec();
+            deck.sort_by(|a, b| a.cmp_by(b, order));
+            deck
+        };
+
+        let deck = sort(SortOrder::SuitThenRank(RankOrder::AceHigh));
+        assert_eq!(deck[..2], [Card(Two, Clubs), Card(Three, Clubs)]);
+        assert_eq!(deck[12..14], [Card(Ace, Clubs), Card(Two, Diamonds)]);
+
+        let deck = sort(SortOrder::RankThenSuit(RankOrder::AceLow));
+        assert_eq!(deck[..2], [Card(Ace, Clubs), Card(Ace, Diamonds)]);
+        assert_eq!(deck[51], Card(King, Spades));
+    }
 }
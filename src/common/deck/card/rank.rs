@@ -1,9 +1,24 @@
+use serde::{Deserialize, Serialize};
 use serde_repr::*;
 
 /// The pips of a standard deck. Important note that the cards have `repr(u8)` and Ace is
 /// represented by 1
+///
+/// Ranks can index an `EnumMap`, for tallying cards by rank
+/// ```
+/// use enum_map::EnumMap;
+/// use lib_table_top::common::deck::Rank::{self, *};
+///
+/// let mut tally: EnumMap<Rank, u8> = EnumMap::default();
+/// for &rank in &[Five, Jack, Five] {
+///     tally[rank] += 1;
+/// }
+///
+/// assert_eq!(tally[Five], 2);
+/// assert_eq!(tally[Ace], 0);
+/// ```
 #[derive(
-    Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Hash, Ord, Serialize_repr, Deserialize_repr,
+    Copy, Clone, Debug, Enum, PartialEq, PartialOrd, Eq, Hash, Ord, Serialize_repr, Deserialize_repr,
 )]
 #[repr(u8)]
 pub enum Rank {
@@ -24,11 +39,44 @@ pub enum Rank {
 
 use Rank::*;
 
+/// Whether aces are the highest or lowest card in a suit
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RankOrder {
+    AceHigh,
+    AceLow,
+}
+
 impl Rank {
     pub const ALL: [Self; 13] = [
         Ace, Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King,
     ];
 
+    /// An iterator over all of the ranks, in the same order as `ALL`
+    /// ```
+    /// use lib_table_top::common::deck::Rank::{self, *};
+    ///
+    /// assert_eq!(Rank::iter().next(), Some(Ace));
+    /// assert_eq!(Rank::iter().count(), 13);
+    /// ```
+    pub fn iter() -> impl Iterator<Item = Self> + Clone {
+        Self::ALL.iter().copied()
+    }
+
+    /// How high the rank is, from 1 to 13 with aces low or 2 to 14 with aces high
+    /// ```
+    /// use lib_table_top::common::deck::{Rank::*, RankOrder};
+    ///
+    /// assert_eq!(Ace.power(RankOrder::AceLow), 1);
+    /// assert_eq!(Ace.power(RankOrder::AceHigh), 14);
+    /// assert_eq!(King.power(RankOrder::AceHigh), 13);
+    /// ```
+    pub fn power(&self, order: RankOrder) -> u8 {
+        match (self, order) {
+            (Ace, RankOrder::AceHigh) => 14,
+            (rank, _) => *rank as u8,
+        }
+    }
+
     /// Returns the next card, with Ace being high
     /// ```
     /// use lib_table_top::common::deck::Rank::*;
@@ -140,6 +188,16 @@ impl Rank {
     }
 }
 
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Rank {
+    type Parameters = ();
+    type Strategy = proptest::sample::Select<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        proptest::sample::select(Self::ALL.to_vec())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,6 +30,16 @@ impl Suit {
     /// ```
     pub const ALL: [Self; 4] = [Clubs, Diamonds, Hearts, Spades];
 
+    /// An iterator over all of the suits, in the same order as `ALL`
+    /// ```
+    /// use lib_table_top::common::deck::Suit::{self, *};
+    ///
+    /// assert_eq!(Suit::iter().collect::<Vec<_>>(), vec![Clubs, Diamonds, Hearts, Spades]);
+    /// ```
+    pub fn iter() -> impl Iterator<Item = Self> + Clone {
+        Self::ALL.iter().copied()
+    }
+
     /// Returns the color of a suit
     /// ```
     /// use lib_table_top::common::deck::{Suit::*, Color::*};
@@ -62,3 +72,13 @@ impl Color {
         }
     }
 }
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Suit {
+    type Parameters = ();
+    type Strategy = proptest::sample::Select<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        proptest::sample::select(Self::ALL.to_vec())
+    }
+}
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The things that can go wrong dealing
+#[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DealingError {
+    #[error("There has to be at least one player to deal to")]
+    NoPlayers,
+    #[error("The deal needs {:?} cards but there are only {:?}", needed, available)]
+    NotEnoughCards { needed: usize, available: usize },
+}
+
+use DealingError::*;
+
+/// The hands of a deal, in seat order, along with the cards that weren't dealt in the order
+/// they were in
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deal<T> {
+    pub hands: Vec<Vec<T>>,
+    pub remaining: Vec<T>,
+}
+
+/// How cards are passed out to the players. Cards are dealt from the front of the deck, starting
+/// with the first seat
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pattern {
+    /// One card to each player in turn, until everyone has the number of cards
+    RoundRobin { cards_per_player: usize },
+    /// Packets of cards to each player in turn, one pass around the table per round. The sizes in
+    /// a round are by seat and repeat for tables with more seats than sizes
+    Packets { rounds: Vec<Vec<usize>> },
+}
+
+impl Pattern {
+    /// Euchre's 3-2 deal, three cards then two cards around the table, and the other way around
+    /// on the second pass, so everyone ends up with five
+    /// ```
+    /// use lib_table_top::common::deck::dealing::Pattern;
+    ///
+    /// let deal = Pattern::euchre().deal((0..24).collect(), 4).unwrap();
+    /// assert_eq!(deal.hands[0], vec![0, 1, 2, 10, 11]);
+    /// assert_eq!(deal.hands[1], vec![3, 4, 12, 13, 14]);
+    /// assert!(deal.hands.iter().all(|hand| hand.len() == 5));
+    /// assert_eq!(deal.remaining, vec![20, 21, 22, 23]);
+    /// ```
+    pub fn euchre() -> Self {
+        Pattern::Packets {
+            rounds: vec![vec![3, 2], vec![2, 3]],
+        }
+    }
+
+    /// How many cards the pattern deals to a seat
+    pub fn cards_for_seat(&self, seat: usize) -> usize {
+        match self {
+            Pattern::RoundRobin { cards_per_player } => *cards_per_player,
+            Pattern::Packets { rounds } => rounds
+                .iter()
+                .filter(|sizes| !sizes.is_empty())
+                .map(|sizes| sizes[seat % sizes.len()])
+                .sum(),
+        }
+    }
+
+    /// Deals the cards to a number of players
+    /// ```
+    /// use lib_table_top::common::deck::dealing::{DealingError, Pattern};
+    ///
+    /// let pattern = Pattern::RoundRobin { cards_per_player: 2 };
+    /// let deal = pattern.deal(vec!['a', 'b', 'c', 'd', 'e'], 2).unwrap();
+    /// assert_eq!(deal.hands, vec![vec!['a', 'c'], vec!['b', 'd']]);
+    /// assert_eq!(deal.remaining, vec!['e']);
+    ///
+    /// assert_eq!(
+    ///   pattern.deal(vec!['a', 'b', 'c'], 2),
+    ///   Err(DealingError::NotEnoughCards { needed: 4, available: 3 })
+    /// );
+    /// ```
+    pub fn deal<T>(&self, cards: Vec<T>, players: usize) -> Result<Deal<T>, DealingError> {
+        if players == 0 {
+            return Err(NoPlayers);
+        }
+
+        let needed = (0..players).map(|seat| self.cards_for_seat(seat)).sum();
+        if needed > cards.len() {
+            return Err(NotEnoughCards {
+                needed,
+                available: cards.len(),
+            });
+        }
+
+        let mut deck = cards.into_iter();
+        let mut hands: Vec<Vec<T>> = (0..players).map(|_| Vec::new()).collect();
+
+        match self {
+            Pattern::RoundRobin { cards_per_player } => {
+                for _ in 0..*cards_per_player {
+                    for hand in &mut hands {
+                        hand.extend(deck.next());
+                    }
+                }
+            }
+            Pattern::Packets { rounds } => {
+                for sizes in rounds.iter().filter(|sizes| !sizes.is_empty()) {
+                    for (seat, hand) in hands.iter_mut().enumerate() {
+                        hand.extend((&mut deck).take(sizes[seat % sizes.len()]));
+                    }
+                }
+            }
+        }
+
+        Ok(Deal {
+            hands,
+            remaining: deck.collect(),
+        })
+    }
+}
+
+/// A pile of a tableau, the top card is the last face up card
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pile<T> {
+    pub face_down: Vec<T>,
+    pub face_up: Vec<T>,
+}
+
+/// The piles of a tableau deal, along with the cards left over for the stock
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tableau<T> {
+    pub piles: Vec<Pile<T>>,
+    pub stock: Vec<T>,
+}
+
+/// Deals a Klondike style tableau, pile `n` (counting from 0) gets `n + 1` cards with only the
+/// last one face up. Cards are dealt a row at a time, left to right, the way it's done by hand
+/// ```
+/// use lib_table_top::common::deck::dealing::tableau;
+///
+/// let klondike = tableau((0..52).collect(), 7).unwrap();
+/// assert_eq!(klondike.piles[0].face_up, vec![0]);
+/// assert_eq!(klondike.piles[1].face_down, vec![1]);
+/// assert_eq!(klondike.piles[1].face_up, vec![7]);
+/// assert_eq!(klondike.piles[6].face_down, vec![6, 12, 17, 21, 24, 26]);
+/// assert_eq!(klondike.piles[6].face_up, vec![27]);
+/// assert_eq!(klondike.stock.len(), 24);
+/// ```
+pub fn tableau<T>(cards: Vec<T>, piles: usize) -> Result<Tableau<T>, DealingError> {
+    let needed = piles * (piles + 1) / 2;
+    if needed > cards.len() {
+        return Err(NotEnoughCards {
+            needed,
+            available: cards.len(),
+        });
+    }
+
+    let mut deck = cards.into_iter();
+    let mut tableau: Vec<Pile<T>> = (0..piles)
+        .map(|_| Pile {
+            face_down: Vec::new(),
+            face_up: Vec::new(),
+        })
+        .collect();
+
+    for row in 0..piles {
+        for (index, pile) in tableau.iter_mut().enumerate().skip(row) {
+            let card = deck.next().expect("The deck was checked to be big enough");
+            if index == row {
+                pile.face_up.push(card);
+            } else {
+                pile.face_down.push(card);
+            }
+        }
+    }
+
+    Ok(Tableau {
+        piles: tableau,
+        stock: deck.collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packets_repeat_their_sizes_around_the_table() {
+        let pattern = Pattern::Packets {
+            rounds: vec![vec![2], vec![], vec![1, 3]],
+        };
+        assert_eq!(pattern.cards_for_seat(0), 3);
+        assert_eq!(pattern.cards_for_seat(3), 5);
+
+        let deal = pattern.deal((0..20).collect(), 3).unwrap();
+        assert_eq!(
+            deal.hands,
+            vec![vec![0, 1, 6], vec![2, 3, 7, 8, 9], vec![4, 5, 10]]
+        );
+        assert_eq!(deal.remaining, (11..20).collect::<Vec<_>>());
+
+        assert_eq!(pattern.deal(vec![0], 0), Err(NoPlayers));
+    }
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use super::{Card, Rank, Suit};
+
+/// Which way foundation piles are built
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuildDirection {
+    Up,
+    Down,
+}
+
+/// How the foundation piles are built. Klondike and FreeCell build up from the ace without
+/// wrapping, Canfield builds up from a random base rank and wraps from King to Ace
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoundationRules {
+    pub base_rank: Rank,
+    pub direction: BuildDirection,
+    pub wrapping: bool,
+}
+
+impl Default for FoundationRules {
+    fn default() -> Self {
+        Self {
+            base_rank: Rank::Ace,
+            direction: BuildDirection::Up,
+            wrapping: false,
+        }
+    }
+}
+
+impl FoundationRules {
+    /// The rank that goes on top of a rank, `None` once the pile can't go any further
+    /// ```
+    /// use lib_table_top::common::deck::foundations::{BuildDirection, FoundationRules};
+    /// use lib_table_top::common::deck::Rank::*;
+    ///
+    /// let klondike = FoundationRules::default();
+    /// assert_eq!(klondike.next_rank(Ace), Some(Two));
+    /// assert_eq!(klondike.next_rank(King), None);
+    ///
+    /// let canfield = FoundationRules { base_rank: Seven, direction: BuildDirection::Up, wrapping: true };
+    /// assert_eq!(canfield.next_rank(King), Some(Ace));
+    /// assert_eq!(canfield.next_rank(Six), None);
+    /// ```
+    pub fn next_rank(&self, rank: Rank) -> Option<Rank> {
+        let next = match (self.direction, self.wrapping) {
+            (BuildDirection::Up, true) => Some(rank.next_with_wrapping()),
+            (BuildDirection::Down, true) => Some(rank.previous_with_wrapping()),
+            (BuildDirection::Up, false) => rank.next_with_ace_low(),
+            (BuildDirection::Down, false) => rank.previous_with_ace_low(),
+        };
+
+        next.filter(|&next| next != self.base_rank)
+    }
+}
+
+/// The things that can go wrong placing a card on the foundations
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FoundationError {
+    #[error("Can't place {:?}, the {:?} pile needs {:?}", card, card.suit(), needed)]
+    OutOfOrder { card: Card, needed: Option<Rank> },
+}
+
+use FoundationError::*;
+
+/// One pile per suit, each built in order from the base rank
+/// ```
+/// use lib_table_top::common::deck::foundations::{FoundationError, Foundations};
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+///
+/// let foundations = Foundations::default()
+///   .place(Card(Ace, Hearts)).unwrap()
+///   .place(Card(Two, Hearts)).unwrap();
+///
+/// assert_eq!(foundations.top(Hearts), Some(Card(Two, Hearts)));
+/// assert_eq!(foundations.len(Hearts), 2);
+/// assert!(foundations.can_place(Card(Ace, Spades)));
+/// assert_eq!(
+///   foundations.place(Card(Four, Hearts)),
+///   Err(FoundationError::OutOfOrder { card: Card(Four, Hearts), needed: Some(Three) })
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Foundations {
+    rules: FoundationRules,
+    tops: BTreeMap<Suit, Rank>,
+}
+
+impl Foundations {
+    pub fn new(rules: FoundationRules) -> Self {
+        Self {
+            rules,
+            tops: BTreeMap::new(),
+        }
+    }
+
+    pub fn rules(&self) -> &FoundationRules {
+        &self.rules
+    }
+
+    /// The card on top of a suit's pile, `None` if nothing has been placed
+    pub fn top(&self, suit: Suit) -> Option<Card> {
+        self.tops.get(&suit).map(|&rank| Card(rank, suit))
+    }
+
+    /// The rank a suit's pile needs next, `None` once the pile is complete
+    pub fn needed(&self, suit: Suit) -> Option<Rank> {
+        match self.tops.get(&suit) {
+            Some(&top) => self.rules.next_rank(top),
+            None => Some(self.rules.base_rank),
+        }
+    }
+
+    pub fn can_place(&self, card: Card) -> bool {
+        self.needed(card.suit()) == Some(card.rank())
+    }
+
+    /// Places a card on its suit's pile
+    pub fn place(&self, card: Card) -> Result<Self, FoundationError> {
+        if !self.can_place(card) {
+            return Err(OutOfOrder {
+                card,
+                needed: self.needed(card.suit()),
+            });
+        }
+
+        let mut foundations = self.clone();
+        foundations.tops.insert(card.suit(), card.rank());
+        Ok(foundations)
+    }
+
+    /// The number of cards on a suit's pile
+    pub fn len(&self, suit: Suit) -> usize {
+        let mut len = 0;
+        let mut next = Some(self.rules.base_rank);
+
+        while let (Some(rank), Some(&top)) = (next, self.tops.get(&suit)) {
+            len += 1;
+            if rank == top {
+                break;
+            }
+            next = self.rules.next_rank(rank);
+        }
+
+        len
+    }
+
+    /// The number of cards on all of the piles
+    pub fn total(&self) -> usize {
+        Suit::ALL.iter().map(|&suit| self.len(suit)).sum()
+    }
+
+    /// Whether every pile has been built all the way
+    pub fn is_complete(&self) -> bool {
+        Suit::ALL.iter().all(|&suit| self.needed(suit).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank::*, Suit::*};
+
+    #[test]
+    fn test_building_every_pile() {
+        let foundations = iproduct!(&Suit::ALL, &Rank::ALL)
+            .try_fold(Foundations::default(), |foundations, (&suit, &rank)| {
+                foundations.place(Card(rank, suit))
+            })
+            .unwrap();
+
+        assert!(foundations.is_complete());
+        assert_eq!(foundations.total(), 52);
+        assert_eq!(foundations.top(Clubs), Some(Card(King, Clubs)));
+    }
+
+    #[test]
+    fn test_wrapping_down_from_a_base_rank() {
+        let rules = FoundationRules {
+            base_rank: Three,
+            direction: BuildDirection::Down,
+            wrapping: true,
+        };
+        let foundations = [Three, Two, Ace, King]
+            .iter()
+            .try_fold(Foundations::new(rules), |foundations, &rank| {
+                foundations.place(Card(rank, Spades))
+            })
+            .unwrap();
+
+        assert_eq!(foundations.len(Spades), 4);
+        assert_eq!(foundations.needed(Spades), Some(Queen));
+        assert_eq!(foundations.needed(Hearts), Some(Three));
+        assert!(!foundations.is_complete());
+    }
+}
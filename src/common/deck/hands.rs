@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+use super::{Card, RankOrder};
+
+/// How good a poker hand is. Hands compare by category, from high card up to straight flush,
+/// and then by the ranks that break ties, most important first. Ranks are their
+/// [`power`](super::Rank::power) with aces high, so an ace is 14, except that the ace in a
+/// five high straight counts as 1
+/// ```
+/// use lib_table_top::common::deck::hands::HandRank;
+///
+/// assert!(HandRank::Straight { high: 5 } > HandRank::ThreeOfAKind { trips: 14, kickers: [13, 12] });
+/// assert!(HandRank::Straight { high: 5 } < HandRank::Straight { high: 6 });
+/// assert!(
+///     HandRank::Pair { pair: 10, kickers: [14, 9, 2] }
+///         > HandRank::Pair { pair: 10, kickers: [14, 8, 7] }
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HandRank {
+    /// The five ranks from highest to lowest
+    HighCard([u8; 5]),
+    Pair {
+        pair: u8,
+        kickers: [u8; 3],
+    },
+    TwoPair {
+        high: u8,
+        low: u8,
+        kicker: u8,
+    },
+    ThreeOfAKind {
+        trips: u8,
+        kickers: [u8; 2],
+    },
+    Straight {
+        high: u8,
+    },
+    /// The five ranks from highest to lowest
+    Flush([u8; 5]),
+    FullHouse {
+        trips: u8,
+        pair: u8,
+    },
+    FourOfAKind {
+        quads: u8,
+        kicker: u8,
+    },
+    StraightFlush {
+        high: u8,
+    },
+}
+
+/// Ranks a five card poker hand
+/// ```
+/// use lib_table_top::common::deck::hands::{evaluate_5, HandRank};
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+///
+/// let wheel = [
+///     Card(Ace, Hearts),
+///     Card(Two, Clubs),
+///     Card(Three, Hearts),
+///     Card(Four, Spades),
+///     Card(Five, Diamonds),
+/// ];
+/// assert_eq!(evaluate_5(&wheel), HandRank::Straight { high: 5 });
+///
+/// let boat = [
+///     Card(Nine, Hearts),
+///     Card(Two, Clubs),
+///     Card(Nine, Spades),
+///     Card(Two, Spades),
+///     Card(Nine, Diamonds),
+/// ];
+/// assert_eq!(evaluate_5(&boat), HandRank::FullHouse { trips: 9, pair: 2 });
+/// ```
+pub fn evaluate_5(cards: &[Card; 5]) -> HandRank {
+    let mut powers = cards.map(|card| card.rank().power(RankOrder::AceHigh));
+    powers.sort_unstable_by_key(|&power| Reverse(power));
+
+    let flush = cards.iter().all(|card| card.suit() == cards[0].suit());
+    let distinct = powers.windows(2).all(|pair| pair[0] != pair[1]);
+    let straight = match powers {
+        [14, 5, 4, 3, 2] => Some(5),
+        [high, .., low] if distinct && high - low == 4 => Some(high),
+        _ => None,
+    };
+
+    // The ranks by how many of each there are, and then by how high they are
+    let mut groups: Vec<(usize, u8)> = Vec::new();
+    for &power in powers.iter() {
+        match groups.iter_mut().find(|(_, grouped)| *grouped == power) {
+            Some((count, _)) => *count += 1,
+            None => groups.push((1, power)),
+        }
+    }
+    groups.sort_unstable_by_key(|&group| Reverse(group));
+
+    match (straight, flush, groups.as_slice()) {
+        (Some(high), true, _) => HandRank::StraightFlush { high },
+        (_, _, &[(4, quads), (_, kicker)]) => HandRank::FourOfAKind { quads, kicker },
+        (_, _, &[(3, trips), (2, pair)]) => HandRank::FullHouse { trips, pair },
+        (_, true, _) => HandRank::Flush(powers),
+        (Some(high), _, _) => HandRank::Straight { high },
+        (_, _, &[(3, trips), (_, a), (_, b)]) => HandRank::ThreeOfAKind {
+            trips,
+            kickers: [a, b],
+        },
+        (_, _, &[(2, high), (2, low), (_, kicker)]) => HandRank::TwoPair { high, low, kicker },
+        (_, _, &[(2, pair), (_, a), (_, b), (_, c)]) => HandRank::Pair {
+            pair,
+            kickers: [a, b, c],
+        },
+        _ => HandRank::HighCard(powers),
+    }
+}
+
+/// The best five card hand out of seven, like two hole cards and the board in Texas Hold'em
+/// ```
+/// use lib_table_top::common::deck::hands::{best_of_7, HandRank};
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+///
+/// let board = [
+///     Card(Ten, Hearts),
+///     Card(Jack, Hearts),
+///     Card(Queen, Clubs),
+///     Card(Two, Hearts),
+///     Card(Two, Spades),
+/// ];
+/// let ann = [Card(King, Spades), Card(Ace, Diamonds), board[0], board[1], board[2], board[3], board[4]];
+/// let bob = [Card(Five, Hearts), Card(Six, Hearts), board[0], board[1], board[2], board[3], board[4]];
+///
+/// assert_eq!(best_of_7(&ann), HandRank::Straight { high: 14 });
+/// assert_eq!(best_of_7(&bob), HandRank::Flush([11, 10, 6, 5, 2]));
+/// assert!(best_of_7(&bob) > best_of_7(&ann));
+/// ```
+pub fn best_of_7(cards: &[Card; 7]) -> HandRank {
+    // Every five card hand leaves out two of the seven
+    (0..7)
+        .flat_map(|skip_a| (skip_a + 1..7).map(move |skip_b| (skip_a, skip_b)))
+        .map(|(skip_a, skip_b)| {
+            let mut hand = [cards[0]; 5];
+            let kept = (0..7).filter(|&index| index != skip_a && index != skip_b);
+            for (slot, index) in kept.enumerate() {
+                hand[slot] = cards[index];
+            }
+            evaluate_5(&hand)
+        })
+        .max()
+        .expect("Seven cards have five card hands")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank::*, Suit::*};
+
+    #[test]
+    fn test_every_category() {
+        let hands = [
+            [
+                Card(Two, Hearts),
+                Card(Five, Clubs),
+                Card(Nine, Hearts),
+                Card(Jack, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Two, Clubs),
+                Card(Nine, Hearts),
+                Card(Jack, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Two, Clubs),
+                Card(Nine, Hearts),
+                Card(Nine, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Two, Clubs),
+                Card(Two, Spades),
+                Card(Nine, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Nine, Hearts),
+                Card(Ten, Clubs),
+                Card(Jack, Spades),
+                Card(Queen, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Five, Hearts),
+                Card(Nine, Hearts),
+                Card(Jack, Hearts),
+                Card(King, Hearts),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Two, Clubs),
+                Card(Two, Spades),
+                Card(King, Spades),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Two, Hearts),
+                Card(Two, Clubs),
+                Card(Two, Spades),
+                Card(Two, Diamonds),
+                Card(King, Diamonds),
+            ],
+            [
+                Card(Ace, Clubs),
+                Card(Two, Clubs),
+                Card(Three, Clubs),
+                Card(Four, Clubs),
+                Card(Five, Clubs),
+            ],
+        ];
+
+        let ranks: Vec<HandRank> = hands.iter().map(evaluate_5).collect();
+        assert_eq!(
+            ranks,
+            vec![
+                HandRank::HighCard([13, 11, 9, 5, 2]),
+                HandRank::Pair {
+                    pair: 2,
+                    kickers: [13, 11, 9]
+                },
+                HandRank::TwoPair {
+                    high: 9,
+                    low: 2,
+                    kicker: 13
+                },
+                HandRank::ThreeOfAKind {
+                    trips: 2,
+                    kickers: [13, 9]
+                },
+                HandRank::Straight { high: 13 },
+                HandRank::Flush([13, 11, 9, 5, 2]),
+                HandRank::FullHouse { trips: 2, pair: 13 },
+                HandRank::FourOfAKind {
+                    quads: 2,
+                    kicker: 13
+                },
+                HandRank::StraightFlush { high: 5 },
+            ]
+        );
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_the_board_can_play_and_split_the_pot() {
+        let board = [
+            Card(Ace, Spades),
+            Card(King, Spades),
+            Card(Queen, Hearts),
+            Card(Jack, Diamonds),
+            Card(Ten, Clubs),
+        ];
+        let with =
+            |a: Card, b: Card| best_of_7(&[a, b, board[0], board[1], board[2], board[3], board[4]]);
+
+        let ann = with(Card(Two, Hearts), Card(Three, Clubs));
+        let bob = with(Card(Nine, Hearts), Card(Nine, Clubs));
+        assert_eq!(ann, HandRank::Straight { high: 14 });
+        assert_eq!(ann, bob);
+
+        // Two pair on the hand is beaten by a better kicker
+        let board = [
+            Card(Eight, Spades),
+            Card(Eight, Hearts),
+            Card(Four, Clubs),
+            Card(Four, Diamonds),
+            Card(Two, Clubs),
+        ];
+        let ann = best_of_7(&[
+            Card(Ace, Hearts),
+            Card(Three, Clubs),
+            board[0],
+            board[1],
+            board[2],
+            board[3],
+            board[4],
+        ]);
+        let bob = best_of_7(&[
+            Card(King, Hearts),
+            Card(Queen, Clubs),
+            board[0],
+            board[1],
+            board[2],
+            board[3],
+            board[4],
+        ]);
+        assert_eq!(
+            ann,
+            HandRank::TwoPair {
+                high: 8,
+                low: 4,
+                kicker: 14
+            }
+        );
+        assert!(ann > bob);
+    }
+}
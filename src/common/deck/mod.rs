@@ -1,12 +1,86 @@
+pub mod audit;
 mod card;
+pub mod dealing;
+pub mod foundations;
+pub mod hands;
+pub mod points;
+pub mod tracking;
 
 pub use self::card::Card;
-pub use self::card::{Color, Rank, Suit};
+pub use self::card::{Color, Rank, RankOrder, SortOrder, Suit};
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::common::rand::RngSeed;
 
 use Rank::*;
 use Suit::*;
 
-type StandardDeck = [Card; 52];
+pub type StandardDeck = [Card; 52];
+
+/// Decks that can be shuffled reproducibly, the same seed gives the same order on every platform
+/// ```
+/// use lib_table_top::common::deck::{Deck, StandardDeck, STANDARD_DECK};
+/// use lib_table_top::common::rand::RngSeed;
+///
+/// let deck = StandardDeck::shuffled(RngSeed([0; 32]));
+/// assert_eq!(deck, StandardDeck::shuffled(RngSeed([0; 32])));
+/// assert_ne!(deck, StandardDeck::shuffled(RngSeed([1; 32])));
+/// assert_ne!(deck, STANDARD_DECK);
+/// ```
+pub trait Deck: Sized {
+    /// Shuffles a new deck with a seed
+    fn shuffled(seed: RngSeed) -> Self {
+        Self::shuffled_with(&mut seed.into_rng())
+    }
+
+    /// Shuffles a new deck with an existing random number generator, for games that keep using
+    /// the generator after dealing
+    fn shuffled_with(rng: &mut impl Rng) -> Self;
+}
+
+impl Deck for StandardDeck {
+    fn shuffled_with(rng: &mut impl Rng) -> Self {
+        let mut deck = STANDARD_DECK;
+        deck.shuffle(rng);
+        deck
+    }
+}
+
+/// A numbered deal, so players can share and replay a deal like "daily deal #42". Each number
+/// always shuffles the deck the same way
+/// ```
+/// use lib_table_top::common::deck::{DealId, Deck, StandardDeck};
+///
+/// let deal = DealId(42);
+/// assert_eq!(deal.to_string(), "#42");
+/// assert_eq!(deal.deck(), StandardDeck::shuffled(deal.seed()));
+/// assert_eq!(serde_json::to_string(&deal).unwrap(), "42");
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DealId(pub u64);
+
+impl DealId {
+    /// The seed for the deal, the number in little endian bytes followed by zeros
+    pub fn seed(&self) -> RngSeed {
+        let mut seed = [0; 32];
+        seed[..8].copy_from_slice(&self.0.to_le_bytes());
+        RngSeed(seed)
+    }
+
+    /// The shuffled deck for the deal
+    pub fn deck(&self) -> StandardDeck {
+        StandardDeck::shuffled(self.seed())
+    }
+}
+
+impl fmt::Display for DealId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
 
 pub const STANDARD_DECK: StandardDeck = [
     Card(Ace, Hearts),
@@ -77,4 +151,20 @@ mod tests {
         assert_eq!(unique_cards.len(), 52);
         assert_eq!(STANDARD_DECK.len(), 52);
     }
+
+    #[test]
+    fn test_deals_are_stable() {
+        // Changing how decks are shuffled breaks every deal that's been shared, so it's pinned
+        let deck = DealId(1).deck();
+        assert_eq!(
+            &deck[..3],
+            &[Card(Three, Diamonds), Card(Four, Clubs), Card(Six, Clubs)]
+        );
+
+        let mut sorted = deck.to_vec();
+        sorted.sort();
+        let mut standard = STANDARD_DECK.to_vec();
+        standard.sort();
+        assert_eq!(sorted, standard);
+    }
 }
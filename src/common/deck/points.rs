@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Card, Rank::*, SortOrder, Suit::*};
+
+/// The ways card games put points on cards
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scheme {
+    /// Penalties for cards left in hand: eights are 50, tens and face cards are 10, aces are 1,
+    /// and everything else is its pips
+    CrazyEights,
+    /// Every heart is 1 and the Queen of Spades is 13, everything else is 0
+    Hearts,
+    /// Face cards are 10, aces are 1, and everything else is its pips
+    Cribbage,
+    /// Face cards are 10 and everything else is its pips. Aces are 1 here, hand totals count one
+    /// ace as 11 when it doesn't bust the hand
+    Blackjack,
+}
+
+impl Scheme {
+    /// The points for a single card
+    /// ```
+    /// use lib_table_top::common::deck::{points::Scheme, Card, Rank::*, Suit::*};
+    ///
+    /// assert_eq!(Scheme::CrazyEights.value(Card(Eight, Clubs)), 50);
+    /// assert_eq!(Scheme::Hearts.value(Card(Queen, Spades)), 13);
+    /// assert_eq!(Scheme::Hearts.value(Card(Queen, Clubs)), 0);
+    /// assert_eq!(Scheme::Cribbage.value(Card(King, Hearts)), 10);
+    /// assert_eq!(Scheme::Blackjack.value(Card(Ace, Spades)), 1);
+    /// ```
+    pub fn value(&self, Card(rank, suit): Card) -> u32 {
+        match (self, rank, suit) {
+            (Scheme::CrazyEights, Eight, _) => 50,
+            (Scheme::Hearts, Queen, Spades) => 13,
+            (Scheme::Hearts, _, Hearts) => 1,
+            (Scheme::Hearts, _, _) => 0,
+            (_, Jack, _) | (_, Queen, _) | (_, King, _) => 10,
+            (_, rank, _) => rank as u32,
+        }
+    }
+}
+
+/// The points in a hand, soft when an ace is being counted high and could still drop to low
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Total {
+    pub points: u32,
+    pub soft: bool,
+}
+
+/// Collections of cards, like a player's hand
+pub trait Hand {
+    /// Adds up the points in the hand
+    /// ```
+    /// use lib_table_top::common::deck::{points::{Hand, Scheme, Total}, Card, Rank::*, Suit::*};
+    ///
+    /// let hand = [Card(Ace, Spades), Card(Six, Hearts)];
+    /// assert_eq!(hand.total(Scheme::Blackjack), Total { points: 17, soft: true });
+    ///
+    /// let hand = [Card(Ace, Spades), Card(Six, Hearts), Card(Nine, Clubs)];
+    /// assert_eq!(hand.total(Scheme::Blackjack), Total { points: 16, soft: false });
+    /// assert_eq!(hand.total(Scheme::Cribbage).points, 16);
+    /// ```
+    fn total(&self, scheme: Scheme) -> Total;
+
+    /// A copy of the cards sorted in an order, for showing a hand to a player
+    /// ```
+    /// use lib_table_top::common::deck::{
+    ///   points::Hand, Card, Rank::*, RankOrder, SortOrder, Suit::*
+    /// };
+    ///
+    /// let hand = [Card(Ace, Spades), Card(Two, Hearts), Card(King, Spades)];
+    /// assert_eq!(
+    ///   hand.sorted_by(SortOrder::SuitThenRank(RankOrder::AceHigh)),
+    ///   vec![Card(Two, Hearts), Card(King, Spades), Card(Ace, Spades)]
+    /// );
+    /// ```
+    fn sorted_by(&self, order: SortOrder) -> Vec<Card>;
+}
+
+impl Hand for [Card] {
+    fn total(&self, scheme: Scheme) -> Total {
+        let points = self.iter().map(|&card| scheme.value(card)).sum();
+        let has_ace = self.iter().any(|&Card(rank, _)| rank == Ace);
+
+        match scheme {
+            Scheme::Blackjack if has_ace && points + 10 <= 21 => Total {
+                points: points + 10,
+                soft: true,
+            },
+            _ => Total {
+                points,
+                soft: false,
+            },
+        }
+    }
+
+    fn sorted_by(&self, order: SortOrder) -> Vec<Card> {
+        let mut sorted = self.to_vec();
+        sorted.sort_by(|a, b| a.cmp_by(b, order));
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::STANDARD_DECK;
+
+    #[test]
+    fn test_deck_totals() {
+        let totals = [
+            (
+                Scheme::CrazyEights,
+                4 * (1 + 2 + 3 + 4 + 5 + 6 + 7 + 9 + 10 + 30 + 50),
+            ),
+            (Scheme::Hearts, 26),
+            (Scheme::Cribbage, 340),
+            (Scheme::Blackjack, 340),
+        ];
+
+        for &(scheme, points) in totals.iter() {
+            assert_eq!(STANDARD_DECK[..].total(scheme).points, points);
+        }
+    }
+
+    #[test]
+    fn test_blackjack_only_counts_one_ace_high() {
+        let hand = [Card(Ace, Spades), Card(Ace, Hearts)];
+        assert_eq!(
+            hand.total(Scheme::Blackjack),
+            Total {
+                points: 12,
+                soft: true
+            }
+        );
+
+        let blackjack = [Card(Ace, Spades), Card(King, Hearts)];
+        assert_eq!(blackjack.total(Scheme::Blackjack).points, 21);
+    }
+}
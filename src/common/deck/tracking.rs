@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use super::{Card, STANDARD_DECK};
+
+/// Tracks which cards have been seen, to reason about where the rest could be. Every unseen card
+/// is assumed to be equally likely to be in any hidden spot, which is true for a shuffled deck
+/// when nobody has given away information about their hand
+/// ```
+/// use lib_table_top::common::deck::{tracking::CardTracker, Card, Rank::*, Suit::*};
+///
+/// let tracker = CardTracker::new()
+///   .see(Card(Eight, Hearts))
+///   .see(Card(Eight, Spades));
+///
+/// assert_eq!(tracker.unseen_count(), 50);
+/// assert!(tracker.is_seen(Card(Eight, Hearts)));
+///
+/// // Two eights are still out there
+/// let is_eight = |card: Card| card.rank() == Eight;
+/// assert_eq!(tracker.unseen_matching(is_eight), 2);
+///
+/// // A 10 card pile holds 10 * 2 / 50 eights on average
+/// assert_eq!(tracker.expected_count_in(is_eight, 10), 0.4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardTracker {
+    unseen: BTreeSet<Card>,
+}
+
+impl Default for CardTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CardTracker {
+    /// Starts with every card in a standard deck unseen
+    pub fn new() -> Self {
+        Self::from_cards(STANDARD_DECK.iter().copied())
+    }
+
+    /// Starts with a custom set of unseen cards, for games that don't use a standard deck
+    pub fn from_cards(cards: impl IntoIterator<Item = Card>) -> Self {
+        Self {
+            unseen: cards.into_iter().collect(),
+        }
+    }
+
+    /// Marks a card as seen
+    pub fn see(&self, card: Card) -> Self {
+        self.see_all(std::iter::once(card))
+    }
+
+    /// Marks several cards as seen
+    pub fn see_all(&self, cards: impl IntoIterator<Item = Card>) -> Self {
+        let mut tracker = self.clone();
+
+        for card in cards {
+            tracker.unseen.remove(&card);
+        }
+
+        tracker
+    }
+
+    pub fn is_seen(&self, card: Card) -> bool {
+        !self.unseen.contains(&card)
+    }
+
+    /// The cards that haven't been seen yet
+    pub fn unseen(&self) -> impl Iterator<Item = Card> + '_ {
+        self.unseen.iter().copied()
+    }
+
+    pub fn unseen_count(&self) -> usize {
+        self.unseen.len()
+    }
+
+    /// How many unseen cards match, e.g. `|card| card.suit() == Hearts`
+    pub fn unseen_matching(&self, matches: impl Fn(Card) -> bool) -> usize {
+        self.unseen().filter(|&card| matches(card)).count()
+    }
+
+    /// The chance that a hidden hand of `hand_size` cards holds at least one matching card
+    /// ```
+    /// use lib_table_top::common::deck::{tracking::CardTracker, Card, Rank::*, Suit::*};
+    ///
+    /// let tracker = CardTracker::from_cards(vec![Card(Ace, Spades), Card(Two, Hearts)]);
+    /// let is_ace = |card: Card| card.rank() == Ace;
+    ///
+    /// assert_eq!(tracker.probability_player_holds(is_ace, 1), 0.5);
+    /// assert_eq!(tracker.probability_player_holds(is_ace, 2), 1.0);
+    /// assert_eq!(tracker.probability_player_holds(is_ace, 0), 0.0);
+    /// ```
+    pub fn probability_player_holds(
+        &self,
+        matches: impl Fn(Card) -> bool,
+        hand_size: usize,
+    ) -> f64 {
+        let unseen = self.unseen_count();
+        let matching = self.unseen_matching(matches);
+        let hand_size = hand_size.min(unseen);
+
+        // One minus the chance that every card in the hand comes from the non matching cards
+        let none_match: f64 = (0..hand_size)
+            .map(|drawn| (unseen - matching).saturating_sub(drawn) as f64 / (unseen - drawn) as f64)
+            .product();
+
+        1.0 - none_match
+    }
+
+    /// The average number of matching cards in a hidden pile of `pile_size` cards
+    pub fn expected_count_in(&self, matches: impl Fn(Card) -> bool, pile_size: usize) -> f64 {
+        let unseen = self.unseen_count();
+
+        if unseen == 0 {
+            return 0.0;
+        }
+
+        pile_size.min(unseen) as f64 * self.unseen_matching(matches) as f64 / unseen as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank::*, Suit::*};
+
+    #[test]
+    fn test_probability_of_holding_a_suit() {
+        let tracker = CardTracker::new();
+        let is_heart = |card: Card| card.suit() == Hearts;
+
+        let expected = 1.0 - (39.0 * 38.0) / (52.0 * 51.0);
+        assert!((tracker.probability_player_holds(is_heart, 2) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_seen_cards_can_not_be_held() {
+        let tracker = CardTracker::new().see_all(vec![
+            Card(Queen, Spades),
+            Card(Queen, Hearts),
+            Card(Queen, Clubs),
+            Card(Queen, Diamonds),
+        ]);
+        let is_queen = |card: Card| card.rank() == Queen;
+
+        assert_eq!(tracker.probability_player_holds(is_queen, 48), 0.0);
+        assert_eq!(tracker.expected_count_in(is_queen, 10), 0.0);
+    }
+
+    #[test]
+    fn test_empty_trackers() {
+        let tracker = CardTracker::from_cards(vec![]);
+        assert_eq!(tracker.probability_player_holds(|_| true, 5), 0.0);
+        assert_eq!(tracker.expected_count_in(|_| true, 5), 0.0);
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt::Debug;
+
+/// The surface every game shares, so engines and hosts can drive any game without knowing which
+/// one it is. Each game's inherent methods of the same names are still there for direct use
+/// ```
+/// use lib_table_top::common::game::Play;
+/// use lib_table_top::games::{marooned, tic_tac_toe};
+///
+/// // Takes the first valid action until the game is over
+/// fn play_out<G: Play>(mut game: G) -> G {
+///     while !game.is_over() {
+///         let action = game.valid_actions().remove(0);
+///         game = game.apply_action(action).ok().unwrap();
+///     }
+///     game
+/// }
+///
+/// let game = play_out(tic_tac_toe::GameState::new());
+/// assert_eq!(Play::history(&game).len(), 7);
+/// assert!(Play::valid_actions(&game).is_empty());
+///
+/// let game = play_out(marooned::GameState::default());
+/// assert!(Play::is_over(&game));
+/// ```
+pub trait Play: Clone + Sized {
+    type Action: Clone;
+    type Player: Clone + Debug;
+    type Status;
+    type Error: Debug;
+
+    /// Takes an action, returns an error if it's illegal
+    fn apply_action(&self, action: Self::Action) -> Result<Self, Self::Error>;
+
+    /// The actions that can be applied next, empty once the game is over
+    fn valid_actions(&self) -> Vec<Self::Action>;
+
+    /// Everyone playing the game, in turn order
+    fn players(&self) -> Vec<Self::Player>;
+
+    /// The player the game is waiting on
+    fn whose_turn(&self) -> Self::Player;
+
+    fn status(&self) -> Self::Status;
+
+    /// Whether the game has finished
+    fn is_over(&self) -> bool;
+
+    /// The actions that have been taken, in order
+    fn history(&self) -> Vec<Self::Action>;
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use thiserror::Error;
+
+use crate::common::game::Play;
+use crate::common::views::{Perspective, Viewable};
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum HotSeatError<P: Debug, E: Debug> {
+    #[error("The game rejected the action {:?}", error)]
+    Game { error: E },
+    #[error("The game is already over")]
+    GameIsOver,
+    #[error("It's {:?}'s turn, not {:?}'s", expected, player)]
+    WrongPlayer { expected: P, player: P },
+    #[error("{:?} has to confirm they have the device before acting", player)]
+    NotRevealed { player: P },
+}
+
+use HotSeatError::*;
+
+/// What a shared device should be showing
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Screen<P, V> {
+    /// Covers the secrets while the device is passed, with the public view of the table behind
+    /// it. Waits for the player to confirm it's them before revealing anything
+    Privacy { next: P, view: V },
+    /// The view of the player holding the device, who can act
+    Revealed { player: P, view: V },
+    /// The game is over, so everything can be shown to everyone
+    GameOver { view: V },
+}
+
+/// Where the device is in the handoff between players
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+enum Handoff<P> {
+    Hidden,
+    Revealed(P),
+}
+
+/// Wraps a hidden information game for players sharing one device. Between turns the device
+/// shows a privacy screen until the next player confirms it's them, so nobody sees a hand that
+/// isn't theirs while it's passed around. A player who keeps the turn after acting, like one who
+/// drew a card they can play, keeps their view
+/// ```
+/// use lib_table_top::common::hotseat::{HotSeat, Screen};
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings, View};
+/// use std::sync::Arc;
+///
+/// let settings = Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Two);
+/// let hotseat = HotSeat::new(GameState::new(Arc::new(settings)));
+/// assert!(matches!(hotseat.screen(), Screen::Privacy { next: P1, view: View::Observer(_) }));
+///
+/// // Nobody can act until the device has been handed over
+/// let action = hotseat.game().current_player_view().suggest_action().unwrap();
+/// assert!(hotseat.apply_action((P1, action)).is_err());
+/// assert!(hotseat.reveal(P2).is_err());
+///
+/// let revealed = hotseat.reveal(P1).unwrap();
+/// assert!(matches!(revealed.screen(), Screen::Revealed { player: P1, view: View::Player(_) }));
+///
+/// let passed = revealed.apply_action((P1, action)).unwrap();
+/// assert!(matches!(passed.screen(), Screen::Privacy { next: P2, .. }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: Serialize, G::Player: Serialize",
+    deserialize = "G: Deserialize<'de>, G::Player: Deserialize<'de>"
+))]
+pub struct HotSeat<G: Play> {
+    game: G,
+    handoff: Handoff<G::Player>,
+}
+
+impl<G> HotSeat<G>
+where
+    G: Play + Viewable<Player = <G as Play>::Player>,
+    <G as Play>::Player: PartialEq,
+{
+    /// Starts with the privacy screen up for the first player
+    pub fn new(game: G) -> Self {
+        Self {
+            game,
+            handoff: Handoff::Hidden,
+        }
+    }
+
+    /// The wrapped game
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// The player who has confirmed they have the device, if the screen is revealed
+    pub fn revealed_to(&self) -> Option<&<G as Play>::Player> {
+        match &self.handoff {
+            Handoff::Revealed(player) if !self.game.is_over() => Some(player),
+            _ => None,
+        }
+    }
+
+    /// The perspective the device is showing
+    pub fn perspective(&self) -> Perspective<<G as Play>::Player> {
+        if self.game.is_over() {
+            return Perspective::Omniscient;
+        }
+
+        match self.revealed_to() {
+            Some(player) => Perspective::Player(player.clone()),
+            None => Perspective::Observer,
+        }
+    }
+
+    /// What the device should be showing
+    pub fn screen(&self) -> Screen<<G as Play>::Player, G::View> {
+        let view = self.game.view(self.perspective());
+
+        if self.game.is_over() {
+            return Screen::GameOver { view };
+        }
+
+        match self.revealed_to() {
+            Some(player) => Screen::Revealed {
+                player: player.clone(),
+                view,
+            },
+            None => Screen::Privacy {
+                next: self.game.whose_turn(),
+                view,
+            },
+        }
+    }
+
+    /// The player confirms they have the device, taking down the privacy screen. Only the
+    /// player whose turn it is can reveal
+    pub fn reveal(
+        &self,
+        player: <G as Play>::Player,
+    ) -> Result<Self, HotSeatError<<G as Play>::Player, G::Error>> {
+        self.check_turn(&player)?;
+
+        let mut hotseat = self.clone();
+        hotseat.handoff = Handoff::Revealed(player);
+        Ok(hotseat)
+    }
+
+    /// Puts the privacy screen back up, for when a player steps away mid turn
+    pub fn hide(&self) -> Self {
+        let mut hotseat = self.clone();
+        hotseat.handoff = Handoff::Hidden;
+        hotseat
+    }
+
+    /// Takes an action for the revealed player. The privacy screen goes up if the turn passes to
+    /// someone else
+    pub fn apply_action(
+        &self,
+        action: <G as Play>::Action,
+    ) -> Result<Self, HotSeatError<<G as Play>::Player, G::Error>> {
+        let player = self.game.whose_turn();
+        self.check_turn(&player)?;
+
+        if self.revealed_to() != Some(&player) {
+            return Err(NotRevealed { player });
+        }
+
+        let game = self
+            .game
+            .apply_action(action)
+            .map_err(|error| Game { error })?;
+
+        let handoff = if game.whose_turn() == player {
+            Handoff::Revealed(player)
+        } else {
+            Handoff::Hidden
+        };
+
+        Ok(Self { game, handoff })
+    }
+
+    fn check_turn(
+        &self,
+        player: &<G as Play>::Player,
+    ) -> Result<(), HotSeatError<<G as Play>::Player, G::Error>> {
+        if self.game.is_over() {
+            return Err(GameIsOver);
+        }
+
+        let expected = self.game.whose_turn();
+        if &expected != player {
+            return Err(WrongPlayer {
+                expected,
+                player: player.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::tic_tac_toe::{Col::*, GameState, Player::*, Row::*};
+
+    #[test]
+    fn test_the_screen_follows_the_turns() {
+        let hotseat = HotSeat::new(GameState::new());
+        assert_eq!(hotseat.perspective(), Perspective::Observer);
+        assert_eq!(
+            hotseat.apply_action((P1, (Col0, Row0))),
+            Err(NotRevealed { player: P1 })
+        );
+        assert_eq!(
+            hotseat.reveal(P2),
+            Err(WrongPlayer {
+                expected: P1,
+                player: P2
+            })
+        );
+
+        let revealed = hotseat.reveal(P1).unwrap();
+        assert_eq!(revealed.perspective(), Perspective::Player(P1));
+        assert_eq!(revealed.hide(), hotseat);
+
+        let game = [(Col0, Row0), (Col1, Row0), (Col0, Row1), (Col1, Row1)]
+            .iter()
+            .fold(revealed, |hotseat, &position| {
+                let player = hotseat.game().whose_turn();
+                let next = hotseat.apply_action((player, position)).unwrap();
+                assert_eq!(next.revealed_to(), None);
+                next.reveal(next.game().whose_turn()).unwrap()
+            });
+
+        let over = game.apply_action((P1, (Col0, Row2))).unwrap();
+        assert_eq!(over.perspective(), Perspective::Omniscient);
+        assert!(matches!(over.screen(), Screen::GameOver { .. }));
+        assert_eq!(over.reveal(P2), Err(GameIsOver));
+    }
+}
@@ -0,0 +1,147 @@
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::common::results::GameKind;
+use crate::games::{crazy_eights, marooned, spades, tic_tac_toe};
+
+/// A description of a game for lobbies to list, each game module has one as `INFO`
+/// ```
+/// use lib_table_top::common::results::GameKind;
+///
+/// for kind in GameKind::ALL.iter() {
+///     let info = kind.info();
+///     assert_eq!(info.kind, *kind);
+///     assert!(info.min_players <= info.max_players);
+///     assert!(info.settings_schema().is_object());
+/// }
+///
+/// let info = GameKind::CrazyEights.info();
+/// assert_eq!(info.name, "Crazy Eights");
+/// assert_eq!((info.min_players, info.max_players), (2, 8));
+/// assert_eq!(info.to_json()["settings_schema"]["required"][0], "seed");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameInfo {
+    pub kind: GameKind,
+    pub name: &'static str,
+    /// A short description of how the game is played
+    pub rules: &'static str,
+    pub min_players: u8,
+    pub max_players: u8,
+    /// How long a typical game takes with the default settings
+    pub estimated_duration: Duration,
+    /// A JSON Schema for the game's settings, as text so the info can be a constant, see
+    /// [`settings_schema`](GameInfo::settings_schema)
+    pub settings_schema_json: &'static str,
+}
+
+impl GameInfo {
+    /// The JSON Schema for the game's settings
+    pub fn settings_schema(&self) -> Value {
+        serde_json::from_str(self.settings_schema_json).expect("Settings schemas are valid JSON")
+    }
+
+    /// The info for a lobby to send to clients
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind,
+            "name": self.name,
+            "rules": self.rules,
+            "min_players": self.min_players,
+            "max_players": self.max_players,
+            "estimated_duration": self.estimated_duration,
+            "settings_schema": self.settings_schema(),
+        })
+    }
+}
+
+impl GameKind {
+    /// Every game in the crate
+    pub const ALL: [Self; 4] = [
+        GameKind::CrazyEights,
+        GameKind::Marooned,
+        GameKind::Spades,
+        GameKind::TicTacToe,
+    ];
+
+    /// The description of the game, see [`GameInfo`](struct@GameInfo)
+    pub fn info(&self) -> &'static GameInfo {
+        match self {
+            GameKind::CrazyEights => &crazy_eights::INFO,
+            GameKind::Marooned => &marooned::INFO,
+            GameKind::Spades => &spades::INFO,
+            GameKind::TicTacToe => &tic_tac_toe::INFO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::rand::RngSeed;
+
+    /// The keys of a settings value that the schema doesn't list
+    fn unlisted_keys(info: &GameInfo, settings: Value) -> Vec<String> {
+        let schema = info.settings_schema();
+        match settings {
+            Value::Object(settings) => settings
+                .keys()
+                .filter(|key| schema["properties"].get(key.as_str()).is_none())
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_schemas_list_every_setting() {
+        let crazy_eights = crazy_eights::SettingsBuilder::new()
+            .seed(RngSeed([0; 32]))
+            .number_of_players(crazy_eights::NumberOfPlayers::Three)
+            .cards_per_player(4)
+            .starter_eight(crazy_eights::StarterEight::Bury)
+            .rules(crazy_eights::Rules {
+                decks: crazy_eights::Decks::Two,
+                voluntary_draws: true,
+                draw: crazy_eights::DrawRule::DrawUntilPlayable { max: Some(3) },
+                match_target: Some(100),
+                stacking_twos: true,
+                queens_skip: true,
+                jacks_reverse: true,
+                eight_on_eight: crazy_eights::EightOnEight::KeepSuit,
+            })
+            .build()
+            .unwrap();
+        let settings = serde_json::to_value(crazy_eights).unwrap();
+        assert!(unlisted_keys(&crazy_eights::INFO, settings.clone()).is_empty());
+        let rules_schema = &crazy_eights::INFO.settings_schema()["properties"]["rules"];
+        for key in settings["rules"].as_object().unwrap().keys() {
+            assert!(rules_schema["properties"].get(key).is_some(), "{}", key);
+        }
+
+        let marooned = marooned::SettingsBuilder::new()
+            .max_repetitions(3)
+            .move_cap(50)
+            .first_to_move(marooned::Player::P2)
+            .build()
+            .unwrap();
+        let settings = serde_json::to_value(marooned).unwrap();
+        assert!(unlisted_keys(&marooned::INFO, settings).is_empty());
+
+        let spades = spades::Settings {
+            seed: RngSeed([0; 32]),
+            rules: spades::Rules {
+                blind_nil_deficit: None,
+                ..Default::default()
+            },
+        };
+        let settings = serde_json::to_value(spades).unwrap();
+        assert!(unlisted_keys(&spades::INFO, settings.clone()).is_empty());
+        let rules_schema = &spades::INFO.settings_schema()["properties"]["rules"];
+        for key in settings["rules"].as_object().unwrap().keys() {
+            assert!(rules_schema["properties"].get(key).is_some(), "{}", key);
+        }
+
+        assert_eq!(tic_tac_toe::INFO.settings_schema()["type"], "null");
+    }
+}
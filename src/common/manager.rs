@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+use crate::common::game::Play;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ManagerError<K: Debug, E: Debug> {
+    #[error("There's no game with the id {:?}", id)]
+    NoSuchGame { id: K },
+    #[error("The game rejected the action {:?}", error)]
+    Game { error: E },
+}
+
+use ManagerError::*;
+
+#[derive(Clone, Debug)]
+struct Entry<G> {
+    game: G,
+    /// When the game was first seen to be over
+    finished_at: Option<SystemTime>,
+}
+
+/// The games a server is running, keyed by id. Actions are routed to the right game, and
+/// finished games are kept around for a while so players can see the end before they're
+/// collected. Times are passed in rather than read from the system clock, so tests and replays
+/// of a server stay deterministic
+/// ```
+/// use lib_table_top::common::manager::GameManager;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+/// use std::time::{Duration, SystemTime};
+///
+/// let start = SystemTime::UNIX_EPOCH;
+/// let mut manager = GameManager::new(Duration::from_secs(60));
+/// manager.insert("table-1", GameState::new(), start);
+/// manager.insert("table-2", GameState::new(), start);
+///
+/// for (i, position) in [(Col0, Row0), (Col1, Row0), (Col0, Row1), (Col1, Row1), (Col0, Row2)].iter().enumerate() {
+///     let player = if i % 2 == 0 { P1 } else { P2 };
+///     manager.apply_action(&"table-1", (player, *position), start).unwrap();
+/// }
+/// assert!(manager.apply_action(&"table-3", (P1, (Col0, Row0)), start).is_err());
+/// assert_eq!(manager.finished_at(&"table-1"), Some(start));
+///
+/// // Finished games stick around until the TTL is up
+/// assert!(manager.collect_garbage(start + Duration::from_secs(30)).is_empty());
+/// let collected = manager.collect_garbage(start + Duration::from_secs(60));
+/// assert_eq!(collected.len(), 1);
+/// assert_eq!(collected[0].0, "table-1");
+/// assert_eq!(manager.ids().collect::<Vec<_>>(), vec![&"table-2"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GameManager<K, G> {
+    ttl: Duration,
+    games: HashMap<K, Entry<G>>,
+}
+
+impl<K, G> GameManager<K, G>
+where
+    K: Clone + Debug + Eq + Hash,
+    G: Play,
+{
+    /// A manager that keeps finished games for the TTL
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            games: HashMap::new(),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Starts managing a game, returning the game that had the id before
+    pub fn insert(&mut self, id: K, game: G, now: SystemTime) -> Option<G> {
+        let finished_at = if game.is_over() { Some(now) } else { None };
+
+        self.games
+            .insert(id, Entry { game, finished_at })
+            .map(|entry| entry.game)
+    }
+
+    pub fn get(&self, id: &K) -> Option<&G> {
+        self.games.get(id).map(|entry| &entry.game)
+    }
+
+    /// Stops managing a game, returning it
+    pub fn remove(&mut self, id: &K) -> Option<G> {
+        self.games.remove(id).map(|entry| entry.game)
+    }
+
+    /// When a game was first seen to be over, `None` if it's still going or isn't managed
+    pub fn finished_at(&self, id: &K) -> Option<SystemTime> {
+        self.games.get(id).and_then(|entry| entry.finished_at)
+    }
+
+    /// Applies an action to a game, returning the game after the action
+    pub fn apply_action(
+        &mut self,
+        id: &K,
+        action: G::Action,
+        now: SystemTime,
+    ) -> Result<&G, ManagerError<K, G::Error>> {
+        let entry = self
+            .games
+            .get_mut(id)
+            .ok_or_else(|| NoSuchGame { id: id.clone() })?;
+
+        entry.game = entry
+            .game
+            .apply_action(action)
+            .map_err(|error| Game { error })?;
+
+        if entry.finished_at.is_none() && entry.game.is_over() {
+            entry.finished_at = Some(now);
+        }
+
+        Ok(&entry.game)
+    }
+
+    /// Removes the games that have been over for at least the TTL, returning them so they can be
+    /// persisted
+    pub fn collect_garbage(&mut self, now: SystemTime) -> Vec<(K, G)> {
+        let ttl = self.ttl;
+        let expired: Vec<K> = self
+            .games
+            .iter()
+            .filter(|(_, entry)| match entry.finished_at {
+                Some(finished_at) => finished_at + ttl <= now,
+                None => false,
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        expired
+            .into_iter()
+            .filter_map(|id| {
+                let entry = self.games.remove(&id)?;
+                Some((id, entry.game))
+            })
+            .collect()
+    }
+
+    /// The ids of every managed game, in no particular order
+    pub fn ids(&self) -> impl Iterator<Item = &K> {
+        self.games.keys()
+    }
+
+    /// Every managed game with its id, in no particular order, for persistence sweeps
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &G)> {
+        self.games.iter().map(|(id, entry)| (id, &entry.game))
+    }
+
+    /// The games that are still being played
+    pub fn in_progress(&self) -> impl Iterator<Item = (&K, &G)> {
+        self.iter().filter(|(_, game)| !game.is_over())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::marooned::GameState;
+
+    #[test]
+    fn test_rejected_actions_leave_the_game_alone() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut manager = GameManager::new(Duration::from_secs(1));
+        let game = GameState::default();
+        manager.insert(1, game.clone(), now);
+
+        let action = Play::valid_actions(&game).remove(0);
+        manager.apply_action(&1, action, now).unwrap();
+        assert!(matches!(
+            manager.apply_action(&1, action, now),
+            Err(Game { .. })
+        ));
+        assert_eq!(manager.get(&1).unwrap().history().count(), 1);
+
+        assert_eq!(manager.in_progress().count(), 1);
+        assert!(manager
+            .collect_garbage(now + Duration::from_secs(10))
+            .is_empty());
+        assert_eq!(
+            manager.remove(&1).map(|game| game.history().count()),
+            Some(1)
+        );
+        assert!(manager.is_empty());
+    }
+}
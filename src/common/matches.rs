@@ -0,0 +1,407 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::common::results::{GameResult, Outcome};
+
+/// How a match that's level after its regulation games gets decided. Tie breaks are tried in
+/// the order they're listed, and the match is shared if none of them decide it
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// The player who scored more points across every game so far wins, for games that keep
+    /// score like crazy eights. Doesn't need any extra games
+    CountBack,
+    /// Up to this many extra games are played, the first player to win one wins the match
+    SuddenDeath { games: usize },
+    /// A single decider that can't be drawn, a draw counts as a win for the player at this index
+    /// in the match's players. In exchange the other player usually gets an advantage like going
+    /// first or more time, which the host is free to pick
+    Armageddon { draw_odds: usize },
+    /// The match ends level
+    Shared,
+}
+
+use TieBreak::*;
+
+/// The format of a match between two players
+/// ```
+/// use lib_table_top::common::matches::{MatchRules, TieBreak};
+///
+/// let rules = MatchRules::best_of(3).with_tie_breaks(vec![
+///   TieBreak::SuddenDeath { games: 2 },
+///   TieBreak::Armageddon { draw_odds: 1 },
+/// ]);
+///
+/// assert_eq!(rules.games, 3);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRules {
+    /// The number of regulation games
+    pub games: usize,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub tie_breaks: Vec<TieBreak>,
+}
+
+impl MatchRules {
+    /// A match of this many regulation games that's shared if it ends level
+    pub fn best_of(games: usize) -> Self {
+        Self {
+            games,
+            tie_breaks: Vec::new(),
+        }
+    }
+
+    pub fn with_tie_breaks(mut self, tie_breaks: Vec<TieBreak>) -> Self {
+        self.tie_breaks = tie_breaks;
+        self
+    }
+}
+
+/// The kind of game to be played next in a match
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NextGame<P> {
+    /// One of the regulation games, counting from 1
+    Regulation { number: usize },
+    /// An extra game where the first win takes the match, counting from 1
+    SuddenDeath { number: usize },
+    /// The decider, a draw counts as a win for the player with draw odds
+    Armageddon { draw_odds: P },
+}
+
+/// Where a match stands
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchStatus<P> {
+    InProgress { next: NextGame<P> },
+    Win { player: P },
+    Shared,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchError {
+    #[error(
+        "The armageddon draw odds index {:?} isn't one of the 2 players",
+        index
+    )]
+    InvalidDrawOdds { index: usize },
+    #[error("The winner isn't in the match")]
+    NotInMatch,
+    #[error("The match is already over")]
+    MatchIsOver,
+}
+
+use MatchError::*;
+
+/// A game played in a match, with the index of the winning player and the points each player
+/// scored, in the match's order
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchGame {
+    pub winner: Option<usize>,
+    pub points: [i64; 2],
+}
+
+/// A series of games between two players, with tie breaks so a match between equal players
+/// always ends with a well defined result. Wins are worth a point and draws half a point each
+/// ```
+/// use lib_table_top::common::matches::{Match, MatchRules, MatchStatus, NextGame, TieBreak};
+///
+/// let rules = MatchRules::best_of(2).with_tie_breaks(vec![TieBreak::Armageddon { draw_odds: 1 }]);
+/// let series = Match::new(["alice", "bob"], rules).unwrap();
+///
+/// // They split the regulation games
+/// let series = series.record(Some(&"alice")).unwrap().record(Some(&"bob")).unwrap();
+/// assert_eq!(
+///   series.status(),
+///   MatchStatus::InProgress { next: NextGame::Armageddon { draw_odds: "bob" } }
+/// );
+///
+/// // Bob only needs a draw in the decider
+/// let series = series.record(None).unwrap();
+/// assert_eq!(series.status(), MatchStatus::Win { player: "bob" });
+/// assert!(series.record(None).is_err());
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Match<P> {
+    players: [P; 2],
+    rules: MatchRules,
+    games: Vec<MatchGame>,
+}
+
+impl<P: Clone + PartialEq> Match<P> {
+    pub fn new(players: [P; 2], rules: MatchRules) -> Result<Self, MatchError> {
+        for tie_break in rules.tie_breaks.iter() {
+            if let Armageddon { draw_odds } = tie_break {
+                if *draw_odds >= 2 {
+                    return Err(InvalidDrawOdds { index: *draw_odds });
+                }
+            }
+        }
+
+        Ok(Self {
+            players,
+            rules,
+            games: Vec::new(),
+        })
+    }
+
+    pub fn players(&self) -> &[P; 2] {
+        &self.players
+    }
+
+    pub fn rules(&self) -> &MatchRules {
+        &self.rules
+    }
+
+    /// Every game played so far, regulation games first
+    pub fn games(&self) -> &[MatchGame] {
+        &self.games
+    }
+
+    /// Records a game without points, `None` is a draw
+    pub fn record(&self, winner: Option<&P>) -> Result<Self, MatchError> {
+        self.record_game(winner, [0, 0])
+    }
+
+    /// Records a game along with the points each player scored in it, in the match's order
+    pub fn record_game(&self, winner: Option<&P>, points: [i64; 2]) -> Result<Self, MatchError> {
+        if !matches!(self.status(), MatchStatus::InProgress { .. }) {
+            return Err(MatchIsOver);
+        }
+
+        let winner = match winner {
+            Some(winner) => Some(self.index_of(winner).ok_or(NotInMatch)?),
+            None => None,
+        };
+
+        let mut series = self.clone();
+        series.games.push(MatchGame { winner, points });
+        Ok(series)
+    }
+
+    /// Records a finished game's result, using its scores as the points. Results with more than
+    /// one winner count as a draw
+    pub fn record_result(&self, result: &GameResult<P>) -> Result<Self, MatchError> {
+        let mut points = [0, 0];
+        for player_result in result.players.iter() {
+            let index = self.index_of(&player_result.player).ok_or(NotInMatch)?;
+            points[index] = player_result.score.unwrap_or(0);
+        }
+
+        let mut winners = result
+            .players
+            .iter()
+            .filter(|player_result| player_result.outcome == Outcome::Win);
+
+        let winner = match (winners.next(), winners.next()) {
+            (Some(winner), None) => Some(&winner.player),
+            _ => None,
+        };
+
+        self.record_game(winner, points)
+    }
+
+    /// Each player's score from the regulation games, in half points so draws stay whole
+    pub fn half_points(&self) -> [u32; 2] {
+        let mut half_points = [0, 0];
+
+        for game in self.games.iter().take(self.rules.games) {
+            match game.winner {
+                Some(winner) => half_points[winner] += 2,
+                None => {
+                    half_points[0] += 1;
+                    half_points[1] += 1;
+                }
+            }
+        }
+
+        half_points
+    }
+
+    /// Each player's points summed over every game
+    pub fn total_points(&self) -> [i64; 2] {
+        self.games.iter().fold([0, 0], |[a, b], game| {
+            [a + game.points[0], b + game.points[1]]
+        })
+    }
+
+    pub fn status(&self) -> MatchStatus<P> {
+        let half_points = self.half_points();
+        let played = self.games.len().min(self.rules.games);
+        let remaining = (self.rules.games - played) as u32;
+
+        // A player who can't be caught wins without playing out the rest
+        for index in 0..2 {
+            if half_points[index] > half_points[1 - index] + 2 * remaining {
+                return self.win(index);
+            }
+        }
+
+        if remaining > 0 {
+            return MatchStatus::InProgress {
+                next: NextGame::Regulation { number: played + 1 },
+            };
+        }
+
+        let mut extra_games = &self.games[self.rules.games..];
+
+        for tie_break in self.rules.tie_breaks.iter() {
+            match *tie_break {
+                CountBack => {
+                    let [a, b] = self.total_points();
+                    if a != b {
+                        return self.win(if a > b { 0 } else { 1 });
+                    }
+                }
+                SuddenDeath { games } => {
+                    let played = extra_games.len().min(games);
+
+                    if let Some(winner) = extra_games[..played].iter().find_map(|game| game.winner)
+                    {
+                        return self.win(winner);
+                    }
+
+                    if played < games {
+                        return MatchStatus::InProgress {
+                            next: NextGame::SuddenDeath { number: played + 1 },
+                        };
+                    }
+
+                    extra_games = &extra_games[played..];
+                }
+                Armageddon { draw_odds } => {
+                    return match extra_games.first() {
+                        Some(game) => self.win(game.winner.unwrap_or(draw_odds)),
+                        None => MatchStatus::InProgress {
+                            next: NextGame::Armageddon {
+                                draw_odds: self.players[draw_odds].clone(),
+                            },
+                        },
+                    };
+                }
+                Shared => break,
+            }
+        }
+
+        MatchStatus::Shared
+    }
+
+    fn win(&self, index: usize) -> MatchStatus<P> {
+        MatchStatus::Win {
+            player: self.players[index].clone(),
+        }
+    }
+
+    fn index_of(&self, player: &P) -> Option<usize> {
+        self.players.iter().position(|p| p == player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::results::{winner_takes_all, GameKind, Termination};
+
+    fn rules(games: usize, tie_breaks: Vec<TieBreak>) -> MatchRules {
+        MatchRules::best_of(games).with_tie_breaks(tie_breaks)
+    }
+
+    #[test]
+    fn test_matches_can_be_clinched_early() {
+        let series = Match::new([1, 2], rules(3, vec![])).unwrap();
+        let series = series.record(Some(&1)).unwrap().record(Some(&1)).unwrap();
+
+        assert_eq!(series.status(), MatchStatus::Win { player: 1 });
+        assert_eq!(series.record(Some(&2)), Err(MatchIsOver));
+    }
+
+    #[test]
+    fn test_level_matches_without_tie_breaks_are_shared() {
+        let series = Match::new([1, 2], rules(2, vec![])).unwrap();
+        assert_eq!(
+            series.status(),
+            MatchStatus::InProgress {
+                next: NextGame::Regulation { number: 1 }
+            }
+        );
+
+        let series = series.record(None).unwrap().record(None).unwrap();
+        assert_eq!(series.half_points(), [2, 2]);
+        assert_eq!(series.status(), MatchStatus::Shared);
+    }
+
+    #[test]
+    fn test_count_back_falls_through_when_points_are_level() {
+        let tie_breaks = vec![CountBack, SuddenDeath { games: 1 }];
+        let series = Match::new([1, 2], rules(1, tie_breaks)).unwrap();
+
+        let decided = series.record_game(None, [3, 5]).unwrap();
+        assert_eq!(decided.status(), MatchStatus::Win { player: 2 });
+
+        let level = series.record_game(None, [4, 4]).unwrap();
+        assert_eq!(
+            level.status(),
+            MatchStatus::InProgress {
+                next: NextGame::SuddenDeath { number: 1 }
+            }
+        );
+        assert_eq!(
+            level.record(Some(&1)).unwrap().status(),
+            MatchStatus::Win { player: 1 }
+        );
+    }
+
+    #[test]
+    fn test_sudden_death_runs_out_into_armageddon() {
+        let tie_breaks = vec![SuddenDeath { games: 2 }, Armageddon { draw_odds: 0 }];
+        let mut series = Match::new([1, 2], rules(0, tie_breaks)).unwrap();
+
+        for number in 1..=2 {
+            assert_eq!(
+                series.status(),
+                MatchStatus::InProgress {
+                    next: NextGame::SuddenDeath { number }
+                }
+            );
+            series = series.record(None).unwrap();
+        }
+
+        assert_eq!(
+            series.status(),
+            MatchStatus::InProgress {
+                next: NextGame::Armageddon { draw_odds: 1 }
+            }
+        );
+        assert_eq!(
+            series.record(Some(&2)).unwrap().status(),
+            MatchStatus::Win { player: 2 }
+        );
+        assert_eq!(
+            series.record(None).unwrap().status(),
+            MatchStatus::Win { player: 1 }
+        );
+    }
+
+    #[test]
+    fn test_recording_game_results() {
+        let series = Match::new(["a", "b"], rules(1, vec![CountBack])).unwrap();
+        let result = GameResult {
+            kind: GameKind::CrazyEights,
+            settings_hash: 0,
+            players: winner_takes_all(["a", "b"].iter().copied(), "b"),
+            move_count: 10,
+            duration: None,
+            termination: Termination::Completed,
+        };
+
+        let series = series.record_result(&result).unwrap();
+        assert_eq!(series.status(), MatchStatus::Win { player: "b" });
+        assert_eq!(
+            Match::new(["a", "c"], rules(1, vec![]))
+                .unwrap()
+                .record_result(&result),
+            Err(NotInMatch)
+        );
+        assert_eq!(
+            Match::new(["a", "b"], rules(1, vec![Armageddon { draw_odds: 2 }])),
+            Err(InvalidDrawOdds { index: 2 })
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use thiserror::Error;
+
+use crate::common::game::Play;
+
+/// Things players can do that aren't moves in the game itself
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetaAction {
+    /// Concedes the game to the opponent
+    Resign,
+    /// Proposes ending the game as a draw, which stands until the opponent answers it or makes
+    /// a move
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+}
+
+use MetaAction::*;
+
+/// An entry in the history of a [`MetaGame`](struct@MetaGame)
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetaHistory<P, A> {
+    Action(A),
+    Meta { player: P, action: MetaAction },
+}
+
+/// The status of a [`MetaGame`](struct@MetaGame), either the wrapped game's status or how the
+/// players ended it
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetaStatus<P, S> {
+    Game(S),
+    Resigned { player: P },
+    DrawAgreed,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum MetaError<P: Debug, E: Debug> {
+    #[error("The game rejected the action {:?}", error)]
+    Game { error: E },
+    #[error("The game is already over")]
+    GameIsOver,
+    #[error("{:?} isn't playing this game", player)]
+    NotInGame { player: P },
+    #[error("There's already a draw offer from {:?} waiting on an answer", player)]
+    DrawAlreadyOffered { player: P },
+    #[error("There's no draw offer for {:?} to answer", player)]
+    NoDrawOffer { player: P },
+}
+
+use MetaError::*;
+
+/// How the players ended a game before it finished
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+enum Ending<P> {
+    Resigned(P),
+    DrawAgreed,
+}
+
+/// Wraps a two player game so players can resign and agree to draws, which the games don't model
+/// themselves. Meta actions are recorded in the history alongside the game's actions
+/// ```
+/// use lib_table_top::common::meta::{MetaAction::*, MetaGame, MetaStatus};
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = MetaGame::new(GameState::new())
+///   .apply_action((P1, (Col1, Row1))).unwrap()
+///   .apply_meta(P1, OfferDraw).unwrap();
+/// assert_eq!(game.draw_offer(), Some(&P1));
+///
+/// // Moving instead of answering declines the offer
+/// let declined = game.apply_action((P2, (Col0, Row0))).unwrap();
+/// assert_eq!(declined.draw_offer(), None);
+///
+/// let agreed = game.apply_meta(P2, AcceptDraw).unwrap();
+/// assert_eq!(agreed.status(), MetaStatus::DrawAgreed);
+/// assert!(agreed.apply_meta(P1, Resign).is_err());
+///
+/// let resigned = declined.apply_meta(P1, Resign).unwrap();
+/// assert_eq!(resigned.status(), MetaStatus::Resigned { player: P1 });
+/// assert_eq!(resigned.history().len(), 4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: Serialize, G::Player: Serialize, G::Action: Serialize",
+    deserialize = "G: Deserialize<'de>, G::Player: Deserialize<'de>, G::Action: Deserialize<'de>"
+))]
+pub struct MetaGame<G: Play> {
+    game: G,
+    history: Vec<MetaHistory<G::Player, G::Action>>,
+    draw_offer: Option<G::Player>,
+    ending: Option<Ending<G::Player>>,
+}
+
+impl<G> MetaGame<G>
+where
+    G: Play,
+    G::Player: PartialEq,
+{
+    pub fn new(game: G) -> Self {
+        Self {
+            game,
+            history: Vec::new(),
+            draw_offer: None,
+            ending: None,
+        }
+    }
+
+    /// The wrapped game
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// Every game and meta action, in order
+    pub fn history(&self) -> &[MetaHistory<G::Player, G::Action>] {
+        &self.history
+    }
+
+    /// The player whose draw offer is waiting on an answer
+    pub fn draw_offer(&self) -> Option<&G::Player> {
+        self.draw_offer.as_ref()
+    }
+
+    pub fn status(&self) -> MetaStatus<G::Player, G::Status> {
+        match &self.ending {
+            Some(Ending::Resigned(player)) => MetaStatus::Resigned {
+                player: player.clone(),
+            },
+            Some(Ending::DrawAgreed) => MetaStatus::DrawAgreed,
+            None => MetaStatus::Game(self.game.status()),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.ending.is_some() || self.game.is_over()
+    }
+
+    /// Takes an action in the wrapped game. A move by the player who was offered a draw declines
+    /// it
+    pub fn apply_action(&self, action: G::Action) -> Result<Self, MetaError<G::Player, G::Error>> {
+        if self.is_over() {
+            return Err(GameIsOver);
+        }
+
+        let player = self.game.whose_turn();
+        let game = self
+            .game
+            .apply_action(action.clone())
+            .map_err(|error| Game { error })?;
+
+        let mut meta = self.clone();
+        meta.game = game;
+        meta.history.push(MetaHistory::Action(action));
+        if meta.draw_offer.as_ref() != Some(&player) {
+            meta.draw_offer = None;
+        }
+        Ok(meta)
+    }
+
+    /// Takes a meta action for a player, who doesn't need it to be their turn
+    pub fn apply_meta(
+        &self,
+        player: G::Player,
+        action: MetaAction,
+    ) -> Result<Self, MetaError<G::Player, G::Error>> {
+        if self.is_over() {
+            return Err(GameIsOver);
+        }
+
+        if !self.game.players().contains(&player) {
+            return Err(NotInGame { player });
+        }
+
+        let mut meta = self.clone();
+
+        match (action, &self.draw_offer) {
+            (Resign, _) => {
+                meta.draw_offer = None;
+                meta.ending = Some(Ending::Resigned(player.clone()));
+            }
+            (OfferDraw, Some(offered_by)) => {
+                return Err(DrawAlreadyOffered {
+                    player: offered_by.clone(),
+                })
+            }
+            (OfferDraw, None) => meta.draw_offer = Some(player.clone()),
+            (AcceptDraw, Some(offered_by)) | (DeclineDraw, Some(offered_by))
+                if offered_by != &player =>
+            {
+                meta.draw_offer = None;
+                if action == AcceptDraw {
+                    meta.ending = Some(Ending::DrawAgreed);
+                }
+            }
+            (AcceptDraw, _) | (DeclineDraw, _) => return Err(NoDrawOffer { player }),
+        }
+
+        meta.history.push(MetaHistory::Meta { player, action });
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::marooned::{GameState, Player::*};
+    use crate::games::tic_tac_toe;
+
+    #[test]
+    fn test_draw_offers_need_the_opponent_to_answer() {
+        let game = MetaGame::new(GameState::default());
+        let offered = game.apply_meta(P1, OfferDraw).unwrap();
+
+        assert_eq!(
+            offered.apply_meta(P2, OfferDraw),
+            Err(DrawAlreadyOffered { player: P1 })
+        );
+        assert_eq!(
+            offered.apply_meta(P1, AcceptDraw),
+            Err(NoDrawOffer { player: P1 })
+        );
+        assert_eq!(
+            game.apply_meta(P2, DeclineDraw),
+            Err(NoDrawOffer { player: P2 })
+        );
+
+        let declined = offered.apply_meta(P2, DeclineDraw).unwrap();
+        assert_eq!(declined.draw_offer(), None);
+        assert!(!declined.is_over());
+
+        // The player who offered can keep playing while the offer stands
+        let action = Play::valid_actions(offered.game()).remove(0);
+        let moved = offered.apply_action(action).unwrap();
+        assert_eq!(moved.draw_offer(), Some(&P1));
+    }
+
+    #[test]
+    fn test_finished_games_reject_meta_actions() {
+        use tic_tac_toe::{Col::*, Player::*, Row::*};
+
+        let game = [
+            (Col0, Row0),
+            (Col1, Row0),
+            (Col0, Row1),
+            (Col1, Row1),
+            (Col0, Row2),
+        ]
+        .iter()
+        .zip([P1, P2].iter().cycle())
+        .fold(
+            MetaGame::new(tic_tac_toe::GameState::new()),
+            |game, (&position, &player)| game.apply_action((player, position)).unwrap(),
+        );
+
+        assert_eq!(
+            game.status(),
+            MetaStatus::Game(tic_tac_toe::Status::Win {
+                player: P1,
+                positions: [(Col0, Row0), (Col0, Row1), (Col0, Row2)]
+            })
+        );
+        assert_eq!(game.apply_meta(P2, Resign), Err(GameIsOver));
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// A fixed shape array of numbers, stored flat in row major order, so the last index changes
+/// fastest. Ready to be copied into a tensor
+/// ```
+/// use lib_table_top::common::ml::Encoding;
+///
+/// let mut encoding = Encoding::zeros(vec![2, 3]);
+/// encoding.set(&[1, 0], 1.0);
+///
+/// assert_eq!(encoding.get(&[1, 0]), 1.0);
+/// assert_eq!(encoding.values, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Encoding {
+    pub shape: Vec<usize>,
+    pub values: Vec<f32>,
+}
+
+impl Encoding {
+    pub fn zeros(shape: Vec<usize>) -> Self {
+        let len = shape.iter().product();
+
+        Self {
+            shape,
+            values: vec![0.0; len],
+        }
+    }
+
+    pub fn get(&self, index: &[usize]) -> f32 {
+        self.values[self.offset(index)]
+    }
+
+    pub fn set(&mut self, index: &[usize], value: f32) {
+        let offset = self.offset(index);
+        self.values[offset] = value;
+    }
+
+    fn offset(&self, index: &[usize]) -> usize {
+        assert_eq!(
+            index.len(),
+            self.shape.len(),
+            "The index needs one entry per dimension"
+        );
+
+        index
+            .iter()
+            .zip(&self.shape)
+            .fold(0, |offset, (&i, &size)| {
+                assert!(i < size, "Index {:?} is out of bounds of {:?}", i, size);
+                offset * size + i
+            })
+    }
+}
+
+/// Games that can be encoded as numbers for machine learning, from the perspective of the
+/// player whose turn it is. Every encoding of a game with the same settings has the same shape,
+/// and each game documents what its values mean
+pub trait Encode {
+    fn encode(&self) -> Encoding;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_are_row_major() {
+        let mut encoding = Encoding::zeros(vec![2, 3, 4]);
+        encoding.set(&[1, 2, 3], 1.0);
+        encoding.set(&[0, 1, 0], 2.0);
+
+        assert_eq!(encoding.values.len(), 24);
+        assert_eq!(encoding.values[23], 1.0);
+        assert_eq!(encoding.values[4], 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_indices_are_bounds_checked() {
+        // Would land on a valid offset if the dimensions weren't checked
+        Encoding::zeros(vec![2, 3]).get(&[0, 4]);
+    }
+}
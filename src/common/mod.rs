@@ -1,2 +1,30 @@
+pub mod ai;
+pub mod analysis;
+pub mod betting;
+pub mod clock;
+pub mod coords;
 pub mod deck;
+pub mod game;
+pub mod hotseat;
+pub mod info;
+pub mod manager;
+pub mod matches;
+pub mod meta;
+pub mod ml;
+pub mod player_info;
 pub mod rand;
+pub mod ratings;
+pub mod replay;
+pub mod results;
+pub mod savefile;
+pub mod search;
+pub mod seating;
+pub mod session;
+#[macro_use]
+pub mod settings;
+pub mod stats;
+pub mod tournament;
+pub mod tricks;
+pub mod turn;
+pub mod verify;
+pub mod views;
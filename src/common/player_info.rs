@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Display information about a player, embedded in views so clients can render the game without
+/// looking up the players separately
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerInfo {
+    /// The name to show for the player
+    pub display_name: String,
+    /// An id the client can use to look up an avatar image
+    pub avatar_id: Option<String>,
+    /// The player's rating, if the host tracks one
+    pub rating: Option<u32>,
+}
+
+impl PlayerInfo {
+    /// Creates player info with just a display name
+    /// ```
+    /// use lib_table_top::common::player_info::PlayerInfo;
+    ///
+    /// let info = PlayerInfo::new("Grant");
+    /// assert_eq!(info.display_name, "Grant");
+    /// assert_eq!(info.avatar_id, None);
+    /// assert_eq!(info.rating, None);
+    /// ```
+    pub fn new(display_name: impl Into<String>) -> Self {
+        Self {
+            display_name: display_name.into(),
+            avatar_id: None,
+            rating: None,
+        }
+    }
+}
+
+/// A lookup of the player info for the players of a game
+pub type PlayerInfoRegistry<P> = HashMap<P, PlayerInfo>;
@@ -9,6 +9,216 @@ impl RngSeed {
     pub fn into_rng(self) -> ChaCha20Rng {
         ChaCha20Rng::from_seed(self.0)
     }
+
+    /// The seed for a daily challenge, so everyone plays the same deal on the same day without
+    /// having to share a seed. The date is a number like `20261016` and the game kind is any
+    /// name that keeps different games' dailies apart, like `"klondike"`
+    ///
+    /// The scheme is part of the public API and must never change, or old dailies can't be
+    /// replayed. The key is the game kind, a `:`, then the date as eight decimal digits, as UTF-8
+    /// bytes. Each 8 byte chunk `i` of the seed is the little endian 64 bit FNV-1a hash of the
+    /// byte `i` followed by the key
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    ///
+    /// let today = RngSeed::for_date(20261016, "klondike");
+    /// assert_eq!(today, RngSeed::for_date(20261016, "klondike"));
+    /// assert_ne!(today, RngSeed::for_date(20261017, "klondike"));
+    /// assert_ne!(today, RngSeed::for_date(20261016, "yahtzee"));
+    /// ```
+    pub fn for_date(yyyy_mm_dd: u32, game_kind: &str) -> Self {
+        let key = format!("{}:{:08}", game_kind, yyyy_mm_dd);
+        let mut seed = [0u8; 32];
+
+        for (chunk, bytes) in seed.chunks_mut(8).enumerate() {
+            let hash = std::iter::once(chunk as u8)
+                .chain(key.bytes())
+                .fold(0xcbf29ce484222325, |hash: u64, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+                });
+
+            bytes.copy_from_slice(&hash.to_le_bytes());
+        }
+
+        RngSeed(seed)
+    }
+}
+
+/// What a game draws random numbers for
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RngUse {
+    /// Shuffling a fresh deck for a deal
+    Shuffle,
+    /// Shuffling cards that were already in play back into a pile to draw from
+    Reshuffle,
+    /// Rolling dice
+    Dice,
+    /// Draws made without saying what they were for
+    Other,
+}
+
+/// A run of draws made for the same use
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RngDraws {
+    pub purpose: RngUse,
+    /// How many 32 bit words were drawn, a 64 bit number counts as two
+    pub words: u64,
+}
+
+/// Generators that can be told what they're about to be used for. Plain generators ignore it,
+/// an [`RngAudit`](struct@RngAudit) records it
+pub trait LabelDraws: RngCore {
+    fn for_use(&mut self, purpose: RngUse) -> &mut Self;
+}
+
+impl LabelDraws for ChaCha20Rng {
+    fn for_use(&mut self, _purpose: RngUse) -> &mut Self {
+        self
+    }
+}
+
+/// Wraps a generator to record how much is drawn from it and what for. Replays of stored seeds
+/// only match if a game draws exactly the same numbers in the same order, so tests can pin the
+/// draws a game makes and catch rule changes that would quietly change every stored deal
+/// ```
+/// use lib_table_top::common::deck::{Deck, StandardDeck};
+/// use lib_table_top::common::rand::{LabelDraws, RngAudit, RngDraws, RngSeed, RngUse};
+/// use rand::Rng;
+///
+/// let mut rng = RngAudit::new(RngSeed([0; 32]).into_rng());
+/// StandardDeck::shuffled_with(rng.for_use(RngUse::Shuffle));
+/// let _roll: u8 = rng.for_use(RngUse::Dice).gen_range(1..=6);
+///
+/// assert_eq!(
+///     rng.draws(),
+///     &[
+///         RngDraws { purpose: RngUse::Shuffle, words: 78 },
+///         RngDraws { purpose: RngUse::Dice, words: 1 },
+///     ]
+/// );
+/// assert_eq!(rng.total(RngUse::Shuffle), 78);
+///
+/// // The audit doesn't change what's drawn
+/// let mut plain = RngSeed([0; 32]).into_rng();
+/// StandardDeck::shuffled_with(&mut plain);
+/// assert_eq!(rng.get_word_pos(), plain.get_word_pos() + 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RngAudit<R> {
+    rng: R,
+    draws: Vec<RngDraws>,
+}
+
+impl<R: RngCore> RngAudit<R> {
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            draws: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// The draws made so far, oldest first. Each call to
+    /// [`for_use`](LabelDraws::for_use) starts a new run
+    pub fn draws(&self) -> &[RngDraws] {
+        &self.draws
+    }
+
+    /// The words drawn for a use across every run
+    pub fn total(&self, purpose: RngUse) -> u64 {
+        self.draws
+            .iter()
+            .filter(|draws| draws.purpose == purpose)
+            .map(|draws| draws.words)
+            .sum()
+    }
+
+    fn record(&mut self, words: u64) {
+        match self.draws.last_mut() {
+            Some(draws) => draws.words += words,
+            None => self.draws.push(RngDraws {
+                purpose: RngUse::Other,
+                words,
+            }),
+        }
+    }
+}
+
+/// The 32 bit words it takes to fill a number of bytes
+fn words(bytes: usize) -> u64 {
+    let bytes = bytes as u64;
+    bytes.div_ceil(4)
+}
+
+impl<R> From<R> for RngAudit<R>
+where
+    R: RngCore,
+{
+    fn from(rng: R) -> Self {
+        Self::new(rng)
+    }
+}
+
+/// Reads go through to the wrapped generator, like its position in the stream
+impl<R> std::ops::Deref for RngAudit<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.rng
+    }
+}
+
+/// Audits are equal when their generators are, the record of draws is only bookkeeping
+impl<R: PartialEq> PartialEq for RngAudit<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rng == other.rng
+    }
+}
+
+impl<R: Eq> Eq for RngAudit<R> {}
+
+impl<R: RngCore> LabelDraws for RngAudit<R> {
+    fn for_use(&mut self, purpose: RngUse) -> &mut Self {
+        self.draws.push(RngDraws { purpose, words: 0 });
+        self
+    }
+}
+
+impl<R: RngCore> RngCore for RngAudit<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.record(1);
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.record(2);
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.record(words(dest.len()));
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.record(words(dest.len()));
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for RngSeed {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        any::<[u8; 32]>().prop_map(RngSeed).boxed()
+    }
 }
 
 #[cfg(test)]
@@ -26,6 +236,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn draws_before_a_use_is_given_are_other() {
+        let mut rng = RngAudit::new(RngSeed([0u8; 32]).into_rng());
+        rng.next_u64();
+        rng.fill_bytes(&mut [0; 5]);
+        rng.for_use(RngUse::Reshuffle);
+        rng.for_use(RngUse::Reshuffle).next_u32();
+
+        assert_eq!(
+            rng.draws(),
+            &[
+                RngDraws {
+                    purpose: RngUse::Other,
+                    words: 4
+                },
+                RngDraws {
+                    purpose: RngUse::Reshuffle,
+                    words: 0
+                },
+                RngDraws {
+                    purpose: RngUse::Reshuffle,
+                    words: 1
+                },
+            ]
+        );
+        assert_eq!(rng.total(RngUse::Reshuffle), 1);
+    }
+
+    #[test]
+    fn daily_seeds_never_change() {
+        // Changing this breaks every daily challenge ever played
+        let seed = RngSeed::for_date(20200101, "klondike");
+        assert_eq!(
+            serde_json::to_value(seed).unwrap(),
+            json!("50b1b7fbdbe5193d05353c068c2d8546e6c3d5d1c13dd5de0bc4b8b5ef925d84")
+        );
+    }
+
     #[test]
     fn you_can_serialize_and_deserialize() {
         let cases = [
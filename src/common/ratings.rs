@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::Hash;
+
+/// The system constant that limits how fast volatility changes, 0.3 to 1.2 are reasonable
+pub const DEFAULT_TAU: f64 = 0.5;
+
+/// Converts between the rating scale and the internal Glicko-2 scale
+const SCALE: f64 = 173.7178;
+const CONVERGENCE: f64 = 0.000001;
+
+/// A player's Glicko-2 rating. The deviation is how uncertain the rating is, it shrinks as the
+/// player plays and grows while they're inactive. The volatility is how erratic their results are
+/// ```
+/// use lib_table_top::common::ratings::{Glicko2, DEFAULT_TAU};
+///
+/// let player = Glicko2 { rating: 1500.0, deviation: 200.0, volatility: 0.06 };
+/// let opponents = [
+///   (Glicko2 { rating: 1400.0, deviation: 30.0, ..Default::default() }, 1.0),
+///   (Glicko2 { rating: 1550.0, deviation: 100.0, ..Default::default() }, 0.0),
+///   (Glicko2 { rating: 1700.0, deviation: 300.0, ..Default::default() }, 0.0),
+/// ];
+///
+/// let updated = player.update(&opponents, DEFAULT_TAU);
+/// assert_eq!(updated.rating.round(), 1464.0);
+/// assert_eq!(updated.deviation.round(), 152.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Glicko2 {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self {
+            rating: 1500.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+}
+
+impl Glicko2 {
+    /// Updates the rating after a rating period. Each game is the opponent's rating at the start
+    /// of the period and the score, 1.0 for a win, 0.5 for a draw, and 0.0 for a loss. With no
+    /// games only the deviation grows
+    pub fn update(&self, games: &[(Glicko2, f64)], tau: f64) -> Self {
+        let mu = (self.rating - 1500.0) / SCALE;
+        let phi = self.deviation / SCALE;
+
+        if games.is_empty() {
+            return Self {
+                deviation: (phi.powi(2) + self.volatility.powi(2)).sqrt() * SCALE,
+                ..*self
+            };
+        }
+
+        let mut inverse_variance = 0.0;
+        let mut improvement = 0.0;
+
+        for (opponent, score) in games {
+            let opponent_mu = (opponent.rating - 1500.0) / SCALE;
+            let g = g(opponent.deviation / SCALE);
+            let expected = 1.0 / (1.0 + (-g * (mu - opponent_mu)).exp());
+
+            inverse_variance += g.powi(2) * expected * (1.0 - expected);
+            improvement += g * (score - expected);
+        }
+
+        let variance = 1.0 / inverse_variance;
+        let delta = variance * improvement;
+        let volatility = self.next_volatility(phi, variance, delta, tau);
+
+        let pre_period_phi = (phi.powi(2) + volatility.powi(2)).sqrt();
+        let new_phi = 1.0 / (1.0 / pre_period_phi.powi(2) + 1.0 / variance).sqrt();
+        let new_mu = mu + new_phi.powi(2) * improvement;
+
+        Self {
+            rating: new_mu * SCALE + 1500.0,
+            deviation: new_phi * SCALE,
+            volatility,
+        }
+    }
+
+    /// The rating minus twice the deviation, the player is 95% likely to be at least this good.
+    /// Useful for leaderboards so new players don't top them after a couple of lucky wins
+    /// ```
+    /// use lib_table_top::common::ratings::Glicko2;
+    ///
+    /// assert_eq!(Glicko2::default().conservative_rating(), 800.0);
+    /// ```
+    pub fn conservative_rating(&self) -> f64 {
+        self.rating - 2.0 * self.deviation
+    }
+
+    /// The volatility iteration from the Glicko-2 paper (the Illinois algorithm)
+    fn next_volatility(&self, phi: f64, variance: f64, delta: f64, tau: f64) -> f64 {
+        let a = self.volatility.powi(2).ln();
+        let f = |x: f64| {
+            let ex = x.exp();
+            let denominator = 2.0 * (phi.powi(2) + variance + ex).powi(2);
+            ex * (delta.powi(2) - phi.powi(2) - variance - ex) / denominator - (x - a) / tau.powi(2)
+        };
+
+        let mut x_a = a;
+        let mut x_b = if delta.powi(2) > phi.powi(2) + variance {
+            (delta.powi(2) - phi.powi(2) - variance).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * tau
+        };
+
+        let mut f_a = f(x_a);
+        let mut f_b = f(x_b);
+
+        while (x_b - x_a).abs() > CONVERGENCE {
+            let c = x_a + (x_a - x_b) * f_a / (f_b - f_a);
+            let f_c = f(c);
+
+            if f_c * f_b <= 0.0 {
+                x_a = x_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+
+            x_b = c;
+            f_b = f_c;
+        }
+
+        (x_a / 2.0).exp()
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi.powi(2) / PI.powi(2)).sqrt()
+}
+
+/// Updates everyone's ratings after a rating period. Games are the two players and the score of
+/// the first player, players without a rating start with the default one. Everyone is rated
+/// against the ratings from the start of the period, so the order of the games doesn't matter
+/// ```
+/// use lib_table_top::common::ratings::{rating_period, Glicko2, DEFAULT_TAU};
+/// use std::collections::HashMap;
+///
+/// let mut ratings = HashMap::new();
+/// ratings.insert("idle", Glicko2::default());
+///
+/// let ratings = rating_period(&ratings, &[("ann", "bob", 1.0)], DEFAULT_TAU);
+/// assert!(ratings["ann"].rating > 1500.0);
+/// assert!(ratings["bob"].rating < 1500.0);
+/// assert!(ratings["idle"].deviation > 350.0);
+/// ```
+pub fn rating_period<P: Clone + Eq + Hash>(
+    ratings: &HashMap<P, Glicko2>,
+    games: &[(P, P, f64)],
+    tau: f64,
+) -> HashMap<P, Glicko2> {
+    let rating_of = |player: &P| ratings.get(player).copied().unwrap_or_default();
+    let mut results: HashMap<P, Vec<(Glicko2, f64)>> = ratings
+        .keys()
+        .map(|player| (player.clone(), Vec::new()))
+        .collect();
+
+    for (first, second, score) in games {
+        results
+            .entry(first.clone())
+            .or_default()
+            .push((rating_of(second), *score));
+        results
+            .entry(second.clone())
+            .or_default()
+            .push((rating_of(first), 1.0 - score));
+    }
+
+    results
+        .into_iter()
+        .map(|(player, games)| {
+            let updated = rating_of(&player).update(&games, tau);
+            (player, updated)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_from_the_glicko2_paper() {
+        let player = Glicko2 {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+        let opponent = |rating, deviation| Glicko2 {
+            rating,
+            deviation,
+            ..Default::default()
+        };
+        let games = [
+            (opponent(1400.0, 30.0), 1.0),
+            (opponent(1550.0, 100.0), 0.0),
+            (opponent(1700.0, 300.0), 0.0),
+        ];
+
+        let updated = player.update(&games, 0.5);
+        assert!((updated.rating - 1464.06).abs() < 0.01);
+        assert!((updated.deviation - 151.52).abs() < 0.01);
+        assert!((updated.volatility - 0.05999).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_draws_between_equals_only_shrink_the_deviation() {
+        let ratings = rating_period(&HashMap::new(), &[(1, 2, 0.5)], DEFAULT_TAU);
+
+        for player in [1, 2] {
+            assert!((ratings[&player].rating - 1500.0).abs() < 0.0001);
+            assert!(ratings[&player].deviation < 350.0);
+        }
+    }
+
+    #[test]
+    fn test_ratings_serialize() {
+        let rating = Glicko2::default();
+        let json = serde_json::to_string(&rating).unwrap();
+        assert_eq!(
+            json,
+            r#"{"rating":1500.0,"deviation":350.0,"volatility":0.06}"#
+        );
+        assert_eq!(serde_json::from_str::<Glicko2>(&json).unwrap(), rating);
+    }
+}
@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Games that can be stepped through action by action
+pub trait Replayable: Clone + Sized {
+    type Action: Clone;
+    type Error;
+
+    /// The game as it was before any actions were taken
+    fn initial(&self) -> Self;
+
+    /// The actions that have been taken on the game, in order
+    fn actions(&self) -> Vec<Self::Action>;
+
+    /// Takes an action on the game, returns an error if it's illegal
+    fn apply(&self, action: Self::Action) -> Result<Self, Self::Error>;
+}
+
+/// A scrubber over the history of a game, for analysis and spectating. The turn is the number of
+/// actions that have been applied, turn 0 is the start of the game
+/// ```
+/// use lib_table_top::common::replay::Replay;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap();
+///
+/// let mut replay = Replay::new(&game).unwrap();
+/// assert_eq!(replay.turn(), 0);
+/// assert_eq!(replay.len(), 2);
+/// assert_eq!(replay.current_state(), &GameState::new());
+///
+/// assert!(replay.step_forward().is_some());
+/// assert_eq!(replay.current_state().board()[Col0][Row0], Some(P1));
+///
+/// assert_eq!(replay.seek(2), Some(&game));
+/// assert_eq!(replay.step_forward(), None);
+///
+/// assert!(replay.step_back().is_some());
+/// assert_eq!(replay.turn(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replay<G: Replayable> {
+    actions: Vec<G::Action>,
+    states: Vec<G>,
+    turn: usize,
+    annotations: BTreeMap<usize, Vec<String>>,
+    variations: BTreeMap<usize, Vec<Vec<G::Action>>>,
+}
+
+/// A replay's actions along with its comments and variations, keyed by turn, for saving
+/// commented games. See [`Replay::transcript`](Replay::transcript)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript<A> {
+    pub actions: Vec<A>,
+    #[serde(default = "BTreeMap::new", skip_serializing_if = "BTreeMap::is_empty")]
+    pub comments: BTreeMap<usize, Vec<String>>,
+    #[serde(default = "BTreeMap::new", skip_serializing_if = "BTreeMap::is_empty")]
+    pub variations: BTreeMap<usize, Vec<Vec<A>>>,
+}
+
+impl<G: Replayable> Replay<G> {
+    /// Makes a replay of a game's history, starting at turn 0
+    pub fn new(game: &G) -> Result<Self, G::Error> {
+        let actions = game.actions();
+        let mut states = vec![game.initial()];
+
+        for action in actions.iter().cloned() {
+            let next = states[states.len() - 1].apply(action)?;
+            states.push(next);
+        }
+
+        Ok(Self {
+            actions,
+            states,
+            turn: 0,
+            annotations: BTreeMap::new(),
+            variations: BTreeMap::new(),
+        })
+    }
+
+    /// The number of actions in the replay
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Whether there are any actions in the replay
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// The current turn of the replay
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// The game as of the current turn
+    pub fn current_state(&self) -> &G {
+        &self.states[self.turn]
+    }
+
+    /// Every action in the replay, in order
+    pub fn actions(&self) -> &[G::Action] {
+        &self.actions
+    }
+
+    /// The game as of a turn, `None` if the turn is past the end of the replay
+    pub fn state(&self, turn: usize) -> Option<&G> {
+        self.states.get(turn)
+    }
+
+    /// The action that led to the current turn, `None` at the start of the game
+    pub fn last_action(&self) -> Option<&G::Action> {
+        self.turn
+            .checked_sub(1)
+            .and_then(|index| self.actions.get(index))
+    }
+
+    /// Moves to a turn, returns `None` and stays put if the turn is past the end of the replay
+    pub fn seek(&mut self, turn: usize) -> Option<&G> {
+        if turn < self.states.len() {
+            self.turn = turn;
+            Some(self.current_state())
+        } else {
+            None
+        }
+    }
+
+    /// Moves forward one turn, returns `None` if already at the end
+    pub fn step_forward(&mut self) -> Option<&G> {
+        self.seek(self.turn + 1)
+    }
+
+    /// Moves back one turn, returns `None` if already at the start
+    pub fn step_back(&mut self) -> Option<&G> {
+        let turn = self.turn.checked_sub(1)?;
+        self.seek(turn)
+    }
+
+    /// Adds a note to the current turn
+    /// ```
+    /// use lib_table_top::common::replay::Replay;
+    /// use lib_table_top::games::marooned::GameState;
+    ///
+    /// let game: GameState = Default::default();
+    /// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+    ///
+    /// let mut replay = Replay::new(&game).unwrap();
+    /// replay.step_forward();
+    /// replay.annotate("An aggressive opening");
+    ///
+    /// assert_eq!(replay.annotations(1), &["An aggressive opening".to_string()]);
+    /// assert!(replay.annotations(0).is_empty());
+    /// ```
+    pub fn annotate(&mut self, note: impl Into<String>) {
+        self.annotations
+            .entry(self.turn)
+            .or_default()
+            .push(note.into());
+    }
+
+    /// The notes for a turn
+    pub fn annotations(&self, turn: usize) -> &[String] {
+        self.annotations
+            .get(&turn)
+            .map(|notes| notes.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// An iterator over the turns that have notes, along with their notes
+    pub fn all_annotations(&self) -> impl Iterator<Item = (usize, &[String])> + '_ {
+        self.annotations
+            .iter()
+            .map(|(&turn, notes)| (turn, notes.as_slice()))
+    }
+
+    /// Adds a note to a turn, returns `false` if the turn is past the end of the replay
+    pub fn add_comment(&mut self, turn: usize, text: impl Into<String>) -> bool {
+        if turn >= self.states.len() {
+            return false;
+        }
+
+        self.annotations.entry(turn).or_default().push(text.into());
+        true
+    }
+
+    /// Adds a line of play that could have been played from a turn instead of what was. The
+    /// line is checked against the game, returning its first illegal action's error, and
+    /// `Ok(false)` if the turn is past the end of the replay
+    /// ```
+    /// use lib_table_top::common::replay::{Replay, Transcript};
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new()
+    ///   .apply_action((P1, (Col0, Row0))).unwrap()
+    ///   .apply_action((P2, (Col1, Row0))).unwrap();
+    ///
+    /// let mut replay = Replay::new(&game).unwrap();
+    /// replay.add_comment(1, "An edge is a mistake");
+    /// assert_eq!(replay.add_variation(1, vec![(P2, (Col1, Row1))]), Ok(true));
+    /// assert!(replay.add_variation(1, vec![(P1, (Col2, Row2))]).is_err());
+    ///
+    /// let transcript = replay.transcript();
+    /// let saved = serde_json::to_string(&transcript).unwrap();
+    /// let loaded: Transcript<_> = serde_json::from_str(&saved).unwrap();
+    ///
+    /// let reloaded = Replay::from_transcript(&GameState::new(), loaded).unwrap();
+    /// assert_eq!(reloaded.variations(1), &[vec![(P2, (Col1, Row1))]]);
+    /// assert_eq!(reloaded.annotations(1), &["An edge is a mistake".to_string()]);
+    /// assert_eq!(reloaded.transcript(), transcript);
+    /// ```
+    pub fn add_variation(&mut self, turn: usize, line: Vec<G::Action>) -> Result<bool, G::Error> {
+        let start = match self.states.get(turn) {
+            Some(state) => state.clone(),
+            None => return Ok(false),
+        };
+
+        line.iter()
+            .cloned()
+            .try_fold(start, |state, action| state.apply(action))?;
+
+        self.variations.entry(turn).or_default().push(line);
+        Ok(true)
+    }
+
+    /// The variations from a turn, in the order they were added
+    pub fn variations(&self, turn: usize) -> &[Vec<G::Action>] {
+        self.variations
+            .get(&turn)
+            .map(|lines| lines.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The actions, comments, and variations of the replay, for saving
+    pub fn transcript(&self) -> Transcript<G::Action> {
+        Transcript {
+            actions: self.actions.clone(),
+            comments: self.annotations.clone(),
+            variations: self.variations.clone(),
+        }
+    }
+
+    /// Rebuilds a replay from a transcript, playing its actions from the start of the game. The
+    /// variations are checked like [`add_variation`](Replay::add_variation) checks them
+    pub fn from_transcript(game: &G, transcript: Transcript<G::Action>) -> Result<Self, G::Error> {
+        let mut states = vec![game.initial()];
+
+        for action in transcript.actions.iter().cloned() {
+            let next = states[states.len() - 1].apply(action)?;
+            states.push(next);
+        }
+
+        let mut replay = Self {
+            actions: transcript.actions,
+            states,
+            turn: 0,
+            annotations: BTreeMap::new(),
+            variations: BTreeMap::new(),
+        };
+
+        for (turn, comments) in transcript.comments {
+            for comment in comments {
+                replay.add_comment(turn, comment);
+            }
+        }
+
+        for (turn, lines) in transcript.variations {
+            for line in lines {
+                replay.add_variation(turn, line)?;
+            }
+        }
+
+        Ok(replay)
+    }
+}
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The games in the crate, for telling results apart once they're stored together
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameKind {
+    CrazyEights,
+    Marooned,
+    Spades,
+    TicTacToe,
+}
+
+/// How a game ended
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Termination {
+    /// The game was played until the rules ended it
+    Completed,
+    /// A player gave up
+    Resignation,
+    /// A player ran out of time
+    Timeout,
+    /// The game was stopped without a result that counts
+    Abandoned,
+}
+
+/// How a game went for a single player
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// A player's outcome, along with their score in games that keep one
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerResult<P> {
+    pub player: P,
+    pub outcome: Outcome,
+    pub score: Option<i64>,
+    /// Where the player finished, counting from 1. Players who tie share a place, see
+    /// [`rank_by`](fn@rank_by)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub place: Option<u32>,
+}
+
+/// A summary of a finished game, the same shape for every game so results can be stored and
+/// queried together. Games don't keep time, so the duration is only set if the host adds it
+/// ```
+/// use lib_table_top::common::results::{GameKind, Outcome, Summarize, Termination};
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row0))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap()
+///   .apply_action((P1, (Col0, Row2))).unwrap();
+///
+/// let result = game.game_result().unwrap();
+/// assert_eq!(result.kind, GameKind::TicTacToe);
+/// assert_eq!(result.move_count, 5);
+/// assert_eq!(result.termination, Termination::Completed);
+/// assert_eq!(result.winners().collect::<Vec<_>>(), vec![&P1]);
+/// assert_eq!(result.players[1].outcome, Outcome::Loss);
+///
+/// // Games that are still going don't have a result
+/// assert_eq!(GameState::new().game_result(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameResult<P> {
+    pub kind: GameKind,
+    /// A hash of the settings, stable across builds so results from the same setup can be grouped
+    pub settings_hash: u64,
+    pub players: Vec<PlayerResult<P>>,
+    pub move_count: usize,
+    pub duration: Option<Duration>,
+    pub termination: Termination,
+}
+
+impl<P> GameResult<P> {
+    /// Sets how long the game took
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets how the game ended
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    /// The players who won
+    pub fn winners(&self) -> impl Iterator<Item = &P> {
+        self.players
+            .iter()
+            .filter(|result| result.outcome == Outcome::Win)
+            .map(|result| &result.player)
+    }
+
+    /// The finishing order, best first. Results without places, like ones stored before games
+    /// recorded them, are ranked by outcome with winners and draws sharing first place
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::common::results::Summarize;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Three);
+    /// let mut game = GameState::new(Arc::new(settings));
+    /// while game.game_result().is_none() {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let mut result = game.game_result().unwrap();
+    /// let rankings = result.rankings();
+    /// assert_eq!(rankings[0], (result.winners().next().unwrap(), 1));
+    /// assert_eq!(rankings.len(), 3);
+    ///
+    /// for player in &mut result.players {
+    ///     player.place = None;
+    /// }
+    /// assert!(result.rankings().iter().skip(1).all(|&(_, place)| place == 2));
+    /// ```
+    pub fn rankings(&self) -> Vec<(&P, u32)> {
+        let places: Option<Vec<(&P, u32)>> = self
+            .players
+            .iter()
+            .map(|result| Some((&result.player, result.place?)))
+            .collect();
+
+        match places {
+            Some(places) => rank_by(places),
+            None => rank_by(self.players.iter().map(|result| {
+                let lost = result.outcome == Outcome::Loss;
+                (&result.player, lost)
+            })),
+        }
+    }
+}
+
+/// Games that can summarize themselves once they're over
+pub trait Summarize {
+    type Player;
+
+    /// The result of the game, `None` while the game is still in progress
+    fn game_result(&self) -> Option<GameResult<Self::Player>>;
+}
+
+/// Hashes settings with FNV-1a over their JSON, unlike `std`'s hasher it gives the same hash on
+/// every platform and version
+/// ```
+/// use lib_table_top::common::results::settings_hash;
+///
+/// assert_eq!(settings_hash(&()), settings_hash(&()));
+/// assert_ne!(settings_hash(&1), settings_hash(&2));
+/// ```
+pub fn settings_hash<S: Serialize>(settings: &S) -> u64 {
+    let bytes = serde_json::to_vec(settings).expect("Settings can always be serialized");
+
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Makes the results for games where one player wins and everyone else loses
+pub(crate) fn winner_takes_all<P: PartialEq>(
+    players: impl Iterator<Item = P>,
+    winner: P,
+) -> Vec<PlayerResult<P>> {
+    players
+        .map(|player| {
+            let won = player == winner;
+
+            PlayerResult {
+                outcome: if won { Outcome::Win } else { Outcome::Loss },
+                place: Some(if won { 1 } else { 2 }),
+                player,
+                score: None,
+            }
+        })
+        .collect()
+}
+
+/// Places players by a key, lowest first, for games with a full finishing order. Players with
+/// the same key share a place and the places after them are skipped, so the order goes 1, 2, 2,
+/// 4 and not 1, 2, 2, 3
+/// ```
+/// use lib_table_top::common::results::rank_by;
+///
+/// let penalties = vec![("ann", 12), ("bob", 3), ("cat", 12), ("dan", 30)];
+/// assert_eq!(
+///     rank_by(penalties),
+///     vec![("bob", 1), ("ann", 2), ("cat", 2), ("dan", 4)]
+/// );
+/// ```
+pub fn rank_by<P, K: Ord>(entries: impl IntoIterator<Item = (P, K)>) -> Vec<(P, u32)> {
+    let mut entries: Vec<(P, K)> = entries.into_iter().collect();
+    entries.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+    let mut places: Vec<(P, u32)> = Vec::with_capacity(entries.len());
+    let mut previous: Option<K> = None;
+    for (index, (player, key)) in entries.into_iter().enumerate() {
+        let place = match (&previous, places.last()) {
+            (Some(previous), Some(&(_, place))) if previous == &key => place,
+            _ => index as u32 + 1,
+        };
+        places.push((player, place));
+        previous = Some(key);
+    }
+
+    places
+}
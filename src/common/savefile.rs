@@ -0,0 +1,261 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::common::results::{settings_hash, GameKind};
+
+/// The version of the save file format written by this build
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The things that can go wrong loading a save file
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SaveFileError {
+    #[error("The save file couldn't be read: {}", message)]
+    Malformed { message: String },
+    #[error("Save file version {:?} isn't supported", version)]
+    UnsupportedVersion { version: u32 },
+    #[error(
+        "The save file's checksum is {:?} but its contents hash to {:?}",
+        expected,
+        actual
+    )]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("Expected a {:?} save file but it's for {:?}", expected, found)]
+    WrongGameKind { expected: GameKind, found: GameKind },
+}
+
+use SaveFileError::*;
+
+impl From<serde_json::Error> for SaveFileError {
+    fn from(error: serde_json::Error) -> Self {
+        Malformed {
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Contents {
+    version: u32,
+    kind: GameKind,
+    game: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clock: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seating: Option<Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    #[serde(flatten)]
+    contents: Contents,
+    checksum: String,
+}
+
+/// One document holding everything needed to pick a game back up: which game it is, its
+/// snapshot (the settings and history), and optionally the clock, the seating, and any metadata
+/// the host wants to keep. Saved files carry a checksum of their contents, so files that were
+/// edited or corrupted are refused when loaded
+/// ```
+/// use lib_table_top::common::results::GameKind;
+/// use lib_table_top::common::savefile::SaveFile;
+/// use lib_table_top::common::seating::Seating;
+/// use lib_table_top::games::marooned::{GameState, Player::*, Snapshot};
+///
+/// let game: GameState = Default::default();
+/// let seating = Seating::new(vec![P1, P2], vec!["alice".to_string(), "bob".to_string()]).unwrap();
+///
+/// let saved = SaveFile::new(GameKind::Marooned, &game.snapshot())
+///   .with_seating(&seating)
+///   .with_metadata("table", "7")
+///   .save();
+///
+/// let loaded = SaveFile::load(&saved).unwrap();
+/// let snapshot: Snapshot = loaded.game(GameKind::Marooned).unwrap();
+/// assert_eq!(GameState::from_snapshot(snapshot), game);
+/// assert_eq!(loaded.seating(), Ok(Some(seating)));
+/// assert_eq!(loaded.metadata()["table"], "7");
+///
+/// // Editing the file breaks the checksum
+/// assert!(SaveFile::load(&saved.replace("alice", "mallory")).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveFile {
+    contents: Contents,
+}
+
+impl SaveFile {
+    /// Starts a save file from a game's snapshot, like `GameState::snapshot` returns
+    pub fn new<S: Serialize>(kind: GameKind, snapshot: &S) -> Self {
+        Self {
+            contents: Contents {
+                version: FORMAT_VERSION,
+                kind,
+                game: to_value(snapshot),
+                clock: None,
+                seating: None,
+                metadata: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Saves the state of the players' clocks, in whatever shape the host keeps them
+    pub fn with_clock<C: Serialize>(mut self, clock: &C) -> Self {
+        self.contents.clock = Some(to_value(clock));
+        self
+    }
+
+    /// Saves who is sitting in which seat, usually a
+    /// [`Seating`](struct@crate::common::seating::Seating)
+    pub fn with_seating<T: Serialize>(mut self, seating: &T) -> Self {
+        self.contents.seating = Some(to_value(seating));
+        self
+    }
+
+    /// Saves a piece of metadata, replacing anything already saved under the key
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.contents.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn kind(&self) -> GameKind {
+        self.contents.kind
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.contents.metadata
+    }
+
+    /// The game's snapshot, checking that the save file is for the expected game
+    pub fn game<S: DeserializeOwned>(&self, kind: GameKind) -> Result<S, SaveFileError> {
+        if kind != self.contents.kind {
+            return Err(WrongGameKind {
+                expected: kind,
+                found: self.contents.kind,
+            });
+        }
+
+        Ok(S::deserialize(&self.contents.game)?)
+    }
+
+    /// The clock, if one was saved
+    pub fn clock<C: DeserializeOwned>(&self) -> Result<Option<C>, SaveFileError> {
+        from_optional_value(&self.contents.clock)
+    }
+
+    /// The seating, if one was saved
+    pub fn seating<T: DeserializeOwned>(&self) -> Result<Option<T>, SaveFileError> {
+        from_optional_value(&self.contents.seating)
+    }
+
+    /// Writes the save file as JSON, along with the checksum of its contents
+    pub fn save(&self) -> String {
+        let document = Document {
+            contents: self.contents.clone(),
+            checksum: checksum(&self.contents),
+        };
+
+        serde_json::to_string(&document).expect("Save files can always be serialized")
+    }
+
+    /// Reads a save file written by [`save`](SaveFile::save), checking its version and checksum
+    pub fn load(saved: &str) -> Result<Self, SaveFileError> {
+        let document: Value = serde_json::from_str(saved)?;
+
+        // Checked first so files from newer builds get a useful error, even if their shape changed
+        if let Some(version) = document.get("version").and_then(Value::as_u64) {
+            if version != u64::from(FORMAT_VERSION) {
+                return Err(UnsupportedVersion {
+                    version: version as u32,
+                });
+            }
+        }
+
+        let Document {
+            contents,
+            checksum: expected,
+        } = serde_json::from_value(document)?;
+        let actual = checksum(&contents);
+
+        if expected != actual {
+            return Err(ChecksumMismatch { expected, actual });
+        }
+
+        Ok(Self { contents })
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("Game state can always be serialized")
+}
+
+fn from_optional_value<T: DeserializeOwned>(
+    value: &Option<Value>,
+) -> Result<Option<T>, SaveFileError> {
+    match value {
+        Some(value) => Ok(Some(T::deserialize(value)?)),
+        None => Ok(None),
+    }
+}
+
+/// The 64 bit FNV-1a hash of the contents as JSON, in hex. Object keys are always written in
+/// sorted order, so the same contents always have the same checksum
+fn checksum(contents: &Contents) -> String {
+    format!("{:016x}", settings_hash(contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::clock::TimeoutPolicy;
+    use crate::games::tic_tac_toe::{Col::*, GameState, Player::*, Row::*, Snapshot};
+    use serde_json::json;
+
+    fn saved_game() -> String {
+        let game = GameState::new().apply_action((P1, (Col1, Row1))).unwrap();
+        SaveFile::new(GameKind::TicTacToe, &game.snapshot())
+            .with_clock(&TimeoutPolicy::SkipTurn)
+            .save()
+    }
+
+    #[test]
+    fn test_loading_the_clock_and_game() {
+        let loaded = SaveFile::load(&saved_game()).unwrap();
+
+        assert_eq!(loaded.kind(), GameKind::TicTacToe);
+        assert_eq!(loaded.clock(), Ok(Some(TimeoutPolicy::SkipTurn)));
+        assert_eq!(loaded.seating::<Value>(), Ok(None));
+        assert!(loaded.metadata().is_empty());
+        assert!(loaded.game::<Snapshot>(GameKind::TicTacToe).is_ok());
+        assert_eq!(
+            loaded.game::<Snapshot>(GameKind::Marooned),
+            Err(WrongGameKind {
+                expected: GameKind::Marooned,
+                found: GameKind::TicTacToe
+            })
+        );
+    }
+
+    #[test]
+    fn test_refusing_bad_save_files() {
+        let mut document: Value = serde_json::from_str(&saved_game()).unwrap();
+
+        document["clock"] = json!("Forfeit");
+        assert!(matches!(
+            SaveFile::load(&document.to_string()),
+            Err(ChecksumMismatch { .. })
+        ));
+
+        document["version"] = json!(2);
+        assert_eq!(
+            SaveFile::load(&document.to_string()),
+            Err(UnsupportedVersion { version: 2 })
+        );
+
+        assert!(matches!(SaveFile::load("{}"), Err(Malformed { .. })));
+    }
+}
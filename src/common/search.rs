@@ -0,0 +1,334 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// The score of a won game, wins found sooner score a little higher
+pub const WIN: i32 = 1_000_000;
+
+/// Two player, perfect information games that can be searched
+pub trait Searchable: Clone {
+    type Action: Clone + PartialEq;
+
+    /// The actions the player to move can take, empty once the game is over
+    fn legal_actions(&self) -> Vec<Self::Action>;
+
+    /// Takes an action returned by `legal_actions`
+    fn play(&self, action: Self::Action) -> Self;
+
+    /// How good the game is for the player to move. Won games should be worth `WIN` and lost
+    /// games `-WIN`, anything in between is a heuristic
+    fn evaluate(&self) -> i32;
+}
+
+/// The outcome of a search, along with how much work went into it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult<A> {
+    /// The best action found, `None` if the game is already over
+    pub best_action: Option<A>,
+    /// The score of the best action for the player to move
+    pub score: i32,
+    /// The deepest search that finished before the budget ran out
+    pub depth: u32,
+    /// The number of positions visited, including ones from searches that didn't finish
+    pub nodes: u64,
+    /// The best line of play for both players, starting with the best action
+    pub principal_variation: Vec<A>,
+}
+
+/// How far along a search is, reported after each depth finishes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub depth: u32,
+    pub nodes: u64,
+    pub score: i32,
+}
+
+/// Lets a long running search be stopped early and watched from another thread, the search
+/// checks in every so often and stops as if it ran out of time
+/// ```
+/// use lib_table_top::common::search::{search_with_control, Progress, SearchControl};
+/// use lib_table_top::games::marooned::GameState;
+/// use std::time::Duration;
+///
+/// fn report(progress: Progress) {
+///   println!("depth {} after {} nodes", progress.depth, progress.nodes);
+/// }
+///
+/// let control = SearchControl::new().with_progress(report);
+/// control.stop();
+///
+/// // A stopped search still finishes the first ply, so there's always a move
+/// let game: GameState = Default::default();
+/// let result = search_with_control(&game, Duration::from_secs(60), &control);
+/// assert_eq!(result.depth, 1);
+/// assert!(result.best_action.is_some());
+/// ```
+#[derive(Debug, Default)]
+pub struct SearchControl {
+    pub stop: AtomicBool,
+    pub progress: Option<fn(Progress)>,
+}
+
+impl SearchControl {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Calls a function with the progress after each depth finishes
+    pub fn with_progress(self, progress: fn(Progress)) -> Self {
+        Self {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
+    /// Asks the search to stop, safe to call from another thread
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+/// Searches deeper and deeper until the time budget runs out, returning the best action from the
+/// deepest search that finished. The first ply is always searched so there is a move to make even
+/// with no budget. Searching stops early when the result is a forced win or loss
+/// ```
+/// use lib_table_top::common::search::{search_with_budget, WIN};
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+/// use std::time::Duration;
+///
+/// // P1 can win by taking the bottom left corner
+/// let game = GameState::new()
+///   .apply_action((P1, (Col0, Row0))).unwrap()
+///   .apply_action((P2, (Col1, Row0))).unwrap()
+///   .apply_action((P1, (Col0, Row1))).unwrap()
+///   .apply_action((P2, (Col1, Row1))).unwrap();
+///
+/// let result = search_with_budget(&game, Duration::from_secs(1));
+/// assert_eq!(result.best_action, Some((P1, (Col0, Row2))));
+/// assert!(result.score > WIN - 10);
+/// ```
+pub fn search_with_budget<G: Searchable>(game: &G, budget: Duration) -> SearchResult<G::Action> {
+    search_with_control(game, budget, &SearchControl::new())
+}
+
+/// [`search_with_budget`](fn@search_with_budget) that can also be stopped early or report its
+/// progress, see [`SearchControl`](struct@SearchControl)
+pub fn search_with_control<G: Searchable>(
+    game: &G,
+    budget: Duration,
+    control: &SearchControl,
+) -> SearchResult<G::Action> {
+    let deadline = Instant::now() + budget;
+    let mut search = Search {
+        control,
+        deadline,
+        nodes: 0,
+        out_of_time: false,
+        depth_limited: false,
+    };
+
+    let mut result = SearchResult {
+        best_action: None,
+        score: game.evaluate(),
+        depth: 0,
+        nodes: 0,
+        principal_variation: Vec::new(),
+    };
+
+    for depth in 1.. {
+        // The first ply always finishes, so there is always an action to return
+        search.out_of_time = false;
+        search.depth_limited = false;
+        let check_time = depth > 1;
+        let (score, line) = search.negamax(
+            game,
+            depth,
+            -WIN - 1,
+            WIN + 1,
+            &result.principal_variation,
+            check_time,
+        );
+
+        if search.out_of_time {
+            break;
+        }
+
+        result = SearchResult {
+            best_action: line.first().cloned(),
+            score,
+            depth,
+            nodes: search.nodes,
+            principal_variation: line,
+        };
+
+        if let Some(progress) = control.progress {
+            progress(Progress {
+                depth,
+                nodes: search.nodes,
+                score,
+            });
+        }
+
+        // Searching deeper won't change anything once every line reaches the end of the game
+        if score.abs() > WIN / 2
+            || !search.depth_limited
+            || control.is_stopped()
+            || Instant::now() >= deadline
+        {
+            break;
+        }
+    }
+
+    result.nodes = search.nodes;
+    result
+}
+
+struct Search<'a> {
+    control: &'a SearchControl,
+    deadline: Instant,
+    nodes: u64,
+    out_of_time: bool,
+    depth_limited: bool,
+}
+
+impl Search<'_> {
+    fn negamax<G: Searchable>(
+        &mut self,
+        game: &G,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+        previous_line: &[G::Action],
+        check_time: bool,
+    ) -> (i32, Vec<G::Action>) {
+        self.nodes += 1;
+
+        if check_time
+            && self.nodes.is_multiple_of(1024)
+            && (self.control.is_stopped() || Instant::now() >= self.deadline)
+        {
+            self.out_of_time = true;
+        }
+
+        let mut actions = game.legal_actions();
+
+        if actions.is_empty() || self.out_of_time {
+            return (game.evaluate(), Vec::new());
+        }
+
+        if depth == 0 {
+            self.depth_limited = true;
+            return (game.evaluate(), Vec::new());
+        }
+
+        // Searching the best action from the last search first makes the most of pruning
+        if let Some(best) = previous_line.first() {
+            if let Some(index) = actions.iter().position(|action| action == best) {
+                actions.swap(0, index);
+            }
+        }
+
+        let mut best = (-WIN - 1, Vec::new());
+
+        for (index, action) in actions.into_iter().enumerate() {
+            let next_line = if index == 0 && !previous_line.is_empty() {
+                &previous_line[1..]
+            } else {
+                &[]
+            };
+
+            let (score, mut line) = self.negamax(
+                &game.play(action.clone()),
+                depth - 1,
+                -beta,
+                -alpha,
+                next_line,
+                check_time,
+            );
+
+            // Wins further away are worth a little less, so the quickest win is preferred
+            let score = match -score {
+                score if score > WIN / 2 => score - 1,
+                score if score < -WIN / 2 => score + 1,
+                score => score,
+            };
+
+            if score > best.0 {
+                line.insert(0, action);
+                best = (score, line);
+            }
+
+            alpha = alpha.max(score);
+
+            if alpha >= beta || self.out_of_time {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::{marooned, tic_tac_toe};
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn test_tic_tac_toe_is_a_draw() {
+        let result = search_with_budget(&tic_tac_toe::GameState::new(), Duration::from_secs(60));
+
+        assert_eq!(result.score, 0);
+        assert_eq!(result.depth, 9);
+        assert_eq!(result.principal_variation.len(), 9);
+        assert_eq!(
+            result.best_action,
+            result.principal_variation.first().cloned()
+        );
+    }
+
+    #[test]
+    fn test_there_is_always_a_move_even_without_a_budget() {
+        let game: marooned::GameState = Default::default();
+        let result = search_with_budget(&game, Duration::from_secs(0));
+
+        assert_eq!(result.depth, 1);
+        assert!(game.apply_action(result.best_action.unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_progress_is_reported_for_every_depth() {
+        static DEPTHS: AtomicU32 = AtomicU32::new(0);
+
+        fn record(progress: Progress) {
+            DEPTHS.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(progress.depth, DEPTHS.load(Ordering::SeqCst));
+        }
+
+        let control = SearchControl::new().with_progress(record);
+        let result = search_with_control(
+            &tic_tac_toe::GameState::new(),
+            Duration::from_secs(60),
+            &control,
+        );
+
+        assert_eq!(DEPTHS.load(Ordering::SeqCst), result.depth);
+    }
+
+    #[test]
+    fn test_finished_games_have_no_best_action() {
+        let game = marooned::SettingsBuilder::new()
+            .rows(1)
+            .cols(2)
+            .build_game()
+            .unwrap();
+        let result = search_with_budget(&game, Duration::from_millis(10));
+
+        assert_eq!(result.best_action, None);
+        assert_eq!(result.score, -WIN);
+    }
+}
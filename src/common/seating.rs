@@ -0,0 +1,202 @@
+use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use thiserror::Error;
+
+use crate::common::rand::RngSeed;
+
+/// The various errors that can happen when seating players
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SeatingError<Id: Debug, P: Debug> {
+    /// Every seat needs exactly one id
+    #[error("There are {:?} seats but {:?} ids", seats, ids)]
+    WrongNumberOfIds { seats: usize, ids: usize },
+    /// An id can only sit in one seat at a time
+    #[error("{:?} is already seated as {:?}", id, player)]
+    IdAlreadySeated { id: Id, player: P },
+    /// Substitutions can only be made for seats that exist
+    #[error("There is no seat for {:?}", player)]
+    NoSeatForPlayer { player: P },
+}
+
+use SeatingError::*;
+
+/// Maps external ids (user ids, connection ids, etc) to the `Player` slots of a game
+/// ```
+/// use lib_table_top::common::seating::Seating;
+/// use lib_table_top::games::marooned::Player::*;
+///
+/// let seating = Seating::new(vec![P1, P2], vec!["alice", "bob"]).unwrap();
+/// assert_eq!(seating.player(&"alice"), Some(P1));
+/// assert_eq!(seating.id(P2), Some(&"bob"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seating<Id, P> {
+    seats: Vec<(P, Id)>,
+}
+
+impl<Id: Clone + Debug + PartialEq, P: Copy + Debug + PartialEq> Seating<Id, P> {
+    /// Seats the ids in order, the first id gets the first player and so on
+    /// ```
+    /// use lib_table_top::common::seating::{Seating, SeatingError::*};
+    /// use lib_table_top::games::crazy_eights::{NumberOfPlayers, Player::*};
+    ///
+    /// let players = NumberOfPlayers::Three.players();
+    ///
+    /// assert!(Seating::new(players.clone(), vec![1, 2, 3]).is_ok());
+    /// assert_eq!(
+    ///   Seating::new(players.clone(), vec![1, 2]),
+    ///   Err(WrongNumberOfIds { seats: 3, ids: 2 })
+    /// );
+    /// assert_eq!(
+    ///   Seating::new(players, vec![1, 2, 1]),
+    ///   Err(IdAlreadySeated { id: 1, player: P1 })
+    /// );
+    /// ```
+    pub fn new(
+        players: impl IntoIterator<Item = P>,
+        ids: impl IntoIterator<Item = Id>,
+    ) -> Result<Self, SeatingError<Id, P>> {
+        let players: Vec<P> = players.into_iter().collect();
+        let ids: Vec<Id> = ids.into_iter().collect();
+
+        if players.len() != ids.len() {
+            return Err(WrongNumberOfIds {
+                seats: players.len(),
+                ids: ids.len(),
+            });
+        }
+
+        let mut seating = Self { seats: Vec::new() };
+
+        for (player, id) in players.into_iter().zip(ids) {
+            if let Some(seated) = seating.player(&id) {
+                return Err(IdAlreadySeated { id, player: seated });
+            }
+            seating.seats.push((player, id));
+        }
+
+        Ok(seating)
+    }
+
+    /// Seats the ids in an order determined by the seed, the same seed and ids always produce the
+    /// same seating
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::common::seating::Seating;
+    /// use lib_table_top::games::crazy_eights::NumberOfPlayers;
+    ///
+    /// let players = NumberOfPlayers::Four.players();
+    /// let ids = vec!["a", "b", "c", "d"];
+    ///
+    /// let seating = Seating::shuffled(players.clone(), ids.clone(), RngSeed([0; 32])).unwrap();
+    /// let again = Seating::shuffled(players, ids, RngSeed([0; 32])).unwrap();
+    /// assert_eq!(seating, again);
+    /// ```
+    pub fn shuffled(
+        players: impl IntoIterator<Item = P>,
+        ids: impl IntoIterator<Item = Id>,
+        seed: RngSeed,
+    ) -> Result<Self, SeatingError<Id, P>> {
+        let mut ids: Vec<Id> = ids.into_iter().collect();
+        ids.shuffle(&mut seed.into_rng());
+        Self::new(players, ids)
+    }
+
+    /// Returns the player an id is seated as
+    pub fn player(&self, id: &Id) -> Option<P> {
+        self.seats
+            .iter()
+            .find(|(_, seated)| seated == id)
+            .map(|&(player, _)| player)
+    }
+
+    /// Returns the id seated as a player
+    pub fn id(&self, player: P) -> Option<&Id> {
+        self.seats
+            .iter()
+            .find(|&&(p, _)| p == player)
+            .map(|(_, id)| id)
+    }
+
+    /// An iterator over the seats, in the order of the players
+    pub fn seats(&self) -> impl Iterator<Item = (P, &Id)> + '_ {
+        self.seats.iter().map(|(player, id)| (*player, id))
+    }
+
+    /// Replaces the id seated as a player with a new id, the new id can't already be seated
+    /// ```
+    /// use lib_table_top::common::seating::{Seating, SeatingError::*};
+    /// use lib_table_top::games::marooned::Player::*;
+    ///
+    /// let seating = Seating::new(vec![P1, P2], vec!["alice", "bob"]).unwrap();
+    ///
+    /// let seating = seating.substitute(P2, "carol").unwrap();
+    /// assert_eq!(seating.id(P2), Some(&"carol"));
+    /// assert_eq!(seating.player(&"bob"), None);
+    ///
+    /// assert_eq!(
+    ///   seating.substitute(P2, "alice"),
+    ///   Err(IdAlreadySeated { id: "alice", player: P1 })
+    /// );
+    /// ```
+    pub fn substitute(&self, player: P, id: Id) -> Result<Self, SeatingError<Id, P>> {
+        if let Some(seated) = self.player(&id) {
+            return Err(IdAlreadySeated { id, player: seated });
+        }
+
+        let mut seating = self.clone();
+
+        match seating.seats.iter_mut().find(|(p, _)| *p == player) {
+            Some(seat) => {
+                seat.1 = id;
+                Ok(seating)
+            }
+            None => Err(NoSeatForPlayer { player }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::crazy_eights::{NumberOfPlayers, Player::*};
+    use serde_json::json;
+
+    #[test]
+    fn test_substituting_an_unseated_player() {
+        let seating = Seating::new(NumberOfPlayers::Two.players(), vec![1, 2]).unwrap();
+        assert_eq!(
+            seating.substitute(P3, 3),
+            Err(NoSeatForPlayer { player: P3 })
+        );
+    }
+
+    #[test]
+    fn test_shuffling_seats_every_id() {
+        let ids = vec![1, 2, 3, 4, 5];
+        let seating = Seating::shuffled(
+            NumberOfPlayers::Five.players(),
+            ids.clone(),
+            RngSeed([7; 32]),
+        )
+        .unwrap();
+
+        for id in ids {
+            assert!(seating.player(&id).is_some());
+        }
+    }
+
+    #[test]
+    fn test_serializing_and_deserializing() {
+        let seating = Seating::new(vec![P1, P2], vec!["alice", "bob"]).unwrap();
+        let serialized = serde_json::to_value(&seating).unwrap();
+        assert_eq!(
+            serialized,
+            json!({"seats": [["P1", "alice"], ["P2", "bob"]]})
+        );
+
+        let deserialized: Seating<String, _> = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized.player(&"alice".to_string()), Some(P1));
+    }
+}
@@ -0,0 +1,151 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::common::rand::RngSeed;
+
+/// The things that can go wrong recording a round
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SessionError {
+    #[error("All {:?} rounds of the session have already been played", rounds)]
+    SessionIsOver { rounds: usize },
+}
+
+use SessionError::*;
+
+/// A finished round of a session
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundRecord<H> {
+    pub seed: RngSeed,
+    pub score: i64,
+    pub history: H,
+}
+
+/// A series of rounds of a single player game, like the nine holes of golf solitaire, played
+/// from one seed. Each round gets its own seed derived from the session's, so the whole session
+/// can be replayed from a single seed
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::common::session::Session;
+///
+/// let session: Session<Vec<u8>> = Session::new(RngSeed([0; 32]), 2);
+/// let first_seed = session.next_seed().unwrap();
+///
+/// let session = session.record(12, vec![1, 2, 3]).unwrap();
+/// assert_eq!(session.rounds()[0].seed, first_seed);
+/// assert_ne!(session.next_seed(), Some(first_seed));
+///
+/// let session = session.record(-3, vec![4]).unwrap();
+/// assert_eq!(session.total_score(), 9);
+/// assert!(session.is_over());
+/// assert_eq!(session.next_seed(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session<H> {
+    seed: RngSeed,
+    number_of_rounds: usize,
+    rounds: Vec<RoundRecord<H>>,
+}
+
+impl<H: Clone> Session<H> {
+    pub fn new(seed: RngSeed, number_of_rounds: usize) -> Self {
+        Self {
+            seed,
+            number_of_rounds,
+            rounds: Vec::new(),
+        }
+    }
+
+    pub fn seed(&self) -> RngSeed {
+        self.seed
+    }
+
+    pub fn number_of_rounds(&self) -> usize {
+        self.number_of_rounds
+    }
+
+    /// The rounds played so far, in order
+    pub fn rounds(&self) -> &[RoundRecord<H>] {
+        &self.rounds
+    }
+
+    /// The seed for a round, the same session seed always gives the same round seeds
+    pub fn round_seed(&self, round: usize) -> RngSeed {
+        let mut rng = self.seed.into_rng();
+        rng.set_stream(round as u64);
+        RngSeed(rng.gen())
+    }
+
+    /// The seed to play the next round with, `None` once every round has been played
+    pub fn next_seed(&self) -> Option<RngSeed> {
+        if self.is_over() {
+            None
+        } else {
+            Some(self.round_seed(self.rounds.len()))
+        }
+    }
+
+    /// Records the result of the next round
+    pub fn record(&self, score: i64, history: H) -> Result<Self, SessionError> {
+        let seed = self.next_seed().ok_or(SessionIsOver {
+            rounds: self.number_of_rounds,
+        })?;
+
+        let mut session = self.clone();
+        session.rounds.push(RoundRecord {
+            seed,
+            score,
+            history,
+        });
+        Ok(session)
+    }
+
+    /// The score of each round played so far
+    pub fn scores(&self) -> impl Iterator<Item = i64> + '_ {
+        self.rounds.iter().map(|round| round.score)
+    }
+
+    pub fn total_score(&self) -> i64 {
+        self.scores().sum()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.rounds.len() >= self.number_of_rounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_seeds_are_distinct_and_reproducible() {
+        let session: Session<()> = Session::new(RngSeed([7; 32]), 9);
+        let seeds: Vec<RngSeed> = (0..9).map(|round| session.round_seed(round)).collect();
+
+        for (index, seed) in seeds.iter().enumerate() {
+            assert!(!seeds[..index].contains(seed));
+            assert_eq!(
+                Session::<()>::new(RngSeed([7; 32]), 9).round_seed(index),
+                *seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_serializing_sessions() {
+        let session = Session::new(RngSeed([0; 32]), 1)
+            .record(4, "hole one")
+            .unwrap();
+        let serialized = serde_json::to_value(&session).unwrap();
+
+        assert_eq!(serialized["number_of_rounds"], json!(1));
+        assert_eq!(serialized["rounds"][0]["score"], json!(4));
+        assert_eq!(serialized["rounds"][0]["history"], json!("hole one"));
+        assert_eq!(
+            session.record(1, "hole two"),
+            Err(SessionError::SessionIsOver { rounds: 1 })
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+/// Builds validated settings for a game. Builders collect values with chained setters, usually
+/// generated with `settings_setters!`, and validate them all at once when built
+pub trait Builder: Sized {
+    type Settings;
+    type Error;
+
+    /// Validates and returns the settings
+    fn build(self) -> Result<Self::Settings, Self::Error>;
+
+    /// Validates the settings and makes a new game with them
+    fn build_game<G>(self) -> Result<G, Self::Error>
+    where
+        G: FromSettings<Settings = Self::Settings>,
+    {
+        self.build()
+            .map(|settings| G::from_settings(Arc::new(settings)))
+    }
+}
+
+/// Games that are made from settings
+pub trait FromSettings {
+    type Settings;
+
+    fn from_settings(settings: Arc<Self::Settings>) -> Self;
+}
+
+/// Generates chained setters for the fields of a builder. Each setter takes the value and stores
+/// it with `Into`, so optional fields can be set with the bare value
+/// ```ignore
+/// impl SettingsBuilder {
+///     settings_setters! {
+///         rows: u8,
+///         p1_starting: Position,
+///     }
+/// }
+/// ```
+macro_rules! settings_setters {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        $(
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.$field = $field.into();
+                self
+            }
+        )*
+    };
+}
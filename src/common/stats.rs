@@ -0,0 +1,434 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::common::replay::Replayable;
+use crate::common::results::{Outcome, Summarize};
+
+/// Collects statistics from games as they're played. Each hook has a default that does nothing,
+/// so collectors only implement the ones they care about
+pub trait Collector<G: Replayable> {
+    /// The key the collector's report is under
+    fn name(&self) -> &'static str;
+
+    /// Called after each action, with how long the action took when the harness measured it
+    fn record_action(
+        &mut self,
+        _before: &G,
+        _action: &G::Action,
+        _after: &G,
+        _elapsed: Option<Duration>,
+    ) {
+    }
+
+    /// Called once a game is finished
+    fn record_game(&mut self, _game: &G) {}
+
+    /// The statistics collected so far
+    fn report(&self) -> Value;
+}
+
+/// A running count, total, min, and max of a measurement
+/// ```
+/// use lib_table_top::common::stats::Tally;
+///
+/// let tally = [3, 5, 10].iter().fold(Tally::default(), |tally, &n| tally.record(n));
+/// assert_eq!(tally.count, 3);
+/// assert_eq!(tally.min, Some(3));
+/// assert_eq!(tally.max, Some(10));
+/// assert_eq!(tally.mean(), Some(6.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    pub count: u64,
+    pub total: u64,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl Tally {
+    /// Adds a measurement
+    pub fn record(self, value: u64) -> Self {
+        Self {
+            count: self.count + 1,
+            total: self.total + value,
+            min: Some(self.min.map_or(value, |min| min.min(value))),
+            max: Some(self.max.map_or(value, |max| max.max(value))),
+        }
+    }
+
+    /// The average, `None` before anything has been recorded
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.total as f64 / self.count as f64)
+        }
+    }
+
+    /// The tally along with its mean, for reports
+    pub fn report(&self) -> Value {
+        let mut report = serde_json::to_value(self).expect("Tallies can always be serialized");
+        report["mean"] = self.mean().into();
+        report
+    }
+}
+
+/// A set of collectors that are fed the same games, for attaching to a self-play harness
+/// ```
+/// use lib_table_top::common::stats::{BranchingFactor, MovesPerGame, Stats};
+/// use lib_table_top::games::tic_tac_toe::GameState;
+///
+/// let mut stats = Stats::new()
+///   .with(MovesPerGame::default())
+///   .with(BranchingFactor::new(|game: &GameState| game.valid_actions().count()));
+///
+/// let game = GameState::new();
+/// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+/// stats.observe(&game).unwrap();
+///
+/// let report = stats.report();
+/// assert_eq!(report["moves_per_game"]["total"], 1);
+/// assert_eq!(report["branching_factor"]["mean"], 9.0);
+/// ```
+pub struct Stats<G: Replayable> {
+    collectors: Vec<Box<dyn Collector<G>>>,
+}
+
+impl<G: Replayable> Default for Stats<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Replayable> Stats<G> {
+    pub fn new() -> Self {
+        Self {
+            collectors: Vec::new(),
+        }
+    }
+
+    /// Attaches a collector
+    pub fn with(mut self, collector: impl Collector<G> + 'static) -> Self {
+        self.collectors.push(Box::new(collector));
+        self
+    }
+
+    /// Records an action as it's played
+    pub fn record_action(
+        &mut self,
+        before: &G,
+        action: &G::Action,
+        after: &G,
+        elapsed: Option<Duration>,
+    ) {
+        for collector in &mut self.collectors {
+            collector.record_action(before, action, after, elapsed);
+        }
+    }
+
+    /// Records a finished game
+    pub fn record_game(&mut self, game: &G) {
+        for collector in &mut self.collectors {
+            collector.record_game(game);
+        }
+    }
+
+    /// Records every action in a game's history and then the game itself, for games that were
+    /// played without the collectors attached. There are no timings for these actions
+    pub fn observe(&mut self, game: &G) -> Result<(), G::Error> {
+        let mut before = game.initial();
+
+        for action in game.actions() {
+            let after = before.apply(action.clone())?;
+            self.record_action(&before, &action, &after, None);
+            before = after;
+        }
+
+        self.record_game(game);
+        Ok(())
+    }
+
+    /// Every collector's report, keyed by the collector's name
+    pub fn report(&self) -> Value {
+        let reports: Map<String, Value> = self
+            .collectors
+            .iter()
+            .map(|collector| (collector.name().to_string(), collector.report()))
+            .collect();
+
+        Value::Object(reports)
+    }
+}
+
+/// How many actions each game took
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MovesPerGame {
+    tally: Tally,
+}
+
+impl<G: Replayable> Collector<G> for MovesPerGame {
+    fn name(&self) -> &'static str {
+        "moves_per_game"
+    }
+
+    fn record_game(&mut self, game: &G) {
+        self.tally = self.tally.record(game.actions().len() as u64);
+    }
+
+    fn report(&self) -> Value {
+        self.tally.report()
+    }
+}
+
+/// How many actions were available at each decision, using a function that counts them
+pub struct BranchingFactor<G> {
+    count_actions: Box<dyn Fn(&G) -> usize>,
+    tally: Tally,
+}
+
+impl<G> BranchingFactor<G> {
+    pub fn new(count_actions: impl Fn(&G) -> usize + 'static) -> Self {
+        Self {
+            count_actions: Box::new(count_actions),
+            tally: Tally::default(),
+        }
+    }
+}
+
+impl<G: Replayable> Collector<G> for BranchingFactor<G> {
+    fn name(&self) -> &'static str {
+        "branching_factor"
+    }
+
+    fn record_action(&mut self, before: &G, _: &G::Action, _: &G, _: Option<Duration>) {
+        self.tally = self.tally.record((self.count_actions)(before) as u64);
+    }
+
+    fn report(&self) -> Value {
+        self.tally.report()
+    }
+}
+
+/// How long each action took in microseconds, actions without a timing are skipped
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimePerMove {
+    tally: Tally,
+}
+
+impl<G: Replayable> Collector<G> for TimePerMove {
+    fn name(&self) -> &'static str {
+        "time_per_move_micros"
+    }
+
+    fn record_action(&mut self, _: &G, _: &G::Action, _: &G, elapsed: Option<Duration>) {
+        if let Some(elapsed) = elapsed {
+            self.tally = self.tally.record(elapsed.as_micros() as u64);
+        }
+    }
+
+    fn report(&self) -> Value {
+        self.tally.report()
+    }
+}
+
+/// How often a player won, lost, and drew
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeCounts {
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+}
+
+impl OutcomeCounts {
+    pub fn record(self, outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Win => Self {
+                wins: self.wins + 1,
+                ..self
+            },
+            Outcome::Loss => Self {
+                losses: self.losses + 1,
+                ..self
+            },
+            Outcome::Draw => Self {
+                draws: self.draws + 1,
+                ..self
+            },
+        }
+    }
+}
+
+/// The games that started with the same actions, see [`Openings`](struct@Openings)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Opening {
+    /// The first actions of the games, serialized
+    pub line: Vec<Value>,
+    pub games: u64,
+    /// Games that were recorded before they were over, which don't have outcomes
+    pub unfinished: u64,
+    /// How the finished games went for each player, keyed by the player serialized as JSON, or
+    /// the string itself for players that serialize to one
+    pub outcomes: BTreeMap<String, OutcomeCounts>,
+}
+
+/// How often games start with the same first actions and how those games turned out, for
+/// features like "most popular openings". Games shorter than the depth are counted under their
+/// whole history
+/// ```
+/// use lib_table_top::common::stats::{Openings, Stats};
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let center = GameState::new().apply_action((P1, (Col1, Row1))).unwrap();
+/// let won = [(P2, (Col0, Row0)), (P1, (Col0, Row1)), (P2, (Col0, Row2)), (P1, (Col2, Row1))]
+///   .iter()
+///   .fold(center.clone(), |game, &action| game.apply_action(action).unwrap());
+/// let corner = GameState::new().apply_action((P1, (Col0, Row0))).unwrap();
+///
+/// let mut stats = Stats::new().with(Openings::new(1));
+/// for game in &[won, center, corner] {
+///     stats.observe(game).unwrap();
+/// }
+///
+/// let report = stats.report();
+/// assert_eq!(report["openings"][0]["line"], serde_json::json!([["P1", [1, 1]]]));
+/// assert_eq!(report["openings"][0]["games"], 2);
+/// assert_eq!(report["openings"][0]["unfinished"], 1);
+/// assert_eq!(report["openings"][0]["outcomes"]["P1"]["wins"], 1);
+/// assert_eq!(report["openings"][0]["outcomes"]["P2"]["losses"], 1);
+/// assert_eq!(report["openings"][1]["games"], 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Openings {
+    depth: usize,
+    /// Keyed by the line serialized as a string, so lines can be compared without the actions
+    /// being hashable
+    openings: BTreeMap<String, Opening>,
+}
+
+impl Openings {
+    /// Groups games by their first `depth` actions
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            openings: BTreeMap::new(),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The `n` openings played the most, ties are broken by the line so the order is stable
+    pub fn most_popular(&self, n: usize) -> Vec<&Opening> {
+        let mut openings: Vec<(&String, &Opening)> = self.openings.iter().collect();
+        openings.sort_by(|(a_key, a), (b_key, b)| b.games.cmp(&a.games).then(a_key.cmp(b_key)));
+        openings
+            .into_iter()
+            .take(n)
+            .map(|(_, opening)| opening)
+            .collect()
+    }
+}
+
+impl<G> Collector<G> for Openings
+where
+    G: Replayable + Summarize,
+    G::Action: Serialize,
+    G::Player: Serialize,
+{
+    fn name(&self) -> &'static str {
+        "openings"
+    }
+
+    fn record_game(&mut self, game: &G) {
+        let line: Vec<Value> = game
+            .actions()
+            .iter()
+            .take(self.depth)
+            .map(|action| serde_json::to_value(action).expect("Actions can always be serialized"))
+            .collect();
+        let key = Value::from(line.clone()).to_string();
+
+        let opening = self.openings.entry(key).or_insert_with(|| Opening {
+            line,
+            ..Default::default()
+        });
+        opening.games += 1;
+
+        match game.game_result() {
+            Some(result) => {
+                for player_result in result.players {
+                    let player = match serde_json::to_value(&player_result.player)
+                        .expect("Players can always be serialized")
+                    {
+                        Value::String(player) => player,
+                        player => player.to_string(),
+                    };
+                    let counts = opening.outcomes.entry(player).or_default();
+                    *counts = counts.record(player_result.outcome);
+                }
+            }
+            None => opening.unfinished += 1,
+        }
+    }
+
+    fn report(&self) -> Value {
+        serde_json::to_value(self.most_popular(self.openings.len()))
+            .expect("Openings can always be serialized")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::tic_tac_toe::GameState;
+
+    #[test]
+    fn test_only_timed_actions_count_towards_time_per_move() {
+        let mut stats = Stats::new().with(TimePerMove::default());
+        let game = GameState::new();
+        let action = game.valid_actions().next().unwrap();
+        let after = game.apply_action(action).unwrap();
+
+        stats.record_action(&game, &action, &after, Some(Duration::from_millis(2)));
+        stats.record_action(&game, &action, &after, None);
+
+        let report = stats.report();
+        assert_eq!(report["time_per_move_micros"]["count"], 1);
+        assert_eq!(report["time_per_move_micros"]["max"], 2000);
+    }
+
+    #[test]
+    fn test_empty_reports() {
+        let stats: Stats<GameState> = Stats::new().with(MovesPerGame::default());
+        assert_eq!(
+            stats.report(),
+            serde_json::json!({
+                "moves_per_game": {"count": 0, "total": 0, "min": null, "max": null, "mean": null}
+            })
+        );
+    }
+
+    #[test]
+    fn test_short_games_count_under_their_whole_history() {
+        use crate::games::marooned;
+
+        let mut openings = Openings::new(3);
+        let game = marooned::GameState::default();
+        let first = game.valid_actions().next().unwrap();
+        let moved = game.apply_action(first).unwrap();
+
+        Collector::<marooned::GameState>::record_game(&mut openings, &game);
+        Collector::<marooned::GameState>::record_game(&mut openings, &moved);
+        Collector::<marooned::GameState>::record_game(&mut openings, &moved);
+
+        let popular = openings.most_popular(5);
+        assert_eq!(popular.len(), 2);
+        assert_eq!(popular[0].line, vec![serde_json::to_value(first).unwrap()]);
+        assert_eq!(popular[0].games, 2);
+        assert!(popular[1].line.is_empty());
+        assert_eq!(openings.most_popular(1).len(), 1);
+    }
+}
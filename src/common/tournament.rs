@@ -0,0 +1,954 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::common::results::GameResult;
+
+/// The index of a match within a bracket
+pub type MatchId = usize;
+
+/// Which part of a bracket a match is in
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    /// The main bracket, everyone starts here
+    Winners,
+    /// Where players go after their first loss in double elimination
+    Losers,
+    /// The winner of the winners bracket against the winner of the losers bracket
+    GrandFinal,
+}
+
+/// Where a player in a match comes from
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Source {
+    /// The entrant with this seed, 0 is the top seed
+    Seed(usize),
+    /// Nobody, the other player advances automatically
+    Bye,
+    /// The winner of an earlier match
+    WinnerOf(MatchId),
+    /// The loser of an earlier match
+    LoserOf(MatchId),
+}
+
+use Source::*;
+
+/// A single match in a bracket
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Match {
+    pub side: Side,
+    /// The round within the side of the bracket, starting at 1
+    pub round: usize,
+    pub sources: [Source; 2],
+    winner: Option<usize>,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketError {
+    #[error("Brackets need at least 2 entrants, got {:?}", entrants)]
+    NotEnoughEntrants { entrants: usize },
+    #[error("There is no match {:?}", id)]
+    NoSuchMatch { id: MatchId },
+    #[error("Match {:?} doesn't have both players yet", id)]
+    MatchNotReady { id: MatchId },
+    #[error("Match {:?} has already been decided", id)]
+    MatchAlreadyDecided { id: MatchId },
+    #[error("The winner isn't playing in match {:?}", id)]
+    NotInMatch { id: MatchId },
+}
+
+use BracketError::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Resolved {
+    Entrant(usize),
+    Bye,
+    Pending,
+}
+
+/// A single or double elimination bracket. Entrants are given in seed order, best first, and
+/// are placed so the top seeds meet as late as possible. When the number of entrants isn't a
+/// power of two the top seeds get byes
+/// ```
+/// use lib_table_top::common::tournament::Bracket;
+///
+/// let bracket = Bracket::single_elimination(vec!["ann", "bob", "cat"]).unwrap();
+///
+/// // Ann has a bye, so the only match ready to play is Bob against Cat
+/// let ready = bracket.ready_matches();
+/// assert_eq!(ready.len(), 1);
+/// let (id, _, _) = ready[0];
+///
+/// let bracket = bracket.record_result(id, &"cat").unwrap();
+/// let (id, p1, p2) = bracket.ready_matches()[0];
+/// assert_eq!((p1, p2), (&"ann", &"cat"));
+///
+/// let bracket = bracket.record_result(id, &"ann").unwrap();
+/// assert_eq!(bracket.champion(), Some(&"ann"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bracket<E> {
+    entrants: Vec<E>,
+    matches: Vec<Match>,
+}
+
+impl<E: PartialEq> Bracket<E> {
+    /// Makes a bracket where players are out after their first loss
+    pub fn single_elimination(entrants: Vec<E>) -> Result<Self, BracketError> {
+        let mut bracket = Self::empty(entrants)?;
+        bracket.add_winners_bracket();
+        Ok(bracket)
+    }
+
+    /// Makes a bracket where players are out after their second loss. Players who lose in the
+    /// winners bracket drop into the losers bracket, and the winner of each meet in a grand final.
+    /// The grand final is a single match, there is no bracket reset
+    /// ```
+    /// use lib_table_top::common::tournament::{Bracket, Side};
+    ///
+    /// let bracket = Bracket::double_elimination(vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(bracket.matches().len(), 6);
+    /// assert_eq!(bracket.matches().last().unwrap().side, Side::GrandFinal);
+    /// ```
+    pub fn double_elimination(entrants: Vec<E>) -> Result<Self, BracketError> {
+        let mut bracket = Self::empty(entrants)?;
+        let winners = bracket.add_winners_bracket();
+
+        let mut survivors: Vec<Source> = winners[0].iter().map(|&id| LoserOf(id)).collect();
+        let mut round = 0;
+
+        if survivors.len() > 1 {
+            round += 1;
+            survivors = bracket.add_round(Side::Losers, round, pairs(&survivors));
+        }
+
+        for winners_round in &winners[1..] {
+            round += 1;
+            let drops = winners_round.iter().rev().map(|&id| LoserOf(id));
+            let games = survivors.into_iter().zip(drops).map(|(a, b)| [a, b]);
+            survivors = bracket.add_round(Side::Losers, round, games.collect());
+
+            if survivors.len() > 1 {
+                round += 1;
+                survivors = bracket.add_round(Side::Losers, round, pairs(&survivors));
+            }
+        }
+
+        let winners_final = winners[winners.len() - 1][0];
+        bracket.add_round(
+            Side::GrandFinal,
+            1,
+            vec![[WinnerOf(winners_final), survivors[0]]],
+        );
+
+        Ok(bracket)
+    }
+
+    /// The entrants in seed order
+    pub fn entrants(&self) -> &[E] {
+        &self.entrants
+    }
+
+    /// All of the matches in the bracket, a match only ever depends on matches before it
+    pub fn matches(&self) -> &[Match] {
+        &self.matches
+    }
+
+    /// The players in a match, `None` for a player that isn't known yet or is a bye
+    pub fn players(&self, id: MatchId) -> Option<[Option<&E>; 2]> {
+        let outcomes = self.outcomes();
+        let game = self.matches.get(id)?;
+
+        Some(
+            game.sources
+                .map(|source| match self.resolve(&outcomes, source) {
+                    Resolved::Entrant(entrant) => Some(&self.entrants[entrant]),
+                    _ => None,
+                }),
+        )
+    }
+
+    /// The matches that have both players and haven't been played yet
+    pub fn ready_matches(&self) -> Vec<(MatchId, &E, &E)> {
+        let outcomes = self.outcomes();
+
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(|(_, game)| game.winner.is_none())
+            .filter_map(|(id, game)| {
+                match game.sources.map(|source| self.resolve(&outcomes, source)) {
+                    [Resolved::Entrant(a), Resolved::Entrant(b)] => {
+                        Some((id, &self.entrants[a], &self.entrants[b]))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Records the winner of a match, advancing the bracket
+    pub fn record_result(&self, id: MatchId, winner: &E) -> Result<Self, BracketError>
+    where
+        E: Clone,
+    {
+        let outcomes = self.outcomes();
+        let game = self.matches.get(id).ok_or(NoSuchMatch { id })?;
+
+        if game.winner.is_some() {
+            return Err(MatchAlreadyDecided { id });
+        }
+
+        let players = match game.sources.map(|source| self.resolve(&outcomes, source)) {
+            [Resolved::Entrant(a), Resolved::Entrant(b)] => [a, b],
+            _ => return Err(MatchNotReady { id }),
+        };
+
+        let winner = players
+            .iter()
+            .copied()
+            .find(|&entrant| &self.entrants[entrant] == winner)
+            .ok_or(NotInMatch { id })?;
+
+        let mut bracket = self.clone();
+        bracket.matches[id].winner = Some(winner);
+        Ok(bracket)
+    }
+
+    /// The winner of the bracket, once the final match has been decided
+    pub fn champion(&self) -> Option<&E> {
+        match self.outcomes().last() {
+            Some((Resolved::Entrant(entrant), _)) => Some(&self.entrants[*entrant]),
+            _ => None,
+        }
+    }
+
+    /// Whether every match has been decided
+    pub fn is_complete(&self) -> bool {
+        self.champion().is_some()
+    }
+
+    fn empty(entrants: Vec<E>) -> Result<Self, BracketError> {
+        if entrants.len() < 2 {
+            return Err(NotEnoughEntrants {
+                entrants: entrants.len(),
+            });
+        }
+
+        Ok(Self {
+            entrants,
+            matches: Vec::new(),
+        })
+    }
+
+    fn add_winners_bracket(&mut self) -> Vec<Vec<MatchId>> {
+        let size = self.entrants.len().next_power_of_two();
+        let mut order = vec![0];
+
+        while order.len() < size {
+            let len = order.len() * 2;
+            order = order
+                .iter()
+                .flat_map(|&seed| [seed, len - 1 - seed])
+                .collect();
+        }
+
+        let first: Vec<[Source; 2]> = order
+            .chunks(2)
+            .map(|pair| {
+                [pair[0], pair[1]].map(|seed| {
+                    if seed < self.entrants.len() {
+                        Seed(seed)
+                    } else {
+                        Bye
+                    }
+                })
+            })
+            .collect();
+
+        let mut survivors = self.add_round(Side::Winners, 1, first);
+        let mut rounds = vec![ids(&survivors)];
+
+        while survivors.len() > 1 {
+            survivors = self.add_round(Side::Winners, rounds.len() + 1, pairs(&survivors));
+            rounds.push(ids(&survivors));
+        }
+
+        rounds
+    }
+
+    fn add_round(&mut self, side: Side, round: usize, games: Vec<[Source; 2]>) -> Vec<Source> {
+        games
+            .into_iter()
+            .map(|sources| {
+                self.matches.push(Match {
+                    side,
+                    round,
+                    sources,
+                    winner: None,
+                });
+                WinnerOf(self.matches.len() - 1)
+            })
+            .collect()
+    }
+
+    fn outcomes(&self) -> Vec<(Resolved, Resolved)> {
+        let mut outcomes = Vec::with_capacity(self.matches.len());
+
+        for game in &self.matches {
+            let outcome = match game.sources.map(|source| self.resolve(&outcomes, source)) {
+                [Resolved::Pending, _] | [_, Resolved::Pending] => {
+                    (Resolved::Pending, Resolved::Pending)
+                }
+                [Resolved::Bye, Resolved::Bye] => (Resolved::Bye, Resolved::Bye),
+                [Resolved::Entrant(entrant), Resolved::Bye]
+                | [Resolved::Bye, Resolved::Entrant(entrant)] => {
+                    (Resolved::Entrant(entrant), Resolved::Bye)
+                }
+                [Resolved::Entrant(a), Resolved::Entrant(b)] => match game.winner {
+                    Some(winner) if winner == a => (Resolved::Entrant(a), Resolved::Entrant(b)),
+                    Some(_) => (Resolved::Entrant(b), Resolved::Entrant(a)),
+                    None => (Resolved::Pending, Resolved::Pending),
+                },
+            };
+
+            outcomes.push(outcome);
+        }
+
+        outcomes
+    }
+
+    fn resolve(&self, outcomes: &[(Resolved, Resolved)], source: Source) -> Resolved {
+        match source {
+            Seed(seed) => Resolved::Entrant(seed),
+            Bye => Resolved::Bye,
+            WinnerOf(id) => outcomes[id].0,
+            LoserOf(id) => outcomes[id].1,
+        }
+    }
+}
+
+/// The result of a game in a Swiss tournament
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    FirstWins,
+    SecondWins,
+    Draw,
+}
+
+/// A game in a Swiss tournament, `second` is `None` when the first player has a bye
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwissGame {
+    pub round: usize,
+    pub first: usize,
+    pub second: Option<usize>,
+    pub outcome: Option<Outcome>,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwissError {
+    #[error("Swiss tournaments need at least 2 entrants, got {:?}", entrants)]
+    NotEnoughEntrants { entrants: usize },
+    #[error("Round {:?} still has games that haven't been decided", round)]
+    RoundInProgress { round: usize },
+    #[error("There is no game {:?}", id)]
+    NoSuchGame { id: usize },
+    #[error("Game {:?} has already been decided", id)]
+    GameAlreadyDecided { id: usize },
+}
+
+/// An entrant's place in a Swiss tournament. Points are 2 for a win or a bye and 1 for a draw,
+/// buchholz is the total points of everyone they've played and breaks ties
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Standing<'a, E> {
+    pub entrant: &'a E,
+    pub points: u32,
+    pub buchholz: u32,
+}
+
+/// A Swiss tournament, where everyone plays every round against someone with a similar score
+/// who they haven't played yet. Entrants are given in seed order, best first. Within a score
+/// group the top half plays the bottom half, and whoever has gone first less often goes first
+/// ```
+/// use lib_table_top::common::tournament::{Outcome, Swiss};
+///
+/// let swiss = Swiss::new(vec!["ann", "bob", "cat", "dan"]).unwrap();
+/// let swiss = swiss.pair_next_round().unwrap();
+///
+/// let pairings: Vec<_> = swiss.current_round().map(|(_, first, second)| (first, second)).collect();
+/// assert_eq!(pairings, vec![(&"ann", Some(&"cat")), (&"bob", Some(&"dan"))]);
+///
+/// let swiss = swiss
+///   .record_result(0, Outcome::FirstWins).unwrap()
+///   .record_result(1, Outcome::Draw).unwrap()
+///   .pair_next_round().unwrap();
+///
+/// // Ann, the only winner, plays Bob, the best seed of the players who drew
+/// let pairings: Vec<_> = swiss.current_round().map(|(_, first, second)| (first, second)).collect();
+/// assert_eq!(pairings, vec![(&"ann", Some(&"bob")), (&"dan", Some(&"cat"))]);
+/// assert_eq!(swiss.standings()[0].entrant, &"ann");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Swiss<E> {
+    entrants: Vec<E>,
+    games: Vec<SwissGame>,
+    rounds: usize,
+}
+
+impl<E> Swiss<E> {
+    /// Starts a tournament with no rounds paired
+    pub fn new(entrants: Vec<E>) -> Result<Self, SwissError> {
+        if entrants.len() < 2 {
+            return Err(SwissError::NotEnoughEntrants {
+                entrants: entrants.len(),
+            });
+        }
+
+        Ok(Self {
+            entrants,
+            games: Vec::new(),
+            rounds: 0,
+        })
+    }
+
+    /// The entrants in seed order
+    pub fn entrants(&self) -> &[E] {
+        &self.entrants
+    }
+
+    /// Every game that has been paired, the index of a game is its id
+    pub fn games(&self) -> &[SwissGame] {
+        &self.games
+    }
+
+    /// The number of rounds that have been paired
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// The games in the latest round, with who goes first and who goes second
+    pub fn current_round(&self) -> impl Iterator<Item = (usize, &E, Option<&E>)> + '_ {
+        self.games
+            .iter()
+            .enumerate()
+            .filter(move |(_, game)| game.round == self.rounds)
+            .map(move |(id, game)| {
+                (
+                    id,
+                    &self.entrants[game.first],
+                    game.second.map(|second| &self.entrants[second]),
+                )
+            })
+    }
+
+    /// Records the outcome of a game
+    pub fn record_result(&self, id: usize, outcome: Outcome) -> Result<Self, SwissError>
+    where
+        E: Clone,
+    {
+        let game = self.games.get(id).ok_or(SwissError::NoSuchGame { id })?;
+
+        if game.outcome.is_some() || game.second.is_none() {
+            return Err(SwissError::GameAlreadyDecided { id });
+        }
+
+        let mut swiss = self.clone();
+        swiss.games[id].outcome = Some(outcome);
+        Ok(swiss)
+    }
+
+    /// Pairs the next round, once every game in the current round has been decided. Rematches
+    /// are avoided whenever possible, and with an odd number of entrants the lowest ranked
+    /// entrant who hasn't had a bye yet gets one
+    pub fn pair_next_round(&self) -> Result<Self, SwissError>
+    where
+        E: Clone,
+    {
+        let undecided = self
+            .games
+            .iter()
+            .any(|game| game.second.is_some() && game.outcome.is_none());
+
+        if undecided {
+            return Err(SwissError::RoundInProgress { round: self.rounds });
+        }
+
+        let points = self.points();
+        let mut ranked: Vec<usize> = (0..self.entrants.len()).collect();
+        ranked.sort_by_key(|&entrant| std::cmp::Reverse(points[entrant]));
+
+        let mut bye_candidates: Vec<Option<usize>> = if ranked.len() % 2 == 1 {
+            ranked
+                .iter()
+                .rev()
+                .copied()
+                .filter(|&entrant| !self.had_bye(entrant))
+                .chain(ranked.iter().rev().copied())
+                .map(Some)
+                .collect()
+        } else {
+            vec![None]
+        };
+        bye_candidates.dedup();
+
+        let pairs = bye_candidates
+            .iter()
+            .find_map(|&bye| self.pairs_for(&ranked, &points, bye, false))
+            .or_else(|| self.pairs_for(&ranked, &points, bye_candidates[0], true))
+            .expect("Pairing with rematches allowed always succeeds");
+
+        let mut swiss = self.clone();
+        swiss.rounds += 1;
+
+        for (a, b) in pairs {
+            let (first, second) = match b {
+                Some(b) if self.should_go_first(b, a) => (b, Some(a)),
+                _ => (a, b),
+            };
+
+            swiss.games.push(SwissGame {
+                round: swiss.rounds,
+                first,
+                second,
+                outcome: None,
+            });
+        }
+
+        Ok(swiss)
+    }
+
+    /// The entrants ranked by points, then buchholz, then seed
+    pub fn standings(&self) -> Vec<Standing<'_, E>> {
+        let points = self.points();
+
+        let mut standings: Vec<(usize, Standing<'_, E>)> = (0..self.entrants.len())
+            .map(|entrant| {
+                let buchholz = self
+                    .opponents(entrant)
+                    .map(|opponent| points[opponent])
+                    .sum();
+
+                let standing = Standing {
+                    entrant: &self.entrants[entrant],
+                    points: points[entrant],
+                    buchholz,
+                };
+
+                (entrant, standing)
+            })
+            .collect();
+
+        standings.sort_by_key(|(seed, standing)| {
+            (
+                std::cmp::Reverse((standing.points, standing.buchholz)),
+                *seed,
+            )
+        });
+        standings
+            .into_iter()
+            .map(|(_, standing)| standing)
+            .collect()
+    }
+
+    fn points(&self) -> Vec<u32> {
+        let mut points = vec![0; self.entrants.len()];
+
+        for game in &self.games {
+            match (game.second, game.outcome) {
+                (None, _) | (Some(_), Some(Outcome::FirstWins)) => points[game.first] += 2,
+                (Some(second), Some(Outcome::SecondWins)) => points[second] += 2,
+                (Some(second), Some(Outcome::Draw)) => {
+                    points[game.first] += 1;
+                    points[second] += 1;
+                }
+                (Some(_), None) => {}
+            }
+        }
+
+        points
+    }
+
+    fn opponents(&self, entrant: usize) -> impl Iterator<Item = usize> + '_ {
+        self.games.iter().filter_map(move |game| match game.second {
+            Some(second) if game.first == entrant => Some(second),
+            Some(second) if second == entrant => Some(game.first),
+            _ => None,
+        })
+    }
+
+    fn had_bye(&self, entrant: usize) -> bool {
+        self.games
+            .iter()
+            .any(|game| game.first == entrant && game.second.is_none())
+    }
+
+    fn have_played(&self, a: usize, b: usize) -> bool {
+        self.opponents(a).any(|opponent| opponent == b)
+    }
+
+    fn should_go_first(&self, a: usize, b: usize) -> bool {
+        let balance = |entrant: usize| -> i32 {
+            self.games
+                .iter()
+                .filter(|game| game.second.is_some())
+                .map(|game| match game.second {
+                    _ if game.first == entrant => 1,
+                    Some(second) if second == entrant => -1,
+                    _ => 0,
+                })
+                .sum()
+        };
+
+        balance(a) < balance(b)
+    }
+
+    fn pairs_for(
+        &self,
+        ranked: &[usize],
+        points: &[u32],
+        bye: Option<usize>,
+        allow_rematches: bool,
+    ) -> Option<Vec<(usize, Option<usize>)>> {
+        let remaining: Vec<usize> = ranked
+            .iter()
+            .copied()
+            .filter(|&entrant| Some(entrant) != bye)
+            .collect();
+
+        let mut pairs = self.pair_up(&remaining, points, allow_rematches)?;
+        pairs.extend(bye.map(|bye| (bye, None)));
+        Some(pairs)
+    }
+
+    fn pair_up(
+        &self,
+        remaining: &[usize],
+        points: &[u32],
+        allow_rematches: bool,
+    ) -> Option<Vec<(usize, Option<usize>)>> {
+        let (&top, rest) = match remaining.split_first() {
+            Some(split) => split,
+            None => return Some(Vec::new()),
+        };
+
+        let group = rest
+            .iter()
+            .take_while(|&&entrant| points[entrant] == points[top])
+            .count();
+        let group_size = group + 1;
+        let half = (group_size / 2).saturating_sub(1);
+
+        // Within the score group the top half plays the bottom half, then everyone else in order
+        let candidates = (half..group).chain(0..half).chain(group..rest.len());
+
+        for index in candidates {
+            let opponent = rest[index];
+
+            if !allow_rematches && self.have_played(top, opponent) {
+                continue;
+            }
+
+            let others: Vec<usize> = rest
+                .iter()
+                .copied()
+                .filter(|&entrant| entrant != opponent)
+                .collect();
+
+            if let Some(mut pairs) = self.pair_up(&others, points, allow_rematches) {
+                pairs.insert(0, (top, Some(opponent)));
+                return Some(pairs);
+            }
+        }
+
+        None
+    }
+}
+
+fn pairs(sources: &[Source]) -> Vec<[Source; 2]> {
+    sources.chunks(2).map(|pair| [pair[0], pair[1]]).collect()
+}
+
+fn ids(sources: &[Source]) -> Vec<MatchId> {
+    sources
+        .iter()
+        .filter_map(|source| match source {
+            WinnerOf(id) => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Totals the points players earn for where they finish across a series of multi player games,
+/// like a league night of crazy eights. `points` is by place, so `points[0]` is for first, and
+/// places past the end of it earn nothing. Players who share a place each get its points.
+/// Returns every player with their total, most points first, then in the order they first
+/// played
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::common::results::Summarize;
+/// use lib_table_top::common::tournament::placement_points;
+/// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+/// use std::sync::Arc;
+///
+/// let results: Vec<_> = (0..3u8)
+///     .map(|seed| {
+///         let settings = Settings::tournament(RngSeed([seed; 32]), NumberOfPlayers::Three);
+///         let mut game = GameState::new(Arc::new(settings));
+///         while game.game_result().is_none() {
+///             let action = game.current_player_view().suggest_action().unwrap();
+///             game = game.apply_action((game.whose_turn(), action)).unwrap();
+///         }
+///         game.game_result().unwrap()
+///     })
+///     .collect();
+///
+/// let table = placement_points(&results, &[5, 3, 1]);
+/// assert_eq!(table.len(), 3);
+/// assert!(table.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+/// // Everyone played every game, so they all scored at least 3 points
+/// assert!(table.iter().all(|&(_, points)| points >= 3));
+/// assert!(table[0].1 >= 5);
+/// ```
+pub fn placement_points<P: Clone + PartialEq>(
+    results: &[GameResult<P>],
+    points: &[u32],
+) -> Vec<(P, u32)> {
+    let mut table: Vec<(P, u32)> = Vec::new();
+
+    for result in results {
+        for (player, place) in result.rankings() {
+            let earned = points.get(place as usize - 1).copied().unwrap_or_default();
+
+            match table.iter_mut().find(|(entry, _)| entry == player) {
+                Some((_, total)) => *total += earned,
+                None => table.push((player.clone(), earned)),
+            }
+        }
+    }
+
+    // The sort is stable, so ties stay in the order they first played
+    table.sort_by(|(_, a), (_, b)| b.cmp(a));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_out<E: Clone + PartialEq>(
+        mut bracket: Bracket<E>,
+        pick: impl Fn(&E, &E) -> E,
+    ) -> Bracket<E> {
+        while let Some(&(id, a, b)) = bracket.ready_matches().first() {
+            let winner = pick(a, b);
+            bracket = bracket.record_result(id, &winner).unwrap();
+        }
+
+        bracket
+    }
+
+    #[test]
+    fn test_brackets_need_two_entrants() {
+        assert_eq!(
+            Bracket::single_elimination(vec![1]),
+            Err(NotEnoughEntrants { entrants: 1 })
+        );
+    }
+
+    #[test]
+    fn test_top_seeds_meet_in_the_final() {
+        let bracket = Bracket::single_elimination((0..8).collect()).unwrap();
+        let bracket = play_out(bracket, |&a, &b| a.min(b));
+        let final_match = bracket.matches().len() - 1;
+
+        assert_eq!(bracket.players(final_match), Some([Some(&0), Some(&1)]));
+        assert_eq!(bracket.champion(), Some(&0));
+    }
+
+    #[test]
+    fn test_top_seeds_get_the_byes() {
+        let bracket = Bracket::single_elimination((0..5).collect()).unwrap();
+        let ready: Vec<_> = bracket
+            .ready_matches()
+            .into_iter()
+            .map(|(_, &a, &b)| (a, b))
+            .collect();
+
+        // Seeds 1 and 2 both have byes, so their second round match is ready too
+        assert_eq!(ready, vec![(3, 4), (1, 2)]);
+    }
+
+    #[test]
+    fn test_results_can_only_be_recorded_once_for_players_in_the_match() {
+        let bracket = Bracket::single_elimination(vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(bracket.record_result(0, &2), Err(NotInMatch { id: 0 }));
+        assert_eq!(bracket.record_result(2, &1), Err(MatchNotReady { id: 2 }));
+        assert_eq!(bracket.record_result(9, &1), Err(NoSuchMatch { id: 9 }));
+
+        let bracket = bracket.record_result(0, &1).unwrap();
+        assert_eq!(
+            bracket.record_result(0, &1),
+            Err(MatchAlreadyDecided { id: 0 })
+        );
+    }
+
+    #[test]
+    fn test_double_elimination_gives_everyone_two_lives() {
+        for entrants in 2..=9 {
+            let bracket = Bracket::double_elimination((0..entrants).collect()).unwrap();
+            // The worst seed wins every match, so they never drop into the losers bracket
+            let bracket = play_out(bracket, |&a, &b| a.max(b));
+
+            assert!(bracket.is_complete());
+            assert_eq!(bracket.champion(), Some(&(entrants - 1)));
+            let final_match = bracket.matches().len() - 1;
+            assert_eq!(
+                bracket.players(final_match).unwrap()[1],
+                Some(&(entrants - 2))
+            );
+        }
+    }
+
+    #[test]
+    fn test_losers_bracket_winner_can_win_the_grand_final() {
+        let bracket = Bracket::double_elimination(vec![1, 2, 3, 4]).unwrap();
+        // The bottom seed loses their first match, then wins everything else
+        let bracket = bracket.record_result(0, &1).unwrap();
+        let bracket = play_out(
+            bracket,
+            |&a, &b| if a == 4 || b == 4 { 4 } else { a.min(b) },
+        );
+
+        assert_eq!(bracket.champion(), Some(&4));
+    }
+
+    fn play_swiss(swiss: Swiss<usize>, rounds: usize) -> Swiss<usize> {
+        (0..rounds).fold(swiss, |swiss, _| {
+            let swiss = swiss.pair_next_round().unwrap();
+            let games: Vec<_> = swiss
+                .current_round()
+                .filter(|(_, _, second)| second.is_some())
+                .map(|(id, first, second)| (id, *first < *second.unwrap()))
+                .collect();
+
+            // The better seed always wins
+            games
+                .into_iter()
+                .fold(swiss, |swiss, (id, first_is_better)| {
+                    let outcome = if first_is_better {
+                        Outcome::FirstWins
+                    } else {
+                        Outcome::SecondWins
+                    };
+                    swiss.record_result(id, outcome).unwrap()
+                })
+        })
+    }
+
+    #[test]
+    fn test_swiss_has_no_rematches() {
+        let swiss = play_swiss(Swiss::new((0..8).collect()).unwrap(), 7);
+
+        for entrant in 0..8 {
+            let mut opponents: Vec<usize> = swiss.opponents(entrant).collect();
+            opponents.sort_unstable();
+            opponents.dedup();
+            assert_eq!(opponents.len(), 7);
+        }
+
+        let standings: Vec<usize> = swiss.standings().iter().map(|s| *s.entrant).collect();
+        assert_eq!(standings, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_swiss_byes_go_to_different_players() {
+        let swiss = play_swiss(Swiss::new((0..5).collect()).unwrap(), 5);
+        let mut byes: Vec<usize> = swiss
+            .games()
+            .iter()
+            .filter(|game| game.second.is_none())
+            .map(|game| game.first)
+            .collect();
+        byes.sort_unstable();
+
+        assert_eq!(byes, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_swiss_balances_going_first() {
+        let swiss = play_swiss(Swiss::new((0..8).collect()).unwrap(), 6);
+
+        for entrant in 0..8 {
+            let firsts = swiss
+                .games()
+                .iter()
+                .filter(|game| game.first == entrant)
+                .count();
+            assert!(
+                (2..=4).contains(&firsts),
+                "{} went first {} times",
+                entrant,
+                firsts
+            );
+        }
+    }
+
+    #[test]
+    fn test_swiss_rounds_must_finish_before_pairing() {
+        let swiss = Swiss::new(vec![1, 2]).unwrap().pair_next_round().unwrap();
+        assert_eq!(
+            swiss.pair_next_round(),
+            Err(SwissError::RoundInProgress { round: 1 })
+        );
+
+        let swiss = swiss.record_result(0, Outcome::Draw).unwrap();
+        assert_eq!(
+            swiss.record_result(0, Outcome::Draw),
+            Err(SwissError::GameAlreadyDecided { id: 0 })
+        );
+        assert!(swiss.pair_next_round().is_ok());
+    }
+
+    #[test]
+    fn test_buchholz_breaks_ties() {
+        let pairings = |swiss: &Swiss<usize>| -> Vec<_> {
+            swiss
+                .current_round()
+                .map(|(_, first, second)| (*first, *second.unwrap()))
+                .collect()
+        };
+
+        let swiss = Swiss::new((0..4).collect()).unwrap();
+        let swiss = swiss.pair_next_round().unwrap();
+        assert_eq!(pairings(&swiss), vec![(0, 2), (1, 3)]);
+        let swiss = swiss
+            .record_result(0, Outcome::FirstWins)
+            .unwrap()
+            .record_result(1, Outcome::SecondWins)
+            .unwrap();
+
+        let swiss = swiss.pair_next_round().unwrap();
+        assert_eq!(pairings(&swiss), vec![(3, 0), (2, 1)]);
+        let swiss = swiss
+            .record_result(2, Outcome::SecondWins)
+            .unwrap()
+            .record_result(3, Outcome::SecondWins)
+            .unwrap();
+
+        // 3 and 1 both won once, but 3 lost to the leader while 1 lost to the last place
+        let standings: Vec<_> = swiss
+            .standings()
+            .iter()
+            .map(|standing| (*standing.entrant, standing.points, standing.buchholz))
+            .collect();
+        assert_eq!(standings, vec![(0, 4, 2), (3, 2, 6), (1, 2, 2), (2, 0, 6)]);
+    }
+
+    #[test]
+    fn test_brackets_serialize() {
+        let bracket = Bracket::double_elimination(vec!["a", "b", "c"]).unwrap();
+        let bracket = bracket.record_result(1, &"b").unwrap();
+        let json = serde_json::to_string(&bracket).unwrap();
+        let deserialized: Bracket<&str> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, bracket);
+    }
+}
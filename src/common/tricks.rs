@@ -0,0 +1,260 @@
+use std::cmp::Ordering;
+
+use crate::common::deck::{Card, Rank, Suit};
+
+pub use crate::common::deck::RankOrder;
+
+/// A hook for games where some cards don't follow the normal suits and ranks. Given a card and
+/// the trump suit, returns the suit the card counts as and its power, or `None` to use the
+/// defaults. Normal powers go from 1 to 14, so use higher numbers to beat every normal card
+pub type RankOverride = fn(Card, Option<Suit>) -> Option<(Suit, u8)>;
+
+/// The euchre bowers: the jack of trump is the highest trump, and the other jack of the same
+/// color counts as trump and is the second highest
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::tricks::euchre_bowers;
+///
+/// assert_eq!(euchre_bowers(Card(Jack, Hearts), Some(Hearts)), Some((Hearts, 16)));
+/// assert_eq!(euchre_bowers(Card(Jack, Diamonds), Some(Hearts)), Some((Hearts, 15)));
+/// assert_eq!(euchre_bowers(Card(Jack, Spades), Some(Hearts)), None);
+/// assert_eq!(euchre_bowers(Card(Jack, Hearts), None), None);
+/// ```
+pub fn euchre_bowers(Card(rank, suit): Card, trump: Option<Suit>) -> Option<(Suit, u8)> {
+    let trump = trump?;
+
+    match rank {
+        Rank::Jack if suit == trump => Some((trump, 16)),
+        Rank::Jack if suit.color() == trump.color() => Some((trump, 15)),
+        _ => None,
+    }
+}
+
+/// The rules for deciding who wins a trick. A trump beats anything that isn't a trump, a card
+/// of the led suit beats any other suit, and within a suit the more powerful card wins. Cards
+/// that are neither trump nor the led suit can never win and are equal to each other
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::tricks::{euchre_bowers, RankOrder, TrickRules};
+///
+/// let rules = TrickRules::new(Some(Spades), RankOrder::AceHigh);
+/// let trick = [("ann", Card(Ace, Hearts)), ("bob", Card(Two, Spades)), ("cat", Card(King, Hearts))];
+/// assert_eq!(rules.winner(&trick), Some("bob"));
+///
+/// let euchre = rules.with_override(euchre_bowers);
+/// let trick = [("ann", Card(Ace, Spades)), ("bob", Card(Jack, Clubs))];
+/// assert_eq!(euchre.winner(&trick), Some("bob"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TrickRules {
+    pub trump: Option<Suit>,
+    pub rank_order: RankOrder,
+    pub rank_override: Option<RankOverride>,
+}
+
+impl TrickRules {
+    pub fn new(trump: Option<Suit>, rank_order: RankOrder) -> Self {
+        Self {
+            trump,
+            rank_order,
+            rank_override: None,
+        }
+    }
+
+    /// Uses a hook to change the suits and powers of special cards
+    pub fn with_override(self, rank_override: RankOverride) -> Self {
+        Self {
+            rank_override: Some(rank_override),
+            ..self
+        }
+    }
+
+    /// The suit a card counts as, which can differ from the suit printed on it
+    pub fn effective_suit(&self, card: Card) -> Suit {
+        self.strength(card).0
+    }
+
+    /// Compares two cards played to a trick where a suit was led
+    pub fn compare(&self, led: Suit, a: Card, b: Card) -> Ordering {
+        let (a_suit, a_power) = self.strength(a);
+        let (b_suit, b_power) = self.strength(b);
+
+        let priority = |suit: Suit| {
+            if Some(suit) == self.trump {
+                2
+            } else if suit == led {
+                1
+            } else {
+                0
+            }
+        };
+
+        match priority(a_suit).cmp(&priority(b_suit)) {
+            Ordering::Equal if priority(a_suit) == 0 => Ordering::Equal,
+            Ordering::Equal => a_power.cmp(&b_power),
+            ordering => ordering,
+        }
+    }
+
+    /// The player who won a trick, given the cards in the order they were played. The first card
+    /// sets the led suit. Returns `None` for an empty trick
+    pub fn winner<P: Copy>(&self, trick: &[(P, Card)]) -> Option<P> {
+        let &(_, first) = trick.first()?;
+        let led = self.effective_suit(first);
+
+        let mut best = (trick[0].0, first);
+
+        for &(player, card) in &trick[1..] {
+            if self.compare(led, card, best.1) == Ordering::Greater {
+                best = (player, card);
+            }
+        }
+
+        Some(best.0)
+    }
+
+    /// The player who won a trick once all of the players have played to it, or `None` while
+    /// it's still going
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::common::tricks::{RankOrder, TrickRules};
+    ///
+    /// let rules = TrickRules::new(Some(Spades), RankOrder::AceHigh);
+    /// let trick = [("ann", Card(Ace, Hearts)), ("bob", Card(Two, Spades))];
+    /// assert_eq!(rules.finished(&trick, 3), None);
+    /// assert_eq!(rules.finished(&trick, 2), Some("bob"));
+    /// ```
+    pub fn finished<P: Copy>(&self, trick: &[(P, Card)], players: usize) -> Option<P> {
+        if trick.len() < players {
+            return None;
+        }
+
+        self.winner(trick)
+    }
+
+    /// The cards in a trick from strongest to weakest. Cards that can't win stay in the order
+    /// they were played
+    pub fn ranked<P: Copy>(&self, trick: &[(P, Card)]) -> Vec<(P, Card)> {
+        let mut ranked = trick.to_vec();
+
+        if let Some(&(_, first)) = trick.first() {
+            let led = self.effective_suit(first);
+            ranked.sort_by(|&(_, a), &(_, b)| self.compare(led, b, a));
+        }
+
+        ranked
+    }
+
+    fn strength(&self, card: Card) -> (Suit, u8) {
+        self.rank_override
+            .and_then(|rank_override| rank_override(card, self.trump))
+            .unwrap_or_else(|| (card.suit(), card.rank().power(self.rank_order)))
+    }
+}
+
+/// The cards in a hand that match, or the whole hand when none do, for rules like following the
+/// led suit
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::tricks::must_match;
+///
+/// let hand = [Card(Ace, Hearts), Card(Two, Clubs)];
+/// assert_eq!(must_match(&hand, |card| card.suit() == Clubs), vec![Card(Two, Clubs)]);
+/// assert_eq!(must_match(&hand, |card| card.suit() == Spades), hand.to_vec());
+/// ```
+pub fn must_match(hand: &[Card], matches: impl Fn(Card) -> bool) -> Vec<Card> {
+    let matching: Vec<Card> = hand.iter().copied().filter(|&card| matches(card)).collect();
+    if matching.is_empty() {
+        hand.to_vec()
+    } else {
+        matching
+    }
+}
+
+/// Makes a comparator for the cards in a trick, for sorting or picking the best card
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::tricks::{compare_cards, RankOrder};
+/// use std::cmp::Ordering;
+///
+/// let compare = compare_cards(Hearts, None, RankOrder::AceHigh);
+/// assert_eq!(compare(Card(Ace, Hearts), Card(King, Hearts)), Ordering::Greater);
+/// assert_eq!(compare(Card(Two, Hearts), Card(Ace, Clubs)), Ordering::Greater);
+///
+/// let compare = compare_cards(Hearts, None, RankOrder::AceLow);
+/// assert_eq!(compare(Card(Ace, Hearts), Card(King, Hearts)), Ordering::Less);
+/// ```
+pub fn compare_cards(
+    led: Suit,
+    trump: Option<Suit>,
+    rank_order: RankOrder,
+) -> impl Fn(Card, Card) -> Ordering {
+    let rules = TrickRules::new(trump, rank_order);
+    move |a, b| rules.compare(led, a, b)
+}
+
+/// The player who won a trick, see [`TrickRules::winner`](TrickRules::winner)
+pub fn trick_winner<P: Copy>(
+    trick: &[(P, Card)],
+    trump: Option<Suit>,
+    rank_order: RankOrder,
+) -> Option<P> {
+    TrickRules::new(trump, rank_order).winner(trick)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank::*, Suit::*};
+
+    #[test]
+    fn test_off_suit_cards_never_win() {
+        let trick = [(1, Card(Two, Clubs)), (2, Card(Ace, Hearts))];
+        assert_eq!(trick_winner(&trick, None, RankOrder::AceHigh), Some(1));
+        assert_eq!(trick_winner::<u8>(&[], None, RankOrder::AceHigh), None);
+    }
+
+    #[test]
+    fn test_the_highest_trump_wins() {
+        let trick = [
+            (1, Card(Ace, Clubs)),
+            (2, Card(Three, Diamonds)),
+            (3, Card(Ten, Diamonds)),
+            (4, Card(King, Clubs)),
+        ];
+        assert_eq!(
+            trick_winner(&trick, Some(Diamonds), RankOrder::AceHigh),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_leading_the_left_bower_leads_trump() {
+        let rules = TrickRules::new(Some(Hearts), RankOrder::AceHigh).with_override(euchre_bowers);
+        let trick = [
+            (1, Card(Jack, Diamonds)),
+            (2, Card(Ace, Diamonds)),
+            (3, Card(Ace, Hearts)),
+            (4, Card(Jack, Hearts)),
+        ];
+
+        assert_eq!(rules.effective_suit(Card(Jack, Diamonds)), Hearts);
+        assert_eq!(
+            rules.ranked(&trick),
+            vec![
+                (4, Card(Jack, Hearts)),
+                (1, Card(Jack, Diamonds)),
+                (3, Card(Ace, Hearts)),
+                (2, Card(Ace, Diamonds)),
+            ]
+        );
+        assert_eq!(rules.winner(&trick), Some(4));
+    }
+
+    #[test]
+    fn test_ties_go_to_the_first_card_played() {
+        let rules = TrickRules::new(None, RankOrder::AceHigh);
+        let trick = [(1, Card(Nine, Spades)), (2, Card(Nine, Spades))];
+        assert_eq!(rules.winner(&trick), Some(1));
+    }
+}
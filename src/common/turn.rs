@@ -0,0 +1,491 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use thiserror::Error;
+
+use crate::common::rand::RngSeed;
+
+/// How the players a game is waiting on are allowed to act
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phase {
+    /// The players act one at a time, usually it's just the player whose turn it is
+    Sequential,
+    /// The players all act at once, and their actions are revealed together once everyone has
+    /// acted (rock paper scissors, submitting words in Boggle)
+    Simultaneous,
+    /// The players may act out of turn in response to something, or pass (slaps, challenges)
+    Reaction,
+}
+
+/// The players a game is waiting on for input
+/// ```
+/// use lib_table_top::common::turn::{PendingInput, Phase};
+/// use lib_table_top::games::crazy_eights::Player::*;
+///
+/// let pending = PendingInput::simultaneous(vec![P1, P2]);
+/// assert_eq!(pending.phase, Phase::Simultaneous);
+/// assert!(pending.is_pending_on(P2));
+/// assert!(!pending.is_pending_on(P3));
+///
+/// assert!(PendingInput::<u8>::none().is_empty());
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingInput<P> {
+    pub phase: Phase,
+    pub players: Vec<P>,
+}
+
+impl<P: PartialEq> PendingInput<P> {
+    /// Waiting on a single player to take their turn
+    pub fn turn(player: P) -> Self {
+        Self {
+            phase: Phase::Sequential,
+            players: vec![player],
+        }
+    }
+
+    /// Waiting on all of the players to act at once
+    pub fn simultaneous(players: Vec<P>) -> Self {
+        Self {
+            phase: Phase::Simultaneous,
+            players,
+        }
+    }
+
+    /// Waiting on any of the players to react or pass
+    pub fn reaction(players: Vec<P>) -> Self {
+        Self {
+            phase: Phase::Reaction,
+            players,
+        }
+    }
+
+    /// Not waiting on anyone, usually because the game is over
+    pub fn none() -> Self {
+        Self {
+            phase: Phase::Sequential,
+            players: vec![],
+        }
+    }
+
+    /// Whether the game is waiting on input from a player
+    pub fn is_pending_on(&self, player: P) -> bool {
+        self.players.contains(&player)
+    }
+
+    /// Whether the game isn't waiting on anyone
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// The various errors that can happen when players act during a simultaneous or reaction phase
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnError<P: Debug> {
+    /// The player isn't one of the players the phase is waiting on
+    #[error("Not waiting on input from {:?}", player)]
+    NotPendingOnPlayer { player: P },
+    /// The player already acted during this phase
+    #[error("{:?} has already acted", player)]
+    AlreadyActed { player: P },
+}
+
+use TurnError::*;
+
+/// Collects the actions of players acting simultaneously, the actions stay secret until every
+/// player has submitted one
+/// ```
+/// use lib_table_top::common::turn::{Commitments, TurnError};
+/// use lib_table_top::games::marooned::Player::*;
+///
+/// let commitments = Commitments::new(vec![P1, P2]);
+/// let commitments = commitments.submit(P1, "rock").unwrap();
+/// assert_eq!(commitments.reveal(), None);
+/// assert_eq!(
+///   commitments.submit(P1, "paper"),
+///   Err(TurnError::AlreadyActed { player: P1 })
+/// );
+///
+/// let commitments = commitments.submit(P2, "scissors").unwrap();
+/// assert_eq!(commitments.reveal(), Some(vec![(P1, "rock"), (P2, "scissors")]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitments<P, A> {
+    players: Vec<P>,
+    submitted: Vec<(P, A)>,
+}
+
+impl<P: Copy + Debug + PartialEq, A: Clone> Commitments<P, A> {
+    /// Starts collecting actions from the players
+    pub fn new(players: Vec<P>) -> Self {
+        Self {
+            players,
+            submitted: Vec::new(),
+        }
+    }
+
+    /// Secretly records a player's action
+    pub fn submit(&self, player: P, action: A) -> Result<Self, TurnError<P>> {
+        if !self.players.contains(&player) {
+            return Err(NotPendingOnPlayer { player });
+        }
+
+        if self.has_submitted(player) {
+            return Err(AlreadyActed { player });
+        }
+
+        let mut commitments = self.clone();
+        commitments.submitted.push((player, action));
+        Ok(commitments)
+    }
+
+    /// Whether a player has submitted their action
+    pub fn has_submitted(&self, player: P) -> bool {
+        self.submitted.iter().any(|&(p, _)| p == player)
+    }
+
+    /// The players who still need to submit an action
+    pub fn pending_input(&self) -> PendingInput<P> {
+        PendingInput::simultaneous(
+            self.players
+                .iter()
+                .copied()
+                .filter(|&player| !self.has_submitted(player))
+                .collect(),
+        )
+    }
+
+    /// The actions in the order of the players, once every player has submitted one
+    pub fn reveal(&self) -> Option<Vec<(P, A)>> {
+        self.players
+            .iter()
+            .map(|&player| self.submitted.iter().find(|&&(p, _)| p == player).cloned())
+            .collect()
+    }
+}
+
+/// Tracks a window where players may act out of turn in response to something. The window closes
+/// when someone reacts or every player has passed
+/// ```
+/// use lib_table_top::common::turn::ReactionWindow;
+/// use lib_table_top::games::crazy_eights::Player::*;
+///
+/// let window = ReactionWindow::new(vec![P2, P3]);
+/// let window = window.pass(P2).unwrap();
+/// assert!(!window.is_closed());
+/// assert!(window.pending_input().is_pending_on(P3));
+///
+/// let window = window.react(P3).unwrap();
+/// assert!(window.is_closed());
+/// assert_eq!(window.reactor(), Some(P3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionWindow<P> {
+    players: Vec<P>,
+    passed: Vec<P>,
+    reactor: Option<P>,
+}
+
+impl<P: Copy + Debug + PartialEq> ReactionWindow<P> {
+    /// Opens a window for the players to react in
+    pub fn new(players: Vec<P>) -> Self {
+        Self {
+            players,
+            passed: Vec::new(),
+            reactor: None,
+        }
+    }
+
+    /// A player declines to react
+    pub fn pass(&self, player: P) -> Result<Self, TurnError<P>> {
+        self.validate(player)?;
+        let mut window = self.clone();
+        window.passed.push(player);
+        Ok(window)
+    }
+
+    /// A player reacts, closing the window
+    pub fn react(&self, player: P) -> Result<Self, TurnError<P>> {
+        self.validate(player)?;
+        let mut window = self.clone();
+        window.reactor = Some(player);
+        Ok(window)
+    }
+
+    /// The player who reacted, if anyone has
+    pub fn reactor(&self) -> Option<P> {
+        self.reactor
+    }
+
+    /// Whether someone has reacted or everyone has passed
+    pub fn is_closed(&self) -> bool {
+        self.reactor.is_some() || self.players.iter().all(|p| self.passed.contains(p))
+    }
+
+    /// The players who may still react
+    pub fn pending_input(&self) -> PendingInput<P> {
+        if self.is_closed() {
+            return PendingInput::none();
+        }
+
+        PendingInput::reaction(
+            self.players
+                .iter()
+                .copied()
+                .filter(|player| !self.passed.contains(player))
+                .collect(),
+        )
+    }
+
+    fn validate(&self, player: P) -> Result<(), TurnError<P>> {
+        if !self.players.contains(&player) || self.is_closed() {
+            return Err(NotPendingOnPlayer { player });
+        }
+
+        if self.passed.contains(&player) {
+            return Err(AlreadyActed { player });
+        }
+
+        Ok(())
+    }
+}
+
+/// Which way play moves around the table
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Clockwise,
+    Counterclockwise,
+}
+
+/// The order players take turns in, with the effects card games use to change it. Each effect
+/// ends the current player's turn and says who goes next
+/// ```
+/// use lib_table_top::common::turn::{Direction, TurnOrder};
+/// use lib_table_top::games::crazy_eights::Player::*;
+///
+/// let order = TurnOrder::new(vec![P1, P2, P3, P4]);
+/// assert_eq!(order.current(), P1);
+///
+/// let order = order.skip();
+/// assert_eq!(order.current(), P3);
+///
+/// let order = order.reverse();
+/// assert_eq!(order.direction(), Direction::Counterclockwise);
+/// assert_eq!(order.current(), P2);
+///
+/// let order = order.play_again().advance();
+/// assert_eq!(order.current(), P1);
+/// assert_eq!(order.upcoming(), P4);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnOrder<P> {
+    players: Vec<P>,
+    current: usize,
+    direction: Direction,
+}
+
+impl<P: Copy> TurnOrder<P> {
+    /// Starts with the first player, going clockwise
+    pub fn new(players: Vec<P>) -> Self {
+        assert!(
+            !players.is_empty(),
+            "A turn order needs at least one player"
+        );
+
+        Self {
+            players,
+            current: 0,
+            direction: Direction::Clockwise,
+        }
+    }
+
+    /// The player whose turn it is
+    pub fn current(&self) -> P {
+        self.players[self.current]
+    }
+
+    /// The player who will go next if nothing changes the order
+    pub fn upcoming(&self) -> P {
+        self.players[self.step(1)]
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Moves on to the next player
+    pub fn advance(&self) -> Self {
+        self.moved(1)
+    }
+
+    /// Skips over the next player
+    pub fn skip(&self) -> Self {
+        self.moved(2)
+    }
+
+    /// Flips the direction of play, then moves on to the next player in the new direction. With
+    /// two players the direction doesn't matter, so this is the same as `advance`
+    pub fn reverse(&self) -> Self {
+        let direction = match self.direction {
+            Direction::Clockwise => Direction::Counterclockwise,
+            Direction::Counterclockwise => Direction::Clockwise,
+        };
+
+        Self {
+            direction,
+            ..self.clone()
+        }
+        .advance()
+    }
+
+    /// The current player goes again
+    pub fn play_again(&self) -> Self {
+        self.clone()
+    }
+
+    fn moved(&self, steps: usize) -> Self {
+        Self {
+            current: self.step(steps),
+            ..self.clone()
+        }
+    }
+
+    fn step(&self, steps: usize) -> usize {
+        let len = self.players.len();
+        let steps = steps % len;
+
+        match self.direction {
+            Direction::Clockwise => (self.current + steps) % len,
+            Direction::Counterclockwise => (self.current + len - steps) % len,
+        }
+    }
+}
+
+/// How the first player of each game in a series is picked
+/// ```
+/// use lib_table_top::common::turn::FirstPlayer;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Player::*};
+///
+/// let players = [P1, P2];
+/// let first_player = FirstPlayer::Alternating;
+///
+/// let firsts: Vec<_> = (0..4).map(|game| first_player.choose(&players, game, None)).collect();
+/// assert_eq!(firsts, vec![P1, P2, P1, P2]);
+///
+/// let game = GameState::starting_with(first_player.choose(&players, 1, None));
+/// assert_eq!(game.whose_turn(), P2);
+///
+/// // The loser of the last game goes first
+/// assert_eq!(FirstPlayer::LoserGoesFirst.choose(&players, 2, Some(P2)), P2);
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FirstPlayer<P> {
+    /// The same player always goes first
+    Fixed(P),
+    /// The players take turns going first, in seating order
+    Alternating,
+    /// A random player goes first, the same seed picks the same players every time
+    Random(RngSeed),
+    /// Whoever lost the previous game goes first, the players alternate for the first game and
+    /// after draws
+    LoserGoesFirst,
+}
+
+impl<P: Copy> FirstPlayer<P> {
+    /// Picks the first player of a game, given the players in seating order, the number of the
+    /// game in the series starting from 0, and the loser of the previous game if there was one
+    pub fn choose(&self, players: &[P], game: usize, previous_loser: Option<P>) -> P {
+        assert!(
+            !players.is_empty(),
+            "Picking a first player needs at least one player"
+        );
+
+        match (self, previous_loser) {
+            (FirstPlayer::Fixed(player), _) => *player,
+            (FirstPlayer::Random(seed), _) => {
+                let mut rng = seed.into_rng();
+                rng.set_stream(game as u64);
+                *players.choose(&mut rng).unwrap()
+            }
+            (FirstPlayer::LoserGoesFirst, Some(loser)) => loser,
+            (FirstPlayer::Alternating, _) | (FirstPlayer::LoserGoesFirst, None) => {
+                players[game % players.len()]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::crazy_eights::Player::{self, *};
+    use itertools::iterate;
+
+    #[test]
+    fn test_only_pending_players_can_commit() {
+        let commitments: Commitments<_, u8> = Commitments::new(vec![P1, P2]);
+        assert_eq!(
+            commitments.submit(P3, 1),
+            Err(NotPendingOnPlayer { player: P3 })
+        );
+    }
+
+    #[test]
+    fn test_commitments_pending_input() {
+        let commitments = Commitments::new(vec![P1, P2, P3]).submit(P2, 1).unwrap();
+        assert_eq!(
+            commitments.pending_input(),
+            PendingInput::simultaneous(vec![P1, P3])
+        );
+    }
+
+    #[test]
+    fn test_reaction_windows_close_when_everyone_passes() {
+        let window = ReactionWindow::new(vec![P1, P2]);
+        let window = window.pass(P1).unwrap();
+        assert_eq!(window.pass(P1), Err(AlreadyActed { player: P1 }));
+
+        let window = window.pass(P2).unwrap();
+        assert!(window.is_closed());
+        assert_eq!(window.reactor(), None);
+        assert!(window.pending_input().is_empty());
+        assert_eq!(window.react(P1), Err(NotPendingOnPlayer { player: P1 }));
+    }
+
+    #[test]
+    fn test_turn_order_wraps_around_in_both_directions() {
+        let order = TurnOrder::new(vec![P1, P2, P3]);
+        let clockwise: Vec<Player> = iterate(order.clone(), TurnOrder::advance)
+            .map(|order| order.current())
+            .take(4)
+            .collect();
+        assert_eq!(clockwise, vec![P1, P2, P3, P1]);
+
+        let counterclockwise = order.reverse();
+        assert_eq!(counterclockwise.current(), P3);
+        assert_eq!(counterclockwise.skip().current(), P1);
+    }
+
+    #[test]
+    fn test_skips_and_reverses_with_two_players() {
+        let order = TurnOrder::new(vec![P1, P2]);
+        assert_eq!(order.skip().current(), P1);
+        assert_eq!(order.reverse().current(), P2);
+    }
+
+    #[test]
+    fn test_random_first_players_are_reproducible() {
+        let first_player = FirstPlayer::Random(RngSeed([3; 32]));
+        let firsts: Vec<Player> = (0..20)
+            .map(|game| first_player.choose(&[P1, P2], game, Some(P1)))
+            .collect();
+
+        assert!(firsts.contains(&P1) && firsts.contains(&P2));
+        assert_eq!(
+            firsts,
+            (0..20)
+                .map(|game| first_player.choose(&[P1, P2], game, None))
+                .collect::<Vec<Player>>()
+        );
+    }
+}
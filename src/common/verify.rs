@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::common::replay::Replayable;
+use crate::common::settings::FromSettings;
+
+/// Games that can hash their whole state, hidden information included. The hash is stable
+/// across builds and platforms, so a client and a server can compare them
+pub trait StateHash {
+    fn state_hash(&self) -> u64;
+}
+
+#[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerifyError {
+    #[error("The action at turn {:?} can't be applied", turn)]
+    IllegalAction { turn: usize },
+    #[error(
+        "The replay diverged at turn {:?}, the claimed hash was {:?} but it's {:?}",
+        turn,
+        claimed,
+        actual
+    )]
+    Diverged {
+        turn: usize,
+        claimed: u64,
+        actual: u64,
+    },
+}
+
+use VerifyError::*;
+
+/// The hash of the state after every turn of a history, starting with the state before any
+/// actions, so clients can submit them alongside a result
+pub fn state_hashes<G>(
+    settings: Arc<G::Settings>,
+    history: &[G::Action],
+) -> Result<Vec<u64>, VerifyError>
+where
+    G: FromSettings + Replayable + StateHash,
+{
+    let mut game = G::from_settings(settings);
+    let mut hashes = Vec::with_capacity(history.len() + 1);
+    hashes.push(game.state_hash());
+
+    for (turn, action) in history.iter().enumerate() {
+        game = game
+            .apply(action.clone())
+            .map_err(|_| IllegalAction { turn })?;
+        hashes.push(game.state_hash());
+    }
+
+    Ok(hashes)
+}
+
+/// Replays a history from scratch and checks it ends in the claimed state, for checking results
+/// submitted by clients. Only the final hash is claimed, so the turn of a mismatch is the last
+/// one, use [`first_divergence`](fn@first_divergence) to pinpoint where a game went wrong
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::common::replay::Replayable;
+/// use lib_table_top::common::verify::{replay_matches, StateHash, VerifyError};
+/// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+/// use std::sync::Arc;
+///
+/// let settings = Arc::new(Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Two));
+/// let mut game = GameState::new(settings.clone());
+/// for _ in 0..6 {
+///     let action = game.current_player_view().suggest_action().unwrap();
+///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+/// }
+///
+/// let history = game.actions();
+/// assert_eq!(replay_matches::<GameState>(settings.clone(), &history, game.state_hash()), Ok(()));
+/// assert!(matches!(
+///     replay_matches::<GameState>(settings.clone(), &history, 1234),
+///     Err(VerifyError::Diverged { turn: 6, .. })
+/// ));
+///
+/// // Replaying a different deal doesn't get the same game
+/// let other = Arc::new(Settings::tournament(RngSeed([1; 32]), NumberOfPlayers::Two));
+/// assert!(replay_matches::<GameState>(other, &history, game.state_hash()).is_err());
+/// ```
+pub fn replay_matches<G>(
+    settings: Arc<G::Settings>,
+    history: &[G::Action],
+    claimed_final_hash: u64,
+) -> Result<(), VerifyError>
+where
+    G: FromSettings + Replayable + StateHash,
+{
+    let actual = *state_hashes::<G>(settings, history)?
+        .last()
+        .expect("There's always the starting state");
+
+    if actual == claimed_final_hash {
+        Ok(())
+    } else {
+        Err(Diverged {
+            turn: history.len(),
+            claimed: claimed_final_hash,
+            actual,
+        })
+    }
+}
+
+/// Replays a history and compares the state after every turn with the claimed hashes, from
+/// [`state_hashes`](fn@state_hashes), returning the first turn that doesn't match. Claimed
+/// hashes past the end of the history are ignored
+pub fn first_divergence<G>(
+    settings: Arc<G::Settings>,
+    history: &[G::Action],
+    claimed_hashes: &[u64],
+) -> Result<(), VerifyError>
+where
+    G: FromSettings + Replayable + StateHash,
+{
+    let hashes = state_hashes::<G>(settings, history)?;
+
+    match hashes
+        .iter()
+        .zip(claimed_hashes)
+        .enumerate()
+        .find(|(_, (actual, claimed))| actual != claimed)
+    {
+        Some((turn, (&actual, &claimed))) => Err(Diverged {
+            turn,
+            claimed,
+            actual,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::marooned::{GameState, Settings, SettingsBuilder};
+
+    fn settings() -> Arc<Settings> {
+        Arc::new(SettingsBuilder::new().build().unwrap())
+    }
+
+    #[test]
+    fn test_pinpointing_a_tampered_turn() {
+        let mut game = GameState::from_settings(settings());
+        for _ in 0..4 {
+            let action = game.valid_actions().next().unwrap();
+            game = game.apply_action(action).unwrap();
+        }
+
+        let history = game.actions();
+        let mut claimed = state_hashes::<GameState>(settings(), &history).unwrap();
+        assert_eq!(claimed.len(), 5);
+        assert_eq!(claimed[4], game.state_hash());
+        assert_eq!(
+            first_divergence::<GameState>(settings(), &history, &claimed),
+            Ok(())
+        );
+
+        claimed[2] ^= 1;
+        assert_eq!(
+            first_divergence::<GameState>(settings(), &history, &claimed),
+            Err(Diverged {
+                turn: 2,
+                claimed: claimed[2],
+                actual: claimed[2] ^ 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_illegal_histories_are_reported() {
+        let game = GameState::from_settings(settings());
+        let action = game.valid_actions().next().unwrap();
+
+        assert_eq!(
+            replay_matches::<GameState>(settings(), &[action, action], 0),
+            Err(IllegalAction { turn: 1 })
+        );
+    }
+}
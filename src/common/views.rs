@@ -0,0 +1,401 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// Who a view of a game is being made for, games with hidden information redact what each
+/// perspective isn't allowed to see
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Perspective<P> {
+    /// Someone watching the game, who can only see public information
+    Observer,
+    /// A player in the game, who can see public information and their own secrets
+    Player(P),
+    /// Sees everything, for after the game is over or for debugging
+    Omniscient,
+}
+
+impl<P: PartialEq> Perspective<P> {
+    /// Whether this perspective can see the secrets belonging to a player
+    /// ```
+    /// use lib_table_top::common::views::Perspective;
+    /// use lib_table_top::games::crazy_eights::Player::*;
+    ///
+    /// assert!(Perspective::Player(P1).can_see(P1));
+    /// assert!(!Perspective::Player(P1).can_see(P2));
+    /// assert!(!Perspective::Observer.can_see(P1));
+    /// assert!(Perspective::Omniscient.can_see(P2));
+    /// ```
+    pub fn can_see(&self, owner: P) -> bool {
+        match self {
+            Perspective::Observer => false,
+            Perspective::Player(player) => player == &owner,
+            Perspective::Omniscient => true,
+        }
+    }
+}
+
+/// Games with hidden information, which make views redacted for a perspective
+pub trait Viewable {
+    type Player;
+    type View;
+
+    /// Makes the view of the game for a perspective
+    fn view(&self, perspective: Perspective<Self::Player>) -> Self::View;
+}
+
+/// Views that hosts can attach their own data to, like hints, tutorial flags, or experiment
+/// data, without changing the view types. It's serialized with the view under `metadata`, and
+/// since each perspective gets its own view, metadata on a player's view only goes to them
+/// ```
+/// use lib_table_top::common::views::ViewMetadata;
+/// use lib_table_top::games::tic_tac_toe::GameState;
+/// use serde_json::json;
+///
+/// let view = GameState::new().observer_view();
+/// assert!(serde_json::to_value(&view).unwrap().get("metadata").is_none());
+///
+/// let view = view.with_metadata("tutorial_step", 3);
+/// assert_eq!(view.metadata()["tutorial_step"], json!(3));
+/// assert_eq!(serde_json::to_value(&view).unwrap()["metadata"], json!({"tutorial_step": 3}));
+/// ```
+pub trait ViewMetadata: Sized {
+    fn metadata(&self) -> &Map<String, Value>;
+
+    fn metadata_mut(&mut self) -> &mut Map<String, Value>;
+
+    /// Sets a piece of metadata, replacing anything already under the key
+    fn with_metadata(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.metadata_mut().insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Holds spectators' views back by a number of turns before releasing them, so a streamed game
+/// doesn't give away the players' timing or tells while it's still being played. Push the
+/// observer view after every turn, and send spectators whatever comes out
+/// ```
+/// use lib_table_top::common::views::DelayedObserver;
+///
+/// let mut delayed = DelayedObserver::new(2);
+/// assert_eq!(delayed.push("turn 1"), None);
+/// assert_eq!(delayed.push("turn 2"), None);
+/// assert_eq!(delayed.push("turn 3"), Some("turn 1"));
+///
+/// // Once the game is over everything can be shown
+/// assert_eq!(delayed.flush(), vec!["turn 2", "turn 3"]);
+/// assert_eq!(delayed.pending(), 0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelayedObserver<V> {
+    delay: usize,
+    buffered: VecDeque<V>,
+}
+
+impl<V> DelayedObserver<V> {
+    pub fn new(delay: usize) -> Self {
+        Self {
+            delay,
+            buffered: VecDeque::with_capacity(delay + 1),
+        }
+    }
+
+    /// The number of turns views are held back
+    pub fn delay(&self) -> usize {
+        self.delay
+    }
+
+    /// Buffers the latest view, returning the view from `delay` turns ago once there is one
+    pub fn push(&mut self, view: V) -> Option<V> {
+        self.buffered.push_back(view);
+
+        if self.buffered.len() > self.delay {
+            self.buffered.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// The number of views being held back
+    pub fn pending(&self) -> usize {
+        self.buffered.len()
+    }
+
+    /// Releases every view being held back, oldest first
+    pub fn flush(&mut self) -> Vec<V> {
+        self.buffered.drain(..).collect()
+    }
+}
+
+/// A secret that showed up in a view, along with the JSON pointer to where it was found
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leak {
+    pub secret: Value,
+    pub path: String,
+}
+
+/// Serializes a view and scans every value in it for the secrets, returning everywhere one
+/// showed up. Secrets are compared by their serialized form, so they should be things that are
+/// only ever in one place, like cards, rather than numbers that could appear for other reasons
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::views::leak_check;
+/// use serde_json::json;
+///
+/// let view = json!({"top_card": [3, "Hearts"], "hands": [[[12, "Spades"]]]});
+///
+/// assert!(leak_check(&view, vec![Card(Ace, Clubs)]).is_empty());
+///
+/// let leaks = leak_check(&view, vec![Card(Ace, Clubs), Card(Queen, Spades)]);
+/// assert_eq!(leaks.len(), 1);
+/// assert_eq!(leaks[0].path, "/hands/0/0");
+/// ```
+pub fn leak_check<V: Serialize, S: Serialize>(
+    view: &V,
+    secrets: impl IntoIterator<Item = S>,
+) -> Vec<Leak> {
+    let view = serde_json::to_value(view).expect("Views can always be serialized");
+    let secrets: Vec<Value> = secrets
+        .into_iter()
+        .map(|secret| serde_json::to_value(secret).expect("Secrets can always be serialized"))
+        .collect();
+
+    let mut leaks = Vec::new();
+    find_leaks(&view, &secrets, String::new(), &mut leaks);
+    leaks
+}
+
+fn find_leaks(value: &Value, secrets: &[Value], path: String, leaks: &mut Vec<Leak>) {
+    if secrets.contains(value) {
+        leaks.push(Leak {
+            secret: value.clone(),
+            path: path.clone(),
+        });
+    }
+
+    match value {
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                find_leaks(value, secrets, format!("{}/{}", path, index), leaks);
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                find_leaks(value, secrets, format!("{}/{}", path, escape(key)), leaks);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A single change to a view, following JSON patch (RFC 6902), with paths as JSON pointers
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// The changes that turn one view into the next, small enough to send every turn
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ViewPatch(pub Vec<PatchOp>);
+
+/// The things that can go wrong applying a patch, usually because it was made from a different
+/// view than the one it's being applied to
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PatchError {
+    #[error("The path {:?} isn't in the view", path)]
+    InvalidPath { path: String },
+    #[error("The patched view isn't valid: {}", message)]
+    InvalidView { message: String },
+}
+
+use PatchError::*;
+
+/// Finds the changes between two views
+/// ```
+/// use lib_table_top::common::views::{apply_patch, diff};
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new();
+/// let next = game.apply_action((P1, (Col1, Row1))).unwrap();
+///
+/// let patch = diff(&game.observer_view(), &next.observer_view());
+/// assert_eq!(patch.0.len(), 3);
+/// assert_eq!(apply_patch(&game.observer_view(), &patch), Ok(next.observer_view()));
+/// ```
+pub fn diff<V: Serialize>(prev: &V, next: &V) -> ViewPatch {
+    let prev = serde_json::to_value(prev).expect("Views can always be serialized");
+    let next = serde_json::to_value(next).expect("Views can always be serialized");
+
+    let mut ops = Vec::new();
+    diff_values(&prev, &next, String::new(), &mut ops);
+    ViewPatch(ops)
+}
+
+fn diff_values(prev: &Value, next: &Value, path: String, ops: &mut Vec<PatchOp>) {
+    match (prev, next) {
+        (Value::Object(prev), Value::Object(next)) => {
+            for (key, prev_value) in prev {
+                let path = format!("{}/{}", path, escape(key));
+                match next.get(key) {
+                    Some(next_value) => diff_values(prev_value, next_value, path, ops),
+                    None => ops.push(PatchOp::Remove { path }),
+                }
+            }
+
+            for (key, value) in next {
+                if !prev.contains_key(key) {
+                    ops.push(PatchOp::Add {
+                        path: format!("{}/{}", path, escape(key)),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(prev), Value::Array(next)) => {
+            for (index, (prev_value, next_value)) in prev.iter().zip(next).enumerate() {
+                diff_values(prev_value, next_value, format!("{}/{}", path, index), ops);
+            }
+
+            for (index, value) in next.iter().enumerate().skip(prev.len()) {
+                ops.push(PatchOp::Add {
+                    path: format!("{}/{}", path, index),
+                    value: value.clone(),
+                });
+            }
+
+            // Removing from the end first keeps the earlier indexes valid
+            for index in (next.len()..prev.len()).rev() {
+                ops.push(PatchOp::Remove {
+                    path: format!("{}/{}", path, index),
+                });
+            }
+        }
+        (prev, next) if prev != next => ops.push(PatchOp::Replace {
+            path,
+            value: next.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Applies the changes from [`diff`](fn@diff) to a view
+pub fn apply_patch<V: Serialize + DeserializeOwned>(
+    view: &V,
+    patch: &ViewPatch,
+) -> Result<V, PatchError> {
+    let mut value = serde_json::to_value(view).expect("Views can always be serialized");
+
+    for op in &patch.0 {
+        apply_op(&mut value, op)?;
+    }
+
+    serde_json::from_value(value).map_err(|error| InvalidView {
+        message: error.to_string(),
+    })
+}
+
+fn apply_op(value: &mut Value, op: &PatchOp) -> Result<(), PatchError> {
+    let path = match op {
+        PatchOp::Add { path, .. } | PatchOp::Remove { path } | PatchOp::Replace { path, .. } => {
+            path
+        }
+    };
+    let invalid_path = || InvalidPath { path: path.clone() };
+
+    if let PatchOp::Replace { value: new, .. } = op {
+        *value.pointer_mut(path).ok_or_else(invalid_path)? = new.clone();
+        return Ok(());
+    }
+
+    let split = path.rfind('/').ok_or_else(invalid_path)?;
+    let key = unescape(&path[split + 1..]);
+    let parent = value.pointer_mut(&path[..split]).ok_or_else(invalid_path)?;
+
+    match (op, parent) {
+        (PatchOp::Add { value: new, .. }, Value::Object(map)) => {
+            map.insert(key, new.clone());
+        }
+        (PatchOp::Remove { .. }, Value::Object(map)) => {
+            map.remove(&key).ok_or_else(invalid_path)?;
+        }
+        (PatchOp::Add { value: new, .. }, Value::Array(values)) => {
+            let index = match key.as_str() {
+                "-" => values.len(),
+                key => key.parse().map_err(|_| invalid_path())?,
+            };
+
+            if index > values.len() {
+                return Err(invalid_path());
+            }
+            values.insert(index, new.clone());
+        }
+        (PatchOp::Remove { .. }, Value::Array(values)) => {
+            let index: usize = key.parse().map_err(|_| invalid_path())?;
+
+            if index >= values.len() {
+                return Err(invalid_path());
+            }
+            values.remove(index);
+        }
+        _ => return Err(invalid_path()),
+    }
+
+    Ok(())
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(key: &str) -> String {
+    key.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_patches_round_trip() {
+        let prev = json!({"a": [1, 2, 3], "b": {"c/d": 1, "e": 2}, "f": null});
+        let next = json!({"a": [1, 5], "b": {"c/d": 2, "g~": [true]}, "h": "new"});
+
+        let patch = diff(&prev, &next);
+        assert_eq!(apply_patch(&prev, &patch), Ok(next.clone()));
+        assert_eq!(apply_patch(&next, &diff(&next, &prev)), Ok(prev.clone()));
+        assert_eq!(diff(&prev, &prev), ViewPatch::default());
+    }
+
+    #[test]
+    fn test_patches_serialize_as_json_patch() {
+        let patch = diff(&json!({"a": 1, "b": [1]}), &json!({"a": 2, "b": []}));
+
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            json!([
+                {"op": "replace", "path": "/a", "value": 2},
+                {"op": "remove", "path": "/b/0"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_patches_for_a_different_view_fail() {
+        let patch = ViewPatch(vec![PatchOp::Remove {
+            path: "/missing".to_string(),
+        }]);
+
+        assert_eq!(
+            apply_patch(&json!({}), &patch),
+            Err(InvalidPath {
+                path: "/missing".to_string()
+            })
+        );
+    }
+}
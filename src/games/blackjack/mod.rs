@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+pub mod strategy;
+
+/// The choices a player has with a hand
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Hit,
+    Stand,
+    /// Doubles the bet and takes exactly one more card, only on a hand's first two cards
+    Double,
+    /// Splits a pair into two hands with a bet each
+    Split,
+    /// Gives up the hand for half the bet back, only on the first two cards
+    Surrender,
+}
+
+/// The table rules that change how the hands should be played. The defaults are the common
+/// Las Vegas Strip rules
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// The dealer hits a soft 17 instead of standing on it, H17 on the felt
+    pub dealer_hits_soft_17: bool,
+    /// Hands made by splitting a pair can be doubled, DAS on the felt
+    pub double_after_split: bool,
+    /// Late surrender, after the dealer has checked for blackjack
+    pub surrender: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            dealer_hits_soft_17: false,
+            double_after_split: true,
+            surrender: false,
+        }
+    }
+}
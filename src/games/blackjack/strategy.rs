@@ -0,0 +1,233 @@
+use crate::common::deck::points::{Hand, Scheme, Total};
+use crate::common::deck::Card;
+
+use super::{Action, Rules};
+
+/// The value of a card for basic strategy, with aces as 11
+fn value(card: Card) -> u32 {
+    match Scheme::Blackjack.value(card) {
+        1 => 11,
+        value => value,
+    }
+}
+
+/// The basic strategy play for a hand against the dealer's up card, from the standard tables for
+/// four to eight decks. Doubling and surrendering are only recommended on the first two cards,
+/// with the next best play otherwise. Ten value cards are all tens, so a jack and a king are a
+/// pair. Busted hands and twenty ones stand
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::games::blackjack::{strategy::recommend, Action, Rules};
+///
+/// let rules = Rules::default();
+/// let hand = [Card(Eight, Spades), Card(Eight, Hearts)];
+/// assert_eq!(recommend(&hand, Card(Ten, Clubs), &rules), Action::Split);
+///
+/// let hand = [Card(Ace, Spades), Card(Seven, Hearts)];
+/// assert_eq!(recommend(&hand, Card(Four, Clubs), &rules), Action::Double);
+/// assert_eq!(recommend(&hand, Card(Nine, Clubs), &rules), Action::Hit);
+///
+/// let hand = [Card(Ten, Spades), Card(Six, Hearts)];
+/// assert_eq!(recommend(&hand, Card(Ten, Clubs), &rules), Action::Hit);
+///
+/// let rules = Rules { surrender: true, ..Rules::default() };
+/// assert_eq!(recommend(&hand, Card(Ten, Clubs), &rules), Action::Surrender);
+/// ```
+pub fn recommend(player_hand: &[Card], dealer_upcard: Card, rules: &Rules) -> Action {
+    let dealer = value(dealer_upcard);
+    let first_two = player_hand.len() == 2;
+    let Total { points, soft } = player_hand.total(Scheme::Blackjack);
+
+    let pair = match player_hand {
+        &[a, b] if value(a) == value(b) => Some(value(a)),
+        _ => None,
+    };
+
+    if first_two && rules.surrender && should_surrender(points, soft, pair, dealer, rules) {
+        return Action::Surrender;
+    }
+
+    if let Some(card) = pair {
+        if should_split(card, dealer, rules) {
+            return Action::Split;
+        }
+    }
+
+    if soft {
+        play_soft(points, dealer, rules, first_two)
+    } else {
+        play_hard(points, dealer, rules, first_two)
+    }
+}
+
+/// Late surrender, where a pair of eights is split instead unless the dealer hits soft 17 and
+/// shows an ace
+fn should_surrender(
+    points: u32,
+    soft: bool,
+    pair: Option<u32>,
+    dealer: u32,
+    rules: &Rules,
+) -> bool {
+    let h17 = rules.dealer_hits_soft_17;
+
+    match (soft, pair, points, dealer) {
+        (true, ..) => false,
+        (false, Some(8), _, 11) => h17,
+        (false, Some(_), ..) => false,
+        (false, None, 16, 9..=11) | (false, None, 15, 10) => true,
+        (false, None, 15, 11) | (false, None, 17, 11) => h17,
+        _ => false,
+    }
+}
+
+fn should_split(card: u32, dealer: u32, rules: &Rules) -> bool {
+    let das = rules.double_after_split;
+
+    match card {
+        11 | 8 => true,
+        9 => !matches!(dealer, 7 | 10 | 11),
+        7 => dealer <= 7,
+        6 => (3..=6).contains(&dealer) || (das && dealer == 2),
+        4 => das && (5..=6).contains(&dealer),
+        2 | 3 => (4..=7).contains(&dealer) || (das && dealer <= 3),
+        _ => false,
+    }
+}
+
+/// Soft hands that would double stand instead when they can't, from soft 18 up
+fn play_soft(points: u32, dealer: u32, rules: &Rules, can_double: bool) -> Action {
+    let h17 = rules.dealer_hits_soft_17;
+
+    let double = match points {
+        13 | 14 => (5..=6).contains(&dealer),
+        15 | 16 => (4..=6).contains(&dealer),
+        17 => (3..=6).contains(&dealer),
+        18 => (3..=6).contains(&dealer) || (h17 && dealer == 2),
+        19 => h17 && dealer == 6,
+        _ => false,
+    };
+
+    match points {
+        _ if double && can_double => Action::Double,
+        19..=21 => Action::Stand,
+        18 if dealer <= 8 => Action::Stand,
+        _ => Action::Hit,
+    }
+}
+
+fn play_hard(points: u32, dealer: u32, rules: &Rules, can_double: bool) -> Action {
+    let double = match points {
+        9 => (3..=6).contains(&dealer),
+        10 => dealer <= 9,
+        11 => dealer <= 10 || rules.dealer_hits_soft_17,
+        _ => false,
+    };
+
+    match points {
+        _ if double && can_double => Action::Double,
+        17.. => Action::Stand,
+        13..=16 if dealer <= 6 => Action::Stand,
+        12 if (4..=6).contains(&dealer) => Action::Stand,
+        _ => Action::Hit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank, Rank::*, Suit::*};
+
+    fn hand(ranks: &[Rank]) -> Vec<Card> {
+        ranks.iter().map(|&rank| Card(rank, Spades)).collect()
+    }
+
+    fn dealer(rank: Rank) -> Card {
+        Card(rank, Hearts)
+    }
+
+    #[test]
+    fn test_doubles_fall_back_after_the_first_two_cards() {
+        let rules = Rules::default();
+        assert_eq!(
+            recommend(&hand(&[Six, Five]), dealer(Six), &rules),
+            Action::Double
+        );
+        assert_eq!(
+            recommend(&hand(&[Two, Four, Five]), dealer(Six), &rules),
+            Action::Hit
+        );
+        assert_eq!(
+            recommend(&hand(&[Ace, Seven]), dealer(Six), &rules),
+            Action::Double
+        );
+        assert_eq!(
+            recommend(&hand(&[Ace, Two, Five]), dealer(Six), &rules),
+            Action::Stand
+        );
+        assert_eq!(
+            recommend(&hand(&[Ten, Six, Nine]), dealer(Ace), &rules),
+            Action::Stand
+        );
+    }
+
+    #[test]
+    fn test_the_rules_change_the_close_plays() {
+        let s17 = Rules::default();
+        let h17 = Rules {
+            dealer_hits_soft_17: true,
+            surrender: true,
+            ..s17
+        };
+        let no_das = Rules {
+            double_after_split: false,
+            ..s17
+        };
+
+        assert_eq!(
+            recommend(&hand(&[Six, Five]), dealer(Ace), &s17),
+            Action::Hit
+        );
+        assert_eq!(
+            recommend(&hand(&[Six, Five]), dealer(Ace), &h17),
+            Action::Double
+        );
+
+        assert_eq!(
+            recommend(&hand(&[Eight, Eight]), dealer(Ace), &s17),
+            Action::Split
+        );
+        assert_eq!(
+            recommend(&hand(&[Eight, Eight]), dealer(Ace), &h17),
+            Action::Surrender
+        );
+        assert_eq!(
+            recommend(&hand(&[Ten, Seven]), dealer(Ace), &h17),
+            Action::Surrender
+        );
+
+        assert_eq!(
+            recommend(&hand(&[Four, Four]), dealer(Five), &s17),
+            Action::Split
+        );
+        assert_eq!(
+            recommend(&hand(&[Four, Four]), dealer(Five), &no_das),
+            Action::Hit
+        );
+        assert_eq!(
+            recommend(&hand(&[Two, Two]), dealer(Two), &no_das),
+            Action::Hit
+        );
+    }
+
+    #[test]
+    fn test_tens_are_a_pair_that_stands() {
+        let rules = Rules::default();
+        let hand = [Card(Jack, Spades), Card(King, Hearts)];
+        assert_eq!(recommend(&hand, dealer(Six), &rules), Action::Stand);
+        assert_eq!(
+            recommend(&[Card(Ace, Spades), Card(Ace, Clubs)], dealer(Ace), &rules),
+            Action::Split
+        );
+    }
+}
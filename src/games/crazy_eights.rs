@@ -3,16 +3,35 @@ use enum_map::EnumMap;
 use im::Vector;
 use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use serde_repr::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
-use crate::common::deck::STANDARD_DECK;
+use crate::common::clock::{ShowsClock, TimeoutHooks};
+use crate::common::deck::points::{Hand, Scheme};
 use crate::common::deck::{Card, Rank, Suit};
-use crate::common::rand::RngSeed;
+use crate::common::deck::{Deck, StandardDeck, STANDARD_DECK};
+use crate::common::game::Play;
+use crate::common::info::GameInfo;
+use crate::common::ml::{Encode, Encoding};
+use crate::common::player_info::PlayerInfoRegistry;
+use crate::common::rand::{LabelDraws, RngSeed, RngUse};
+use crate::common::replay::Replayable;
+use crate::common::results::{
+    rank_by, settings_hash, GameKind, GameResult, Outcome, PlayerResult, Summarize, Termination,
+};
+use crate::common::settings::{Builder, FromSettings};
+use crate::common::turn::PendingInput;
+use crate::common::verify::StateHash;
+use crate::common::views::{Perspective, ViewMetadata, Viewable};
 
-#[derive(Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 #[repr(u8)]
 pub enum Player {
     P1 = 1,
@@ -82,10 +101,533 @@ impl NumberOfPlayers {
     }
 }
 
+/// What happens when the card turned up to start the discard pile is an eight
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StarterEight {
+    /// The eight starts the pile like any other card, and its suit is the current suit
+    #[default]
+    Play,
+    /// The eight goes to the bottom of the draw pile and the next card is turned up, until the
+    /// starter isn't an eight
+    Bury,
+    /// The dealer, the last player, names the suit they hold the most of, ties going to the
+    /// earlier suit in `Suit::ALL`
+    DealerPicksSuit,
+}
+
+/// House rules for dealing a game
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealRules {
+    /// Overrides the number of cards dealt to each player, see
+    /// [`starting_number_of_cards_per_player`](NumberOfPlayers::starting_number_of_cards_per_player)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cards_per_player: Option<u8>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub starter_eight: StarterEight,
+}
+
+impl DealRules {
+    /// The number of cards each player starts with
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{DealRules, NumberOfPlayers::*};
+    ///
+    /// assert_eq!(DealRules::default().cards_per_player(Two), 7);
+    /// assert_eq!(DealRules { cards_per_player: Some(3), ..Default::default() }.cards_per_player(Two), 3);
+    /// ```
+    pub fn cards_per_player(&self, number_of_players: NumberOfPlayers) -> u8 {
+        self.cards_per_player
+            .unwrap_or_else(|| number_of_players.starting_number_of_cards_per_player())
+    }
+}
+
+/// How many standard decks are shuffled together for a game
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Decks {
+    #[default]
+    One,
+    Two,
+    /// One deck for up to five players and two for six or more, so big games don't run dry
+    TwoForSixOrMore,
+}
+
+impl Decks {
+    /// The number of decks used for a number of players
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{Decks, NumberOfPlayers::*};
+    ///
+    /// assert_eq!(Decks::One.count(Eight), 1);
+    /// assert_eq!(Decks::TwoForSixOrMore.count(Five), 1);
+    /// assert_eq!(Decks::TwoForSixOrMore.count(Six), 2);
+    /// ```
+    pub fn count(&self, number_of_players: NumberOfPlayers) -> usize {
+        match self {
+            Decks::One => 1,
+            Decks::Two => 2,
+            Decks::TwoForSixOrMore if number_of_players as u8 >= 6 => 2,
+            Decks::TwoForSixOrMore => 1,
+        }
+    }
+}
+
+/// What happens when a player draws
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrawRule {
+    /// Draw one card and pass
+    #[default]
+    DrawOne,
+    /// Keep drawing until a card that can be played turns up, and then play on. Once `max` cards
+    /// have been drawn without one, or the cards run out, the turn passes. At least one card is
+    /// always drawn
+    DrawUntilPlayable { max: Option<u8> },
+}
+
+/// What happens when an eight is played on top of another eight
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EightOnEight {
+    /// The second eight names a new suit, like any other eight
+    #[default]
+    Redeclare,
+    /// The second eight can be played, but the suit named by the first one stays
+    KeepSuit,
+    /// Eights can't be played on eights, the next player has to follow the named suit or draw.
+    /// This includes an eight turned up to start the pile, unless
+    /// [`StarterEight`](StarterEight) buries it
+    Forbidden,
+}
+
+impl EightOnEight {
+    /// The suits an eight can name when it's played on the top card
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::{self, *}};
+    /// use lib_table_top::games::crazy_eights::EightOnEight;
+    ///
+    /// assert_eq!(EightOnEight::Forbidden.suits(Card(Two, Clubs), Clubs), Suit::ALL.to_vec());
+    /// assert_eq!(EightOnEight::Redeclare.suits(Card(Eight, Clubs), Hearts), Suit::ALL.to_vec());
+    /// assert_eq!(EightOnEight::KeepSuit.suits(Card(Eight, Clubs), Hearts), vec![Hearts]);
+    /// assert_eq!(EightOnEight::Forbidden.suits(Card(Eight, Clubs), Hearts), vec![]);
+    /// ```
+    pub fn suits(&self, top_card: Card, current_suit: Suit) -> Vec<Suit> {
+        match self {
+            _ if top_card.0 != Rank::Eight => Suit::ALL.to_vec(),
+            EightOnEight::Redeclare => Suit::ALL.to_vec(),
+            EightOnEight::KeepSuit => vec![current_suit],
+            EightOnEight::Forbidden => vec![],
+        }
+    }
+}
+
+/// The rules of play, on top of how the cards are dealt
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rules {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub decks: Decks,
+    /// Lets players draw while holding a card they could play, which many playgroups allow so
+    /// cards can be held back. Otherwise a player who can play must
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub voluntary_draws: bool,
+    /// How many cards a player draws on their turn
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub draw: DrawRule,
+    /// Plays rounds until a player reaches this score instead of stopping at the first emptied
+    /// hand. The player who empties their hand scores the cards left in everyone else's hands,
+    /// see [`Scheme::CrazyEights`](crate::common::deck::points::Scheme::CrazyEights), and the
+    /// cards are re-dealt for the next round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_target: Option<u32>,
+    /// Playing a two makes the next player draw two cards, unless they play a two of their own,
+    /// which passes all of the cards on to the player after them
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub stacking_twos: bool,
+    /// Playing a queen skips the next player
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub queens_skip: bool,
+    /// Playing a jack reverses the direction of play, with two players this doesn't change who
+    /// plays next
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub jacks_reverse: bool,
+    /// Whether an eight can be played on an eight, and whether it can name a new suit
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub eight_on_eight: EightOnEight,
+}
+
+impl Rules {
+    /// The canonical rules for organized play, so events can point at one configuration. There
+    /// are no special cards besides eights, a player who can't play draws one card and passes,
+    /// and a player who can play must, which is how every game is played. Games of six or more
+    /// use two decks. Tournament rules always use the standard deal, see
+    /// [`SettingsError::TournamentDealOverridden`](SettingsError::TournamentDealOverridden)
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{Decks, NumberOfPlayers, Rules, SettingsBuilder};
+    ///
+    /// assert_eq!(Rules::tournament().decks, Decks::TwoForSixOrMore);
+    ///
+    /// let game = SettingsBuilder::new()
+    ///   .seed(RngSeed([0; 32]))
+    ///   .number_of_players(NumberOfPlayers::Seven)
+    ///   .rules(Rules::tournament())
+    ///   .build_game()
+    ///   .unwrap();
+    /// assert_eq!(game.observer_view().draw_pile_remaining, 104 - 7 * 5 - 1);
+    /// ```
+    pub fn tournament() -> Self {
+        Self {
+            decks: Decks::TwoForSixOrMore,
+            voluntary_draws: false,
+            draw: DrawRule::DrawOne,
+            match_target: None,
+            stacking_twos: false,
+            queens_skip: false,
+            jacks_reverse: false,
+            eight_on_eight: EightOnEight::Redeclare,
+        }
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// The description of the game for lobbies, see [`GameInfo`](struct@GameInfo)
+pub const INFO: GameInfo = GameInfo {
+    kind: GameKind::CrazyEights,
+    name: "Crazy Eights",
+    rules: "Players take turns playing a card that matches the rank or suit of the top card of \
+            the discard pile, or drawing when they can't. Eights are wild and name the next suit, \
+            the first player to empty their hand wins",
+    min_players: 2,
+    max_players: 8,
+    estimated_duration: Duration::from_secs(10 * 60),
+    settings_schema_json: r#"{
+        "type": "object",
+        "required": ["seed", "number_of_players"],
+        "properties": {
+            "seed": {"type": "string", "pattern": "^[0-9a-f]{64}$"},
+            "number_of_players": {"type": "integer", "minimum": 2, "maximum": 8},
+            "deal": {
+                "type": "object",
+                "properties": {
+                    "cards_per_player": {"type": "integer", "minimum": 1, "maximum": 255},
+                    "starter_eight": {"enum": ["Play", "Bury", "DealerPicksSuit"], "default": "Play"}
+                }
+            },
+            "rules": {
+                "type": "object",
+                "properties": {
+                    "decks": {"enum": ["One", "Two", "TwoForSixOrMore"], "default": "One"},
+                    "voluntary_draws": {"type": "boolean", "default": false},
+                    "draw": {
+                        "oneOf": [
+                            {"const": "DrawOne"},
+                            {
+                                "type": "object",
+                                "required": ["DrawUntilPlayable"],
+                                "properties": {
+                                    "DrawUntilPlayable": {
+                                        "type": "object",
+                                        "required": ["max"],
+                                        "properties": {
+                                            "max": {"type": ["integer", "null"], "minimum": 1, "maximum": 255}
+                                        }
+                                    }
+                                }
+                            }
+                        ],
+                        "default": "DrawOne"
+                    },
+                    "match_target": {"type": "integer", "minimum": 0},
+                    "stacking_twos": {"type": "boolean", "default": false},
+                    "queens_skip": {"type": "boolean", "default": false},
+                    "jacks_reverse": {"type": "boolean", "default": false},
+                    "eight_on_eight": {"enum": ["Redeclare", "KeepSuit", "Forbidden"], "default": "Redeclare"}
+                }
+            }
+        }
+    }"#,
+};
+
+/// The settings of a game. They can only be made valid, by [`Settings::new`],
+/// [`Settings::tournament`], the [`SettingsBuilder`](struct@SettingsBuilder), or deserializing,
+/// which checks them, so every `Settings` can deal a game
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::crazy_eights::{NumberOfPlayers::*, Settings};
+///
+/// let mut json = serde_json::to_value(Settings::new(RngSeed([0; 32]), Eight)).unwrap();
+/// json["deal"] = serde_json::json!({ "cards_per_player": 7 });
+///
+/// let settings: Result<Settings, _> = serde_json::from_value(json);
+/// assert_eq!(
+///     settings.unwrap_err().to_string(),
+///     "Can't deal 7 cards to each of Eight players"
+/// );
+/// ```
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedSettings")]
 pub struct Settings {
-    pub seed: RngSeed,
-    pub number_of_players: NumberOfPlayers,
+    seed: RngSeed,
+    number_of_players: NumberOfPlayers,
+    #[serde(default, skip_serializing_if = "is_default")]
+    deal: DealRules,
+    #[serde(default, skip_serializing_if = "is_default")]
+    rules: Rules,
+}
+
+/// Settings as they're written, before they're checked
+#[derive(Deserialize)]
+struct UncheckedSettings {
+    seed: RngSeed,
+    number_of_players: NumberOfPlayers,
+    #[serde(default)]
+    deal: DealRules,
+    #[serde(default)]
+    rules: Rules,
+}
+
+impl TryFrom<UncheckedSettings> for Settings {
+    type Error = SettingsError;
+
+    fn try_from(unchecked: UncheckedSettings) -> Result<Self, SettingsError> {
+        let settings = Self {
+            seed: unchecked.seed,
+            number_of_players: unchecked.number_of_players,
+            deal: unchecked.deal,
+            rules: unchecked.rules,
+        };
+
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    /// The standard deal with the default rules
+    pub fn new(seed: RngSeed, number_of_players: NumberOfPlayers) -> Self {
+        Self::with_rules(seed, number_of_players, Default::default())
+    }
+
+    /// The standard deal with house rules, any rules can deal the standard hands
+    pub fn with_rules(seed: RngSeed, number_of_players: NumberOfPlayers, rules: Rules) -> Self {
+        Self {
+            seed,
+            number_of_players,
+            deal: Default::default(),
+            rules,
+        }
+    }
+
+    /// Settings with a house deal, or the standard deal if the house deal can't be used with the
+    /// rules, for generating settings from fuzzed input
+    fn with_deal_or_standard(
+        seed: RngSeed,
+        number_of_players: NumberOfPlayers,
+        deal: DealRules,
+        rules: Rules,
+    ) -> Self {
+        let settings = Self {
+            seed,
+            number_of_players,
+            deal,
+            rules,
+        };
+
+        match settings.validate() {
+            Ok(()) => settings,
+            Err(_) => Self::with_rules(seed, number_of_players, rules),
+        }
+    }
+
+    /// The standard deal with tournament rules, see [`Rules::tournament`](Rules::tournament)
+    pub fn tournament(seed: RngSeed, number_of_players: NumberOfPlayers) -> Self {
+        Self::with_rules(seed, number_of_players, Rules::tournament())
+    }
+
+    pub fn seed(&self) -> RngSeed {
+        self.seed
+    }
+
+    pub fn number_of_players(&self) -> NumberOfPlayers {
+        self.number_of_players
+    }
+
+    /// How the cards are dealt
+    pub fn deal(&self) -> DealRules {
+        self.deal
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Checks that the settings can make a game and aren't ambiguous. Everyone needs at least
+    /// one card, there has to be a card left to start the discard pile, and tournament rules
+    /// would mean two different games both called "tournament" if they allowed a house deal
+    fn validate(&self) -> Result<(), SettingsError> {
+        if self.rules == Rules::tournament() && self.deal != DealRules::default() {
+            return Err(SettingsError::TournamentDealOverridden);
+        }
+
+        let cards_per_player = self.deal.cards_per_player(self.number_of_players);
+        let deck_size = self.rules.decks.count(self.number_of_players) * STANDARD_DECK.len();
+        if cards_per_player == 0
+            || usize::from(cards_per_player) * self.number_of_players as usize >= deck_size
+        {
+            return Err(SettingsError::InvalidCardsPerPlayer {
+                cards_per_player,
+                number_of_players: self.number_of_players,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The various errors that can be returned from building invalid settings
+#[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingsError {
+    #[error("A seed is required")]
+    MissingSeed,
+    #[error("The number of players is required")]
+    MissingNumberOfPlayers,
+    #[error(
+        "Can't deal {:?} cards to each of {:?} players",
+        cards_per_player,
+        number_of_players
+    )]
+    InvalidCardsPerPlayer {
+        cards_per_player: u8,
+        number_of_players: NumberOfPlayers,
+    },
+    #[error("Tournament rules use the standard deal")]
+    TournamentDealOverridden,
+}
+
+impl SettingsError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::crazy_eights::SettingsError::*;
+    ///
+    /// assert_eq!(MissingSeed.code(), "missing_seed");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            SettingsError::MissingSeed => "missing_seed",
+            SettingsError::MissingNumberOfPlayers => "missing_number_of_players",
+            SettingsError::InvalidCardsPerPlayer { .. } => "invalid_cards_per_player",
+            SettingsError::TournamentDealOverridden => "tournament_deal_overridden",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            SettingsError::MissingSeed
+            | SettingsError::MissingNumberOfPlayers
+            | SettingsError::TournamentDealOverridden => json!({}),
+            SettingsError::InvalidCardsPerPlayer {
+                cards_per_player,
+                number_of_players,
+            } => json!({
+                "cards_per_player": cards_per_player,
+                "number_of_players": number_of_players,
+            }),
+        }
+    }
+}
+
+/// Tools to build Crazy Eights games
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::crazy_eights::{NumberOfPlayers, Rules, SettingsBuilder, SettingsError};
+///
+/// let game = SettingsBuilder::new()
+///   .seed(RngSeed([0; 32]))
+///   .number_of_players(NumberOfPlayers::Four)
+///   .build_game()
+///   .unwrap();
+/// assert_eq!(game.players().count(), 4);
+///
+/// assert_eq!(
+///   SettingsBuilder::new().number_of_players(NumberOfPlayers::Two).build(),
+///   Err(SettingsError::MissingSeed)
+/// );
+///
+/// // Everyone needs at least one card, and there has to be a card left to start the discard pile
+/// assert_eq!(
+///   SettingsBuilder::new()
+///     .seed(RngSeed([0; 32]))
+///     .number_of_players(NumberOfPlayers::Eight)
+///     .cards_per_player(7)
+///     .build(),
+///   Err(SettingsError::InvalidCardsPerPlayer { cards_per_player: 7, number_of_players: NumberOfPlayers::Eight })
+/// );
+///
+/// // Tournament rules with a house deal would mean two games both called "tournament"
+/// assert_eq!(
+///   SettingsBuilder::new()
+///     .seed(RngSeed([0; 32]))
+///     .number_of_players(NumberOfPlayers::Six)
+///     .rules(Rules::tournament())
+///     .cards_per_player(8)
+///     .build(),
+///   Err(SettingsError::TournamentDealOverridden)
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SettingsBuilder {
+    seed: Option<RngSeed>,
+    number_of_players: Option<NumberOfPlayers>,
+    cards_per_player: Option<u8>,
+    starter_eight: StarterEight,
+    rules: Rules,
+}
+
+impl SettingsBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    settings_setters! {
+        seed: RngSeed,
+        number_of_players: NumberOfPlayers,
+        cards_per_player: u8,
+        starter_eight: StarterEight,
+        rules: Rules,
+    }
+
+    pub fn build(self) -> Result<Settings, SettingsError> {
+        Builder::build(self)
+    }
+
+    pub fn build_game(self) -> Result<GameState, SettingsError> {
+        Builder::build_game(self)
+    }
+}
+
+impl Builder for SettingsBuilder {
+    type Settings = Settings;
+    type Error = SettingsError;
+
+    fn build(self) -> Result<Settings, SettingsError> {
+        let seed = self.seed.ok_or(SettingsError::MissingSeed)?;
+        let number_of_players = self
+            .number_of_players
+            .ok_or(SettingsError::MissingNumberOfPlayers)?;
+        let deal = DealRules {
+            cards_per_player: self.cards_per_player,
+            starter_eight: self.starter_eight,
+        };
+
+        let settings = Settings {
+            seed,
+            number_of_players,
+            deal,
+            rules: self.rules,
+        };
+
+        settings.validate()?;
+        Ok(settings)
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,25 +636,118 @@ pub struct GameHistory {
     history: Vector<Action>,
 }
 
+/// The generator for deals and reshuffles. Debug builds audit it, so tests can check that rule
+/// changes don't draw more or less and change the deals of stored seeds, see
+/// [`GameState::rng_audit`](GameState::rng_audit)
+#[cfg(debug_assertions)]
+type GameRng = crate::common::rand::RngAudit<ChaCha20Rng>;
+#[cfg(not(debug_assertions))]
+type GameRng = ChaCha20Rng;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GameState {
     game_history: GameHistory,
-    rng: Arc<ChaCha20Rng>,
+    rng: Arc<GameRng>,
     discarded: Vector<Card>,
     hands: EnumMap<Player, Vec<Card>>,
     draw_pile: Vector<Card>,
     top_card: Card,
     current_suit: Suit,
+    /// The turns of the draws that reshuffled the discard pile into the draw pile
+    reshuffles: Vector<usize>,
+    /// The points each player has scored in match play
+    scores: EnumMap<Player, u32>,
+    rounds_played: u32,
+    /// Who took each action in the history, house rules can change the order of play
+    turns: Vector<Player>,
+    whose_turn: Player,
+    /// Whether a jack has turned play around, see [`Rules::jacks_reverse`](Rules::jacks_reverse)
+    reversed: bool,
+    /// The cards the player to move has to draw unless they play a two, see
+    /// [`Rules::stacking_twos`](Rules::stacking_twos)
+    pending_draws: u8,
+    /// Kept up to date as cards are played, so views don't walk the discard pile
+    suit_history: SuitHistory,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Status {
     InProgress,
-    Win { player: Player },
+    Win {
+        player: Player,
+    },
+    /// A player reached the target score in match play, see
+    /// [`Rules::match_target`](Rules::match_target)
+    MatchWin {
+        player: Player,
+        scores: BTreeMap<Player, u32>,
+    },
+    /// Nobody can ever play again, every card is in someone's hand and none of them can go on
+    /// the top card. Eights can always be played, so a dealt game only blocks if nobody holds
+    /// one. The scores are the penalty points left in each hand, lowest wins. This ends a match
+    /// too
+    Blocked {
+        scores: BTreeMap<Player, u32>,
+    },
 }
 
 use Status::*;
 
+/// How many cards of each suit and rank have been played this round, the top card the round
+/// started with isn't counted
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::crazy_eights::{Action::*, GameState, NumberOfPlayers, Player::*, Settings};
+/// use std::sync::Arc;
+///
+/// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+/// assert!(game.observer_view().suit_history.suits.is_empty());
+///
+/// let game = game.apply_action((P1, Play(Card(Nine, Clubs)))).unwrap();
+/// let history = game.observer_view().suit_history;
+/// assert_eq!(history.suits[&Clubs], 1);
+/// assert_eq!(history.ranks, vec![(Nine, 1)]);
+/// assert_eq!((history.suit(Clubs), history.rank(Nine), history.rank(Eight)), (1, 1, 0));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuitHistory {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub suits: HashMap<Suit, u32>,
+    /// The ranks that have been played, lowest first. Ranks serialize as numbers, which can't be
+    /// the keys of a map in JSON
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranks: Vec<(Rank, u32)>,
+}
+
+impl SuitHistory {
+    /// How many cards of a suit have been played
+    pub fn suit(&self, suit: Suit) -> u32 {
+        self.suits.get(&suit).copied().unwrap_or(0)
+    }
+
+    /// How many cards of a rank have been played
+    pub fn rank(&self, rank: Rank) -> u32 {
+        self.ranks
+            .iter()
+            .find(|(played, _)| played == &rank)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    fn record(&mut self, Card(rank, suit): Card) {
+        *self.suits.entry(suit).or_insert(0) += 1;
+
+        match self
+            .ranks
+            .binary_search_by_key(&rank, |(played, _)| *played)
+        {
+            Ok(index) => self.ranks[index].1 += 1,
+            Err(index) => self.ranks.insert(index, (rank, 1)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ObserverView {
     /// The player whose turn it is, may or may not be the same as the player this view is for. If
@@ -129,6 +764,87 @@ pub struct ObserverView {
     pub player_card_count: HashMap<Player, usize>,
     /// The number of cards in the draw pile
     pub draw_pile_remaining: u8,
+    /// How many times the discard pile has been shuffled back into the draw pile, clients can
+    /// compare it with the last view they saw to animate a reshuffle
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub reshuffle_count: usize,
+    /// The points each player has scored, empty unless the game is played as a match
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scores: HashMap<Player, u32>,
+    /// Whether a jack has turned play around, see [`Rules::jacks_reverse`](Rules::jacks_reverse)
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub reversed: bool,
+    /// The cards the player to move has to draw unless they play a two, see
+    /// [`Rules::stacking_twos`](Rules::stacking_twos)
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub pending_draws: u8,
+    /// The cards played this round by suit and rank, see [`SuitHistory`](struct@SuitHistory)
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub suit_history: SuitHistory,
+    /// The rules the game is played with, so clients know which actions to offer
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rules: Rules,
+    /// Display information for the players, empty unless added with
+    /// [`with_player_info`](ObserverView::with_player_info)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub player_info: PlayerInfoRegistry<Player>,
+    /// When the player to move runs out of time, empty unless added with
+    /// [`with_clock`](crate::common::clock::ShowsClock::with_clock)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<SystemTime>,
+    /// How long the player to move had left when the view was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remaining: Option<Duration>,
+    /// Data the host attached to the view, see [`ViewMetadata`](crate::common::views::ViewMetadata)
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+}
+
+impl ViewMetadata for ObserverView {
+    fn metadata(&self) -> &Map<String, Value> {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.metadata
+    }
+}
+
+impl ShowsClock for ObserverView {
+    fn set_countdown(&mut self, deadline: SystemTime, time_remaining: Duration) {
+        self.deadline = Some(deadline);
+        self.time_remaining = Some(time_remaining);
+    }
+}
+
+impl ObserverView {
+    /// Embeds the display information for the players of the game, so the view can be rendered
+    /// without looking up the players separately. Info for players not in the game is ignored
+    /// ```
+    /// use lib_table_top::common::player_info::{PlayerInfo, PlayerInfoRegistry};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    ///
+    /// let mut registry = PlayerInfoRegistry::new();
+    /// registry.insert(P1, PlayerInfo::new("Alice"));
+    /// registry.insert(P3, PlayerInfo::new("Not Playing"));
+    ///
+    /// let view = game.observer_view().with_player_info(&registry);
+    /// assert_eq!(view.player_info[&P1].display_name, "Alice");
+    /// assert!(!view.player_info.contains_key(&P3));
+    /// ```
+    pub fn with_player_info(mut self, registry: &PlayerInfoRegistry<Player>) -> Self {
+        self.player_info = registry
+            .iter()
+            .filter(|(player, _)| self.player_card_count.contains_key(player))
+            .map(|(&player, info)| (player, info.clone()))
+            .collect();
+        self
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -139,9 +855,36 @@ pub struct PlayerView {
     pub hand: Vector<Card>,
     /// The view that any observer can see, the totally non secret parts of the game
     pub observer_view: ObserverView,
+    /// Data the host attached for just this player, see [`ViewMetadata`](crate::common::views::ViewMetadata)
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+}
+
+impl ViewMetadata for PlayerView {
+    fn metadata(&self) -> &Map<String, Value> {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.metadata
+    }
+}
+
+/// The countdown lives on the embedded observer view, since everyone can see it
+impl ShowsClock for PlayerView {
+    fn set_countdown(&mut self, deadline: SystemTime, time_remaining: Duration) {
+        self.observer_view.set_countdown(deadline, time_remaining);
+    }
 }
 
 impl PlayerView {
+    /// Embeds the display information for the players of the game, see
+    /// [`ObserverView::with_player_info`](ObserverView::with_player_info)
+    pub fn with_player_info(mut self, registry: &PlayerInfoRegistry<Player>) -> Self {
+        self.observer_view = self.observer_view.with_player_info(registry);
+        self
+    }
+
     /// Returns the valid actions for a player. Player views are specific to a turn and player.
     /// There are no valid actions if it's not that player's turn
     /// ```
@@ -152,7 +895,7 @@ impl PlayerView {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let game = GameState::new(Arc::new(Settings { number_of_players: NumberOfPlayers::Two, seed: RngSeed([1; 32])}));
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
     ///
     /// // If it's not that player's turn the valid actions are empty
     /// assert!(game.whose_turn() != P2);
@@ -166,14 +909,39 @@ impl PlayerView {
     /// ]);
     /// ```
     pub fn valid_actions(&self) -> Vec<Action> {
-        if self.observer_view.whose_turn == self.player {
+        let mut actions = self.playable_or_draw();
+
+        // Drawing does nothing once every card is in someone's hand
+        if self.observer_view.draw_pile_remaining == 0 && self.observer_view.discarded.is_empty() {
+            actions.retain(|&action| action != Draw);
+            if actions.is_empty() && self.observer_view.whose_turn == self.player {
+                actions.push(Pass);
+            }
+        }
+
+        actions
+    }
+
+    fn playable_or_draw(&self) -> Vec<Action> {
+        if self.observer_view.whose_turn == self.player && self.observer_view.pending_draws > 0 {
+            // Twos can be stacked, anything else means taking the cards
+            self.hand
+                .iter()
+                .filter(|card| card.0 == Rank::Two)
+                .map(|&card| Play(card))
+                .chain(Some(Draw))
+                .collect()
+        } else if self.observer_view.whose_turn == self.player {
             let playable: Vec<Action> = self
                 .hand
                 .iter()
                 .flat_map(|card| match card {
-                    Card(Rank::Eight, suit) => Suit::ALL
-                        .iter()
-                        .cloned()
+                    Card(Rank::Eight, suit) => self
+                        .observer_view
+                        .rules
+                        .eight_on_eight
+                        .suits(self.observer_view.top_card, self.observer_view.current_suit)
+                        .into_iter()
                         .map(move |s| PlayEight(Card(Rank::Eight, *suit), s))
                         .collect(),
                     Card(rank, suit)
@@ -190,6 +958,8 @@ impl PlayerView {
 
             if playable.is_empty() {
                 vec![Draw]
+            } else if self.observer_view.rules.voluntary_draws {
+                playable.into_iter().chain(Some(Draw)).collect()
             } else {
                 playable
             }
@@ -197,21 +967,200 @@ impl PlayerView {
             vec![]
         }
     }
+
+    /// The valid actions along with what each one does, for sorting and annotating a move list
+    /// ```
+    /// use lib_table_top::common::deck::{Rank::*, Suit::*, Card};
+    /// use lib_table_top::games::crazy_eights::{
+    ///   Action::*, ActionTag::*, AnalyzedAction, GameState, NumberOfPlayers, Player::*, Settings
+    /// };
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+    ///
+    /// assert_eq!(game.player_view(P1).analyzed_actions(), vec![
+    ///   AnalyzedAction { action: Play(Card(Nine, Clubs)), tags: vec![MatchesSuit] },
+    ///   AnalyzedAction { action: Play(Card(Seven, Clubs)), tags: vec![MatchesSuit] },
+    /// ]);
+    /// ```
+    pub fn analyzed_actions(&self) -> Vec<AnalyzedAction> {
+        let actions = self.valid_actions();
+        let forced = actions.len() == 1;
+
+        actions
+            .iter()
+            .map(|&action| {
+                let mut tags = Vec::new();
+
+                match action {
+                    Draw if forced => tags.push(ActionTag::ForcedDraw),
+                    Draw | Pass => {}
+                    Play(Card(rank, suit)) => {
+                        if rank == self.observer_view.top_card.0 {
+                            tags.push(ActionTag::MatchesRank);
+                        }
+                        if suit == self.observer_view.current_suit {
+                            tags.push(ActionTag::MatchesSuit);
+                        } else {
+                            tags.push(ActionTag::ChangesSuit);
+                        }
+                    }
+                    PlayEight(_, suit) => {
+                        tags.push(ActionTag::Wild);
+                        if suit != self.observer_view.current_suit {
+                            tags.push(ActionTag::ChangesSuit);
+                        }
+                    }
+                }
+
+                if matches!(action, Play(_) | PlayEight(..)) && self.hand.len() == 1 {
+                    tags.push(ActionTag::WinningMove);
+                }
+
+                AnalyzedAction { action, tags }
+            })
+            .collect()
+    }
+
+    /// A reasonable action for a "hint" button, `None` if it's not this player's turn. Eights are
+    /// held until nothing else can be played, cards whose rank is held more than once are played
+    /// first so there's a way to change suits later, and eights change to the suit held the most
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+    ///
+    /// assert_eq!(game.player_view(P2).suggest_action(), None);
+    ///
+    /// let view = game.player_view(P1);
+    /// assert!(view.valid_actions().contains(&view.suggest_action().unwrap()));
+    /// ```
+    pub fn suggest_action(&self) -> Option<Action> {
+        if self.observer_view.whose_turn != self.player {
+            return None;
+        }
+
+        let count =
+            |matches: &dyn Fn(&Card) -> bool| self.hand.iter().filter(|c| matches(c)).count();
+        let actions = self.valid_actions();
+
+        // Later actions win ties in `max_by_key`, so search from the back to keep the first
+        let best_play = actions
+            .iter()
+            .rev()
+            .filter_map(|&action| match action {
+                Play(card) => Some(card),
+                _ => None,
+            })
+            .max_by_key(|&Card(rank, suit)| {
+                (
+                    count(&|card: &Card| card.0 == rank),
+                    count(&|card: &Card| card.1 == suit && card.0 != Rank::Eight),
+                )
+            });
+
+        if let Some(card) = best_play {
+            return Some(Play(card));
+        }
+
+        let eight = actions.iter().find_map(|&action| match action {
+            PlayEight(eight, _) => Some(eight),
+            _ => None,
+        });
+
+        if let Some(eight) = eight {
+            let suit = actions
+                .iter()
+                .rev()
+                .filter_map(|&action| match action {
+                    PlayEight(card, suit) if card == eight => Some(suit),
+                    _ => None,
+                })
+                .max_by_key(|&suit| count(&|card: &Card| card.1 == suit && card.0 != Rank::Eight))
+                .expect("There is always a suit");
+
+            return Some(PlayEight(eight, suit));
+        }
+
+        actions
+            .into_iter()
+            .find(|&action| action == Draw || action == Pass)
+    }
+}
+
+/// The view of everything in the game, including every hand and the order of the draw pile. This
+/// shouldn't be shown to players while the game is in progress
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OmniscientView {
+    /// The cards in each player's hand
+    pub hands: HashMap<Player, Vector<Card>>,
+    /// The draw pile, the next card to be drawn is at the end
+    pub draw_pile: Vector<Card>,
+    /// The view that any observer can see, the totally non secret parts of the game
+    pub observer_view: ObserverView,
+}
+
+/// A view of the game redacted for a [`Perspective`](enum@Perspective)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    Observer(ObserverView),
+    Player(PlayerView),
+    Omniscient(OmniscientView),
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
-    /// Draw a card from the draw pile. Reshuffles the deck if there are no cards remaining in the
-    /// draw pile. If there are no cards in the draw pile or discard pile, this is a no-op.
+    /// Draw a card from the draw pile, or more depending on the [`DrawRule`](enum@DrawRule).
+    /// Reshuffles the deck if there are no cards remaining in the draw pile, and can't be taken
+    /// when there are no cards in the draw pile or discard pile
     Draw,
     /// Play a card from your hand
     Play(Card),
     /// Play and eight, and select the next suit
     PlayEight(Card, Suit),
+    /// Pass the turn, which is only allowed when there's nothing to play and no cards left to
+    /// draw, so every card is in someone's hand
+    Pass,
 }
 
 use Action::*;
 
+/// Something that happened in a game, see [`GameState::events`](GameState::events)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+    /// A player took an action
+    Action { player: Player, action: Action },
+    /// The discard pile was shuffled into the draw pile, before the draw at `turn`
+    ReshuffleOccurred { turn: usize },
+}
+
+/// What an action does, see [`PlayerView::analyzed_actions`](PlayerView::analyzed_actions)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionTag {
+    /// Drawing because there's nothing that can be played
+    ForcedDraw,
+    /// Plays the last card in hand, winning the game
+    WinningMove,
+    /// Plays an eight, which can go on anything
+    Wild,
+    /// Plays a card of the same rank as the top card
+    MatchesRank,
+    /// Plays a card of the current suit
+    MatchesSuit,
+    /// Leaves a different suit to play next
+    ChangesSuit,
+}
+
+/// A valid action along with the tags describing it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyzedAction {
+    pub action: Action,
+    pub tags: Vec<ActionTag>,
+}
+
 #[derive(Clone, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionError {
     #[error(
@@ -241,10 +1190,134 @@ pub enum ActionError {
     CantPlayEightAsRegularCard { card: Card },
     #[error("Can't play {:?} as an eight", card)]
     CantPlayNonEightAsEight { card: Card },
+    #[error(
+        "Player {:?} has to play a two or draw {:?} cards, and can't play {:?}",
+        player,
+        pending_draws,
+        card
+    )]
+    MustStackTwoOrDraw {
+        player: Player,
+        card: Card,
+        pending_draws: u8,
+    },
+    #[error(
+        "Player {:?} can't draw because there are no cards left to draw",
+        player
+    )]
+    NoCardsToDraw { player: Player },
+    #[error("Player {:?} can't pass because they can {:?}", player, actions)]
+    CantPass {
+        player: Player,
+        actions: Vec<Action>,
+    },
+    #[error(
+        "Player {:?} can't name {:?}, an eight on an eight can be played but keeps the suit {:?}",
+        player,
+        suit,
+        current_suit
+    )]
+    MustKeepSuit {
+        player: Player,
+        suit: Suit,
+        current_suit: Suit,
+    },
 }
 
 use ActionError::*;
 
+impl ActionError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{ActionError::*, Player::*};
+    ///
+    /// let error = NotPlayerTurn { attempted_player: P1, correct_player: P2 };
+    /// assert_eq!(error.code(), "not_player_turn");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            NotPlayerTurn { .. } => "not_player_turn",
+            CantDrawWhenYouHavePlayableCards { .. } => "cant_draw_when_you_have_playable_cards",
+            PlayerDoesNotHaveCard { .. } => "player_does_not_have_card",
+            CardCantBePlayed { .. } => "card_cant_be_played",
+            CantPlayEightAsRegularCard { .. } => "cant_play_eight_as_regular_card",
+            CantPlayNonEightAsEight { .. } => "cant_play_non_eight_as_eight",
+            MustStackTwoOrDraw { .. } => "must_stack_two_or_draw",
+            NoCardsToDraw { .. } => "no_cards_to_draw",
+            CantPass { .. } => "cant_pass",
+            MustKeepSuit { .. } => "must_keep_suit",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::games::crazy_eights::{ActionError::*, Player::*};
+    /// use serde_json::json;
+    ///
+    /// let error = CardCantBePlayed {
+    ///   attempted_card: Card(Ten, Clubs),
+    ///   top_card: Card(Nine, Spades),
+    ///   current_suit: Spades,
+    /// };
+    /// assert_eq!(
+    ///   error.details(),
+    ///   json!({
+    ///     "attempted_card": [10, "Clubs"],
+    ///     "top_card": [9, "Spades"],
+    ///     "current_suit": "Spades"
+    ///   })
+    /// );
+    /// ```
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            NotPlayerTurn {
+                attempted_player,
+                correct_player,
+            } => json!({
+                "attempted_player": attempted_player,
+                "correct_player": correct_player,
+            }),
+            CantDrawWhenYouHavePlayableCards { player, playable } => {
+                json!({ "player": player, "playable": playable })
+            }
+            PlayerDoesNotHaveCard { player, card } => json!({ "player": player, "card": card }),
+            CardCantBePlayed {
+                attempted_card,
+                top_card,
+                current_suit,
+            } => json!({
+                "attempted_card": attempted_card,
+                "top_card": top_card,
+                "current_suit": current_suit,
+            }),
+            CantPlayEightAsRegularCard { card } | CantPlayNonEightAsEight { card } => {
+                json!({ "card": card })
+            }
+            MustStackTwoOrDraw {
+                player,
+                card,
+                pending_draws,
+            } => json!({ "player": player, "card": card, "pending_draws": pending_draws }),
+            NoCardsToDraw { player } => json!({ "player": player }),
+            CantPass { player, actions } => json!({ "player": player, "actions": actions }),
+            MustKeepSuit {
+                player,
+                suit,
+                current_suit,
+            } => json!({ "player": player, "suit": suit, "current_suit": current_suit }),
+        }
+    }
+}
+
+impl FromSettings for GameState {
+    type Settings = Settings;
+
+    fn from_settings(settings: Arc<Settings>) -> Self {
+        Self::new(settings)
+    }
+}
+
 impl GameState {
     /// Creates a new game from a game type and seed
     /// ```
@@ -252,28 +1325,13 @@ impl GameState {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Two, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(game.whose_turn(), P1);
     /// ```
     pub fn new(settings: Arc<Settings>) -> Self {
-        let mut rng = settings.seed.into_rng();
-        let mut cards: Vec<Card> = STANDARD_DECK.into();
-        cards.shuffle(&mut rng);
-        let mut deck = cards.into_iter();
-
-        let mut hands = enum_map! { _ => Vec::new() };
-
-        let num_cards_per_player = settings
-            .number_of_players
-            .starting_number_of_cards_per_player();
-        for player in settings.number_of_players.players() {
-            hands[player] = (&mut deck).take(num_cards_per_player as usize).collect();
-        }
-
-        // Can't fail because deck is 52 cards
-        let top_card = deck.next().unwrap();
-        let draw_pile = deck.collect();
+        let mut rng = GameRng::from(settings.seed.into_rng());
+        let (hands, top_card, draw_pile, current_suit) = Self::deal(&settings, &mut rng);
 
         Self {
             game_history: GameHistory {
@@ -284,9 +1342,76 @@ impl GameState {
             draw_pile,
             hands,
             top_card,
-            current_suit: top_card.1,
+            current_suit,
             discarded: Vector::new(),
+            reshuffles: Vector::new(),
+            scores: EnumMap::default(),
+            rounds_played: 0,
+            turns: Vector::new(),
+            whose_turn: P1,
+            reversed: false,
+            pending_draws: 0,
+            suit_history: SuitHistory::default(),
+        }
+    }
+
+    /// Shuffles and deals a round, returning the hands, the top card, the draw pile, and the
+    /// suit to play
+    fn deal(
+        settings: &Settings,
+        rng: &mut GameRng,
+    ) -> (EnumMap<Player, Vec<Card>>, Card, Vector<Card>, Suit) {
+        let rng = rng.for_use(RngUse::Shuffle);
+        let cards: Vec<Card> = match settings.rules.decks.count(settings.number_of_players) {
+            1 => StandardDeck::shuffled_with(rng).into(),
+            decks => {
+                let mut cards = STANDARD_DECK.repeat(decks);
+                cards.shuffle(rng);
+                cards
+            }
+        };
+        let mut deck = cards.into_iter();
+
+        let mut hands = enum_map! { _ => Vec::new() };
+
+        let num_cards_per_player = settings.deal.cards_per_player(settings.number_of_players);
+        for player in settings.number_of_players.players() {
+            hands[player] = (&mut deck).take(num_cards_per_player as usize).collect();
+        }
+
+        // Can't fail, settings always leave a card over after the deal
+        let mut top_card = deck.next().unwrap();
+        let mut draw_pile: Vector<Card> = deck.collect();
+        let mut current_suit = top_card.1;
+
+        match settings.deal.starter_eight {
+            StarterEight::Play => {}
+            StarterEight::Bury => {
+                // Cards are drawn from the back, so the front is the bottom of the pile
+                for _ in 0..draw_pile.len() {
+                    if top_card.0 != Rank::Eight {
+                        break;
+                    }
+                    draw_pile.push_front(top_card);
+                    top_card = draw_pile.pop_back().unwrap();
+                }
+                current_suit = top_card.1;
+            }
+            StarterEight::DealerPicksSuit if top_card.0 == Rank::Eight => {
+                let dealer = settings.number_of_players.players().last().unwrap();
+                let held = |suit: Suit| hands[dealer].iter().filter(|card| card.1 == suit).count();
+                current_suit = Suit::ALL.iter().copied().fold(Suit::ALL[0], |best, suit| {
+                    if held(suit) > held(best) {
+                        suit
+                    } else {
+                        best
+                    }
+                });
+            }
+            StarterEight::DealerPicksSuit => {}
         }
+
+        (hands, top_card, draw_pile, current_suit)
     }
 
     /// Gives the game history of the current game state, the game history is a minimal
@@ -296,7 +1421,7 @@ impl GameState {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Two, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(game.game_history().game_state(), Ok(game));
     /// ```
@@ -312,12 +1437,161 @@ impl GameState {
     /// use std::sync::Arc;
     ///
     /// // A new game has an empty history
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Two, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
     /// let game = GameState::new(Arc::new(settings));
     /// assert!(equal(game.history(), vec![]));
     /// ```
     pub fn history(&self) -> impl Iterator<Item = (Player, Action)> + '_ {
-        self.game_history.history()
+        self.turns
+            .iter()
+            .zip(self.game_history.history.iter())
+            .map(|(&player, &action)| (player, action))
+    }
+
+    /// The action taken on a turn, counting from 0, and who took it. House rules can skip
+    /// players and turn play around, so the player isn't always the seat the turn number lands on
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    /// let action = game.current_player_view().suggest_action().unwrap();
+    /// let game = game.apply_action((P1, action)).unwrap();
+    ///
+    /// assert_eq!(game.nth_turn(0), Some((P1, action)));
+    /// assert_eq!(game.nth_turn(1), None);
+    /// ```
+    pub fn nth_turn(&self, turn: usize) -> Option<(Player, Action)> {
+        Some((
+            *self.turns.get(turn)?,
+            *self.game_history.history.get(turn)?,
+        ))
+    }
+
+    /// The actions a player has taken along with the turns they were taken on, counting from 0
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two);
+    /// let mut game = GameState::new(Arc::new(settings));
+    /// for _ in 0..5 {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let turns: Vec<usize> = game.history_for_player(P2).map(|(turn, _)| turn).collect();
+    /// assert_eq!(turns, vec![1, 3]);
+    /// ```
+    pub fn history_for_player(&self, player: Player) -> impl Iterator<Item = (usize, Action)> + '_ {
+        self.history()
+            .enumerate()
+            .filter(move |(_, (by, _))| by == &player)
+            .map(|(turn, (_, action))| (turn, action))
+    }
+
+    /// The most recent action and who took it, `None` at the start of the game
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    /// assert_eq!(game.last_action(), None);
+    ///
+    /// let action = game.forced_action().unwrap();
+    /// let game = game.apply_action((P1, action)).unwrap();
+    /// assert_eq!(game.last_action(), Some((P1, action)));
+    /// ```
+    pub fn last_action(&self) -> Option<(Player, Action)> {
+        self.recent_actions(1).pop()
+    }
+
+    /// The last `n` actions and who took them, oldest first, or the whole history if there have
+    /// been fewer. For showing what the other players did since the player's last turn
+    pub fn recent_actions(&self, n: usize) -> Vec<(Player, Action)> {
+        let len = self.game_history.history.len();
+        self.history().skip(len.saturating_sub(n)).collect()
+    }
+
+    /// How many times the discard pile has been shuffled back into the draw pile. Drawing from
+    /// an empty draw pile reshuffles, unless the discard pile is empty too.
+    ///
+    /// The deal and every reshuffle take turns drawing from one ChaCha20 generator seeded with
+    /// [`Settings::seed`](struct@Settings), the deal shuffles first and the reshuffles follow in
+    /// order, so the same seed and history always reshuffle into the same draw pile
+    pub fn reshuffle_count(&self) -> usize {
+        self.reshuffles.len()
+    }
+
+    /// What the game has drawn from its generator, oldest first. Only debug builds keep track,
+    /// release builds always return nothing. A game restored from a snapshot only has the draws
+    /// made since
+    /// ```
+    /// use lib_table_top::common::rand::{RngDraws, RngSeed, RngUse};
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Three);
+    /// let game = GameState::new(Arc::new(settings));
+    /// // Examples can run against a release build of the library, which doesn't keep track
+    /// let draws = game.rng_audit();
+    /// if !draws.is_empty() {
+    ///     assert_eq!(draws, &[RngDraws { purpose: RngUse::Shuffle, words: 78 }]);
+    /// }
+    /// ```
+    pub fn rng_audit(&self) -> &[crate::common::rand::RngDraws] {
+        #[cfg(debug_assertions)]
+        let draws = self.rng.draws();
+        #[cfg(not(debug_assertions))]
+        let draws = &[];
+        draws
+    }
+
+    /// Everything that happened in the game, in order. A reshuffle comes right before the draw
+    /// that caused it
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{
+    ///   Action::*, Event, GameState, NumberOfPlayers, Settings, Status::*
+    /// };
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([4; 32]), NumberOfPlayers::Four);
+    /// let mut game = GameState::new(Arc::new(settings));
+    /// while game.reshuffle_count() == 0 {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let events = game.events();
+    /// let turn = game.history().count() - 1;
+    /// assert_eq!(events[events.len() - 2], Event::ReshuffleOccurred { turn });
+    /// assert!(matches!(events.last(), Some(Event::Action { action: Draw, .. })));
+    /// assert_eq!(game.observer_view().reshuffle_count, 1);
+    /// assert_eq!(game.status(), InProgress);
+    /// ```
+    pub fn events(&self) -> Vec<Event> {
+        let mut reshuffles = self.reshuffles.iter().peekable();
+        let mut events =
+            Vec::with_capacity(self.game_history.history.len() + self.reshuffles.len());
+
+        for (turn, (player, action)) in self.history().enumerate() {
+            while let Some(&&reshuffle_turn) = reshuffles.peek() {
+                if reshuffle_turn != turn {
+                    break;
+                }
+                events.push(Event::ReshuffleOccurred { turn });
+                reshuffles.next();
+            }
+            events.push(Event::Action { player, action });
+        }
+
+        events
     }
 
     /// Returns the settings for a game
@@ -331,12 +1605,30 @@ impl GameState {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Two, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(game.whose_turn(), P1);
     /// ```
     pub fn whose_turn(&self) -> Player {
-        self.game_history.whose_turn()
+        self.whose_turn
+    }
+
+    /// The players the game is waiting on, nobody once the game is over
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    /// assert!(game.pending_input().is_pending_on(P1));
+    /// assert!(!game.pending_input().is_pending_on(P2));
+    /// ```
+    pub fn pending_input(&self) -> PendingInput<Player> {
+        match self.status() {
+            InProgress => PendingInput::turn(self.whose_turn()),
+            Win { .. } | MatchWin { .. } | Blocked { .. } => PendingInput::none(),
+        }
     }
 
     /// Returns the player view for the current player
@@ -345,7 +1637,7 @@ impl GameState {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Three, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(
     ///   game.player_view(game.whose_turn()),
@@ -356,6 +1648,124 @@ impl GameState {
         self.player_view(self.whose_turn())
     }
 
+    /// The only action the current player can take, like having to draw because nothing in
+    /// their hand can be played, `None` if they have a choice or the game is over
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{Action::*, GameState, NumberOfPlayers, Settings};
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use itertools::iterate;
+    /// use std::sync::Arc;
+    ///
+    /// // Holding a single playable card and no eights means it has to be played
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    /// assert_eq!(game.forced_action(), Some(Play(Card(Five, Clubs))));
+    ///
+    /// let game = iterate(game, |game| {
+    ///   let action = game.current_player_view().valid_actions().pop().unwrap();
+    ///   game.apply_action((game.whose_turn(), action)).unwrap()
+    /// })
+    ///   .find(|game| game.forced_action() == Some(Draw))
+    ///   .unwrap();
+    ///
+    /// assert_eq!(game.current_player_view().valid_actions(), vec![Draw]);
+    /// ```
+    pub fn forced_action(&self) -> Option<Action> {
+        if self.status() != InProgress {
+            return None;
+        }
+
+        match self.current_player_view().valid_actions().as_slice() {
+            [action] => Some(*action),
+            _ => None,
+        }
+    }
+
+    /// The number of actions in the fixed action space: drawing, playing each of the 52 cards,
+    /// playing each of the four eights calling each of the four suits, and passing. For
+    /// reinforcement learning environments that need every action to have an index
+    pub fn action_space_size() -> usize {
+        1 + 52 + 4 * 4 + 1
+    }
+
+    /// The index of an action in the action space. `Draw` is 0, then `Play` takes 1 through 52,
+    /// ordered by suit (`Suit::ALL`) then rank (`Rank::ALL`), `PlayEight` takes 53 through 68,
+    /// ordered by the suit of the eight then the suit called, and `Pass` is 69. Playing an eight
+    /// without calling a suit has an index but is never legal
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::games::crazy_eights::{Action::*, GameState};
+    ///
+    /// assert_eq!(GameState::action_index(Draw), 0);
+    /// assert_eq!(GameState::action_index(Play(Card(Ace, Clubs))), 1);
+    /// assert_eq!(GameState::action_index(Play(Card(King, Spades))), 52);
+    /// assert_eq!(GameState::action_index(PlayEight(Card(Eight, Clubs), Clubs)), 53);
+    /// assert_eq!(GameState::action_index(PlayEight(Card(Eight, Spades), Hearts)), 67);
+    /// assert_eq!(GameState::action_index(Pass), 69);
+    /// ```
+    pub fn action_index(action: Action) -> usize {
+        match action {
+            Draw => 0,
+            Play(card) => 1 + card_index(card),
+            PlayEight(Card(_, eight), called) => 53 + eight as usize * 4 + called as usize,
+            Pass => 69,
+        }
+    }
+
+    /// The action at an index in the action space, `None` if the index is out of range
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::games::crazy_eights::{Action::*, GameState};
+    ///
+    /// assert_eq!(GameState::action_from_index(14), Some(Play(Card(Ace, Diamonds))));
+    /// assert_eq!(GameState::action_from_index(69), Some(Pass));
+    /// assert_eq!(GameState::action_from_index(70), None);
+    /// ```
+    pub fn action_from_index(index: usize) -> Option<Action> {
+        match index {
+            0 => Some(Draw),
+            1..=52 => Some(Play(Card(
+                Rank::ALL[(index - 1) % 13],
+                Suit::ALL[(index - 1) / 13],
+            ))),
+            53..=68 => Some(PlayEight(
+                Card(Rank::Eight, Suit::ALL[(index - 53) / 4]),
+                Suit::ALL[(index - 53) % 4],
+            )),
+            69 => Some(Pass),
+            _ => None,
+        }
+    }
+
+    /// Which indices of the action space the current player can take right now, see
+    /// [`action_index`](GameState::action_index) for the order. Nothing is legal once the game
+    /// is over
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::games::crazy_eights::{Action::*, GameState, NumberOfPlayers, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+    /// let mask = game.legal_action_mask();
+    ///
+    /// assert_eq!(mask.len(), GameState::action_space_size());
+    /// assert!(mask[GameState::action_index(Play(Card(Nine, Clubs)))]);
+    /// assert!(!mask[GameState::action_index(Draw)]);
+    /// ```
+    pub fn legal_action_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; Self::action_space_size()];
+
+        if self.status() == InProgress {
+            for action in self.current_player_view().valid_actions() {
+                mask[Self::action_index(action)] = true;
+            }
+        }
+
+        mask
+    }
+
     /// Returns the view accessible to a particular player, contains all the information needed to
     /// show the game to a particular player and have them decide on their action
     /// ```
@@ -371,7 +1781,7 @@ impl GameState {
     ///
     /// # use lib_table_top::games::crazy_eights::ActionError;
     /// # fn main() -> Result<(), ActionError> {
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Three, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
     /// let game = GameState::new(Arc::new(settings));
     /// let player_view: PlayerView = game.player_view(P1);
     ///
@@ -380,6 +1790,11 @@ impl GameState {
     ///     whose_turn: P1,
     ///     discarded: Vector::new(),
     ///     draw_pile_remaining: 36,
+    ///     reshuffle_count: 0,
+    ///     scores: HashMap::new(),
+    ///     reversed: false,
+    ///     pending_draws: 0,
+    ///     suit_history: Default::default(),
     ///     top_card: Card(Four, Diamonds),
     ///     current_suit: Diamonds,
     ///     player_card_count: [
@@ -387,6 +1802,11 @@ impl GameState {
     ///       (P2, 5),
     ///       (P3, 5),
     ///     ].iter().copied().collect(),
+    ///     rules: Default::default(),
+    ///     player_info: HashMap::new(),
+    ///     deadline: None,
+    ///     time_remaining: None,
+    ///     metadata: Default::default(),
     ///   },
     ///   player: P1,
     ///   hand: vector![
@@ -396,6 +1816,7 @@ impl GameState {
     ///     Card(Jack, Diamonds),
     ///     Card(King, Spades)
     ///   ],
+    ///   metadata: Default::default(),
     /// });
     /// # Ok(())
     /// # }
@@ -405,6 +1826,7 @@ impl GameState {
             player,
             hand: self.hands[player].clone().into(),
             observer_view: self.observer_view(),
+            metadata: Map::new(),
         }
     }
 
@@ -421,7 +1843,7 @@ impl GameState {
     /// use std::sync::Arc;
     ///
     /// # use lib_table_top::games::crazy_eights::ActionError;
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Three, seed: RngSeed([0; 32])};
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
     /// let game = GameState::new(Arc::new(settings));
     /// let observer_view: ObserverView = game.observer_view();
     ///
@@ -429,6 +1851,11 @@ impl GameState {
     ///     whose_turn: P1,
     ///     discarded: Vector::new(),
     ///     draw_pile_remaining: 36,
+    ///     reshuffle_count: 0,
+    ///     scores: HashMap::new(),
+    ///     reversed: false,
+    ///     pending_draws: 0,
+    ///     suit_history: Default::default(),
     ///     top_card: Card(Four, Diamonds),
     ///     current_suit: Diamonds,
     ///     player_card_count: [
@@ -436,6 +1863,11 @@ impl GameState {
     ///       (P2, 5),
     ///       (P3, 5),
     ///     ].iter().copied().collect(),
+    ///     rules: Default::default(),
+    ///     player_info: HashMap::new(),
+    ///     deadline: None,
+    ///     time_remaining: None,
+    ///     metadata: Default::default(),
     ///   });
     /// ```
     pub fn observer_view(&self) -> ObserverView {
@@ -448,9 +1880,50 @@ impl GameState {
             current_suit: self.current_suit,
             discarded: self.discarded.clone(),
             draw_pile_remaining: self.draw_pile.len() as u8,
+            reshuffle_count: self.reshuffle_count(),
+            scores: match self.settings().rules.match_target {
+                Some(_) => self
+                    .players()
+                    .map(|player| (player, self.scores[player]))
+                    .collect(),
+                None => HashMap::new(),
+            },
+            reversed: self.reversed,
+            pending_draws: self.pending_draws,
+            suit_history: self.suit_history.clone(),
             player_card_count,
             top_card: self.top_card,
-            whose_turn: self.game_history.whose_turn(),
+            whose_turn: self.whose_turn,
+            rules: self.settings().rules,
+            player_info: HashMap::new(),
+            deadline: None,
+            time_remaining: None,
+            metadata: Map::new(),
+        }
+    }
+
+    /// Returns the view of everything in the game, see [`OmniscientView`](struct@OmniscientView)
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
+    /// let game = GameState::new(Arc::new(settings));
+    /// let view = game.omniscient_view();
+    ///
+    /// assert_eq!(view.hands[&P2], game.player_view(P2).hand);
+    /// assert_eq!(view.draw_pile.len(), 36);
+    /// assert_eq!(view.observer_view, game.observer_view());
+    /// ```
+    pub fn omniscient_view(&self) -> OmniscientView {
+        OmniscientView {
+            hands: self
+                .players()
+                .map(|player| (player, self.hands[player].clone().into()))
+                .collect(),
+            draw_pile: self.draw_pile.clone(),
+            observer_view: self.observer_view(),
         }
     }
 
@@ -464,7 +1937,7 @@ impl GameState {
     /// use std::sync::Arc;
     ///
     /// // You can play a valid action
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Three, seed: RngSeed([1; 32])};
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Three);
     /// let game = GameState::new(Arc::new(settings));
     /// let action = game.current_player_view().valid_actions().pop().unwrap();
     /// let game = game.apply_action((P1, action)).unwrap();
@@ -551,40 +2024,122 @@ impl GameState {
     /// ```
     pub fn apply_action(&self, (player, action): (Player, Action)) -> Result<Self, ActionError> {
         self.validate_action_structure((player, action))?;
-        let mut new_game = self.clone();
 
+        // Everything is checked before cloning, so invalid actions are cheap to reject
         match action {
+            Play(card) | PlayEight(card, _) if self.pending_draws > 0 && card.0 != Rank::Two => {
+                return Err(MustStackTwoOrDraw {
+                    player,
+                    card,
+                    pending_draws: self.pending_draws,
+                });
+            }
+            Draw if self.draw_pile.is_empty() && self.discarded.is_empty() => {
+                return Err(NoCardsToDraw { player });
+            }
+            Draw if self.pending_draws > 0 => {}
             Draw => {
-                let playable: Vec<Card> = new_game
-                    .player_hand(player)
-                    .iter()
-                    .filter(|card| self.valid_to_play(card))
-                    .copied()
-                    .collect();
+                if !self.settings().rules.voluntary_draws
+                    && self
+                        .player_hand(player)
+                        .iter()
+                        .any(|card| self.valid_to_play(card))
+                {
+                    let playable = self
+                        .player_hand(player)
+                        .iter()
+                        .filter(|card| self.valid_to_play(card))
+                        .copied()
+                        .collect();
 
-                if !playable.is_empty() {
                     return Err(CantDrawWhenYouHavePlayableCards { player, playable });
                 }
-
-                if new_game.draw_pile.is_empty() {
-                    new_game.reshuffle();
+            }
+            Play(card) => self.validate_card(player, card)?,
+            PlayEight(card, suit) => {
+                self.validate_card(player, card)?;
+                if !self.eight_suits().contains(&suit) {
+                    return Err(MustKeepSuit {
+                        player,
+                        suit,
+                        current_suit: self.current_suit,
+                    });
+                }
+            }
+            Pass => {
+                let actions = self.player_view(player).valid_actions();
+                if actions != vec![Pass] {
+                    return Err(CantPass { player, actions });
                 }
+            }
+        }
+
+        let mut new_game = self.clone();
+        let mut keeps_turn = false;
 
-                new_game.hands[player].extend(new_game.draw_pile.pop_back().iter());
+        match action {
+            Draw if new_game.pending_draws > 0 => {
+                for _ in 0..new_game.pending_draws {
+                    new_game.draw_card(player);
+                }
+                new_game.pending_draws = 0;
             }
+            Draw => keeps_turn = new_game.draw_for_turn(player),
             Play(card) => {
-                new_game.play_card(player, card)?;
+                new_game.play_card(player, card);
                 new_game.current_suit = card.1;
             }
             PlayEight(card, suit) => {
-                new_game.play_card(player, card)?;
+                new_game.play_card(player, card);
                 new_game.current_suit = suit;
             }
+            // There's nothing left to draw, so any twos played at the player are forgotten
+            Pass => new_game.pending_draws = 0,
         }
         new_game.game_history.history.push_back(action);
+        new_game.turns.push_back(player);
+        if !keeps_turn {
+            new_game.pass_turn(player, action);
+        }
+        new_game.finish_round(player);
         Ok(new_game)
     }
 
+    /// Draws a card into a player's hand, reshuffling the discard pile if the draw pile is empty.
+    /// Returns the card, `None` if there weren't any cards to draw
+    fn draw_card(&mut self, player: Player) -> Option<Card> {
+        if self.draw_pile.is_empty() && !self.discarded.is_empty() {
+            self.reshuffle();
+        }
+
+        let card = self.draw_pile.pop_back()?;
+        self.hands[player].push(card);
+        Some(card)
+    }
+
+    /// Draws for a player's turn following the [`DrawRule`](enum@DrawRule), returns whether
+    /// they drew a card they can play and keep the turn
+    fn draw_for_turn(&mut self, player: Player) -> bool {
+        let max = match self.settings().rules.draw {
+            DrawRule::DrawOne => {
+                self.draw_card(player);
+                return false;
+            }
+            DrawRule::DrawUntilPlayable { max } => max.map(|max| max.max(1)),
+        };
+
+        let mut drawn = 0;
+        while max != Some(drawn) {
+            match self.draw_card(player) {
+                Some(card) if self.valid_to_play(&card) => return true,
+                Some(_) => drawn += 1,
+                None => return false,
+            }
+        }
+
+        false
+    }
+
     /// Returns the status of the game
     /// ```
     /// use lib_table_top::games::crazy_eights::{
@@ -594,10 +2149,7 @@ impl GameState {
     /// use std::sync::Arc;
     /// use itertools::iterate;
     ///
-    /// let settings = Settings {
-    ///   number_of_players: NumberOfPlayers::Three,
-    ///   seed: RngSeed([1; 32])
-    /// };
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Three);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(game.status(), InProgress);
     ///
@@ -614,44 +2166,237 @@ impl GameState {
     /// assert_eq!(game.status(), Win { player: P2 });
     /// ```
     pub fn status(&self) -> Status {
-        self.players()
-            .filter(|&player| self.hands[player].is_empty())
-            .map(|player| Win { player })
-            .next()
-            .unwrap_or(InProgress)
-    }
+        // In match play the hands are re-dealt after every round but the last
+        let winner = match self.players().find(|&player| self.hands[player].is_empty()) {
+            Some(player) => player,
+            None if self.is_blocked() => {
+                return Blocked {
+                    scores: self
+                        .players()
+                        .map(|player| (player, self.penalty_points(player)))
+                        .collect(),
+                }
+            }
+            None => return InProgress,
+        };
 
-    fn player_hand(&self, player: Player) -> &[Card] {
-        &self.hands[player].as_slice()
+        match self.settings().rules.match_target {
+            Some(_) => MatchWin {
+                player: winner,
+                scores: self
+                    .players()
+                    .map(|player| (player, self.scores[player]))
+                    .collect(),
+            },
+            None => Win { player: winner },
+        }
     }
 
-    fn play_card(&mut self, player: Player, card: Card) -> Result<(), ActionError> {
-        if !self.player_hand(player).contains(&card) {
-            return Err(PlayerDoesNotHaveCard { player, card });
-        }
+    /// The finishing order once the game is over, best first, `None` while it's in progress.
+    /// After a win everyone else is placed by the penalty points left in their hand, fewest
+    /// first, a match is placed by score, and a blocked game by penalty points. Ties share a
+    /// place, see [`rank_by`](fn@crate::common::results::rank_by)
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings, Status};
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Four);
+    /// let mut game = GameState::new(Arc::new(settings));
+    /// assert_eq!(game.rankings(), None);
+    ///
+    /// while game.status() == Status::InProgress {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let rankings = game.rankings().unwrap();
+    /// assert_eq!(rankings.len(), 4);
+    /// assert_eq!(rankings[0], (game.last_action().unwrap().0, 1));
+    /// assert!(rankings.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    /// ```
+    pub fn rankings(&self) -> Option<Vec<(Player, u32)>> {
+        let places = match self.status() {
+            InProgress => return None,
+            Win { player: winner } => rank_by(
+                self.players()
+                    .map(|player| (player, (player != winner, self.penalty_points(player)))),
+            ),
+            MatchWin { scores, .. } => rank_by(
+                scores
+                    .into_iter()
+                    .map(|(player, score)| (player, std::cmp::Reverse(score))),
+            ),
+            Blocked { scores } => rank_by(scores),
+        };
 
-        if !self.valid_to_play(&card) {
-            return Err(CardCantBePlayed {
-                attempted_card: card,
-                top_card: self.top_card,
-                current_suit: self.current_suit,
-            });
-        }
+        Some(places)
+    }
 
-        let old_top_card = std::mem::replace(&mut self.top_card, card);
-        self.discarded.push_back(old_top_card);
-        self.hands[player].retain(|c| c != &card);
+    /// The points the cards in a player's hand count against them
+    fn penalty_points(&self, player: Player) -> u32 {
+        self.hands[player].total(Scheme::CrazyEights).points
+    }
 
-        Ok(())
+    /// Whether there's nothing left to draw and nobody holds a card that can be played, so the
+    /// players would pass forever
+    fn is_blocked(&self) -> bool {
+        self.draw_pile.is_empty()
+            && self.discarded.is_empty()
+            && self.players().all(|player| {
+                !self.hands[player]
+                    .iter()
+                    .any(|card| self.valid_to_play(card))
+            })
     }
 
-    fn valid_to_play(&self, Card(rank, suit): &Card) -> bool {
-        let Card(current_rank, _suit) = self.top_card;
-        rank == &Rank::Eight || rank == &current_rank || suit == &self.current_suit
+    /// The points a player has scored in match play, see
+    /// [`Rules::match_target`](Rules::match_target)
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::crazy_eights::{
+    ///   NumberOfPlayers, Player::*, Rules, SettingsBuilder, Status::*
+    /// };
+    ///
+    /// let mut game = SettingsBuilder::new()
+    ///   .seed(RngSeed([0; 32]))
+    ///   .number_of_players(NumberOfPlayers::Two)
+    ///   .rules(Rules { match_target: Some(100), ..Default::default() })
+    ///   .build_game()
+    ///   .unwrap();
+    ///
+    /// while game.rounds_played() == 0 {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// // Unless the first round reached the target, the cards were dealt again
+    /// let scored = game.score(P1) + game.score(P2);
+    /// assert!(scored > 0);
+    /// if scored < 100 {
+    ///     assert_eq!(game.status(), InProgress);
+    ///     assert_eq!(game.player_view(P1).hand.len(), 7);
+    /// }
+    /// ```
+    pub fn score(&self, player: Player) -> u32 {
+        self.scores[player]
     }
 
-    fn validate_action_structure(
-        &self,
+    /// How many rounds have been finished, always 0 outside of match play until the game is over
+    pub fn rounds_played(&self) -> u32 {
+        self.rounds_played
+    }
+
+    /// Scores the round once a player has emptied their hand. In match play the round winner
+    /// scores the cards left in everyone else's hands, and the cards are dealt again with the next
+    /// values from the game's random number generator unless the match is over
+    fn finish_round(&mut self, player: Player) {
+        if !self.hands[player].is_empty() {
+            return;
+        }
+
+        self.rounds_played += 1;
+        let target = match self.settings().rules.match_target {
+            Some(target) => target,
+            None => return,
+        };
+
+        let points: u32 = self.players().map(|other| self.penalty_points(other)).sum();
+        self.scores[player] += points;
+
+        if self.scores[player] < target {
+            let settings = self.game_history.settings.clone();
+            let mut rng = (*self.rng).clone();
+            let (hands, top_card, draw_pile, current_suit) = Self::deal(&settings, &mut rng);
+
+            self.hands = hands;
+            self.top_card = top_card;
+            self.draw_pile = draw_pile;
+            self.current_suit = current_suit;
+            self.discarded = Vector::new();
+            self.rng = Arc::new(rng);
+            self.reversed = false;
+            self.pending_draws = 0;
+            self.suit_history = SuitHistory::default();
+        }
+    }
+
+    /// Moves play on from the player who just took an action, following the house rules
+    fn pass_turn(&mut self, player: Player, action: Action) {
+        let rules = self.settings().rules;
+        let mut steps = 1;
+
+        match action {
+            Play(Card(Rank::Two, _)) if rules.stacking_twos => self.pending_draws += 2,
+            Play(Card(Rank::Queen, _)) if rules.queens_skip => steps = 2,
+            Play(Card(Rank::Jack, _)) if rules.jacks_reverse => self.reversed = !self.reversed,
+            _ => {}
+        }
+
+        let players: Vec<Player> = self.players().collect();
+        let count = players.len();
+        let index = players
+            .iter()
+            .position(|&p| p == player)
+            .expect("Only players in the game can take actions");
+        let next = if self.reversed {
+            (index + count - steps % count) % count
+        } else {
+            (index + steps) % count
+        };
+
+        self.whose_turn = players[next];
+    }
+
+    fn player_hand(&self, player: Player) -> &[Card] {
+        &self.hands[player].as_slice()
+    }
+
+    fn validate_card(&self, player: Player, card: Card) -> Result<(), ActionError> {
+        if !self.player_hand(player).contains(&card) {
+            return Err(PlayerDoesNotHaveCard { player, card });
+        }
+
+        if !self.valid_to_play(&card) {
+            return Err(CardCantBePlayed {
+                attempted_card: card,
+                top_card: self.top_card,
+                current_suit: self.current_suit,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Moves a card from a hand to the top of the discard pile, see `validate_card`
+    fn play_card(&mut self, player: Player, card: Card) {
+        let old_top_card = std::mem::replace(&mut self.top_card, card);
+        self.discarded.push_back(old_top_card);
+        self.suit_history.record(card);
+        // With more than one deck there can be copies of the card, only one of them is played
+        if let Some(index) = self.hands[player].iter().position(|c| c == &card) {
+            self.hands[player].remove(index);
+        }
+    }
+
+    fn valid_to_play(&self, Card(rank, suit): &Card) -> bool {
+        let Card(current_rank, _suit) = self.top_card;
+        if rank == &Rank::Eight {
+            return !self.eight_suits().is_empty();
+        }
+        rank == &current_rank || suit == &self.current_suit
+    }
+
+    /// The suits an eight can name right now, see [`Rules::eight_on_eight`](Rules::eight_on_eight)
+    fn eight_suits(&self) -> Vec<Suit> {
+        self.settings()
+            .rules
+            .eight_on_eight
+            .suits(self.top_card, self.current_suit)
+    }
+
+    fn validate_action_structure(
+        &self,
         (player, action): (Player, Action),
     ) -> Result<(), ActionError> {
         let whose_turn = self.whose_turn();
@@ -683,7 +2428,12 @@ impl GameState {
         self.game_history.settings.number_of_players.players()
     }
 
+    /// Shuffles the discard pile, without the top card, into the draw pile. The shuffle takes the
+    /// next values from the same ChaCha20 stream that dealt the game, so the order only depends
+    /// on the seed and the number of shuffles before it, see
+    /// [`reshuffle_count`](GameState::reshuffle_count)
     fn reshuffle(&mut self) {
+        self.reshuffles.push_back(self.game_history.history.len());
         let mut new_rng = (*self.rng).clone();
         let mut draw_pile: Vec<Card> = self
             .draw_pile
@@ -691,8 +2441,7 @@ impl GameState {
             .chain(self.discarded.iter())
             .copied()
             .collect();
-        self.draw_pile.extend(self.discarded.clone());
-        draw_pile.shuffle(&mut new_rng);
+        draw_pile.shuffle(new_rng.for_use(RngUse::Reshuffle));
         self.draw_pile = draw_pile.into();
         self.discarded = Vector::new();
         self.rng = Arc::new(new_rng);
@@ -715,30 +2464,1112 @@ impl GameHistory {
     /// use lib_table_top::common::rand::RngSeed;
     /// use std::sync::Arc;
     ///
-    /// let settings = Settings {number_of_players: NumberOfPlayers::Two, seed: RngSeed([1; 32])};
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two);
     /// let game = GameState::new(Arc::new(settings));
     /// assert_eq!(game.game_history().game_state(), Ok(game));
     /// ```
     pub fn game_state(&self) -> Result<GameState, ActionError> {
-        let game_state = GameState::new(self.settings.clone());
+        self.game_state_at(self.history.len())
+    }
+
+    /// The number of actions in the history
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
 
-        self.history
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+
+    /// Builds the `GameState` after a number of turns, for replay UIs. Turns past the end of the
+    /// history give the state at the end
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two);
+    /// let start = GameState::new(Arc::new(settings));
+    /// let mut game = start.clone();
+    /// for _ in 0..4 {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let history = game.game_history();
+    /// assert_eq!(history.game_state_at(0), Ok(start));
+    /// assert_eq!(history.game_state_at(4), Ok(game.clone()));
+    /// assert_eq!(history.game_state_at(100), Ok(game.clone()));
+    /// assert_eq!(history.game_state_at(usize::MAX), Ok(game.clone()));
+    /// assert_eq!(history.game_state_at(2).unwrap().history().count(), 2);
+    /// ```
+    pub fn game_state_at(&self, turn: usize) -> Result<GameState, ActionError> {
+        self.states()
+            .take(turn.saturating_add(1))
+            .last()
+            .expect("There's always the starting state")
+    }
+
+    /// Every state of the game in order, starting before any actions and ending after the last
+    /// one. Each state is built from the one before, so walking the whole game only applies each
+    /// action once. If an action can't be applied its error is the last item
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two);
+    /// let mut game = GameState::new(Arc::new(settings));
+    /// for _ in 0..4 {
+    ///     let action = game.current_player_view().suggest_action().unwrap();
+    ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+    /// }
+    ///
+    /// let states: Vec<GameState> = game.game_history().states().map(Result::unwrap).collect();
+    /// assert_eq!(states.len(), 5);
+    /// assert_eq!(states.last(), Some(&game));
+    /// ```
+    pub fn states(&self) -> impl Iterator<Item = Result<GameState, ActionError>> + '_ {
+        let mut next = Some(Ok(GameState::new(self.settings.clone())));
+        let mut actions = self.history.iter();
+
+        std::iter::from_fn(move || {
+            let current = next.take()?;
+            if let Ok(game_state) = &current {
+                next = actions
+                    .next()
+                    .map(|&action| game_state.apply_action((game_state.whose_turn(), action)));
+            }
+            Some(current)
+        })
+    }
+}
+
+impl GameState {
+    /// The cards a perspective isn't allowed to see, for checking views with
+    /// [`leak_check`](fn@crate::common::views::leak_check). With two decks a hidden card can
+    /// have a twin the perspective does see, and the twins can't be told apart, so those are left
+    /// out
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::common::views::{leak_check, Perspective, Viewable};
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two)));
+    /// let perspective = Perspective::Player(P1);
+    ///
+    /// assert_eq!(game.hidden_cards(perspective).len(), 52 - 1 - 7);
+    /// assert!(leak_check(&game.view(perspective), game.hidden_cards(perspective)).is_empty());
+    /// ```
+    pub fn hidden_cards(&self, perspective: Perspective<Player>) -> Vec<Card> {
+        let (seen, unseen): (Vec<Player>, Vec<Player>) = self
+            .players()
+            .partition(|&player| perspective.can_see(player));
+        let visible: Vec<Card> = seen
             .iter()
-            .try_fold(game_state, |game_state, &action| {
-                let player = game_state.whose_turn();
-                game_state.apply_action((player, action))
+            .flat_map(|&player| self.hands[player].iter().copied())
+            .chain(self.discarded.iter().copied())
+            .chain(Some(self.top_card))
+            .collect();
+        let hands = unseen
+            .into_iter()
+            .flat_map(|player| self.hands[player].iter().copied());
+
+        match perspective {
+            Perspective::Omniscient => vec![],
+            _ => hands
+                .chain(self.draw_pile.iter().copied())
+                .filter(|card| !visible.contains(card))
+                .collect(),
+        }
+    }
+}
+
+impl Viewable for GameState {
+    type Player = Player;
+    type View = View;
+
+    /// Makes the view of the game for a perspective
+    /// ```
+    /// use lib_table_top::common::views::{Perspective, Viewable};
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Player::*, Settings, View};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    ///
+    /// assert_eq!(game.view(Perspective::Observer), View::Observer(game.observer_view()));
+    /// assert_eq!(game.view(Perspective::Player(P2)), View::Player(game.player_view(P2)));
+    /// ```
+    fn view(&self, perspective: Perspective<Player>) -> View {
+        match perspective {
+            Perspective::Observer => View::Observer(self.observer_view()),
+            Perspective::Player(player) => View::Player(self.player_view(player)),
+            Perspective::Omniscient => View::Omniscient(self.omniscient_view()),
+        }
+    }
+}
+
+impl Play for GameState {
+    type Action = (Player, Action);
+    type Player = Player;
+    type Status = Status;
+    type Error = ActionError;
+
+    fn apply_action(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<(Player, Action)> {
+        if self.status() != InProgress {
+            return Vec::new();
+        }
+
+        let player = self.whose_turn();
+        self.player_view(player)
+            .valid_actions()
+            .into_iter()
+            .map(|action| (player, action))
+            .collect()
+    }
+
+    fn players(&self) -> Vec<Player> {
+        self.players().collect()
+    }
+
+    fn whose_turn(&self) -> Player {
+        self.whose_turn()
+    }
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != InProgress
+    }
+
+    fn history(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = (Player, Action);
+    type Error = ActionError;
+
+    fn initial(&self) -> Self {
+        Self::new(self.game_history.settings.clone())
+    }
+
+    fn actions(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+
+    fn apply(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+}
+
+/// Hashes the omniscient view, so the hands and the order of the draw pile are covered
+impl StateHash for GameState {
+    fn state_hash(&self) -> u64 {
+        // Going through a `Value` sorts the keys of the view's maps, which are in a random order
+        let value = serde_json::to_value((self.settings(), self.omniscient_view()))
+            .expect("Views can always be serialized");
+        settings_hash(&value)
+    }
+}
+
+/// Timed out players draw when they can't play, otherwise they play the hint
+impl TimeoutHooks for GameState {
+    type Player = Player;
+    type Action = Action;
+
+    fn timeout_actions(&self, player: Player) -> Vec<Action> {
+        self.player_view(player).valid_actions()
+    }
+
+    fn default_action(&self, player: Player) -> Option<Action> {
+        self.player_view(player).suggest_action()
+    }
+}
+
+impl Summarize for GameState {
+    type Player = Player;
+
+    fn game_result(&self) -> Option<GameResult<Player>> {
+        let places = self.rankings()?;
+        let scores: BTreeMap<Player, u32> = match self.status() {
+            InProgress => return None,
+            MatchWin { scores, .. } | Blocked { scores } => scores,
+            Win { player } => {
+                // The winner scores the penalties for the cards left in everyone else's hands
+                let penalties = self.players().map(|other| self.penalty_points(other)).sum();
+                [(player, penalties)].iter().copied().collect()
+            }
+        };
+
+        let players = self
+            .players()
+            .map(|player| {
+                let place = places
+                    .iter()
+                    .find(|(placed, _)| placed == &player)
+                    .map(|&(_, place)| place);
+
+                PlayerResult {
+                    player,
+                    outcome: if place == Some(1) {
+                        Outcome::Win
+                    } else {
+                        Outcome::Loss
+                    },
+                    score: Some(i64::from(scores.get(&player).copied().unwrap_or_default())),
+                    place,
+                }
             })
+            .collect();
+
+        Some(GameResult {
+            kind: GameKind::CrazyEights,
+            settings_hash: settings_hash(self.settings()),
+            players,
+            move_count: self.history().count(),
+            duration: None,
+            termination: Termination::Completed,
+        })
     }
+}
 
-    fn history(&self) -> impl Iterator<Item = (Player, Action)> + '_ {
-        self.history
+/// Where a card goes in encodings, ordered by suit (`Suit::ALL`) then rank (`Rank::ALL`)
+fn card_index(Card(rank, suit): Card) -> usize {
+    suit as usize * 13 + (rank as usize - 1)
+}
+
+/// A vector of 169 numbers from the perspective of the player whose turn it is. Cards are
+/// ordered by suit (`Suit::ALL`) then rank (`Rank::ALL`)
+///
+/// - 0..52, the cards in the player's hand
+/// - 52..104, the cards in the discard pile under the top card
+/// - 104..156, the top card
+/// - 156..160, the current suit
+/// - 160..168, how many cards each player holds, starting with the player and going in turn
+///   order, zero for seats that aren't in the game
+/// - 168, how many cards are left in the draw pile
+/// ```
+/// use lib_table_top::common::ml::Encode;
+/// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+/// use lib_table_top::common::rand::RngSeed;
+/// use std::sync::Arc;
+///
+/// let game = GameState::new(Arc::new(Settings::new(RngSeed([1; 32]), NumberOfPlayers::Two)));
+/// let encoding = game.encode();
+///
+/// assert_eq!(encoding.shape, vec![169]);
+/// assert_eq!(encoding.values[..52].iter().sum::<f32>(), 7.0);
+/// assert_eq!(&encoding.values[160..164], &[7.0, 7.0, 0.0, 0.0]);
+/// assert_eq!(encoding.values[168], 37.0);
+/// ```
+impl Encode for GameState {
+    fn encode(&self) -> Encoding {
+        let mut encoding = Encoding::zeros(vec![169]);
+        let whose_turn = self.whose_turn();
+
+        for &card in &self.hands[whose_turn] {
+            encoding.set(&[card_index(card)], 1.0);
+        }
+        for &card in &self.discarded {
+            encoding.set(&[52 + card_index(card)], 1.0);
+        }
+        encoding.set(&[104 + card_index(self.top_card)], 1.0);
+        encoding.set(&[156 + self.current_suit as usize], 1.0);
+
+        let players: Vec<Player> = self.players().collect();
+        let seat = players
             .iter()
-            .zip(self.settings.number_of_players.players().cycle())
-            .map(|(&action, player)| (player, action))
+            .position(|&player| player == whose_turn)
+            .expect("It's always the turn of a player in the game");
+        for (offset, player) in players[seat..].iter().chain(&players[..seat]).enumerate() {
+            encoding.set(&[160 + offset], self.hands[*player].len() as f32);
+        }
+        encoding.set(&[168], self.draw_pile.len() as f32);
+
+        encoding
     }
+}
 
-    fn whose_turn(&self) -> Player {
-        let index = self.history.len() % (self.settings.number_of_players as usize);
-        [P1, P2, P3, P4, P5, P6, P7, P8][index]
+/// A versioned capture of the full state of a game, including the position of the random number
+/// generator, so a game can be resumed without replaying its history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum Snapshot {
+    #[serde(rename = "v1")]
+    V1 {
+        game_history: GameHistory,
+        rng_word_pos: u64,
+        discarded: Vector<Card>,
+        hands: HashMap<Player, Vec<Card>>,
+        draw_pile: Vector<Card>,
+        top_card: Card,
+        current_suit: Suit,
+        #[serde(default, skip_serializing_if = "Vector::is_empty")]
+        reshuffles: Vector<usize>,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        scores: HashMap<Player, u32>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        rounds_played: u32,
+        /// Who took each action and who plays next, left out when play has gone around the table
+        /// in seat order
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        turns: Option<(Vector<Player>, Player)>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        reversed: bool,
+        #[serde(default, skip_serializing_if = "is_default")]
+        pending_draws: u8,
+        #[serde(default, skip_serializing_if = "is_default")]
+        suit_history: SuitHistory,
+    },
+}
+
+impl GameState {
+    /// Captures the full state of the game, see [`Snapshot`](enum@Snapshot)
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{GameState, NumberOfPlayers, Settings};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    /// assert_eq!(GameState::from_snapshot(game.snapshot()), game);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::V1 {
+            game_history: self.game_history.clone(),
+            // Games only ever use a handful of words per shuffle, so this never truncates
+            rng_word_pos: self.rng.get_word_pos() as u64,
+            discarded: self.discarded.clone(),
+            hands: self
+                .players()
+                .map(|player| (player, self.hands[player].clone()))
+                .collect(),
+            draw_pile: self.draw_pile.clone(),
+            top_card: self.top_card,
+            current_suit: self.current_suit,
+            reshuffles: self.reshuffles.clone(),
+            scores: self
+                .players()
+                .filter(|&player| self.scores[player] > 0)
+                .map(|player| (player, self.scores[player]))
+                .collect(),
+            rounds_played: self.rounds_played,
+            turns: if self.turns_in_seat_order() {
+                None
+            } else {
+                Some((self.turns.clone(), self.whose_turn))
+            },
+            reversed: self.reversed,
+            pending_draws: self.pending_draws,
+            suit_history: self.suit_history.clone(),
+        }
+    }
+
+    fn turns_in_seat_order(&self) -> bool {
+        let mut seats = self.players().cycle();
+
+        self.turns
+            .iter()
+            .all(|&player| seats.next() == Some(player))
+            && seats.next() == Some(self.whose_turn)
+    }
+
+    /// Resumes a game from a [`Snapshot`](enum@Snapshot), the random number generator picks up
+    /// where it was when the snapshot was taken
+    pub fn from_snapshot(snapshot: Snapshot) -> Self {
+        match snapshot {
+            Snapshot::V1 {
+                game_history,
+                rng_word_pos,
+                discarded,
+                hands: snapshot_hands,
+                draw_pile,
+                top_card,
+                current_suit,
+                reshuffles,
+                scores: snapshot_scores,
+                rounds_played,
+                turns,
+                reversed,
+                pending_draws,
+                suit_history,
+            } => {
+                let mut rng = game_history.settings.seed.into_rng();
+                rng.set_word_pos(rng_word_pos.into());
+
+                let mut hands = enum_map! { _ => Vec::new() };
+                for (player, hand) in snapshot_hands {
+                    hands[player] = hand;
+                }
+
+                let mut scores = EnumMap::default();
+                for (player, score) in snapshot_scores {
+                    scores[player] = score;
+                }
+
+                let (turns, whose_turn) = turns.unwrap_or_else(|| {
+                    let mut seats = game_history.settings.number_of_players.players().cycle();
+                    let turns = (&mut seats).take(game_history.history.len()).collect();
+                    (turns, seats.next().expect("The seats go around forever"))
+                });
+
+                Self {
+                    game_history,
+                    rng: Arc::new(GameRng::from(rng)),
+                    discarded,
+                    hands,
+                    draw_pile,
+                    top_card,
+                    current_suit,
+                    reshuffles,
+                    scores,
+                    rounds_played,
+                    turns,
+                    whose_turn,
+                    reversed,
+                    pending_draws,
+                    suit_history,
+                }
+            }
+        }
+    }
+}
+
+/// A compact binary encoding of the observer view, for servers sending every turn to lots of
+/// spectators. The view is encoded once and the same bytes go to everyone, so the format leaves
+/// out what a host attaches per viewer: player info, clocks, and metadata
+pub mod broadcast {
+    use super::*;
+
+    /// The version of the format, the first byte of every broadcast
+    pub const VERSION: u8 = 1;
+
+    const PLAYERS: [Player; 8] = [P1, P2, P3, P4, P5, P6, P7, P8];
+
+    #[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum BroadcastError {
+        #[error("Broadcasts of version {:?} can't be decoded", version)]
+        UnsupportedVersion { version: u8 },
+        #[error("The broadcast ended early")]
+        Truncated,
+        #[error("The byte {:?} at {:?} isn't valid there", byte, offset)]
+        InvalidByte { offset: usize, byte: u8 },
+        #[error("There are {:?} bytes left over after the broadcast", extra)]
+        TrailingBytes { extra: usize },
+    }
+
+    use BroadcastError::*;
+
+    // Flags for the booleans, packed into one byte
+    const REVERSED: u8 = 1;
+    const SCORES: u8 = 1 << 1;
+    const VOLUNTARY_DRAWS: u8 = 1 << 2;
+    const STACKING_TWOS: u8 = 1 << 3;
+    const QUEENS_SKIP: u8 = 1 << 4;
+    const JACKS_REVERSE: u8 = 1 << 5;
+    const MATCH_TARGET: u8 = 1 << 6;
+
+    impl ObserverView {
+        /// Encodes the view for broadcasting. Cards take a byte each, numbered 0..52 by suit
+        /// (`Suit::ALL`) then rank (`Rank::ALL`), which is one less than their
+        /// [`GameState::action_index`](GameState::action_index) for `Play`. Counts are LEB128
+        /// varints, so a typical view is well under a hundred bytes
+        /// ```
+        /// use lib_table_top::common::rand::RngSeed;
+        /// use lib_table_top::games::crazy_eights::{
+        ///     Action::Play, GameState, NumberOfPlayers, ObserverView, Settings,
+        /// };
+        /// use std::sync::Arc;
+        ///
+        /// let settings = Settings::tournament(RngSeed([0; 32]), NumberOfPlayers::Four);
+        /// let mut game = GameState::new(Arc::new(settings));
+        /// for _ in 0..10 {
+        ///     let action = game.current_player_view().suggest_action().unwrap();
+        ///     game = game.apply_action((game.whose_turn(), action)).unwrap();
+        /// }
+        ///
+        /// let view = game.observer_view();
+        /// let bytes = view.broadcast_bytes();
+        /// assert!(bytes.len() < serde_json::to_vec(&view).unwrap().len() / 4);
+        /// // The top card follows the version, whose turn, and the four hand sizes
+        /// let top_card = GameState::action_index(Play(view.top_card)) - 1;
+        /// assert_eq!(bytes[7] as usize, top_card);
+        /// assert_eq!(ObserverView::from_broadcast_bytes(&bytes), Ok(view));
+        /// ```
+        pub fn broadcast_bytes(&self) -> Vec<u8> {
+            let players = &PLAYERS[..self.player_card_count.len()];
+            let rules = &self.rules;
+            let mut bytes = vec![VERSION, self.whose_turn as u8 - 1, players.len() as u8];
+
+            for player in players {
+                write_varint(&mut bytes, self.player_card_count[player] as u64);
+            }
+
+            bytes.push(card_index(self.top_card) as u8);
+            bytes.push(self.current_suit as u8);
+            bytes.push(self.draw_pile_remaining);
+            write_varint(&mut bytes, self.discarded.len() as u64);
+            bytes.extend(self.discarded.iter().map(|&card| card_index(card) as u8));
+            write_varint(&mut bytes, self.reshuffle_count as u64);
+
+            let flags = [
+                (REVERSED, self.reversed),
+                (SCORES, !self.scores.is_empty()),
+                (VOLUNTARY_DRAWS, rules.voluntary_draws),
+                (STACKING_TWOS, rules.stacking_twos),
+                (QUEENS_SKIP, rules.queens_skip),
+                (JACKS_REVERSE, rules.jacks_reverse),
+                (MATCH_TARGET, rules.match_target.is_some()),
+            ];
+            bytes.push(
+                flags
+                    .iter()
+                    .filter(|(_, set)| *set)
+                    .fold(0, |flags, (flag, _)| flags | flag),
+            );
+            bytes.push(self.pending_draws);
+
+            if !self.scores.is_empty() {
+                for player in players {
+                    let score = self.scores.get(player).copied().unwrap_or_default();
+                    write_varint(&mut bytes, score.into());
+                }
+            }
+
+            for suit in Suit::ALL.iter() {
+                write_varint(&mut bytes, self.suit_history.suit(*suit).into());
+            }
+            for rank in Rank::ALL.iter() {
+                write_varint(&mut bytes, self.suit_history.rank(*rank).into());
+            }
+
+            bytes.push(match rules.decks {
+                Decks::One => 0,
+                Decks::Two => 1,
+                Decks::TwoForSixOrMore => 2,
+            });
+            bytes.push(match rules.eight_on_eight {
+                EightOnEight::Redeclare => 0,
+                EightOnEight::KeepSuit => 1,
+                EightOnEight::Forbidden => 2,
+            });
+            match rules.draw {
+                DrawRule::DrawOne => bytes.push(0),
+                DrawRule::DrawUntilPlayable { max: None } => bytes.push(1),
+                DrawRule::DrawUntilPlayable { max: Some(max) } => bytes.extend(&[2, max]),
+            }
+            if let Some(target) = rules.match_target {
+                write_varint(&mut bytes, target.into());
+            }
+
+            bytes
+        }
+
+        /// Decodes a view from [`broadcast_bytes`](ObserverView::broadcast_bytes)
+        /// ```
+        /// use lib_table_top::games::crazy_eights::{broadcast::BroadcastError, ObserverView};
+        ///
+        /// assert_eq!(
+        ///     ObserverView::from_broadcast_bytes(&[9, 0]),
+        ///     Err(BroadcastError::UnsupportedVersion { version: 9 })
+        /// );
+        /// assert_eq!(ObserverView::from_broadcast_bytes(&[1, 0]), Err(BroadcastError::Truncated));
+        /// ```
+        pub fn from_broadcast_bytes(bytes: &[u8]) -> Result<Self, BroadcastError> {
+            let mut reader = Reader { bytes, offset: 0 };
+
+            match reader.byte()? {
+                VERSION => {}
+                version => return Err(UnsupportedVersion { version }),
+            }
+
+            let whose_turn_offset = reader.offset;
+            let whose_turn = reader.parse(|byte| PLAYERS.get(byte as usize).copied())?;
+            let players = match reader.byte()? {
+                count @ 2..=8 => &PLAYERS[..count as usize],
+                byte => return Err(reader.invalid(byte)),
+            };
+            if !players.contains(&whose_turn) {
+                return Err(InvalidByte {
+                    offset: whose_turn_offset,
+                    byte: bytes[whose_turn_offset],
+                });
+            }
+
+            let mut player_card_count = HashMap::new();
+            for &player in players {
+                player_card_count.insert(player, reader.varint()? as usize);
+            }
+
+            let top_card = reader.parse(card_from_index)?;
+            let current_suit = reader.parse(|byte| Suit::ALL.get(byte as usize).copied())?;
+            let draw_pile_remaining = reader.byte()?;
+            let discarded = (0..reader.varint()?)
+                .map(|_| reader.parse(card_from_index))
+                .collect::<Result<Vector<Card>, _>>()?;
+            let reshuffle_count = reader.varint()? as usize;
+            let flags = reader.byte()?;
+            let pending_draws = reader.byte()?;
+
+            let mut scores = HashMap::new();
+            if flags & SCORES != 0 {
+                for &player in players {
+                    scores.insert(player, reader.varint()? as u32);
+                }
+            }
+
+            let mut suit_history = SuitHistory::default();
+            for &suit in Suit::ALL.iter() {
+                match reader.varint()? {
+                    0 => {}
+                    count => {
+                        suit_history.suits.insert(suit, count as u32);
+                    }
+                }
+            }
+            for &rank in Rank::ALL.iter() {
+                match reader.varint()? {
+                    0 => {}
+                    count => suit_history.ranks.push((rank, count as u32)),
+                }
+            }
+
+            let decks = reader.parse(|byte| match byte {
+                0 => Some(Decks::One),
+                1 => Some(Decks::Two),
+                2 => Some(Decks::TwoForSixOrMore),
+                _ => None,
+            })?;
+            let eight_on_eight = reader.parse(|byte| match byte {
+                0 => Some(EightOnEight::Redeclare),
+                1 => Some(EightOnEight::KeepSuit),
+                2 => Some(EightOnEight::Forbidden),
+                _ => None,
+            })?;
+            let draw = match reader.byte()? {
+                0 => DrawRule::DrawOne,
+                1 => DrawRule::DrawUntilPlayable { max: None },
+                2 => DrawRule::DrawUntilPlayable {
+                    max: Some(reader.byte()?),
+                },
+                byte => return Err(reader.invalid(byte)),
+            };
+            let match_target = if flags & MATCH_TARGET != 0 {
+                Some(reader.varint()? as u32)
+            } else {
+                None
+            };
+
+            if reader.offset < bytes.len() {
+                return Err(TrailingBytes {
+                    extra: bytes.len() - reader.offset,
+                });
+            }
+
+            Ok(ObserverView {
+                whose_turn,
+                current_suit,
+                discarded,
+                top_card,
+                player_card_count,
+                draw_pile_remaining,
+                reshuffle_count,
+                scores,
+                reversed: flags & REVERSED != 0,
+                pending_draws,
+                suit_history,
+                rules: Rules {
+                    decks,
+                    voluntary_draws: flags & VOLUNTARY_DRAWS != 0,
+                    draw,
+                    match_target,
+                    stacking_twos: flags & STACKING_TWOS != 0,
+                    queens_skip: flags & QUEENS_SKIP != 0,
+                    jacks_reverse: flags & JACKS_REVERSE != 0,
+                    eight_on_eight,
+                },
+                player_info: HashMap::new(),
+                deadline: None,
+                time_remaining: None,
+                metadata: Map::new(),
+            })
+        }
+    }
+
+    fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+    }
+
+    fn card_from_index(index: u8) -> Option<Card> {
+        let index = usize::from(index);
+        if index < 52 {
+            Some(Card(Rank::ALL[index % 13], Suit::ALL[index / 13]))
+        } else {
+            None
+        }
+    }
+
+    struct Reader<'a> {
+        bytes: &'a [u8],
+        offset: usize,
+    }
+
+    impl Reader<'_> {
+        fn byte(&mut self) -> Result<u8, BroadcastError> {
+            let byte = *self.bytes.get(self.offset).ok_or(Truncated)?;
+            self.offset += 1;
+            Ok(byte)
+        }
+
+        fn parse<T>(&mut self, parse: impl Fn(u8) -> Option<T>) -> Result<T, BroadcastError> {
+            let byte = self.byte()?;
+            parse(byte).ok_or_else(|| self.invalid(byte))
+        }
+
+        /// The error for the byte that was just read
+        fn invalid(&self, byte: u8) -> BroadcastError {
+            InvalidByte {
+                offset: self.offset - 1,
+                byte,
+            }
+        }
+
+        fn varint(&mut self) -> Result<u64, BroadcastError> {
+            let mut value = 0;
+            for shift in (0..64).step_by(7) {
+                let byte = self.byte()?;
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+            }
+            Err(self.invalid(self.bytes[self.offset - 1]))
+        }
+    }
+}
+
+pub mod stats {
+    use super::*;
+    use crate::common::stats::{Collector, Tally};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    /// How many cards each game had players draw, and how many times the discard pile was
+    /// reshuffled into the draw pile. A single draw can take several cards and reshuffle along the
+    /// way, when twos are stacked or players draw until they can play
+    /// ```
+    /// use lib_table_top::common::stats::Stats;
+    /// use lib_table_top::games::crazy_eights::{stats::DrawsAndReshuffles, GameState, Settings};
+    /// use lib_table_top::games::crazy_eights::{Action::Draw, NumberOfPlayers, Player::*};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use std::sync::Arc;
+    ///
+    /// let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    /// let game = GameState::new(Arc::new(settings));
+    ///
+    /// let mut stats = Stats::new().with(DrawsAndReshuffles::default());
+    /// stats.observe(&game).unwrap();
+    /// assert_eq!(stats.report()["draws_and_reshuffles"]["draws"]["total"], 0);
+    /// ```
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct DrawsAndReshuffles {
+        draws: u64,
+        reshuffles: u64,
+        draws_per_game: Tally,
+        reshuffles_per_game: Tally,
+    }
+
+    impl Collector<GameState> for DrawsAndReshuffles {
+        fn name(&self) -> &'static str {
+            "draws_and_reshuffles"
+        }
+
+        fn record_action(
+            &mut self,
+            before: &GameState,
+            &(player, action): &(Player, Action),
+            after: &GameState,
+            _: Option<Duration>,
+        ) {
+            if let Draw = action {
+                let hand = |game: &GameState| game.player_hand(player).len() as u64;
+                self.draws += hand(after) - hand(before);
+            }
+
+            self.reshuffles += (after.reshuffle_count() - before.reshuffle_count()) as u64;
+        }
+
+        fn record_game(&mut self, _: &GameState) {
+            self.draws_per_game = self.draws_per_game.record(self.draws);
+            self.reshuffles_per_game = self.reshuffles_per_game.record(self.reshuffles);
+            self.draws = 0;
+            self.reshuffles = 0;
+        }
+
+        fn report(&self) -> Value {
+            json!({
+                "draws": self.draws_per_game.report(),
+                "reshuffles": self.reshuffles_per_game.report(),
+            })
+        }
+    }
+}
+
+/// Entry points for fuzzing the engine with untrusted input
+pub mod fuzz {
+    use super::*;
+
+    /// Decodes arbitrary bytes into settings and actions, then applies the actions to a new game,
+    /// skipping any that the engine rejects. The first 32 bytes are the seed, the 33rd is the
+    /// number of players, the 34th is the deal, and the next three are the rules, so every house
+    /// rule gets fuzzed. A deal that can't be used with the rules falls back to the standard one.
+    /// Every 4 bytes after that are decoded into a player and an action. Returns `None` if there
+    /// aren't enough bytes to decode the settings.
+    ///
+    /// This never panics unless there is a bug in the engine, including the game history failing
+    /// to rebuild the resulting game
+    /// ```
+    /// use lib_table_top::games::crazy_eights::{fuzz::apply_raw, DrawRule};
+    ///
+    /// assert!(apply_raw(&[]).is_none());
+    /// assert!(apply_raw(&[1; 36]).is_none());
+    ///
+    /// let game = apply_raw(&[1; 100]).unwrap();
+    /// assert_eq!(game.players().count(), 3);
+    /// assert_eq!(game.settings().deal().cards_per_player, Some(1));
+    /// assert_eq!(game.settings().rules().draw, DrawRule::DrawUntilPlayable { max: None });
+    /// ```
+    pub fn apply_raw(bytes: &[u8]) -> Option<GameState> {
+        if bytes.len() < 37 {
+            return None;
+        }
+
+        let (settings_bytes, action_bytes) = bytes.split_at(37);
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&settings_bytes[..32]);
+        let seed = RngSeed(seed);
+        let number_of_players = decode_number_of_players(settings_bytes[32]);
+        let deal = decode_deal(settings_bytes[33]);
+        let rules = decode_rules(settings_bytes[34], settings_bytes[35], settings_bytes[36]);
+
+        let settings = Settings::with_deal_or_standard(seed, number_of_players, deal, rules);
+
+        let game = GameState::new(Arc::new(settings));
+        let game = action_bytes.chunks_exact(4).fold(game, |game, chunk| {
+            let player = decode_player(chunk[0]);
+            let action = decode_action(chunk[1], chunk[2], chunk[3]);
+            game.apply_action((player, action)).unwrap_or(game)
+        });
+
+        game.status();
+        game.current_player_view().valid_actions();
+        assert_eq!(game.game_history().game_state().as_ref(), Ok(&game));
+
+        Some(game)
+    }
+
+    /// The low four bits are the cards per player, with 0 for the standard deal, and the high
+    /// four pick the starter eight rule
+    fn decode_deal(byte: u8) -> DealRules {
+        use StarterEight::*;
+
+        DealRules {
+            cards_per_player: Some(byte & 0x0f).filter(|&cards| cards > 0),
+            starter_eight: [Play, Bury, DealerPicksSuit][(byte >> 4) as usize % 3],
+        }
+    }
+
+    /// The first byte holds the decks, the eight on eight rule, and a bit for each of the other
+    /// house rules. The second is the draw rule, and the third is the match target in tens, with
+    /// 0 for a single round
+    fn decode_rules(flags: u8, draw: u8, match_target: u8) -> Rules {
+        use EightOnEight::*;
+
+        Rules {
+            decks: [Decks::One, Decks::Two, Decks::TwoForSixOrMore][(flags & 0b11) as usize % 3],
+            voluntary_draws: flags & 0b100 != 0,
+            stacking_twos: flags & 0b1000 != 0,
+            queens_skip: flags & 0b1_0000 != 0,
+            jacks_reverse: flags & 0b10_0000 != 0,
+            eight_on_eight: [Redeclare, KeepSuit, Forbidden][(flags >> 6) as usize % 3],
+            draw: match draw % 4 {
+                0 => DrawRule::DrawOne,
+                1 => DrawRule::DrawUntilPlayable { max: None },
+                _ => DrawRule::DrawUntilPlayable {
+                    max: Some(draw >> 2),
+                },
+            },
+            match_target: Some(u32::from(match_target) * 10).filter(|&target| target > 0),
+        }
+    }
+
+    fn decode_number_of_players(byte: u8) -> NumberOfPlayers {
+        use NumberOfPlayers::*;
+
+        [Two, Three, Four, Five, Six, Seven, Eight][(byte % 7) as usize]
+    }
+
+    fn decode_player(byte: u8) -> Player {
+        [P1, P2, P3, P4, P5, P6, P7, P8][(byte % 8) as usize]
+    }
+
+    fn decode_card(byte: u8) -> Card {
+        Card(
+            Rank::ALL[(byte % 13) as usize],
+            Suit::ALL[((byte / 13) % 4) as usize],
+        )
+    }
+
+    fn decode_action(kind: u8, card: u8, suit: u8) -> Action {
+        match kind % 4 {
+            0 => Draw,
+            1 => Play(decode_card(card)),
+            2 => PlayEight(decode_card(card), Suit::ALL[(suit % 4) as usize]),
+            _ => Pass,
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for NumberOfPlayers {
+    type Parameters = ();
+    type Strategy = proptest::sample::Select<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use NumberOfPlayers::*;
+
+        proptest::sample::select(vec![Two, Three, Four, Five, Six, Seven, Eight])
+    }
+}
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for DealRules {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use StarterEight::*;
+
+        (
+            proptest::option::of(1..=12u8),
+            proptest::sample::select(vec![Play, Bury, DealerPicksSuit]),
+        )
+            .prop_map(|(cards_per_player, starter_eight)| DealRules {
+                cards_per_player,
+                starter_eight,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Rules {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use EightOnEight::*;
+
+        let draw = prop_oneof![
+            Just(DrawRule::DrawOne),
+            proptest::option::of(1..=5u8).prop_map(|max| DrawRule::DrawUntilPlayable { max }),
+        ];
+
+        (
+            proptest::sample::select(vec![Decks::One, Decks::Two, Decks::TwoForSixOrMore]),
+            any::<bool>(),
+            draw,
+            proptest::option::of(20..=200u32),
+            any::<[bool; 3]>(),
+            proptest::sample::select(vec![Redeclare, KeepSuit, Forbidden]),
+        )
+            .prop_map(
+                |(decks, voluntary_draws, draw, match_target, special_cards, eight_on_eight)| {
+                    let [stacking_twos, queens_skip, jacks_reverse] = special_cards;
+                    Rules {
+                        decks,
+                        voluntary_draws,
+                        draw,
+                        match_target,
+                        stacking_twos,
+                        queens_skip,
+                        jacks_reverse,
+                        eight_on_eight,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+/// Settings with arbitrary deals and house rules. A deal that can't be used with the rules, like
+/// too many cards for the players, falls back to the standard deal so the rules are still covered
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Settings {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        (
+            any::<RngSeed>(),
+            any::<NumberOfPlayers>(),
+            any::<DealRules>(),
+            any::<Rules>(),
+        )
+            .prop_map(|(seed, number_of_players, deal, rules)| {
+                Settings::with_deal_or_standard(seed, number_of_players, deal, rules)
+            })
+            .boxed()
+    }
+}
+
+/// Generates games from arbitrary settings by playing up to 200 random valid actions, stopping
+/// early if the game is over
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for GameState {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use proptest::sample::Index;
+
+        (
+            any::<Settings>(),
+            proptest::collection::vec(any::<Index>(), 0..200),
+        )
+            .prop_map(|(settings, choices)| {
+                choices
+                    .iter()
+                    .fold(GameState::new(Arc::new(settings)), |game, choice| {
+                        if game.status() != InProgress {
+                            return game;
+                        }
+
+                        let actions = game.current_player_view().valid_actions();
+                        let action = *choice.get(&actions);
+                        game.apply_action((game.whose_turn(), action)).unwrap()
+                    })
+            })
+            .boxed()
     }
 }
@@ -1,10 +1,30 @@
 use enum_map::EnumMap;
 use im::Vector;
+use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use serde_repr::*;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
+use crate::common::clock::{ShowsClock, TimeoutHooks};
+use crate::common::coords::{self, CoordsError};
+use crate::common::game::Play;
+use crate::common::info::GameInfo;
+use crate::common::ml::{Encode, Encoding};
+use crate::common::rand::RngSeed;
+use crate::common::replay::Replayable;
+use crate::common::results::{
+    settings_hash, winner_takes_all, GameKind, GameResult, Outcome, PlayerResult, Summarize,
+    Termination,
+};
+use crate::common::search::{Searchable, WIN};
+use crate::common::settings::{Builder, FromSettings};
+use crate::common::turn::PendingInput;
+use crate::common::verify::StateHash;
+use crate::common::views::{Perspective, ViewMetadata, Viewable};
+
 /// A row value inside of a position (y coordinate)
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Row(pub u8);
@@ -16,6 +36,17 @@ pub struct Col(pub u8);
 /// A position on the board denoted in column, then row (x, y)
 pub type Position = (Col, Row);
 
+/// The label of a position, like "a1" for `(Col(0), Row(0))`, see
+/// [`coords::label`](fn@coords::label)
+/// ```
+/// use lib_table_top::games::marooned::{position_label, Col, Row};
+///
+/// assert_eq!(position_label((Col(2), Row(6))), "c7");
+/// ```
+pub fn position_label((Col(col), Row(row)): Position) -> String {
+    coords::label(col, row)
+}
+
 /// Players 1 and 2
 #[derive(
     Copy, Clone, Debug, Enum, PartialEq, Eq, PartialOrd, Ord, Serialize_repr, Deserialize_repr,
@@ -64,10 +95,57 @@ pub enum SettingsError {
     /// A player can't start on a removed square
     #[error("Can't start player {:?} on removed position {:?}", player, position)]
     PlayerCantStartOnRemovedSquare { player: Player, position: Position },
+    /// A position has to be allowed to repeat at least once before the game is drawn
+    #[error("Max repetitions must be at least 2, got {:?}", max_repetitions)]
+    MaxRepetitionsTooLow { max_repetitions: u8 },
 }
 
 use SettingsError::*;
 
+impl SettingsError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::marooned::SettingsError::*;
+    ///
+    /// assert_eq!(InvalidDimensions.code(), "invalid_dimensions");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            InvalidDimensions => "invalid_dimensions",
+            CantRemovePositionNotOnBoard { .. } => "cant_remove_position_not_on_board",
+            PlayersCantStartAtSamePosition => "players_cant_start_at_same_position",
+            PlayersMustStartOnBoard { .. } => "players_must_start_on_board",
+            PlayerCantStartOnRemovedSquare { .. } => "player_cant_start_on_removed_square",
+            MaxRepetitionsTooLow { .. } => "max_repetitions_too_low",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    /// ```
+    /// use lib_table_top::games::marooned::{SettingsError::*, Col, Row, Player::*};
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(InvalidDimensions.details(), json!({}));
+    /// assert_eq!(
+    ///   PlayersMustStartOnBoard { player: P1, position: (Col(9), Row(9)) }.details(),
+    ///   json!({"player": 1, "position": [9, 9]})
+    /// );
+    /// ```
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            InvalidDimensions | PlayersCantStartAtSamePosition => json!({}),
+            CantRemovePositionNotOnBoard { pos } => json!({ "pos": pos }),
+            PlayersMustStartOnBoard { player, position }
+            | PlayerCantStartOnRemovedSquare { player, position } => {
+                json!({ "player": player, "position": position })
+            }
+            MaxRepetitionsTooLow { max_repetitions } => {
+                json!({ "max_repetitions": max_repetitions })
+            }
+        }
+    }
+}
+
 /// Representation of the dimensions of the game board
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Dimensions {
@@ -113,6 +191,18 @@ impl Dimensions {
         iproduct!(0..self.cols, 0..self.rows).map(|(col, row)| (Col(col), Row(row)))
     }
 
+    /// Reads a position from its label, see [`position_label`](fn@position_label)
+    /// ```
+    /// use lib_table_top::games::marooned::{Col, Dimensions, Row};
+    ///
+    /// let dimensions = Dimensions { rows: 8, cols: 6 };
+    /// assert_eq!(dimensions.parse_position("c7"), Ok((Col(2), Row(6))));
+    /// assert!(dimensions.parse_position("g1").is_err());
+    /// ```
+    pub fn parse_position(&self, label: &str) -> Result<Position, CoordsError> {
+        coords::parse_label(label, self.cols, self.rows).map(|(col, row)| (Col(col), Row(row)))
+    }
+
     /// Returns whether a position is on the board
     /// ```
     /// use lib_table_top::games::marooned::{Dimensions, Col, Row};
@@ -179,6 +269,76 @@ impl Dimensions {
         }
     }
 
+    /// The square opposite a position, turning the board half way around
+    fn opposite(&self, (Col(col), Row(row)): Position) -> Position {
+        (Col(self.cols - 1 - col), Row(self.rows - 1 - row))
+    }
+
+    /// Random starting positions on opposite squares, `None` if the board is too small for both
+    /// players to have a move, along with opposite pairs of squares to remove that aren't next
+    /// to either player
+    fn random_starts(
+        &self,
+        removed: &[Position],
+        seed: RngSeed,
+    ) -> (Option<EnumMap<Player, Position>>, Vec<Position>) {
+        let mut rng = seed.into_rng();
+        let is_open = |position: Position| !removed.contains(&position);
+
+        let starts: Vec<Position> = self
+            .all_positions()
+            .filter(|&position| {
+                let opposite = self.opposite(position);
+                let has_moves = |from: Position, other: Position| {
+                    self.adjacenct_positions(from)
+                        .any(|to| to != other && is_open(to))
+                };
+
+                opposite != position
+                    && is_open(position)
+                    && is_open(opposite)
+                    && has_moves(position, opposite)
+                    && has_moves(opposite, position)
+            })
+            .collect();
+
+        let p1_starting = match starts.choose(&mut rng) {
+            Some(&position) => position,
+            None => return (None, Vec::new()),
+        };
+        let p2_starting = self.opposite(p1_starting);
+        let near_start = |position: Position| {
+            [p1_starting, p2_starting].iter().any(|&start| {
+                position == start || self.adjacenct_positions(start).any(|near| near == position)
+            })
+        };
+
+        let mut candidates: Vec<Position> = self
+            .all_positions()
+            .filter(|&position| is_open(position) && !near_start(position))
+            .collect();
+        candidates.shuffle(&mut rng);
+
+        // About one square in eight, leaving plenty of room to move
+        let target = usize::from(self.rows) * usize::from(self.cols) / 8;
+        let mut to_remove = Vec::new();
+
+        for position in candidates {
+            if to_remove.len() + 2 > target {
+                break;
+            }
+
+            let opposite = self.opposite(position);
+            if opposite != position && is_open(opposite) && !to_remove.contains(&position) {
+                to_remove.push(position);
+                to_remove.push(opposite);
+            }
+        }
+
+        let starting = enum_map! { P1 => p1_starting, P2 => p2_starting };
+        (Some(starting), to_remove)
+    }
+
     fn checked_adjacent(starting_offset: u8, max: u8) -> impl Iterator<Item = u8> + Clone {
         let vals = vec![
             starting_offset.checked_add(1),
@@ -198,12 +358,71 @@ impl Default for Dimensions {
     }
 }
 
+/// The description of the game for lobbies, see [`GameInfo`](struct@GameInfo)
+pub const INFO: GameInfo = GameInfo {
+    kind: GameKind::Marooned,
+    name: "Marooned",
+    rules: "Each turn a player moves to an adjacent square and then removes a square from the \
+            board, the first player left without a square to move to loses",
+    min_players: 2,
+    max_players: 2,
+    estimated_duration: Duration::from_secs(5 * 60),
+    settings_schema_json: r##"{
+        "type": "object",
+        "required": ["dimensions", "p1_starting", "p2_starting", "starting_removed"],
+        "definitions": {
+            "position": {
+                "type": "array",
+                "description": "A column and a row, counting from 0",
+                "items": {"type": "integer", "minimum": 0, "maximum": 255},
+                "minItems": 2,
+                "maxItems": 2
+            }
+        },
+        "properties": {
+            "dimensions": {
+                "type": "object",
+                "required": ["rows", "cols"],
+                "properties": {
+                    "rows": {"type": "integer", "minimum": 1, "maximum": 255},
+                    "cols": {"type": "integer", "minimum": 1, "maximum": 255}
+                }
+            },
+            "p1_starting": {"$ref": "#/definitions/position"},
+            "p2_starting": {"$ref": "#/definitions/position"},
+            "starting_removed": {"type": "array", "items": {"$ref": "#/definitions/position"}},
+            "max_repetitions": {"type": "integer", "minimum": 2, "maximum": 255},
+            "move_cap": {"type": "integer", "minimum": 0, "maximum": 65535},
+            "first_to_move": {"enum": [1, 2], "default": 1}
+        }
+    }"##,
+};
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Settings {
     pub dimensions: Dimensions,
     pub p1_starting: Position,
     pub p2_starting: Position,
     pub starting_removed: Vec<Position>,
+    /// The game is drawn once the players are back where they were, with the same player to
+    /// move, this many times
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repetitions: Option<u8>,
+    /// The game is drawn once this many moves have been made without a winner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_cap: Option<u16>,
+    /// The player who makes the first move, P1 unless the game starts from a
+    /// [`Scenario`](struct@scenario::Scenario)
+    #[serde(default = "first_to_move", skip_serializing_if = "is_first_to_move")]
+    pub first_to_move: Player,
+}
+
+fn first_to_move() -> Player {
+    P1
+}
+
+fn is_first_to_move(player: &Player) -> bool {
+    *player == first_to_move()
 }
 
 /// Tools to build Marooned games
@@ -229,6 +448,31 @@ pub struct Settings {
 ///
 /// let game: GameState = Default::default();
 /// ```
+///
+/// Players who circle each other forever can be stopped with the optional draw rules
+/// ```
+/// use lib_table_top::games::marooned::SettingsBuilder;
+///
+/// let settings = SettingsBuilder::new()
+///    .max_repetitions(3)
+///    .move_cap(40)
+///    .build()
+///    .unwrap();
+///
+/// assert_eq!(settings.max_repetitions, Some(3));
+/// assert_eq!(settings.move_cap, Some(40));
+/// ```
+///
+/// Starting positions can be picked at random, so repeated games don't always start the same
+/// way, see [`randomize_starts`](SettingsBuilder::randomize_starts)
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::marooned::SettingsBuilder;
+///
+/// let settings = SettingsBuilder::new().randomize_starts(RngSeed([7; 32])).build().unwrap();
+/// let again = SettingsBuilder::new().randomize_starts(RngSeed([7; 32])).build().unwrap();
+/// assert_eq!(settings, again);
+/// ```
 #[derive(Clone, Debug)]
 pub struct SettingsBuilder {
     rows: u8,
@@ -236,6 +480,10 @@ pub struct SettingsBuilder {
     p1_starting: Option<Position>,
     p2_starting: Option<Position>,
     starting_removed: Vec<Position>,
+    max_repetitions: Option<u8>,
+    move_cap: Option<u16>,
+    first_to_move: Player,
+    randomize_starts: Option<RngSeed>,
 }
 
 impl Default for SettingsBuilder {
@@ -247,6 +495,10 @@ impl Default for SettingsBuilder {
             p1_starting: None,
             p2_starting: None,
             starting_removed: Default::default(),
+            max_repetitions: None,
+            move_cap: None,
+            first_to_move: first_to_move(),
+            randomize_starts: None,
         }
     }
 }
@@ -256,43 +508,75 @@ impl SettingsBuilder {
         Default::default()
     }
 
-    pub fn rows(mut self, rows: u8) -> Self {
-        self.rows = rows;
-        self
-    }
-    pub fn cols(mut self, cols: u8) -> Self {
-        self.cols = cols;
-        self
+    settings_setters! {
+        rows: u8,
+        cols: u8,
+        starting_removed: Vec<Position>,
+        p1_starting: Position,
+        p2_starting: Position,
+        max_repetitions: u8,
+        move_cap: u16,
+        first_to_move: Player,
     }
 
-    pub fn starting_removed(mut self, positions: Vec<Position>) -> Self {
-        self.starting_removed = positions;
+    /// Picks the starting positions and a few squares to remove at random from the seed,
+    /// replacing `p1_starting` and `p2_starting`. P2 starts on the square opposite P1, and the
+    /// removed squares come in opposite pairs, so neither player is favored by the layout. Both
+    /// players always have somewhere to move, and squares removed with `starting_removed` stay
+    /// removed
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::marooned::{Col, Row, SettingsBuilder};
+    ///
+    /// let settings = SettingsBuilder::new()
+    ///   .starting_removed(vec![(Col(0), Row(0))])
+    ///   .randomize_starts(RngSeed([1; 32]))
+    ///   .build()
+    ///   .unwrap();
+    ///
+    /// let (Col(col), Row(row)) = settings.p1_starting;
+    /// assert_eq!(settings.p2_starting, (Col(5 - col), Row(7 - row)));
+    /// assert!(settings.starting_removed.contains(&(Col(0), Row(0))));
+    /// assert_eq!(settings.starting_removed.len(), 7);
+    /// ```
+    pub fn randomize_starts(mut self, seed: RngSeed) -> Self {
+        self.randomize_starts = Some(seed);
         self
     }
 
-    pub fn p1_starting(mut self, pos: Position) -> Self {
-        self.p1_starting = Some(pos);
-        self
+    pub fn build(self) -> Result<Settings, SettingsError> {
+        Builder::build(self)
     }
 
-    pub fn p2_starting(mut self, pos: Position) -> Self {
-        self.p2_starting = Some(pos);
-        self
+    pub fn build_game(self) -> Result<GameState, SettingsError> {
+        Builder::build_game(self)
     }
+}
 
-    pub fn build(self) -> Result<Settings, SettingsError> {
-        Settings::new(self)
-    }
+impl Builder for SettingsBuilder {
+    type Settings = Settings;
+    type Error = SettingsError;
 
-    pub fn build_game(self) -> Result<GameState, SettingsError> {
-        self.build()
-            .map(|settings| GameState::new(Arc::new(settings)))
+    fn build(self) -> Result<Settings, SettingsError> {
+        Settings::new(self)
     }
 }
 
 impl Settings {
-    pub fn new(builder: SettingsBuilder) -> Result<Self, SettingsError> {
+    pub fn new(mut builder: SettingsBuilder) -> Result<Self, SettingsError> {
         let dimensions = Dimensions::new(builder.rows, builder.cols)?;
+
+        if let Some(seed) = builder.randomize_starts {
+            let (starting, removed) = dimensions.random_starts(&builder.starting_removed, seed);
+            builder.p1_starting = starting
+                .map(|starting| starting[P1])
+                .or(builder.p1_starting);
+            builder.p2_starting = starting
+                .map(|starting| starting[P2])
+                .or(builder.p2_starting);
+            builder.starting_removed.extend(removed);
+        }
+
         let default_starting = dimensions.default_player_starting_positions();
         let p1_starting = builder.p1_starting.unwrap_or(default_starting[P1]);
         let p2_starting = builder.p2_starting.unwrap_or(default_starting[P2]);
@@ -320,15 +604,60 @@ impl Settings {
             return Err(PlayersCantStartAtSamePosition);
         }
 
+        if let Some(max_repetitions) = builder.max_repetitions.filter(|&max| max < 2) {
+            return Err(MaxRepetitionsTooLow { max_repetitions });
+        }
+
         Ok(Self {
             dimensions,
             p1_starting,
             p2_starting,
             starting_removed,
+            max_repetitions: builder.max_repetitions,
+            move_cap: builder.move_cap,
+            first_to_move: builder.first_to_move,
         })
     }
 }
 
+/// Curated games for hosts to offer as difficulty tiers
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Preset {
+    /// A 5 row, 4 col board for quick games
+    Small,
+    /// The default 8 row, 6 col board
+    Standard,
+    /// A 10 row, 8 col board where repeating a position three times is a draw, so the longer
+    /// games still end
+    Large,
+}
+
+impl Preset {
+    pub const ALL: [Self; 3] = [Preset::Small, Preset::Standard, Preset::Large];
+}
+
+impl Settings {
+    /// The settings for a preset
+    /// ```
+    /// use lib_table_top::games::marooned::{Dimensions, Preset, Settings};
+    ///
+    /// assert_eq!(Settings::preset(Preset::Standard), Settings::default());
+    /// assert_eq!(Settings::preset(Preset::Small).dimensions, Dimensions { rows: 5, cols: 4 });
+    /// assert_eq!(Settings::preset(Preset::Large).max_repetitions, Some(3));
+    /// ```
+    pub fn preset(preset: Preset) -> Self {
+        let builder = SettingsBuilder::new();
+
+        let builder = match preset {
+            Preset::Small => builder.rows(5).cols(4),
+            Preset::Standard => return Default::default(),
+            Preset::Large => builder.rows(10).cols(8).max_repetitions(3),
+        };
+
+        builder.build().expect("Presets are always valid")
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -336,6 +665,9 @@ impl Default for Settings {
             p1_starting: (Col(2), Row(0)),
             p2_starting: (Col(3), Row(7)),
             starting_removed: Default::default(),
+            max_repetitions: None,
+            move_cap: None,
+            first_to_move: first_to_move(),
         }
     }
 }
@@ -349,16 +681,58 @@ pub struct Action {
 }
 
 /// The current status of the game
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Status {
     /// The game is still in progress
     InProgress,
     /// The game is over, no more actions can be taken on this game
     Win { player: Player },
+    /// The game hit one of the optional draw rules, no more actions can be taken on this game
+    Draw,
 }
 
 use Status::*;
 
+/// Everything a spectator can see, without the full history of the game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObserverView {
+    pub dimensions: Dimensions,
+    pub p1: Position,
+    pub p2: Position,
+    /// Every removed position, including the ones removed before the game started
+    pub removed: Vec<Position>,
+    pub whose_turn: Player,
+    pub last_action: Option<Action>,
+    pub status: Status,
+    /// When the player to move runs out of time, empty unless added with
+    /// [`with_clock`](crate::common::clock::ShowsClock::with_clock)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<SystemTime>,
+    /// How long the player to move had left when the view was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remaining: Option<Duration>,
+    /// Data the host attached to the view, see [`ViewMetadata`](crate::common::views::ViewMetadata)
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+}
+
+impl ViewMetadata for ObserverView {
+    fn metadata(&self) -> &Map<String, Value> {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.metadata
+    }
+}
+
+impl ShowsClock for ObserverView {
+    fn set_countdown(&mut self, deadline: SystemTime, time_remaining: Duration) {
+        self.deadline = Some(deadline);
+        self.time_remaining = Some(time_remaining);
+    }
+}
+
 /// The game state
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameState {
@@ -366,6 +740,14 @@ pub struct GameState {
     history: Vector<Action>,
 }
 
+impl FromSettings for GameState {
+    type Settings = Settings;
+
+    fn from_settings(settings: Arc<Settings>) -> Self {
+        Self::new(settings)
+    }
+}
+
 impl GameState {
     /// Makes a new game, you're better off using [`SettingsBuilder`](struct@SettingsBuilder) to
     /// construct a new game
@@ -394,7 +776,12 @@ impl GameState {
     ///
     /// // A game with no more available spaces to move for the current player is over
     /// let game = SettingsBuilder::new().rows(1).cols(2).build_game().unwrap();
-    /// assert_eq!(game.status(), Status::Win { player: P2 })
+    /// assert_eq!(game.status(), Status::Win { player: P2 });
+    ///
+    /// // A game that reaches the move cap without a winner is drawn
+    /// let game = SettingsBuilder::new().move_cap(1).build_game().unwrap();
+    /// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+    /// assert_eq!(game.status(), Status::Draw);
     /// ```
     pub fn status(&self) -> Status {
         let current_player = self.whose_turn();
@@ -407,12 +794,51 @@ impl GameState {
             Win {
                 player: current_player.opponent(),
             }
+        } else if self.is_drawn() {
+            Draw
         } else {
             InProgress
         }
     }
 
-    /// Returns the player who's turn it currently is. All games start with P1
+    fn is_drawn(&self) -> bool {
+        if let Some(move_cap) = self.settings.move_cap {
+            if self.history.len() >= move_cap as usize {
+                return true;
+            }
+        }
+
+        if let Some(max_repetitions) = self.settings.max_repetitions {
+            if self.repetitions_of_current_position() >= max_repetitions as usize {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// How many times the players have been on their current positions with the current player to
+    /// move, including now
+    fn repetitions_of_current_position(&self) -> usize {
+        let mut positions = enum_map! {
+            P1 => self.settings.p1_starting,
+            P2 => self.settings.p2_starting,
+        };
+        let current = (self.player_positions(), self.whose_turn());
+        let mut repetitions = (current == (positions, self.settings.first_to_move)) as usize;
+
+        for &Action { player, to, .. } in self.history.iter() {
+            positions[player] = to;
+            if current == (positions, player.opponent()) {
+                repetitions += 1;
+            }
+        }
+
+        repetitions
+    }
+
+    /// Returns the player who's turn it currently is. Games start with P1 unless the settings say
+    /// otherwise
     /// ```
     /// use lib_table_top::games::marooned::{Player, GameState};
     ///
@@ -423,7 +849,54 @@ impl GameState {
         self.history
             .last()
             .map(|Action { player, .. }| player.opponent())
-            .unwrap_or(P1)
+            .unwrap_or(self.settings.first_to_move)
+    }
+
+    /// The players the game is waiting on, nobody once the game is over
+    /// ```
+    /// use lib_table_top::games::marooned::{GameState, Player::*, SettingsBuilder};
+    ///
+    /// let game: GameState = Default::default();
+    /// assert!(game.pending_input().is_pending_on(P1));
+    ///
+    /// let game = SettingsBuilder::new().rows(1).cols(2).build_game().unwrap();
+    /// assert!(game.pending_input().is_empty());
+    /// ```
+    pub fn pending_input(&self) -> PendingInput<Player> {
+        match self.status() {
+            InProgress => PendingInput::turn(self.whose_turn()),
+            Win { .. } | Draw => PendingInput::none(),
+        }
+    }
+
+    /// The view of the game for spectators, see [`ObserverView`](struct@ObserverView)
+    /// ```
+    /// use lib_table_top::games::marooned::{GameState, Player::*, Status};
+    ///
+    /// let game: GameState = Default::default();
+    /// let action = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(action).unwrap();
+    /// let view = game.observer_view();
+    ///
+    /// assert_eq!(view.p1, action.to);
+    /// assert_eq!(view.removed, vec![action.remove]);
+    /// assert_eq!(view.whose_turn, P2);
+    /// assert_eq!(view.last_action, Some(action));
+    /// assert_eq!(view.status, Status::InProgress);
+    /// ```
+    pub fn observer_view(&self) -> ObserverView {
+        ObserverView {
+            dimensions: self.dimensions().clone(),
+            p1: self.player_position(P1),
+            p2: self.player_position(P2),
+            removed: self.removed().collect(),
+            whose_turn: self.whose_turn(),
+            last_action: self.last_action(),
+            status: self.status(),
+            deadline: None,
+            time_remaining: None,
+            metadata: Map::new(),
+        }
     }
 
     /// An iterator over the actions made, in order, starting from the beginning of the game
@@ -455,6 +928,42 @@ impl GameState {
         self.history.iter()
     }
 
+    /// The most recent action, `None` at the start of the game
+    /// ```
+    /// use lib_table_top::games::marooned::GameState;
+    ///
+    /// let game: GameState = Default::default();
+    /// assert_eq!(game.last_action(), None);
+    ///
+    /// let action = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(action).unwrap();
+    /// assert_eq!(game.last_action(), Some(action));
+    /// ```
+    pub fn last_action(&self) -> Option<Action> {
+        self.history.last().copied()
+    }
+
+    /// The last `n` actions, oldest first, or the whole history if there have been fewer
+    /// ```
+    /// use lib_table_top::games::marooned::GameState;
+    ///
+    /// let game: GameState = Default::default();
+    /// let first = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(first).unwrap();
+    /// let second = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(second).unwrap();
+    ///
+    /// assert_eq!(game.recent_actions(1), vec![second]);
+    /// assert_eq!(game.recent_actions(5), vec![first, second]);
+    /// ```
+    pub fn recent_actions(&self, n: usize) -> Vec<Action> {
+        self.history
+            .iter()
+            .skip(self.history.len().saturating_sub(n))
+            .copied()
+            .collect()
+    }
+
     /// Returns an iterator of the positions that have already been removed
     /// ```
     /// use lib_table_top::games::marooned::{GameState, Position, SettingsBuilder, Row, Col};
@@ -576,12 +1085,124 @@ impl GameState {
     pub fn valid_actions(&self) -> impl Iterator<Item = Action> + Clone + '_ {
         let player = self.whose_turn();
 
-        iproduct!(
-            self.allowed_movement_targets_for_player(player),
-            self.removable()
-        )
-        .filter(|(to, remove)| to != remove)
-        .map(move |(to, remove)| Action { player, to, remove })
+        // Working out the targets and removable squares once, rather than once per pair, keeps
+        // this from rescanning the history for every square on the board
+        let (targets, removable): (Vec<Position>, Vec<Position>) = if self.is_drawn() {
+            Default::default()
+        } else {
+            let removed: Vec<Position> = self.removed().collect();
+            let opponent_position = self.player_position(player.opponent());
+            let open =
+                |position: &Position| !removed.contains(position) && *position != opponent_position;
+
+            (
+                self.settings
+                    .dimensions
+                    .adjacenct_positions(self.player_position(player))
+                    .filter(open)
+                    .collect(),
+                self.settings
+                    .dimensions
+                    .all_positions()
+                    .filter(open)
+                    .collect(),
+            )
+        };
+
+        iproduct!(0..targets.len(), 0..removable.len())
+            .map(move |(to, remove)| (targets[to], removable[remove]))
+            .filter(|(to, remove)| to != remove)
+            .map(move |(to, remove)| Action { player, to, remove })
+    }
+
+    /// The only action the player to move can take, `None` if they have a choice or the game is
+    /// over. Useful for skipping straight past moves that don't need a decision
+    /// ```
+    /// use lib_table_top::games::marooned::{Col, Row, SettingsBuilder};
+    ///
+    /// // P1 has to step into the middle and sink the square they came from
+    /// let game = SettingsBuilder::new()
+    ///   .rows(1)
+    ///   .cols(3)
+    ///   .p1_starting((Col(0), Row(0)))
+    ///   .p2_starting((Col(2), Row(0)))
+    ///   .build_game()
+    ///   .unwrap();
+    ///
+    /// let action = game.forced_action().unwrap();
+    /// assert_eq!(action.to, (Col(1), Row(0)));
+    /// assert_eq!(action.remove, (Col(0), Row(0)));
+    ///
+    /// let game = game.apply_action(action).unwrap();
+    /// assert_eq!(game.forced_action(), None);
+    /// ```
+    pub fn forced_action(&self) -> Option<Action> {
+        match self.legal_actions().as_slice() {
+            [action] => Some(*action),
+            _ => None,
+        }
+    }
+
+    /// The valid actions along with what each one does, for sorting and annotating a move list
+    /// ```
+    /// use lib_table_top::games::marooned::{ActionTag::*, Col, Row, SettingsBuilder};
+    ///
+    /// // P2 is cornered with two escapes, P1 wins by moving onto one and removing the other
+    /// let game = SettingsBuilder::new()
+    ///   .rows(2)
+    ///   .cols(3)
+    ///   .p1_starting((Col(2), Row(0)))
+    ///   .p2_starting((Col(0), Row(0)))
+    ///   .starting_removed(vec![(Col(0), Row(1))])
+    ///   .build_game()
+    ///   .unwrap();
+    ///
+    /// let winning: Vec<_> = game
+    ///   .analyzed_actions()
+    ///   .into_iter()
+    ///   .filter(|analyzed| analyzed.tags.contains(&WinningMove))
+    ///   .collect();
+    ///
+    /// assert_eq!(winning.len(), 2);
+    /// assert!(winning.iter().all(|analyzed| analyzed.tags.contains(&BlocksOpponent)));
+    /// ```
+    pub fn analyzed_actions(&self) -> Vec<AnalyzedAction> {
+        let actions: Vec<Action> = self.valid_actions().collect();
+        let only_move = actions.len() == 1;
+
+        actions
+            .into_iter()
+            .map(|action| {
+                let mut tags = Vec::new();
+                let opponent = action.player.opponent();
+
+                if only_move {
+                    tags.push(ActionTag::OnlyMove);
+                }
+
+                if self
+                    .dimensions()
+                    .adjacenct_positions(self.player_position(opponent))
+                    .any(|pos| pos == action.remove)
+                {
+                    tags.push(ActionTag::BlocksOpponent);
+                }
+
+                let after = self
+                    .apply_action(action)
+                    .expect("Valid actions can always be applied");
+
+                if after.status()
+                    == (Win {
+                        player: action.player,
+                    })
+                {
+                    tags.push(ActionTag::WinningMove);
+                }
+
+                AnalyzedAction { action, tags }
+            })
+            .collect()
     }
 
     fn player_positions(&self) -> EnumMap<Player, Position> {
@@ -613,6 +1234,24 @@ impl GameState {
     }
 }
 
+/// What an action does, see [`GameState::analyzed_actions`](GameState::analyzed_actions)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionTag {
+    /// There's nothing else the player can do
+    OnlyMove,
+    /// Removes a position the opponent could have moved to
+    BlocksOpponent,
+    /// Leaves the opponent with nowhere to move, winning the game
+    WinningMove,
+}
+
+/// A valid action along with the tags describing it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyzedAction {
+    pub action: Action,
+    pub tags: Vec<ActionTag>,
+}
+
 /// The various things that can go wrong with making a move
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum ActionError {
@@ -624,10 +1263,51 @@ pub enum ActionError {
     InvalidRemove { target: Position },
     #[error("Can't move to the same position as being removed")]
     CantRemoveTheSamePositionAsMoveTo { target: Position },
+    #[error("The game is over")]
+    GameIsOver,
 }
 
 use ActionError::*;
 
+impl ActionError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::marooned::{ActionError::*, Player::*};
+    ///
+    /// assert_eq!(OtherPlayerTurn { attempted: P2 }.code(), "other_player_turn");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            OtherPlayerTurn { .. } => "other_player_turn",
+            InvalidMoveToTarget { .. } => "invalid_move_to_target",
+            InvalidRemove { .. } => "invalid_remove",
+            CantRemoveTheSamePositionAsMoveTo { .. } => "cant_remove_the_same_position_as_move_to",
+            GameIsOver => "game_is_over",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    /// ```
+    /// use lib_table_top::games::marooned::{ActionError::*, Col, Row, Player::*};
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(
+    ///   InvalidMoveToTarget { target: (Col(1), Row(2)), player: P2 }.details(),
+    ///   json!({"target": [1, 2], "player": 2})
+    /// );
+    /// ```
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            OtherPlayerTurn { attempted } => json!({ "attempted": attempted }),
+            InvalidMoveToTarget { target, player } => json!({ "target": target, "player": player }),
+            InvalidRemove { target } | CantRemoveTheSamePositionAsMoveTo { target } => {
+                json!({ "target": target })
+            }
+            GameIsOver => json!({}),
+        }
+    }
+}
+
 impl GameState {
     /// Moves the game forward by doing an action, returns an error and doesn't do anything if the
     /// action isn't valid for some reason.
@@ -676,6 +1356,10 @@ impl GameState {
             });
         }
 
+        if self.is_drawn() {
+            return Err(GameIsOver);
+        }
+
         if !self
             .allowed_movement_targets_for_player(action.player)
             .any(|pos| action.to == pos)
@@ -738,6 +1422,639 @@ impl GameState {
     }
 }
 
+impl Play for GameState {
+    type Action = Action;
+    type Player = Player;
+    type Status = Status;
+    type Error = ActionError;
+
+    fn apply_action(&self, action: Action) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<Action> {
+        self.legal_actions()
+    }
+
+    fn players(&self) -> Vec<Player> {
+        vec![P1, P2]
+    }
+
+    fn whose_turn(&self) -> Player {
+        self.whose_turn()
+    }
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != InProgress
+    }
+
+    fn history(&self) -> Vec<Action> {
+        self.history().copied().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = Action;
+    type Error = ActionError;
+
+    fn initial(&self) -> Self {
+        Self::new(self.settings.clone())
+    }
+
+    fn actions(&self) -> Vec<Action> {
+        self.history().copied().collect()
+    }
+
+    fn apply(&self, action: Action) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+}
+
+impl Searchable for GameState {
+    type Action = Action;
+
+    fn legal_actions(&self) -> Vec<Action> {
+        self.valid_actions().collect()
+    }
+
+    fn play(&self, action: Action) -> Self {
+        self.apply_action(action)
+            .expect("Legal actions can always be applied")
+    }
+
+    /// Losing is running out of moves, so having more room to move than the opponent is good
+    fn evaluate(&self) -> i32 {
+        let player = self.whose_turn();
+
+        match self.status() {
+            Win { player: winner } if winner == player => WIN,
+            Win { .. } => -WIN,
+            Draw => 0,
+            InProgress => {
+                let mobility = |player| self.allowed_movement_targets_for_player(player).count();
+                mobility(player) as i32 - mobility(player.opponent()) as i32
+            }
+        }
+    }
+}
+
+/// The game is its settings and history, so hashing it covers everything
+impl StateHash for GameState {
+    fn state_hash(&self) -> u64 {
+        settings_hash(self)
+    }
+}
+
+/// There's no safe default move, so timed out players can only forfeit or move randomly
+impl TimeoutHooks for GameState {
+    type Player = Player;
+    type Action = Action;
+
+    fn timeout_actions(&self, player: Player) -> Vec<Action> {
+        if player == self.whose_turn() {
+            self.valid_actions().collect()
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Summarize for GameState {
+    type Player = Player;
+
+    fn game_result(&self) -> Option<GameResult<Player>> {
+        let players = match self.status() {
+            InProgress => return None,
+            Win { player } => winner_takes_all([P1, P2].iter().copied(), player),
+            Draw => [P1, P2]
+                .iter()
+                .map(|&player| PlayerResult {
+                    player,
+                    outcome: Outcome::Draw,
+                    score: None,
+                    place: Some(1),
+                })
+                .collect(),
+        };
+
+        Some(GameResult {
+            kind: GameKind::Marooned,
+            settings_hash: settings_hash(self.settings()),
+            players,
+            move_count: self.history.len(),
+            duration: None,
+            termination: Termination::Completed,
+        })
+    }
+}
+
+/// Three planes indexed by row then col, the position of the player whose turn it is, the
+/// position of their opponent, and the removed squares, so the shape is `[3, rows, cols]`
+/// ```
+/// use lib_table_top::common::ml::Encode;
+/// use lib_table_top::games::marooned::{Col, GameState, Row};
+///
+/// let game: GameState = Default::default();
+/// let encoding = game.encode();
+/// let (Col(col), Row(row)) = game.player_position(game.whose_turn());
+///
+/// assert_eq!(encoding.shape, vec![3, 8, 6]);
+/// assert_eq!(encoding.get(&[0, row as usize, col as usize]), 1.0);
+/// assert_eq!(encoding.values.iter().sum::<f32>(), 2.0);
+/// ```
+impl Encode for GameState {
+    fn encode(&self) -> Encoding {
+        let Dimensions { rows, cols } = *self.dimensions();
+        let mut encoding = Encoding::zeros(vec![3, rows as usize, cols as usize]);
+        let whose_turn = self.whose_turn();
+        let mut mark = |plane: usize, (Col(col), Row(row)): Position| {
+            encoding.set(&[plane, row as usize, col as usize], 1.0)
+        };
+
+        mark(0, self.player_position(whose_turn));
+        mark(1, self.player_position(whose_turn.opponent()));
+        for position in self.removed() {
+            mark(2, position);
+        }
+
+        encoding
+    }
+}
+
+/// Every perspective sees the same thing, there's nothing hidden in Marooned
+impl Viewable for GameState {
+    type Player = Player;
+    type View = ObserverView;
+
+    fn view(&self, _perspective: Perspective<Player>) -> ObserverView {
+        self.observer_view()
+    }
+}
+
+/// A versioned capture of the full state of a game, so a game can be persisted and resumed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum Snapshot {
+    #[serde(rename = "v1")]
+    V1 {
+        settings: Arc<Settings>,
+        history: Vector<Action>,
+    },
+}
+
+impl GameState {
+    /// Captures the full state of the game, see [`Snapshot`](enum@Snapshot)
+    /// ```
+    /// use lib_table_top::games::marooned::GameState;
+    ///
+    /// let game: GameState = Default::default();
+    /// let action = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(action).unwrap();
+    /// assert_eq!(GameState::from_snapshot(game.snapshot()), game);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::V1 {
+            settings: self.settings.clone(),
+            history: self.history.clone(),
+        }
+    }
+
+    /// Resumes a game from a [`Snapshot`](enum@Snapshot)
+    pub fn from_snapshot(snapshot: Snapshot) -> Self {
+        match snapshot {
+            Snapshot::V1 { settings, history } => Self { settings, history },
+        }
+    }
+}
+
+pub mod scenario {
+    use super::*;
+
+    /// A position to start a game from without the history that led to it, for puzzles like
+    /// "win in two removals" or for studying a position with the solver
+    /// ```
+    /// use lib_table_top::common::search::{search_with_budget, WIN};
+    /// use lib_table_top::games::marooned::{scenario::Scenario, Col, Dimensions, Row, Player::*};
+    /// use std::time::Duration;
+    ///
+    /// // P2 is cornered with one square left to move to, P1 to move can take it away
+    /// let scenario = Scenario {
+    ///   dimensions: Dimensions::new(3, 3).unwrap(),
+    ///   p1: (Col(2), Row(2)),
+    ///   p2: (Col(0), Row(0)),
+    ///   removed: vec![(Col(1), Row(0)), (Col(0), Row(1))],
+    ///   to_move: P1,
+    /// };
+    ///
+    /// let game = scenario.game().unwrap();
+    /// let result = search_with_budget(&game, Duration::from_secs(1));
+    /// assert_eq!(result.best_action.unwrap().remove, (Col(1), Row(1)));
+    /// assert!(result.score > WIN / 2);
+    /// ```
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Scenario {
+        pub dimensions: Dimensions,
+        pub p1: Position,
+        pub p2: Position,
+        pub removed: Vec<Position>,
+        pub to_move: Player,
+    }
+
+    impl Scenario {
+        /// Captures where a game currently stands
+        /// ```
+        /// use lib_table_top::games::marooned::{scenario::Scenario, GameState};
+        ///
+        /// let game: GameState = Default::default();
+        /// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+        ///
+        /// let resumed = Scenario::from_game(&game).game().unwrap();
+        /// assert_eq!(resumed.whose_turn(), game.whose_turn());
+        /// assert_eq!(resumed.removed().collect::<Vec<_>>(), game.removed().collect::<Vec<_>>());
+        /// assert!(resumed.valid_actions().eq(game.valid_actions()));
+        /// ```
+        pub fn from_game(game: &GameState) -> Self {
+            Self {
+                dimensions: game.dimensions().clone(),
+                p1: game.player_position(P1),
+                p2: game.player_position(P2),
+                removed: game.removed().collect(),
+                to_move: game.whose_turn(),
+            }
+        }
+
+        /// Validates the scenario and makes a game starting from it
+        pub fn game(&self) -> Result<GameState, SettingsError> {
+            SettingsBuilder::new()
+                .rows(self.dimensions.rows)
+                .cols(self.dimensions.cols)
+                .p1_starting(self.p1)
+                .p2_starting(self.p2)
+                .starting_removed(self.removed.clone())
+                .first_to_move(self.to_move)
+                .build_game()
+        }
+    }
+}
+
+pub mod editor {
+    use super::*;
+    use scenario::Scenario;
+    use std::collections::BTreeSet;
+
+    /// A board for tools to lay out freely, squares and players can be put anywhere, even off the
+    /// board or on top of each other, and nothing is checked until the board is turned into
+    /// [`Settings`](struct@Settings) or a game
+    /// ```
+    /// use lib_table_top::games::marooned::{editor::BoardEditor, Col, Dimensions, Row, Player::*};
+    ///
+    /// let mut editor = BoardEditor::new(Dimensions::new(3, 3).unwrap());
+    /// editor.place_player(P1, (Col(0), Row(0)));
+    /// editor.place_player(P2, (Col(2), Row(2)));
+    /// editor.remove((Col(1), Row(1)));
+    ///
+    /// // Players can't start on removed squares
+    /// editor.remove((Col(2), Row(2)));
+    /// assert!(editor.validate().is_err());
+    /// editor.restore((Col(2), Row(2)));
+    ///
+    /// let settings = editor.settings().unwrap();
+    /// assert_eq!(settings.starting_removed, vec![(Col(1), Row(1))]);
+    ///
+    /// editor.set_to_move(P2);
+    /// let game = editor.game().unwrap();
+    /// assert_eq!(game.whose_turn(), P2);
+    /// assert_eq!(BoardEditor::from_game(&game), editor);
+    /// ```
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BoardEditor {
+        dimensions: Dimensions,
+        p1: Position,
+        p2: Position,
+        removed: BTreeSet<Position>,
+        to_move: Player,
+    }
+
+    impl BoardEditor {
+        /// An empty board with the players on their default starting squares
+        pub fn new(dimensions: Dimensions) -> Self {
+            let starting = dimensions.default_player_starting_positions();
+
+            Self {
+                p1: starting[P1],
+                p2: starting[P2],
+                dimensions,
+                removed: BTreeSet::new(),
+                to_move: first_to_move(),
+            }
+        }
+
+        /// Starts editing from the starting board of some settings, the draw rules aren't kept
+        pub fn from_settings(settings: &Settings) -> Self {
+            Self {
+                dimensions: settings.dimensions.clone(),
+                p1: settings.p1_starting,
+                p2: settings.p2_starting,
+                removed: settings.starting_removed.iter().copied().collect(),
+                to_move: settings.first_to_move,
+            }
+        }
+
+        /// Starts editing from where a game currently stands
+        pub fn from_game(game: &GameState) -> Self {
+            Self::from_scenario(&Scenario::from_game(game))
+        }
+
+        pub fn from_scenario(scenario: &Scenario) -> Self {
+            Self {
+                dimensions: scenario.dimensions.clone(),
+                p1: scenario.p1,
+                p2: scenario.p2,
+                removed: scenario.removed.iter().copied().collect(),
+                to_move: scenario.to_move,
+            }
+        }
+
+        pub fn dimensions(&self) -> &Dimensions {
+            &self.dimensions
+        }
+
+        /// Changes the size of the board, squares and players outside of it stay where they are
+        /// until they're moved
+        pub fn resize(&mut self, dimensions: Dimensions) {
+            self.dimensions = dimensions;
+        }
+
+        pub fn player_position(&self, player: Player) -> Position {
+            match player {
+                P1 => self.p1,
+                P2 => self.p2,
+            }
+        }
+
+        pub fn place_player(&mut self, player: Player, position: Position) {
+            match player {
+                P1 => self.p1 = position,
+                P2 => self.p2 = position,
+            }
+        }
+
+        pub fn to_move(&self) -> Player {
+            self.to_move
+        }
+
+        pub fn set_to_move(&mut self, player: Player) {
+            self.to_move = player;
+        }
+
+        /// The removed squares, in order
+        pub fn removed(&self) -> impl Iterator<Item = Position> + '_ {
+            self.removed.iter().copied()
+        }
+
+        pub fn is_removed(&self, position: Position) -> bool {
+            self.removed.contains(&position)
+        }
+
+        /// Removes a square, returns whether it was there to remove
+        pub fn remove(&mut self, position: Position) -> bool {
+            self.removed.insert(position)
+        }
+
+        /// Puts back a removed square, returns whether it had been removed
+        pub fn restore(&mut self, position: Position) -> bool {
+            self.removed.remove(&position)
+        }
+
+        /// Puts back every removed square
+        pub fn clear(&mut self) {
+            self.removed.clear();
+        }
+
+        /// Checks the board can be played, with the same rules as [`Settings`](struct@Settings)
+        pub fn validate(&self) -> Result<(), SettingsError> {
+            self.settings().map(|_| ())
+        }
+
+        /// The board as a [`Scenario`](struct@scenario::Scenario), which isn't validated
+        pub fn scenario(&self) -> Scenario {
+            Scenario {
+                dimensions: self.dimensions.clone(),
+                p1: self.p1,
+                p2: self.p2,
+                removed: self.removed().collect(),
+                to_move: self.to_move,
+            }
+        }
+
+        /// The settings for a game that starts on this board
+        pub fn settings(&self) -> Result<Settings, SettingsError> {
+            SettingsBuilder::new()
+                .rows(self.dimensions.rows)
+                .cols(self.dimensions.cols)
+                .p1_starting(self.p1)
+                .p2_starting(self.p2)
+                .starting_removed(self.removed().collect())
+                .first_to_move(self.to_move)
+                .build()
+        }
+
+        /// A game starting from this board, see [`Scenario::game`](scenario::Scenario::game)
+        pub fn game(&self) -> Result<GameState, SettingsError> {
+            self.scenario().game()
+        }
+    }
+}
+
+pub mod stats {
+    use super::*;
+    use crate::common::stats::{Collector, Tally};
+    use serde_json::Value;
+
+    /// How many squares were removed by the end of each game, counting the starting removed ones
+    /// ```
+    /// use lib_table_top::common::stats::Stats;
+    /// use lib_table_top::games::marooned::{stats::SquaresRemoved, GameState};
+    ///
+    /// let game: GameState = Default::default();
+    /// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+    ///
+    /// let mut stats = Stats::new().with(SquaresRemoved::default());
+    /// stats.observe(&game).unwrap();
+    /// assert_eq!(stats.report()["squares_removed"]["total"], 1);
+    /// ```
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct SquaresRemoved {
+        tally: Tally,
+    }
+
+    impl Collector<GameState> for SquaresRemoved {
+        fn name(&self) -> &'static str {
+            "squares_removed"
+        }
+
+        fn record_game(&mut self, game: &GameState) {
+            self.tally = self.tally.record(game.removed().count() as u64);
+        }
+
+        fn report(&self) -> Value {
+            self.tally.report()
+        }
+    }
+}
+
+/// Entry points for fuzzing the engine with untrusted input
+pub mod fuzz {
+    use super::*;
+
+    /// Decodes arbitrary bytes into settings and actions, then applies the actions to a new game,
+    /// skipping any that the engine rejects. The first 7 bytes are the rows, cols, P1 starting
+    /// position, P2 starting position, and the number of starting removed positions, followed by
+    /// the starting removed positions. Every 5 bytes after that are decoded into an action.
+    /// Returns `None` if the bytes don't decode into valid settings.
+    ///
+    /// This never panics unless there is a bug in the engine, including the history failing to
+    /// replay into the resulting game
+    /// ```
+    /// use lib_table_top::games::marooned::{fuzz::apply_raw, Col, Row};
+    ///
+    /// assert!(apply_raw(&[]).is_none());
+    /// // Zero rows is invalid
+    /// assert!(apply_raw(&[0, 3, 0, 0, 1, 1, 0]).is_none());
+    ///
+    /// let game = apply_raw(&[3, 3, 0, 0, 2, 2, 1, 1, 1]).unwrap();
+    /// assert_eq!(game.removed().collect::<Vec<_>>(), vec![(Col(1), Row(1))]);
+    /// ```
+    pub fn apply_raw(bytes: &[u8]) -> Option<GameState> {
+        if bytes.len() < 7 {
+            return None;
+        }
+
+        let (header, rest) = bytes.split_at(7);
+        let removed_len = (header[6] as usize * 2).min(rest.len());
+        let (removed_bytes, action_bytes) = rest.split_at(removed_len);
+
+        let game = SettingsBuilder::new()
+            .rows(header[0])
+            .cols(header[1])
+            .p1_starting((Col(header[2]), Row(header[3])))
+            .p2_starting((Col(header[4]), Row(header[5])))
+            .starting_removed(
+                removed_bytes
+                    .chunks_exact(2)
+                    .map(|chunk| (Col(chunk[0]), Row(chunk[1])))
+                    .collect(),
+            )
+            .build_game()
+            .ok()?;
+
+        let game = action_bytes.chunks_exact(5).fold(game, |game, chunk| {
+            let action = Action {
+                player: if chunk[0] % 2 == 0 { P1 } else { P2 },
+                to: (Col(chunk[1]), Row(chunk[2])),
+                remove: (Col(chunk[3]), Row(chunk[4])),
+            };
+            game.apply_action(action).unwrap_or(game)
+        });
+
+        game.status();
+        let replayed = game.history().try_fold(
+            GameState::new(Arc::new(game.settings().clone())),
+            |replayed, &action| replayed.apply_action(action),
+        );
+        assert_eq!(replayed.as_ref(), Ok(&game));
+
+        Some(game)
+    }
+}
+
+/// Generates valid settings on boards up to 10x10, with distinct starting positions and a random
+/// set of starting removed positions that doesn't include either player
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for Settings {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use proptest::sample::Index;
+
+        (1..=10u8, 1..=10u8)
+            .prop_filter("boards need at least two positions", |&(rows, cols)| {
+                rows * cols >= 2
+            })
+            .prop_flat_map(|(rows, cols)| {
+                (
+                    Just(Dimensions { rows, cols }),
+                    any::<Index>(),
+                    any::<Index>(),
+                    proptest::collection::vec(any::<Index>(), 0..10),
+                )
+            })
+            .prop_map(|(dimensions, p1, p2, removed)| {
+                let positions: Vec<Position> = dimensions.all_positions().collect();
+                let p1_starting = *p1.get(&positions);
+                let others: Vec<Position> = positions
+                    .iter()
+                    .copied()
+                    .filter(|&pos| pos != p1_starting)
+                    .collect();
+                let p2_starting = *p2.get(&others);
+                let starting_removed = removed
+                    .iter()
+                    .map(|index| *index.get(&positions))
+                    .filter(|&pos| pos != p1_starting && pos != p2_starting)
+                    .collect();
+
+                SettingsBuilder::new()
+                    .rows(dimensions.rows)
+                    .cols(dimensions.cols)
+                    .p1_starting(p1_starting)
+                    .p2_starting(p2_starting)
+                    .starting_removed(starting_removed)
+                    .build()
+                    .unwrap()
+            })
+            .boxed()
+    }
+}
+
+/// Generates games from arbitrary settings by playing up to 100 random valid actions, stopping
+/// early if the game is over
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for GameState {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use proptest::sample::Index;
+
+        (
+            any::<Settings>(),
+            proptest::collection::vec(any::<Index>(), 0..100),
+        )
+            .prop_map(|(settings, choices)| {
+                choices
+                    .iter()
+                    .fold(GameState::new(Arc::new(settings)), |game, choice| {
+                        let actions: Vec<Action> = game.valid_actions().collect();
+
+                        if actions.is_empty() {
+                            game
+                        } else {
+                            game.apply_action(*choice.get(&actions)).unwrap()
+                        }
+                    })
+            })
+            .boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -765,6 +2082,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_randomized_starts_are_fair_and_playable() {
+        for &(rows, cols) in [(2, 3), (3, 3), (5, 4), (8, 6), (10, 8)].iter() {
+            for seed in 0..20 {
+                let settings = SettingsBuilder::new()
+                    .rows(rows)
+                    .cols(cols)
+                    .randomize_starts(RngSeed([seed; 32]))
+                    .build()
+                    .unwrap();
+                let dimensions = &settings.dimensions;
+
+                assert_eq!(
+                    settings.p2_starting,
+                    dimensions.opposite(settings.p1_starting)
+                );
+                for &removed in &settings.starting_removed {
+                    assert!(settings
+                        .starting_removed
+                        .contains(&dimensions.opposite(removed)));
+                }
+
+                let game = GameState::new(Arc::new(settings.clone()));
+                assert!(game.valid_actions().next().is_some());
+
+                let settings = Settings {
+                    first_to_move: P2,
+                    ..settings
+                };
+                let game = GameState::new(Arc::new(settings));
+                assert!(game.valid_actions().next().is_some());
+            }
+        }
+
+        // There's no room to randomize on the smallest boards
+        assert_eq!(
+            SettingsBuilder::new()
+                .rows(1)
+                .cols(2)
+                .randomize_starts(RngSeed([0; 32]))
+                .build(),
+            SettingsBuilder::new().rows(1).cols(2).build()
+        );
+    }
+
     #[test]
     fn test_settings_builder_does_validation() {
         assert!(SettingsBuilder::new().build().is_ok());
@@ -910,4 +2272,37 @@ mod tests {
 
         assert_eq!(Win { player: P2 }, game.status());
     }
+    #[test]
+    fn test_repeating_a_position_is_a_draw() {
+        let game = SettingsBuilder::new()
+            .max_repetitions(2)
+            .build_game()
+            .unwrap();
+        assert_eq!(
+            SettingsBuilder::new().max_repetitions(1).build(),
+            Err(MaxRepetitionsTooLow { max_repetitions: 1 })
+        );
+
+        let moves = vec![
+            (P1, (Col(3), Row(1)), (Col(0), Row(3))),
+            (P2, (Col(2), Row(6)), (Col(5), Row(3))),
+            (P1, (Col(3), Row(0)), (Col(0), Row(4))),
+            (P2, (Col(2), Row(7)), (Col(5), Row(4))),
+        ];
+        let game = moves.into_iter().fold(game, |game, (player, to, remove)| {
+            assert_eq!(game.status(), InProgress);
+            game.apply_action(Action { player, to, remove }).unwrap()
+        });
+
+        assert_eq!(game.status(), Draw);
+        assert_eq!(game.valid_actions().next(), None);
+        assert_eq!(
+            game.apply_action(Action {
+                player: P1,
+                to: (Col(3), Row(1)),
+                remove: (Col(0), Row(5))
+            }),
+            Err(GameIsOver)
+        );
+    }
 }
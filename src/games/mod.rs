@@ -1,3 +1,7 @@
+pub mod blackjack;
 pub mod crazy_eights;
 pub mod marooned;
+pub mod solitaire;
+pub mod spades;
 pub mod tic_tac_toe;
+pub mod trick_game_template;
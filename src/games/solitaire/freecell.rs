@@ -0,0 +1,601 @@
+use im::Vector;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::common::deck::foundations::Foundations;
+use crate::common::deck::{Card, Deck, Rank, StandardDeck, Suit};
+use crate::common::game::Play;
+use crate::common::rand::RngSeed;
+use crate::common::replay::Replayable;
+
+/// The number of free cells
+pub const CELLS: usize = 4;
+/// The number of cascades the cards are dealt into
+pub const CASCADES: usize = 8;
+
+/// Where the cards of a game come from
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Deal {
+    /// The numbered deals of Microsoft FreeCell, so players can look up and share deals like
+    /// the famously unsolvable #11982. Numbers up to 2^31 - 1 match the extended numbering of
+    /// later versions
+    Microsoft(u32),
+    /// A deal shuffled from a seed, the same way the other card games shuffle
+    Seeded(RngSeed),
+}
+
+impl Deal {
+    /// The cards of each cascade, dealt a row at a time from left to right, so the first four
+    /// cascades get seven cards and the rest get six. The last card of a cascade is on top
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::games::solitaire::freecell::Deal;
+    ///
+    /// let cascades = Deal::Microsoft(1).cascades();
+    /// assert_eq!(cascades[0][..2], [Card(Jack, Diamonds), Card(King, Diamonds)]);
+    /// assert_eq!(cascades[0].len(), 7);
+    /// assert_eq!(cascades[7].len(), 6);
+    /// ```
+    pub fn cascades(&self) -> [Vec<Card>; CASCADES] {
+        let cards: Vec<Card> = match *self {
+            Deal::Microsoft(number) => microsoft_order(number),
+            Deal::Seeded(seed) => StandardDeck::shuffled(seed).to_vec(),
+        };
+
+        let mut cascades: [Vec<Card>; CASCADES] = Default::default();
+        for (index, card) in cards.into_iter().enumerate() {
+            cascades[index % CASCADES].push(card);
+        }
+        cascades
+    }
+}
+
+/// The cards in the order Microsoft FreeCell deals them, shuffled with the random number
+/// generator of Microsoft's C runtime seeded with the deal number
+fn microsoft_order(number: u32) -> Vec<Card> {
+    let mut state = number;
+    let mut rand = move || {
+        state = state.wrapping_mul(214_013).wrapping_add(2_531_011) & 0x7fff_ffff;
+        (state >> 16) as usize
+    };
+
+    // Cards are numbered up by rank, with the suits in order, so 0 is the ace of clubs and 51
+    // is the king of spades. The deck starts from the king of spades
+    let mut deck: Vec<usize> = (0..52).rev().collect();
+    for index in 0..51 {
+        let swap = 51 - rand() % (52 - index);
+        deck.swap(index, swap);
+    }
+
+    deck.into_iter()
+        .map(|number| Card(Rank::ALL[number / 4], Suit::ALL[number % 4]))
+        .collect()
+}
+
+/// A place cards can be moved from or to
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pile {
+    /// The cascades, numbered from 0 on the left
+    Cascade(u8),
+    /// The free cells, numbered from 0 on the left
+    Cell(u8),
+    /// The foundation of the card's suit
+    Foundation,
+}
+
+use Pile::*;
+
+/// Moves the top cards of one pile to another. More than one card can only be moved between
+/// cascades, see [`max_cards`](GameState::max_cards)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Action {
+    pub from: Pile,
+    pub to: Pile,
+    pub cards: u8,
+}
+
+/// The things that can go wrong moving cards
+#[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionError {
+    #[error("There's no {:?}", pile)]
+    NoSuchPile { pile: Pile },
+    #[error("The cards are already on {:?}", pile)]
+    SamePile { pile: Pile },
+    #[error("Cards can't be taken back off the foundations")]
+    CantMoveFromFoundation,
+    #[error("A move has to move at least one card")]
+    NoCardsMoved,
+    #[error("There are no cards on {:?}", pile)]
+    EmptyPile { pile: Pile },
+    #[error(
+        "The top {:?} cards of {:?} aren't in alternating colors going down",
+        cards,
+        pile
+    )]
+    NotASequence { pile: Pile, cards: u8 },
+    #[error("Can only move {:?} cards there, not {:?}", max, cards)]
+    TooManyCards { cards: u8, max: usize },
+    #[error("Cell {:?} already has a card", cell)]
+    CellIsTaken { cell: u8 },
+    #[error("{:?} can't go on {:?}", card, onto)]
+    CantStack { card: Card, onto: Card },
+    #[error("{:?} can't go on the foundations yet", card)]
+    CantPlaceOnFoundation { card: Card },
+}
+
+use ActionError::*;
+
+impl ActionError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::solitaire::freecell::ActionError::*;
+    ///
+    /// assert_eq!(NoCardsMoved.code(), "no_cards_moved");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            NoSuchPile { .. } => "no_such_pile",
+            SamePile { .. } => "same_pile",
+            CantMoveFromFoundation => "cant_move_from_foundation",
+            NoCardsMoved => "no_cards_moved",
+            EmptyPile { .. } => "empty_pile",
+            NotASequence { .. } => "not_a_sequence",
+            TooManyCards { .. } => "too_many_cards",
+            CellIsTaken { .. } => "cell_is_taken",
+            CantStack { .. } => "cant_stack",
+            CantPlaceOnFoundation { .. } => "cant_place_on_foundation",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            NoSuchPile { pile } | SamePile { pile } | EmptyPile { pile } => {
+                json!({ "pile": pile })
+            }
+            CantMoveFromFoundation | NoCardsMoved => json!({}),
+            NotASequence { pile, cards } => json!({ "pile": pile, "cards": cards }),
+            TooManyCards { cards, max } => json!({ "cards": cards, "max": max }),
+            CellIsTaken { cell } => json!({ "cell": cell }),
+            CantStack { card, onto } => json!({ "card": card, "onto": onto }),
+            CantPlaceOnFoundation { card } => json!({ "card": card }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    InProgress,
+    /// Every card is on the foundations
+    Win,
+    /// There are cards left and nothing can be moved
+    Stuck,
+}
+
+/// A game of FreeCell. Every card is dealt face up into eight cascades, and the goal is to build
+/// the four foundations up from the aces. Cards move one at a time between the cascades, the
+/// four free cells that hold one card each, and the foundations. A cascade is built down in
+/// alternating colors, and a run can be moved as a whole when there's room to move it a card
+/// at a time
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::games::solitaire::freecell::{Action, Deal, GameState, Pile::*, Status};
+///
+/// let game = GameState::new(Deal::Microsoft(1));
+/// assert_eq!(game.cascades()[0].last(), Some(&Card(Six, Spades)));
+///
+/// let game = game
+///     .apply_action(Action { from: Cascade(0), to: Cell(0), cards: 1 })
+///     .unwrap();
+///
+/// assert_eq!(game.cells()[0], Some(Card(Six, Spades)));
+/// assert_eq!(game.cascades()[0].last(), Some(&Card(Six, Diamonds)));
+/// assert_eq!(game.free_cells(), 3);
+/// assert_eq!(game.status(), Status::InProgress);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameState {
+    deal: Deal,
+    cells: [Option<Card>; CELLS],
+    foundations: Foundations,
+    cascades: [Vec<Card>; CASCADES],
+    history: Vector<Action>,
+}
+
+impl GameState {
+    pub fn new(deal: Deal) -> Self {
+        Self {
+            deal,
+            cells: [None; CELLS],
+            foundations: Foundations::default(),
+            cascades: deal.cascades(),
+            history: Vector::new(),
+        }
+    }
+
+    pub fn deal(&self) -> Deal {
+        self.deal
+    }
+
+    pub fn cells(&self) -> &[Option<Card>; CELLS] {
+        &self.cells
+    }
+
+    pub fn foundations(&self) -> &Foundations {
+        &self.foundations
+    }
+
+    /// The cascades from left to right, the last card of each is on top
+    pub fn cascades(&self) -> &[Vec<Card>; CASCADES] {
+        &self.cascades
+    }
+
+    /// The moves that have been made, in order
+    pub fn history(&self) -> impl Iterator<Item = &Action> + '_ {
+        self.history.iter()
+    }
+
+    pub fn free_cells(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_none()).count()
+    }
+
+    pub fn empty_cascades(&self) -> usize {
+        self.cascades
+            .iter()
+            .filter(|cascade| cascade.is_empty())
+            .count()
+    }
+
+    /// The most cards that can be moved to a pile at once. A run is moved a card at a time
+    /// through the free cells and empty cascades, so it's `(free cells + 1) * 2^(empty cascades)`,
+    /// not counting the destination if it's an empty cascade. Cells and the foundations take
+    /// one card
+    /// ```
+    /// use lib_table_top::games::solitaire::freecell::{Deal, GameState, Pile::*};
+    ///
+    /// let game = GameState::new(Deal::Microsoft(1));
+    /// assert_eq!(game.max_cards(Cascade(0)), 5);
+    /// assert_eq!(game.max_cards(Cell(0)), 1);
+    /// ```
+    pub fn max_cards(&self, to: Pile) -> usize {
+        match to {
+            Cell(_) | Foundation => 1,
+            Cascade(index) => {
+                let destination_empty =
+                    self.cascades.get(index as usize).is_some_and(Vec::is_empty);
+                let empty = self.empty_cascades() - usize::from(destination_empty);
+                (self.free_cells() + 1) << empty
+            }
+        }
+    }
+
+    /// The number of cards on top of a pile that can be moved together, the run in alternating
+    /// colors going down for a cascade
+    pub fn run_length(&self, pile: Pile) -> usize {
+        match pile {
+            Foundation => 0,
+            Cell(index) => self.cells[index as usize].iter().count(),
+            Cascade(index) => {
+                let cascade = &self.cascades[index as usize];
+                let stacked = cascade
+                    .windows(2)
+                    .rev()
+                    .take_while(|pair| can_stack(pair[1], pair[0]))
+                    .count();
+                (stacked + 1).min(cascade.len())
+            }
+        }
+    }
+
+    fn exists(&self, pile: Pile) -> bool {
+        match pile {
+            Cascade(index) => usize::from(index) < CASCADES,
+            Cell(index) => usize::from(index) < CELLS,
+            Foundation => true,
+        }
+    }
+
+    fn check(&self, Action { from, to, cards }: Action) -> Result<(), ActionError> {
+        for &pile in &[from, to] {
+            if !self.exists(pile) {
+                return Err(NoSuchPile { pile });
+            }
+        }
+        if from == to {
+            return Err(SamePile { pile: from });
+        }
+        if from == Foundation {
+            return Err(CantMoveFromFoundation);
+        }
+        if cards == 0 {
+            return Err(NoCardsMoved);
+        }
+
+        let moving: Vec<Card> = match from {
+            Cell(index) => self.cells[index as usize].into_iter().collect(),
+            Cascade(index) => self.cascades[index as usize].clone(),
+            Foundation => unreachable!("Checked above"),
+        };
+        if moving.is_empty() {
+            return Err(EmptyPile { pile: from });
+        }
+
+        let max = self.max_cards(to);
+        if usize::from(cards) > max {
+            return Err(TooManyCards { cards, max });
+        }
+        if usize::from(cards) > self.run_length(from) {
+            return Err(NotASequence { pile: from, cards });
+        }
+
+        let card = moving[moving.len() - usize::from(cards)];
+        match to {
+            Cell(index) if self.cells[index as usize].is_some() => Err(CellIsTaken { cell: index }),
+            Cell(_) => Ok(()),
+            Foundation if self.foundations.can_place(card) => Ok(()),
+            Foundation => Err(CantPlaceOnFoundation { card }),
+            Cascade(index) => match self.cascades[index as usize].last() {
+                Some(&onto) if !can_stack(card, onto) => Err(CantStack { card, onto }),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Moves cards, returns an error if the move isn't allowed
+    /// ```
+    /// use lib_table_top::games::solitaire::freecell::{Action, ActionError, Deal, GameState, Pile::*};
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    ///
+    /// let game = GameState::new(Deal::Microsoft(1));
+    /// assert_eq!(
+    ///     game.apply_action(Action { from: Cascade(0), to: Cascade(1), cards: 1 }),
+    ///     Err(ActionError::CantStack { card: Card(Six, Spades), onto: Card(Nine, Clubs) })
+    /// );
+    /// ```
+    pub fn apply_action(&self, action: Action) -> Result<Self, ActionError> {
+        self.check(action)?;
+
+        let mut game = self.clone();
+        let moving: Vec<Card> = match action.from {
+            Cell(index) => game.cells[index as usize].take().into_iter().collect(),
+            Cascade(index) => {
+                let cascade = &mut game.cascades[index as usize];
+                cascade.split_off(cascade.len() - usize::from(action.cards))
+            }
+            Foundation => unreachable!("Checked when the action was checked"),
+        };
+
+        match action.to {
+            Cell(index) => game.cells[index as usize] = Some(moving[0]),
+            Cascade(index) => game.cascades[index as usize].extend(moving),
+            Foundation => {
+                game.foundations = game
+                    .foundations
+                    .place(moving[0])
+                    .expect("Checked when the action was checked")
+            }
+        }
+
+        game.history.push_back(action);
+        Ok(game)
+    }
+
+    /// Every move that can be made, including each number of cards of a run that can be moved
+    /// ```
+    /// use lib_table_top::games::solitaire::freecell::{Action, Deal, GameState, Pile::*};
+    ///
+    /// let game = GameState::new(Deal::Microsoft(1));
+    /// let actions = game.valid_actions();
+    /// assert!(actions.contains(&Action { from: Cascade(0), to: Cell(3), cards: 1 }));
+    /// assert!(actions.iter().all(|&action| game.apply_action(action).is_ok()));
+    /// ```
+    pub fn valid_actions(&self) -> Vec<Action> {
+        let sources = (0..CASCADES as u8)
+            .map(Cascade)
+            .chain((0..CELLS as u8).map(Cell));
+        let destinations: Vec<Pile> = sources.clone().chain(Some(Foundation)).collect();
+
+        sources
+            .flat_map(|from| {
+                let run = self.run_length(from) as u8;
+                destinations
+                    .iter()
+                    .flat_map(move |&to| (1..=run).map(move |cards| Action { from, to, cards }))
+            })
+            .filter(|&action| self.check(action).is_ok())
+            .collect()
+    }
+
+    /// ```
+    /// use lib_table_top::games::solitaire::freecell::{Deal, GameState, Status};
+    ///
+    /// assert_eq!(GameState::new(Deal::Microsoft(1)).status(), Status::InProgress);
+    /// ```
+    pub fn status(&self) -> Status {
+        if self.foundations.is_complete() {
+            Status::Win
+        } else if self.valid_actions().is_empty() {
+            Status::Stuck
+        } else {
+            Status::InProgress
+        }
+    }
+}
+
+/// Whether a card can go on another in a cascade, one rank lower in the other color
+fn can_stack(card: Card, onto: Card) -> bool {
+    card.color() != onto.color() && card.rank() as u8 + 1 == onto.rank() as u8
+}
+
+impl Play for GameState {
+    type Action = Action;
+    type Player = ();
+    type Status = Status;
+    type Error = ActionError;
+
+    fn apply_action(&self, action: Action) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<Action> {
+        self.valid_actions()
+    }
+
+    fn players(&self) -> Vec<()> {
+        vec![()]
+    }
+
+    fn whose_turn(&self) {}
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != Status::InProgress
+    }
+
+    fn history(&self) -> Vec<Action> {
+        self.history().copied().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = Action;
+    type Error = ActionError;
+
+    fn initial(&self) -> Self {
+        Self::new(self.deal)
+    }
+
+    fn actions(&self) -> Vec<Action> {
+        self.history().copied().collect()
+    }
+
+    fn apply(&self, action: Action) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::deck::{Rank::*, Suit::*};
+
+    #[test]
+    fn test_microsoft_deal_one() {
+        let rows = [
+            "JD 2D 9H JC 5D 7H 7C 5H",
+            "KD KC 9S 5S AD QC KH 3H",
+            "2S KS 9D QD JS AS AH 3C",
+            "4C 5C TS QH 4H AC 4D 7S",
+            "3S TD 4S TH 8H 2C JH 7D",
+            "6D 8S 8D QS 6C 3D 8C TC",
+            "6S 9C 2H 6H",
+        ];
+        let parse = |label: &str| {
+            let mut chars = label.chars();
+            let rank = "A23456789TJQK".find(chars.next().unwrap()).unwrap();
+            let suit = "CDHS".find(chars.next().unwrap()).unwrap();
+            Card(Rank::ALL[rank], Suit::ALL[suit])
+        };
+
+        let cascades = Deal::Microsoft(1).cascades();
+        for (row, labels) in rows.iter().enumerate() {
+            for (cascade, label) in labels.split(' ').enumerate() {
+                assert_eq!(cascades[cascade][row], parse(label));
+            }
+        }
+    }
+
+    #[test]
+    fn test_supermoves() {
+        let mut game = GameState::new(Deal::Microsoft(1));
+        game.cascades = Default::default();
+        game.cascades[0] = vec![
+            Card(King, Hearts),
+            Card(Queen, Hearts),
+            Card(Jack, Spades),
+            Card(Ten, Diamonds),
+            Card(Nine, Clubs),
+            Card(Eight, Hearts),
+        ];
+        game.cascades[1] = vec![Card(Two, Spades), Card(Nine, Diamonds)];
+        game.cascades[2] = vec![Card(Ace, Spades)];
+        game.cells = [Some(Card(Ace, Hearts)), Some(Card(Ace, Clubs)), None, None];
+        let run = |cards| Action {
+            from: Cascade(0),
+            to: Cascade(3),
+            cards,
+        };
+
+        // Two free cells and five empty cascades, four of them usable for an empty destination
+        assert_eq!(game.run_length(Cascade(0)), 5);
+        assert_eq!(game.max_cards(Cascade(1)), 96);
+        assert_eq!(game.max_cards(Cascade(3)), 48);
+        assert_eq!(
+            game.apply_action(run(6)),
+            Err(NotASequence {
+                pile: Cascade(0),
+                cards: 6
+            })
+        );
+
+        game.cells = [Some(Card(Ace, Hearts)); CELLS];
+        game.cascades[4..].iter_mut().for_each(|cascade| {
+            cascade.push(Card(Ace, Diamonds));
+        });
+        assert_eq!(game.max_cards(Cascade(3)), 1);
+        assert_eq!(
+            game.apply_action(run(2)),
+            Err(TooManyCards { cards: 2, max: 1 })
+        );
+
+        game.cells[0] = None;
+        let moved = game.apply_action(run(2)).unwrap();
+        assert_eq!(moved.cascades[3], [Card(Nine, Clubs), Card(Eight, Hearts)]);
+        assert_eq!(moved.cascades[0].last(), Some(&Card(Ten, Diamonds)));
+        assert_eq!(
+            game.apply_action(Action {
+                from: Cascade(0),
+                to: Cascade(1),
+                cards: 1
+            }),
+            Err(CantStack {
+                card: Card(Eight, Hearts),
+                onto: Card(Nine, Diamonds)
+            })
+        );
+    }
+
+    #[test]
+    fn test_moves_keep_every_card() {
+        let mut game = GameState::new(Deal::Seeded(RngSeed([3; 32])));
+
+        for _ in 0..200 {
+            let actions = game.valid_actions();
+            if actions.is_empty() {
+                break;
+            }
+            // Prefers the foundations so the game makes progress
+            let action = actions
+                .iter()
+                .find(|action| action.to == Foundation)
+                .unwrap_or(&actions[actions.len() / 2]);
+            game = game.apply_action(*action).unwrap();
+
+            let cards = game.cells.iter().flatten().count()
+                + game.cascades.iter().map(Vec::len).sum::<usize>()
+                + game.foundations.total();
+            assert_eq!(cards, 52);
+        }
+
+        let replayed = game
+            .actions()
+            .into_iter()
+            .try_fold(game.initial(), |game, action| game.apply_action(action))
+            .unwrap();
+        assert_eq!(replayed, game);
+    }
+}
@@ -0,0 +1 @@
+pub mod freecell;
@@ -0,0 +1,1187 @@
+use enum_map::EnumMap;
+use im::Vector;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::common::deck::dealing::Pattern;
+use crate::common::deck::{Card, Deck, StandardDeck, Suit};
+use crate::common::game::Play;
+use crate::common::info::GameInfo;
+use crate::common::rand::RngSeed;
+use crate::common::replay::Replayable;
+use crate::common::results::{
+    settings_hash, GameKind, GameResult, Outcome, PlayerResult, Summarize, Termination,
+};
+use crate::common::settings::{Builder, FromSettings};
+use crate::common::tricks::{must_match, RankOrder, TrickRules};
+use crate::common::views::{Perspective, Viewable};
+
+/// The seats around the table, in the order play goes. Partners sit across from each other
+#[derive(
+    Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[repr(u8)]
+pub enum Player {
+    P1 = 1,
+    P2 = 2,
+    P3 = 3,
+    P4 = 4,
+}
+
+use Player::*;
+
+impl Player {
+    pub const ALL: [Self; 4] = [P1, P2, P3, P4];
+
+    /// The player to the left, who plays next
+    /// ```
+    /// use lib_table_top::games::spades::Player::*;
+    ///
+    /// assert_eq!(P1.next(), P2);
+    /// assert_eq!(P4.next(), P1);
+    /// ```
+    pub fn next(&self) -> Self {
+        match self {
+            P1 => P2,
+            P2 => P3,
+            P3 => P4,
+            P4 => P1,
+        }
+    }
+
+    /// The player across the table
+    /// ```
+    /// use lib_table_top::games::spades::Player::*;
+    ///
+    /// assert_eq!(P1.partner(), P3);
+    /// assert_eq!(P4.partner(), P2);
+    /// ```
+    pub fn partner(&self) -> Self {
+        self.next().next()
+    }
+
+    pub fn partnership(&self) -> Partnership {
+        match self {
+            P1 | P3 => NorthSouth,
+            P2 | P4 => EastWest,
+        }
+    }
+}
+
+/// The two teams, `P1` sits north and play goes clockwise
+#[derive(
+    Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum Partnership {
+    NorthSouth,
+    EastWest,
+}
+
+use Partnership::*;
+
+impl Partnership {
+    pub const ALL: [Self; 2] = [NorthSouth, EastWest];
+
+    /// The partners, in seat order
+    pub fn players(&self) -> [Player; 2] {
+        match self {
+            NorthSouth => [P1, P3],
+            EastWest => [P2, P4],
+        }
+    }
+
+    pub fn opponents(&self) -> Self {
+        match self {
+            NorthSouth => EastWest,
+            EastWest => NorthSouth,
+        }
+    }
+}
+
+/// The points for making a nil bid, they're lost instead if the bidder takes a trick
+pub const NIL_POINTS: i32 = 100;
+
+/// The points for making a blind nil bid, they're lost instead if the bidder takes a trick
+pub const BLIND_NIL_POINTS: i32 = 200;
+
+/// How many tricks a player says they'll take
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bid {
+    /// At least this many tricks, from 1 to 13, added to the partner's bid for the partnership's
+    /// contract
+    Tricks(u8),
+    /// No tricks at all, scored on its own instead of with the partner's bid
+    Nil,
+    /// Nil, bid before looking at the hand, for double the points. Only a partnership that's
+    /// far enough behind can bid it, see [`Rules::blind_nil_deficit`](Rules::blind_nil_deficit)
+    /// and [`Action::LookAtHand`](Action::LookAtHand)
+    BlindNil,
+}
+
+impl Bid {
+    /// The tricks the bid adds to the partnership's contract
+    pub fn tricks(&self) -> u8 {
+        match self {
+            Bid::Tricks(tricks) => *tricks,
+            Bid::Nil | Bid::BlindNil => 0,
+        }
+    }
+
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Bid::Nil | Bid::BlindNil)
+    }
+}
+
+/// What a partnership scores in a round from each partner's bid and the tricks they took,
+/// returning the points and the bags, before any bag penalty. A made contract scores ten points
+/// a trick, and each trick over is a bag worth a point. A missed contract loses ten points a
+/// trick. Nil bids score on their own, and the tricks a nil bidder takes don't count toward
+/// their partner's contract but are bags
+/// ```
+/// use lib_table_top::games::spades::{score_partnership, Bid::*};
+///
+/// assert_eq!(score_partnership([(Tricks(4), 5), (Tricks(2), 2)]), (61, 1));
+/// assert_eq!(score_partnership([(Tricks(4), 3), (Tricks(2), 2)]), (-60, 0));
+/// assert_eq!(score_partnership([(Tricks(4), 4), (Nil, 0)]), (140, 0));
+/// assert_eq!(score_partnership([(Tricks(4), 3), (BlindNil, 1)]), (-40 - 200 + 1, 1));
+/// ```
+pub fn score_partnership(partners: [(Bid, u8); 2]) -> (i32, u32) {
+    let contract: u8 = partners.iter().map(|(bid, _)| bid.tricks()).sum();
+    let taken: u8 = partners
+        .iter()
+        .filter(|(bid, _)| !bid.is_nil())
+        .map(|(_, tricks)| tricks)
+        .sum();
+
+    let mut points = 0;
+    let mut bags = 0;
+
+    for &(bid, tricks) in partners.iter() {
+        let value = match bid {
+            Bid::Nil => NIL_POINTS,
+            Bid::BlindNil => BLIND_NIL_POINTS,
+            Bid::Tricks(_) => continue,
+        };
+
+        if tricks == 0 {
+            points += value;
+        } else {
+            points += i32::from(tricks) - value;
+            bags += u32::from(tricks);
+        }
+    }
+
+    if contract > 0 {
+        if taken >= contract {
+            points += 10 * i32::from(contract) + i32::from(taken - contract);
+            bags += u32::from(taken - contract);
+        } else {
+            points -= 10 * i32::from(contract);
+        }
+    }
+
+    (points, bags)
+}
+
+/// The scoring rules, which vary a lot between playgroups. The defaults are the most common ones
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// The game ends after a round where a partnership has at least this many points and more
+    /// than the other partnership
+    pub target_score: i32,
+    /// Every time a partnership collects this many bags it loses the bag penalty
+    pub bag_limit: u32,
+    pub bag_penalty: i32,
+    /// How far behind a partnership has to be for its players to bid blind nil, `None` if blind
+    /// nil can't be bid at all
+    pub blind_nil_deficit: Option<i32>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            target_score: 500,
+            bag_limit: 10,
+            bag_penalty: 100,
+            blind_nil_deficit: Some(100),
+        }
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// The description of the game for lobbies, see [`GameInfo`](struct@GameInfo)
+pub const INFO: GameInfo = GameInfo {
+    kind: GameKind::Spades,
+    name: "Spades",
+    rules: "Two partnerships bid how many tricks they'll take and then play them out, following \
+            suit when they can, with spades as trump. Making a bid scores ten points a trick and \
+            tricks over are bags, collecting too many bags costs points. Nil bids score on their \
+            own, the first partnership to 500 wins",
+    min_players: 4,
+    max_players: 4,
+    estimated_duration: Duration::from_secs(45 * 60),
+    settings_schema_json: r#"{
+        "type": "object",
+        "required": ["seed"],
+        "properties": {
+            "seed": {"type": "string", "pattern": "^[0-9a-f]{64}$"},
+            "rules": {
+                "type": "object",
+                "properties": {
+                    "target_score": {"type": "integer", "minimum": 1, "default": 500},
+                    "bag_limit": {"type": "integer", "minimum": 1, "default": 10},
+                    "bag_penalty": {"type": "integer", "default": 100},
+                    "blind_nil_deficit": {"type": ["integer", "null"], "minimum": 0, "default": 100}
+                }
+            }
+        }
+    }"#,
+};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    pub seed: RngSeed,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rules: Rules,
+}
+
+impl Settings {
+    /// Checks that the settings can make a game that ends. The builder always checks, settings
+    /// made by hand or deserialized should be checked before they're used
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{Settings, SettingsError};
+    ///
+    /// let mut settings = Settings { seed: RngSeed([0; 32]), rules: Default::default() };
+    /// assert_eq!(settings.validate(), Ok(()));
+    ///
+    /// settings.rules.bag_limit = 0;
+    /// assert_eq!(settings.validate(), Err(SettingsError::InvalidBagLimit));
+    /// ```
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if self.rules.target_score <= 0 {
+            return Err(SettingsError::InvalidTargetScore {
+                target_score: self.rules.target_score,
+            });
+        }
+
+        if self.rules.bag_limit == 0 {
+            return Err(SettingsError::InvalidBagLimit);
+        }
+
+        Ok(())
+    }
+}
+
+/// The various errors that can be returned from building invalid settings
+#[derive(Clone, Copy, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingsError {
+    #[error("A seed is required")]
+    MissingSeed,
+    #[error("The target score has to be positive, not {:?}", target_score)]
+    InvalidTargetScore { target_score: i32 },
+    #[error("At least one bag has to be collected for a penalty")]
+    InvalidBagLimit,
+}
+
+impl SettingsError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::spades::SettingsError::*;
+    ///
+    /// assert_eq!(MissingSeed.code(), "missing_seed");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            SettingsError::MissingSeed => "missing_seed",
+            SettingsError::InvalidTargetScore { .. } => "invalid_target_score",
+            SettingsError::InvalidBagLimit => "invalid_bag_limit",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            SettingsError::MissingSeed | SettingsError::InvalidBagLimit => json!({}),
+            SettingsError::InvalidTargetScore { target_score } => {
+                json!({ "target_score": target_score })
+            }
+        }
+    }
+}
+
+/// Tools to build Spades games
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::spades::{Rules, SettingsBuilder, SettingsError};
+///
+/// let game = SettingsBuilder::new().seed(RngSeed([0; 32])).build_game().unwrap();
+/// assert_eq!(game.settings().rules.target_score, 500);
+///
+/// assert_eq!(SettingsBuilder::new().build(), Err(SettingsError::MissingSeed));
+///
+/// let rules = Rules { target_score: -100, ..Default::default() };
+/// assert_eq!(
+///   SettingsBuilder::new().seed(RngSeed([0; 32])).rules(rules).build(),
+///   Err(SettingsError::InvalidTargetScore { target_score: -100 })
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SettingsBuilder {
+    seed: Option<RngSeed>,
+    rules: Rules,
+}
+
+impl SettingsBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    settings_setters! {
+        seed: RngSeed,
+        rules: Rules,
+    }
+
+    pub fn build(self) -> Result<Settings, SettingsError> {
+        Builder::build(self)
+    }
+
+    pub fn build_game(self) -> Result<GameState, SettingsError> {
+        Builder::build_game(self)
+    }
+}
+
+impl Builder for SettingsBuilder {
+    type Settings = Settings;
+    type Error = SettingsError;
+
+    fn build(self) -> Result<Settings, SettingsError> {
+        let seed = self.seed.ok_or(SettingsError::MissingSeed)?;
+        let settings = Settings {
+            seed,
+            rules: self.rules,
+        };
+
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Bid(Bid),
+    Play(Card),
+    /// Picks up the hand while bidding. Players who could bid blind nil are dealt their hand
+    /// face down, and can't bid blind nil once they've looked at it
+    LookAtHand,
+}
+
+/// Whether the players are bidding or playing out the tricks
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phase {
+    Bidding,
+    Playing,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    InProgress,
+    /// A partnership reached the target score and is ahead, see
+    /// [`Rules::target_score`](Rules::target_score)
+    Win {
+        partnership: Partnership,
+        scores: BTreeMap<Partnership, i32>,
+    },
+}
+
+use Status::*;
+
+/// How a round went, kept for score sheets
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundScore {
+    pub bids: BTreeMap<Player, Bid>,
+    pub tricks: BTreeMap<Player, u8>,
+    /// The points each partnership scored in the round, including bag penalties
+    pub points: BTreeMap<Partnership, i32>,
+    /// The bags each partnership took in the round
+    pub bags: BTreeMap<Partnership, u32>,
+}
+
+#[derive(Clone, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionError {
+    #[error(
+        "It's {:?}'s turn and not {:?}'s turn",
+        correct_player,
+        attempted_player
+    )]
+    NotPlayerTurn {
+        attempted_player: Player,
+        correct_player: Player,
+    },
+    #[error("The game is already over")]
+    GameIsOver,
+    #[error("Bidding is over, {:?} has to play a card", player)]
+    NotBidding { player: Player },
+    #[error("{:?} can't play a card until everyone has bid", player)]
+    StillBidding { player: Player },
+    #[error("{:?} isn't a bid, bids are from 1 to 13 tricks or nil", bid)]
+    InvalidBid { bid: Bid },
+    #[error("{:?}'s partnership isn't far enough behind to bid blind nil", player)]
+    BlindNilNotAllowed { player: Player },
+    #[error("{:?} has already seen their hand", player)]
+    HandAlreadySeen { player: Player },
+    #[error("Player {:?} does not have card {:?}", player, card)]
+    PlayerDoesNotHaveCard { player: Player, card: Card },
+    #[error(
+        "Player {:?} has to follow {:?} and can't play {:?}",
+        player,
+        led,
+        card
+    )]
+    MustFollowSuit {
+        player: Player,
+        card: Card,
+        led: Suit,
+    },
+    #[error(
+        "Player {:?} can't lead {:?} until spades have been broken",
+        player,
+        card
+    )]
+    SpadesNotBroken { player: Player, card: Card },
+}
+
+use ActionError::*;
+
+impl ActionError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::spades::{ActionError::*, Player::*};
+    ///
+    /// let error = NotPlayerTurn { attempted_player: P1, correct_player: P2 };
+    /// assert_eq!(error.code(), "not_player_turn");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            NotPlayerTurn { .. } => "not_player_turn",
+            GameIsOver => "game_is_over",
+            NotBidding { .. } => "not_bidding",
+            StillBidding { .. } => "still_bidding",
+            InvalidBid { .. } => "invalid_bid",
+            BlindNilNotAllowed { .. } => "blind_nil_not_allowed",
+            HandAlreadySeen { .. } => "hand_already_seen",
+            PlayerDoesNotHaveCard { .. } => "player_does_not_have_card",
+            MustFollowSuit { .. } => "must_follow_suit",
+            SpadesNotBroken { .. } => "spades_not_broken",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            NotPlayerTurn {
+                attempted_player,
+                correct_player,
+            } => json!({
+                "attempted_player": attempted_player,
+                "correct_player": correct_player,
+            }),
+            GameIsOver => json!({}),
+            NotBidding { player }
+            | StillBidding { player }
+            | BlindNilNotAllowed { player }
+            | HandAlreadySeen { player } => json!({ "player": player }),
+            InvalidBid { bid } => json!({ "bid": bid }),
+            PlayerDoesNotHaveCard { player, card } | SpadesNotBroken { player, card } => {
+                json!({ "player": player, "card": card })
+            }
+            MustFollowSuit { player, card, led } => {
+                json!({ "player": player, "card": card, "led": led })
+            }
+        }
+    }
+}
+
+/// Spades is trump and aces are high
+fn trick_rules() -> TrickRules {
+    TrickRules::new(Some(Suit::Spades), RankOrder::AceHigh)
+}
+
+/// The cards that can go on a trick. Players follow the led suit if they can, and spades can't
+/// be led until one has been played on another suit, unless the hand is all spades
+fn playable(hand: &[Card], trick: &[(Player, Card)], spades_broken: bool) -> Vec<Card> {
+    match trick.first() {
+        Some(&(_, led)) => must_match(hand, |card| card.suit() == led.suit()),
+        None if spades_broken => hand.to_vec(),
+        None => must_match(hand, |card| card.suit() != Suit::Spades),
+    }
+}
+
+/// Whether a partnership is far enough behind to bid blind nil
+fn can_bid_blind_nil(
+    rules: &Rules,
+    scores: impl Fn(Partnership) -> i32,
+    partnership: Partnership,
+) -> bool {
+    match rules.blind_nil_deficit {
+        Some(deficit) => scores(partnership.opponents()) - scores(partnership) >= deficit,
+        None => false,
+    }
+}
+
+/// The minimal representation of a game, the settings and the actions taken, for serializing
+/// and persisting
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameHistory {
+    settings: Arc<Settings>,
+    history: Vector<(Player, Action)>,
+}
+
+impl GameHistory {
+    /// Replays the history to get the game state
+    pub fn game_state(&self) -> Result<GameState, ActionError> {
+        self.history
+            .iter()
+            .try_fold(GameState::new(self.settings.clone()), |game, &action| {
+                game.apply_action(action)
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameState {
+    game_history: GameHistory,
+    rng: Arc<ChaCha20Rng>,
+    dealer: Player,
+    hands: EnumMap<Player, Vec<Card>>,
+    bids: EnumMap<Player, Option<Bid>>,
+    /// Who has looked at their hand this round, see [`Action::LookAtHand`](Action::LookAtHand)
+    looked: EnumMap<Player, bool>,
+    tricks: EnumMap<Player, u8>,
+    /// The cards played to the trick in progress, in order
+    trick: Vec<(Player, Card)>,
+    last_trick: Option<Vec<(Player, Card)>>,
+    spades_broken: bool,
+    whose_turn: Player,
+    scores: EnumMap<Partnership, i32>,
+    /// The bags collected since the last bag penalty
+    bags: EnumMap<Partnership, u32>,
+    rounds: Vector<RoundScore>,
+}
+
+/// The view that anyone can see, the totally non secret parts of the game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObserverView {
+    pub whose_turn: Player,
+    pub dealer: Player,
+    pub phase: Phase,
+    pub bids: HashMap<Player, Bid>,
+    /// The tricks each player has taken this round
+    pub tricks: HashMap<Player, u8>,
+    /// The cards played to the trick in progress, in order
+    pub trick: Vec<(Player, Card)>,
+    pub last_trick: Option<Vec<(Player, Card)>>,
+    pub player_card_count: HashMap<Player, usize>,
+    pub spades_broken: bool,
+    pub scores: HashMap<Partnership, i32>,
+    /// The bags collected since the last bag penalty
+    pub bags: HashMap<Partnership, u32>,
+    pub rounds: Vec<RoundScore>,
+    pub rules: Rules,
+}
+
+/// The view of a player, their hand along with everything an observer can see
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub player: Player,
+    /// Empty while the hand is face down, see [`Action::LookAtHand`](Action::LookAtHand)
+    pub hand: Vec<Card>,
+    pub observer_view: ObserverView,
+}
+
+impl PlayerView {
+    /// The actions the player can take, empty if it isn't their turn. Bids are in order from
+    /// one trick to thirteen, then nil, and then blind nil and looking at the hand while it's
+    /// face down
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{Action, Bid, GameState, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings { seed: RngSeed([0; 32]), rules: Default::default() }));
+    /// let actions = game.player_view(P1).valid_actions();
+    /// assert_eq!(actions.len(), 14);
+    /// assert_eq!(actions[0], Action::Bid(Bid::Tricks(1)));
+    /// assert_eq!(actions[13], Action::Bid(Bid::Nil));
+    ///
+    /// assert!(game.player_view(P2).valid_actions().is_empty());
+    /// ```
+    pub fn valid_actions(&self) -> Vec<Action> {
+        let view = &self.observer_view;
+        if view.whose_turn != self.player {
+            return vec![];
+        }
+
+        match view.phase {
+            Phase::Bidding => {
+                let scores = |partnership| view.scores.get(&partnership).copied().unwrap_or(0);
+                // Everyone holds thirteen cards while bidding, so an empty hand is face down
+                let face_down = self.hand.is_empty()
+                    && can_bid_blind_nil(&view.rules, scores, self.player.partnership());
+
+                (1..=13)
+                    .map(Bid::Tricks)
+                    .chain(Some(Bid::Nil))
+                    .chain(if face_down { Some(Bid::BlindNil) } else { None })
+                    .map(Action::Bid)
+                    .chain(if face_down {
+                        Some(Action::LookAtHand)
+                    } else {
+                        None
+                    })
+                    .collect()
+            }
+            Phase::Playing => playable(&self.hand, &view.trick, view.spades_broken)
+                .into_iter()
+                .map(Action::Play)
+                .collect(),
+        }
+    }
+}
+
+/// The view of everything in the game, including every hand. This shouldn't be shown to players
+/// while the game is in progress
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OmniscientView {
+    pub hands: HashMap<Player, Vec<Card>>,
+    pub observer_view: ObserverView,
+}
+
+/// A view of the game redacted for a [`Perspective`](enum@Perspective)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    Observer(ObserverView),
+    Player(PlayerView),
+    Omniscient(OmniscientView),
+}
+
+impl FromSettings for GameState {
+    type Settings = Settings;
+
+    fn from_settings(settings: Arc<Settings>) -> Self {
+        Self::new(settings)
+    }
+}
+
+impl GameState {
+    /// Creates a new game and deals the first round. `P4` deals, so `P1` bids first and leads
+    /// the first trick
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{GameState, Phase, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings { seed: RngSeed([0; 32]), rules: Default::default() }));
+    /// assert_eq!(game.whose_turn(), P1);
+    /// assert_eq!(game.phase(), Phase::Bidding);
+    /// assert!(game.observer_view().player_card_count.values().all(|&count| count == 13));
+    /// ```
+    pub fn new(settings: Arc<Settings>) -> Self {
+        let rng = settings.seed.into_rng();
+        let mut game = Self {
+            game_history: GameHistory {
+                settings,
+                history: Vector::new(),
+            },
+            rng: Arc::new(rng),
+            dealer: P4,
+            hands: EnumMap::default(),
+            bids: EnumMap::default(),
+            looked: EnumMap::default(),
+            tricks: EnumMap::default(),
+            trick: Vec::new(),
+            last_trick: None,
+            spades_broken: false,
+            whose_turn: P1,
+            scores: EnumMap::default(),
+            bags: EnumMap::default(),
+            rounds: Vector::new(),
+        };
+        game.deal();
+        game
+    }
+
+    /// Shuffles and deals thirteen cards to everyone, starting to the dealer's left
+    fn deal(&mut self) {
+        let deck = StandardDeck::shuffled_with(Arc::make_mut(&mut self.rng));
+        let deal = Pattern::RoundRobin {
+            cards_per_player: 13,
+        }
+        .deal(deck.to_vec(), 4)
+        .expect("A deck has enough cards for four hands of thirteen");
+
+        let mut seat = self.dealer.next();
+        for hand in deal.hands {
+            self.hands[seat] = hand;
+            seat = seat.next();
+        }
+
+        self.bids = EnumMap::default();
+        self.looked = EnumMap::default();
+        self.tricks = EnumMap::default();
+        self.trick = Vec::new();
+        self.last_trick = None;
+        self.spades_broken = false;
+        self.whose_turn = self.dealer.next();
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.game_history.settings
+    }
+
+    /// The game history of the current game state, see [`GameHistory`](struct@GameHistory)
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{GameState, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings { seed: RngSeed([0; 32]), rules: Default::default() }));
+    /// assert_eq!(game.game_history().game_state(), Ok(game));
+    /// ```
+    pub fn game_history(&self) -> &GameHistory {
+        &self.game_history
+    }
+
+    /// The actions taken so far, with who took them
+    pub fn history(&self) -> impl Iterator<Item = (Player, Action)> + '_ {
+        self.game_history.history.iter().copied()
+    }
+
+    pub fn whose_turn(&self) -> Player {
+        self.whose_turn
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = Player> + Clone {
+        Player::ALL.iter().copied()
+    }
+
+    pub fn phase(&self) -> Phase {
+        if self.bids.values().all(Option::is_some) {
+            Phase::Playing
+        } else {
+            Phase::Bidding
+        }
+    }
+
+    /// A partnership's score across the rounds played
+    pub fn score(&self, partnership: Partnership) -> i32 {
+        self.scores[partnership]
+    }
+
+    pub fn rounds_played(&self) -> usize {
+        self.rounds.len()
+    }
+
+    pub fn status(&self) -> Status {
+        let scores: BTreeMap<Partnership, i32> = self.scores.iter().map(|(p, &s)| (p, s)).collect();
+        let (ns, ew) = (self.scores[NorthSouth], self.scores[EastWest]);
+
+        if ns.max(ew) >= self.settings().rules.target_score && ns != ew {
+            let partnership = if ns > ew { NorthSouth } else { EastWest };
+            Win {
+                partnership,
+                scores,
+            }
+        } else {
+            InProgress
+        }
+    }
+
+    pub fn observer_view(&self) -> ObserverView {
+        ObserverView {
+            whose_turn: self.whose_turn,
+            dealer: self.dealer,
+            phase: self.phase(),
+            bids: self
+                .bids
+                .iter()
+                .filter_map(|(player, bid)| Some((player, (*bid)?)))
+                .collect(),
+            tricks: self.tricks.iter().map(|(p, &t)| (p, t)).collect(),
+            trick: self.trick.clone(),
+            last_trick: self.last_trick.clone(),
+            player_card_count: self.hands.iter().map(|(p, hand)| (p, hand.len())).collect(),
+            spades_broken: self.spades_broken,
+            scores: self.scores.iter().map(|(p, &s)| (p, s)).collect(),
+            bags: self.bags.iter().map(|(p, &b)| (p, b)).collect(),
+            rounds: self.rounds.iter().cloned().collect(),
+            rules: self.settings().rules,
+        }
+    }
+
+    /// What a player can see. A player who could bid blind nil sees an empty hand until they
+    /// look at it or bid
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{Action, GameState, Player::*, Rules, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let rules = Rules { blind_nil_deficit: Some(0), ..Default::default() };
+    /// let game = GameState::new(Arc::new(Settings { seed: RngSeed([0; 32]), rules }));
+    /// assert!(game.player_view(P1).hand.is_empty());
+    ///
+    /// let game = game.apply_action((P1, Action::LookAtHand)).unwrap();
+    /// assert_eq!(game.player_view(P1).hand.len(), 13);
+    /// assert_eq!(game.whose_turn(), P1);
+    /// ```
+    pub fn player_view(&self, player: Player) -> PlayerView {
+        let hand = if self.face_down(player) {
+            Vec::new()
+        } else {
+            self.hands[player].clone()
+        };
+
+        PlayerView {
+            player,
+            hand,
+            observer_view: self.observer_view(),
+        }
+    }
+
+    pub fn current_player_view(&self) -> PlayerView {
+        self.player_view(self.whose_turn)
+    }
+
+    pub fn omniscient_view(&self) -> OmniscientView {
+        OmniscientView {
+            hands: self
+                .hands
+                .iter()
+                .map(|(p, hand)| (p, hand.clone()))
+                .collect(),
+            observer_view: self.observer_view(),
+        }
+    }
+
+    /// Bids, looks at a hand, or plays a card. Once the fourth card of a trick is played the trick goes to its
+    /// winner, who leads the next one, and once the last trick is taken the round is scored and
+    /// the next round is dealt
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::spades::{Action, ActionError, Bid, GameState, Player::*, Settings};
+    /// use std::sync::Arc;
+    ///
+    /// let game = GameState::new(Arc::new(Settings { seed: RngSeed([0; 32]), rules: Default::default() }));
+    /// assert_eq!(
+    ///   game.apply_action((P2, Action::Bid(Bid::Tricks(3)))),
+    ///   Err(ActionError::NotPlayerTurn { attempted_player: P2, correct_player: P1 })
+    /// );
+    /// assert_eq!(
+    ///   game.apply_action((P1, Action::Bid(Bid::Tricks(14)))),
+    ///   Err(ActionError::InvalidBid { bid: Bid::Tricks(14) })
+    /// );
+    /// assert_eq!(
+    ///   game.apply_action((P1, Action::Play(Card(Ace, Spades)))),
+    ///   Err(ActionError::StillBidding { player: P1 })
+    /// );
+    ///
+    /// let game = game.apply_action((P1, Action::Bid(Bid::Tricks(3)))).unwrap();
+    /// assert_eq!(game.whose_turn(), P2);
+    /// ```
+    pub fn apply_action(&self, (player, action): (Player, Action)) -> Result<Self, ActionError> {
+        if self.status() != InProgress {
+            return Err(GameIsOver);
+        }
+
+        if player != self.whose_turn {
+            return Err(NotPlayerTurn {
+                attempted_player: player,
+                correct_player: self.whose_turn,
+            });
+        }
+
+        match action {
+            Action::Bid(bid) => self.validate_bid(player, bid)?,
+            Action::Play(card) => self.validate_play(player, card)?,
+            Action::LookAtHand => self.validate_look(player)?,
+        }
+
+        let mut new_game = self.clone();
+
+        match action {
+            Action::Bid(bid) => {
+                new_game.bids[player] = Some(bid);
+                // The player left of the dealer bids first and leads, so after the last bid the
+                // turn is already with the right player
+                new_game.whose_turn = player.next();
+            }
+            Action::Play(card) => new_game.play_card(player, card),
+            // Looking doesn't use up the turn, the player still has to bid
+            Action::LookAtHand => new_game.looked[player] = true,
+        }
+
+        new_game.game_history.history.push_back((player, action));
+        Ok(new_game)
+    }
+
+    fn validate_bid(&self, player: Player, bid: Bid) -> Result<(), ActionError> {
+        if self.phase() != Phase::Bidding {
+            return Err(NotBidding { player });
+        }
+
+        match bid {
+            Bid::Tricks(tricks) if !(1..=13).contains(&tricks) => Err(InvalidBid { bid }),
+            Bid::BlindNil
+                if !can_bid_blind_nil(
+                    &self.settings().rules,
+                    |partnership| self.scores[partnership],
+                    player.partnership(),
+                ) =>
+            {
+                Err(BlindNilNotAllowed { player })
+            }
+            Bid::BlindNil if !self.face_down(player) => Err(HandAlreadySeen { player }),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_look(&self, player: Player) -> Result<(), ActionError> {
+        if self.phase() != Phase::Bidding {
+            return Err(NotBidding { player });
+        }
+
+        if !self.face_down(player) {
+            return Err(HandAlreadySeen { player });
+        }
+
+        Ok(())
+    }
+
+    /// Whether a player's hand is still face down, because they could bid blind nil and haven't
+    /// looked at it or bid yet
+    fn face_down(&self, player: Player) -> bool {
+        self.phase() == Phase::Bidding
+            && self.bids[player].is_none()
+            && !self.looked[player]
+            && can_bid_blind_nil(
+                &self.settings().rules,
+                |partnership| self.scores[partnership],
+                player.partnership(),
+            )
+    }
+
+    fn validate_play(&self, player: Player, card: Card) -> Result<(), ActionError> {
+        if self.phase() != Phase::Playing {
+            return Err(StillBidding { player });
+        }
+
+        if !self.hands[player].contains(&card) {
+            return Err(PlayerDoesNotHaveCard { player, card });
+        }
+
+        if playable(&self.hands[player], &self.trick, self.spades_broken).contains(&card) {
+            return Ok(());
+        }
+
+        match self.trick.first() {
+            Some(&(_, led)) => Err(MustFollowSuit {
+                player,
+                card,
+                led: led.suit(),
+            }),
+            None => Err(SpadesNotBroken { player, card }),
+        }
+    }
+
+    /// Plays a card to the trick, see `validate_play`
+    fn play_card(&mut self, player: Player, card: Card) {
+        self.hands[player].retain(|&held| held != card);
+        self.spades_broken |= card.suit() == Suit::Spades;
+        self.trick.push((player, card));
+
+        let winner = match trick_rules().finished(&self.trick, Player::ALL.len()) {
+            Some(winner) => winner,
+            None => {
+                self.whose_turn = player.next();
+                return;
+            }
+        };
+        self.tricks[winner] += 1;
+        self.last_trick = Some(std::mem::take(&mut self.trick));
+        self.whose_turn = winner;
+
+        if self.hands.values().all(Vec::is_empty) {
+            self.finish_round();
+        }
+    }
+
+    /// Scores the round and deals the next one, unless the game is over
+    fn finish_round(&mut self) {
+        let rules = self.settings().rules;
+        let mut round = RoundScore {
+            bids: self
+                .bids
+                .iter()
+                .map(|(player, bid)| (player, bid.expect("Everyone bids before playing")))
+                .collect(),
+            tricks: self.tricks.iter().map(|(p, &t)| (p, t)).collect(),
+            points: BTreeMap::new(),
+            bags: BTreeMap::new(),
+        };
+
+        for &partnership in Partnership::ALL.iter() {
+            let partners = partnership
+                .players()
+                .map(|player| (round.bids[&player], round.tricks[&player]));
+            let (mut points, bags) = score_partnership(partners);
+
+            self.bags[partnership] += bags;
+            while self.bags[partnership] >= rules.bag_limit {
+                self.bags[partnership] -= rules.bag_limit;
+                points -= rules.bag_penalty;
+            }
+
+            self.scores[partnership] += points;
+            round.points.insert(partnership, points);
+            round.bags.insert(partnership, bags);
+        }
+
+        self.rounds.push_back(round);
+
+        if self.status() == InProgress {
+            self.dealer = self.dealer.next();
+            self.deal();
+        }
+    }
+
+    /// The cards a perspective isn't allowed to see, for checking views with
+    /// [`leak_check`](fn@crate::common::views::leak_check)
+    pub fn hidden_cards(&self, perspective: Perspective<Player>) -> Vec<Card> {
+        self.players()
+            .filter(|&player| match perspective {
+                Perspective::Omniscient => false,
+                _ => !perspective.can_see(player) || self.face_down(player),
+            })
+            .flat_map(|player| self.hands[player].iter().copied())
+            .collect()
+    }
+}
+
+impl Viewable for GameState {
+    type Player = Player;
+    type View = View;
+
+    fn view(&self, perspective: Perspective<Player>) -> View {
+        match perspective {
+            Perspective::Observer => View::Observer(self.observer_view()),
+            Perspective::Player(player) => View::Player(self.player_view(player)),
+            Perspective::Omniscient => View::Omniscient(self.omniscient_view()),
+        }
+    }
+}
+
+impl Play for GameState {
+    type Action = (Player, Action);
+    type Player = Player;
+    type Status = Status;
+    type Error = ActionError;
+
+    fn apply_action(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<(Player, Action)> {
+        if self.status() != InProgress {
+            return Vec::new();
+        }
+
+        let player = self.whose_turn();
+        self.player_view(player)
+            .valid_actions()
+            .into_iter()
+            .map(|action| (player, action))
+            .collect()
+    }
+
+    fn players(&self) -> Vec<Player> {
+        self.players().collect()
+    }
+
+    fn whose_turn(&self) -> Player {
+        self.whose_turn()
+    }
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != InProgress
+    }
+
+    fn history(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = (Player, Action);
+    type Error = ActionError;
+
+    fn initial(&self) -> Self {
+        Self::new(self.game_history.settings.clone())
+    }
+
+    fn actions(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+
+    fn apply(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+}
+
+/// Both partners share the partnership's result and score
+impl Summarize for GameState {
+    type Player = Player;
+
+    fn game_result(&self) -> Option<GameResult<Player>> {
+        let winner = match self.status() {
+            InProgress => return None,
+            Win { partnership, .. } => partnership,
+        };
+
+        let players = self
+            .players()
+            .map(|player| {
+                let won = player.partnership() == winner;
+
+                PlayerResult {
+                    player,
+                    outcome: if won { Outcome::Win } else { Outcome::Loss },
+                    score: Some(i64::from(self.scores[player.partnership()])),
+                    place: Some(if won { 1 } else { 2 }),
+                }
+            })
+            .collect();
+
+        Some(GameResult {
+            kind: GameKind::Spades,
+            settings_hash: settings_hash(self.settings()),
+            players,
+            move_count: self.history().count(),
+            duration: None,
+            termination: Termination::Completed,
+        })
+    }
+}
@@ -1,9 +1,37 @@
 use enum_map::EnumMap;
 use im::Vector;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use serde_repr::*;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
+use crate::common::clock::{ShowsClock, TimeoutHooks};
+use crate::common::coords::{self, CoordsError};
+use crate::common::game::Play;
+use crate::common::info::GameInfo;
+use crate::common::ml::{Encode, Encoding};
+use crate::common::replay::Replayable;
+use crate::common::results::{
+    settings_hash, winner_takes_all, GameKind, GameResult, Outcome, PlayerResult, Summarize,
+    Termination,
+};
+use crate::common::search::{Searchable, WIN};
+use crate::common::turn::PendingInput;
+use crate::common::views::{Perspective, ViewMetadata, Viewable};
+
+/// The description of the game for lobbies, see [`GameInfo`](struct@GameInfo)
+pub const INFO: GameInfo = GameInfo {
+    kind: GameKind::TicTacToe,
+    name: "Tic Tac Toe",
+    rules: "Players take turns placing their mark on a 3x3 grid, the first to get three in a row \
+            across, down, or diagonally wins, and a full grid without a line is a draw",
+    min_players: 2,
+    max_players: 2,
+    estimated_duration: Duration::from_secs(60),
+    settings_schema_json: r#"{"type": "null"}"#,
+};
+
 /// Player pieces, (P1 == X & P2 == O)
 #[derive(Copy, Clone, Debug, Enum, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Player {
@@ -42,6 +70,38 @@ pub enum Error {
 
 use Error::*;
 
+impl Error {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{Error::*, Player::*};
+    ///
+    /// assert_eq!(OtherPlayerTurn { attempted: P2 }.code(), "other_player_turn");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            SpaceIsTaken { .. } => "space_is_taken",
+            OtherPlayerTurn { .. } => "other_player_turn",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{Error::*, Row::*, Col::*};
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(
+    ///   SpaceIsTaken { attempted: (Col1, Row2) }.details(),
+    ///   json!({"attempted": [1, 2]})
+    /// );
+    /// ```
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            SpaceIsTaken { attempted } => json!({ "attempted": attempted }),
+            OtherPlayerTurn { attempted } => json!({ "attempted": attempted }),
+        }
+    }
+}
+
 /// A `Row` of the Tic-Tac-Toe board
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Enum, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
@@ -91,13 +151,35 @@ pub const POSSIBLE_WINS: [[(Col, Row); 3]; 8] = [
 
 /// A type representing a position on the board, denoted in terms of (x, y)
 pub type Position = (Col, Row);
+
+/// The label of a position, from "a1" for `(Col0, Row0)` to "c3" for `(Col2, Row2)`
+/// ```
+/// use lib_table_top::games::tic_tac_toe::{position_label, Col::*, Row::*};
+///
+/// assert_eq!(position_label((Col1, Row2)), "b3");
+/// ```
+pub fn position_label((col, row): Position) -> String {
+    coords::label(col as u8, row as u8)
+}
+
+/// Reads a position from its label
+/// ```
+/// use lib_table_top::games::tic_tac_toe::{parse_position, Col::*, Row::*};
+///
+/// assert_eq!(parse_position("B3"), Ok((Col1, Row2)));
+/// assert!(parse_position("d1").is_err());
+/// ```
+pub fn parse_position(label: &str) -> Result<Position, CoordsError> {
+    coords::parse_label(label, 3, 3)
+        .map(|(col, row)| (Col::ALL[col as usize], Row::ALL[row as usize]))
+}
 /// A representation of the Tic-Tac-Toe Board
 pub type Board = EnumMap<Col, EnumMap<Row, Option<Player>>>;
 /// An action being taken by a player to claim a position
 pub type Action = (Player, Position);
 
 /// The three states a game can be in
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Status {
     /// There are still available positions to be claimed on the board
     InProgress,
@@ -112,10 +194,57 @@ pub enum Status {
 
 use Status::*;
 
+/// Everything a spectator can see, without the full history of the game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObserverView {
+    /// The claimed positions, indexed by col and then row like [`Board`](type@Board)
+    pub board: [[Option<Player>; 3]; 3],
+    pub whose_turn: Player,
+    pub last_action: Option<Action>,
+    pub status: Status,
+    /// When the player to move runs out of time, empty unless added with
+    /// [`with_clock`](crate::common::clock::ShowsClock::with_clock)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<SystemTime>,
+    /// How long the player to move had left when the view was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remaining: Option<Duration>,
+    /// Data the host attached to the view, see [`ViewMetadata`](crate::common::views::ViewMetadata)
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+}
+
+impl ViewMetadata for ObserverView {
+    fn metadata(&self) -> &Map<String, Value> {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.metadata
+    }
+}
+
+impl ShowsClock for ObserverView {
+    fn set_countdown(&mut self, deadline: SystemTime, time_remaining: Duration) {
+        self.deadline = Some(deadline);
+        self.time_remaining = Some(time_remaining);
+    }
+}
+
 /// Representation of a Tic-Tac-Toe game
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameState {
     history: Vector<Position>,
+    #[serde(default = "first_player", skip_serializing_if = "is_first_player")]
+    first_player: Player,
+}
+
+fn first_player() -> Player {
+    P1
+}
+
+fn is_first_player(player: &Player) -> bool {
+    *player == first_player()
 }
 
 impl Default for GameState {
@@ -134,8 +263,25 @@ impl GameState {
     /// assert_eq!(game1, game2);
     /// ```
     pub fn new() -> Self {
+        Self::starting_with(first_player())
+    }
+
+    /// Make a new Tic-Tac-Toe game where either player moves first, for series where the first
+    /// player changes, see [`FirstPlayer`](enum@crate::common::turn::FirstPlayer)
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Player::*};
+    ///
+    /// let game = GameState::starting_with(P2);
+    /// assert_eq!(game.whose_turn(), P2);
+    ///
+    /// let game = game.apply_action(game.valid_actions().next().unwrap()).unwrap();
+    /// assert_eq!(game.history().next().unwrap().0, P2);
+    /// assert_eq!(game.whose_turn(), P1);
+    /// ```
+    pub fn starting_with(first_player: Player) -> Self {
         GameState {
             history: Vector::new(),
+            first_player,
         }
     }
 
@@ -164,11 +310,49 @@ impl GameState {
     /// )
     /// ```
     pub fn history(&self) -> impl Iterator<Item = Action> + '_ {
-        let players = [P1, P2].iter().cycle();
+        let first_player = self.first_player;
         self.history
             .iter()
-            .zip(players)
-            .map(|(&position, &player)| (player, position))
+            .enumerate()
+            .map(move |(turn, &position)| {
+                if turn % 2 == 0 {
+                    (first_player, position)
+                } else {
+                    (first_player.opponent(), position)
+                }
+            })
+    }
+
+    /// The most recent action, `None` at the start of the game
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new();
+    /// assert_eq!(game.last_action(), None);
+    ///
+    /// let game = game.apply_action((P1, (Col1, Row1))).unwrap();
+    /// assert_eq!(game.last_action(), Some((P1, (Col1, Row1))));
+    /// ```
+    pub fn last_action(&self) -> Option<Action> {
+        self.history().last()
+    }
+
+    /// The last `n` actions, oldest first, or the whole history if there have been fewer
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new()
+    ///   .apply_action((P1, (Col1, Row1))).unwrap()
+    ///   .apply_action((P2, (Col0, Row0))).unwrap()
+    ///   .apply_action((P1, (Col2, Row2))).unwrap();
+    ///
+    /// assert_eq!(game.recent_actions(2), vec![(P2, (Col0, Row0)), (P1, (Col2, Row2))]);
+    /// assert_eq!(game.recent_actions(5).len(), 3);
+    /// ```
+    pub fn recent_actions(&self, n: usize) -> Vec<Action> {
+        self.history()
+            .skip(self.history.len().saturating_sub(n))
+            .collect()
     }
 
     /// Maps Col => Row => Players for the current state of the game
@@ -253,6 +437,93 @@ impl GameState {
         self.available().map(move |action| (whose_turn, action))
     }
 
+    /// The only action the next player can take, `None` if they have a choice or the game is
+    /// over. Useful for skipping straight past moves that don't need a decision
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new();
+    /// assert_eq!(game.forced_action(), None);
+    ///
+    /// let game = vec![
+    ///   (Col0, Row0), (Col1, Row0), (Col2, Row0), (Col1, Row1),
+    ///   (Col0, Row1), (Col2, Row1), (Col1, Row2), (Col0, Row2)
+    /// ]
+    ///   .into_iter()
+    ///   .fold(game, |game, position| game.apply_action((game.whose_turn(), position)).unwrap());
+    ///
+    /// assert_eq!(game.forced_action(), Some((P1, (Col2, Row2))));
+    /// ```
+    pub fn forced_action(&self) -> Option<Action> {
+        match self.legal_actions().as_slice() {
+            [action] => Some(*action),
+            _ => None,
+        }
+    }
+
+    /// The number of actions in the fixed action space, one for each square on the board. For
+    /// reinforcement learning environments that need every action to have an index
+    pub fn action_space_size() -> usize {
+        9
+    }
+
+    /// The index of an action in the action space. Squares are numbered by col then row, so
+    /// `(Col0, Row0)` is 0, `(Col0, Row1)` is 1, and `(Col2, Row2)` is 8, the same order as
+    /// [`valid_actions`](GameState::valid_actions). The player isn't part of the index, it's
+    /// always the player whose turn it is
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// assert_eq!(GameState::action_index((P1, (Col0, Row0))), 0);
+    /// assert_eq!(GameState::action_index((P2, (Col1, Row2))), 5);
+    /// ```
+    pub fn action_index((_player, (col, row)): Action) -> usize {
+        col as usize * 3 + row as usize
+    }
+
+    /// The action for the player whose turn it is at an index in the action space, `None` if
+    /// the index is out of range
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new();
+    /// assert_eq!(game.action_from_index(5), Some((P1, (Col1, Row2))));
+    /// assert_eq!(game.action_from_index(9), None);
+    /// ```
+    pub fn action_from_index(&self, index: usize) -> Option<Action> {
+        if index < Self::action_space_size() {
+            Some((
+                self.whose_turn(),
+                (Col::ALL[index / 3], Row::ALL[index % 3]),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Which indices of the action space are legal right now, see
+    /// [`action_index`](GameState::action_index) for the order. Nothing is legal once the game
+    /// is over
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new().apply_action((P1, (Col0, Row1))).unwrap();
+    /// let mask = game.legal_action_mask();
+    ///
+    /// assert_eq!(mask.len(), GameState::action_space_size());
+    /// assert_eq!(mask.iter().filter(|&&legal| legal).count(), 8);
+    /// assert!(!mask[1]);
+    /// ```
+    pub fn legal_action_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; Self::action_space_size()];
+
+        for action in self.legal_actions() {
+            mask[Self::action_index(action)] = true;
+        }
+
+        mask
+    }
+
     /// Returns the player who plays the next turn, games always start with `P1`
     /// ```
     /// use lib_table_top::games::tic_tac_toe::{GameState, Player::*};
@@ -269,9 +540,50 @@ impl GameState {
     /// ```
     pub fn whose_turn(&self) -> Player {
         if self.history.len() % 2 == 0 {
-            P1
+            self.first_player
         } else {
-            P2
+            self.first_player.opponent()
+        }
+    }
+
+    /// The players the game is waiting on, nobody once the game is over
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Player::*};
+    ///
+    /// let game: GameState = Default::default();
+    /// assert!(game.pending_input().is_pending_on(P1));
+    /// assert!(!game.pending_input().is_pending_on(P2));
+    /// ```
+    pub fn pending_input(&self) -> PendingInput<Player> {
+        match self.status() {
+            InProgress => PendingInput::turn(self.whose_turn()),
+            _ => PendingInput::none(),
+        }
+    }
+
+    /// The view of the game for spectators, see [`ObserverView`](struct@ObserverView)
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{GameState, Status, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new().apply_action((P1, (Col2, Row0))).unwrap();
+    /// let view = game.observer_view();
+    ///
+    /// assert_eq!(view.board[2][0], Some(P1));
+    /// assert_eq!(view.whose_turn, P2);
+    /// assert_eq!(view.last_action, Some((P1, (Col2, Row0))));
+    /// assert_eq!(view.status, Status::InProgress);
+    /// ```
+    pub fn observer_view(&self) -> ObserverView {
+        let board = self.board();
+
+        ObserverView {
+            board: Col::ALL.map(|col| Row::ALL.map(|row| board[col][row])),
+            whose_turn: self.whose_turn(),
+            last_action: self.last_action(),
+            status: self.status(),
+            deadline: None,
+            time_remaining: None,
+            metadata: Map::new(),
         }
     }
 
@@ -283,6 +595,11 @@ impl GameState {
     /// assert_eq!(game.status(), Status::InProgress);
     /// ```
     pub fn status(&self) -> Status {
+        // Nobody can have three in a row before the fifth move
+        if self.history.len() < 5 {
+            return InProgress;
+        }
+
         let board = self.board();
 
         POSSIBLE_WINS
@@ -352,3 +669,415 @@ impl GameState {
         }
     }
 }
+
+impl Play for GameState {
+    type Action = Action;
+    type Player = Player;
+    type Status = Status;
+    type Error = Error;
+
+    fn apply_action(&self, action: Action) -> Result<Self, Error> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<Action> {
+        self.legal_actions()
+    }
+
+    fn players(&self) -> Vec<Player> {
+        vec![P1, P2]
+    }
+
+    fn whose_turn(&self) -> Player {
+        self.whose_turn()
+    }
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != InProgress
+    }
+
+    fn history(&self) -> Vec<Action> {
+        self.history().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = Action;
+    type Error = Error;
+
+    fn initial(&self) -> Self {
+        Self::starting_with(self.first_player)
+    }
+
+    fn actions(&self) -> Vec<Action> {
+        self.history().collect()
+    }
+
+    fn apply(&self, action: Action) -> Result<Self, Error> {
+        self.apply_action(action)
+    }
+}
+
+impl Searchable for GameState {
+    type Action = Action;
+
+    fn legal_actions(&self) -> Vec<Action> {
+        match self.status() {
+            InProgress => self.valid_actions().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn play(&self, action: Action) -> Self {
+        self.apply_action(action)
+            .expect("Legal actions can always be applied")
+    }
+
+    fn evaluate(&self) -> i32 {
+        match self.status() {
+            // The winner is always the player who just moved
+            Win { .. } => -WIN,
+            InProgress | Draw => 0,
+        }
+    }
+}
+
+/// There's no safe default move, so timed out players can only forfeit or move randomly
+impl TimeoutHooks for GameState {
+    type Player = Player;
+    type Action = Action;
+
+    fn timeout_actions(&self, player: Player) -> Vec<Action> {
+        if player == self.whose_turn() {
+            self.valid_actions().collect()
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Summarize for GameState {
+    type Player = Player;
+
+    fn game_result(&self) -> Option<GameResult<Player>> {
+        let players = match self.status() {
+            InProgress => return None,
+            Win { player, .. } => winner_takes_all([P1, P2].iter().copied(), player),
+            Draw => [P1, P2]
+                .iter()
+                .map(|&player| PlayerResult {
+                    player,
+                    outcome: Outcome::Draw,
+                    score: None,
+                    place: Some(1),
+                })
+                .collect(),
+        };
+
+        Some(GameResult {
+            kind: GameKind::TicTacToe,
+            settings_hash: settings_hash(&()),
+            players,
+            move_count: self.history.len(),
+            duration: None,
+            termination: Termination::Completed,
+        })
+    }
+}
+
+/// Two planes indexed by col then row, the squares taken by the player whose turn it is and the
+/// squares taken by their opponent, so the shape is `[2, 3, 3]`
+/// ```
+/// use lib_table_top::common::ml::Encode;
+/// use lib_table_top::games::tic_tac_toe::{GameState, Row::*, Col::*, Player::*};
+///
+/// let game = GameState::new().apply_action((P1, (Col2, Row0))).unwrap();
+/// let encoding = game.encode();
+///
+/// assert_eq!(encoding.shape, vec![2, 3, 3]);
+/// // It's P2's turn, so P1's square is on the opponent's plane
+/// assert_eq!(encoding.get(&[1, 2, 0]), 1.0);
+/// assert_eq!(encoding.values.iter().sum::<f32>(), 1.0);
+/// ```
+impl Encode for GameState {
+    fn encode(&self) -> Encoding {
+        let mut encoding = Encoding::zeros(vec![2, 3, 3]);
+        let whose_turn = self.whose_turn();
+
+        for (player, (col, row)) in self.history() {
+            let plane = if player == whose_turn { 0 } else { 1 };
+            encoding.set(&[plane, col as usize, row as usize], 1.0);
+        }
+
+        encoding
+    }
+}
+
+/// Every perspective sees the same thing, there's nothing hidden in Tic-Tac-Toe
+impl Viewable for GameState {
+    type Player = Player;
+    type View = ObserverView;
+
+    fn view(&self, _perspective: Perspective<Player>) -> ObserverView {
+        self.observer_view()
+    }
+}
+
+/// A versioned capture of the full state of a game, so a game can be persisted and resumed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum Snapshot {
+    #[serde(rename = "v1")]
+    V1 {
+        history: Vector<Position>,
+        #[serde(default = "first_player", skip_serializing_if = "is_first_player")]
+        first_player: Player,
+    },
+}
+
+impl GameState {
+    /// Captures the full state of the game, see [`Snapshot`](enum@Snapshot)
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::GameState;
+    ///
+    /// let game: GameState = Default::default();
+    /// let action = game.valid_actions().next().unwrap();
+    /// let game = game.apply_action(action).unwrap();
+    /// assert_eq!(GameState::from_snapshot(game.snapshot()), game);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::V1 {
+            history: self.history.clone(),
+            first_player: self.first_player,
+        }
+    }
+
+    /// Resumes a game from a [`Snapshot`](enum@Snapshot)
+    pub fn from_snapshot(snapshot: Snapshot) -> Self {
+        match snapshot {
+            Snapshot::V1 {
+                history,
+                first_player,
+            } => Self {
+                history,
+                first_player,
+            },
+        }
+    }
+}
+
+pub mod puzzles {
+    use super::*;
+    use crate::common::rand::RngSeed;
+    use crate::common::search::search_with_budget;
+    use rand::seq::SliceRandom;
+    use std::time::Duration;
+
+    /// How hard a puzzle is to solve
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum Difficulty {
+        /// There's exactly one way to win right away
+        Easy,
+        /// The opponent is about to win, and there's exactly one move that doesn't lose
+        Medium,
+        /// There's no win right away, but exactly one move leads to a forced win
+        Hard,
+    }
+
+    /// A position with a single correct move
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Puzzle {
+        pub difficulty: Difficulty,
+        /// The board from the top row to the bottom, see [`position_string`](fn@position_string)
+        pub position: String,
+        pub to_move: Player,
+        pub solution: Position,
+    }
+
+    impl Puzzle {
+        /// The puzzle's position as a game, ready for the solution to be played
+        /// ```
+        /// use lib_table_top::games::tic_tac_toe::puzzles::{generate, position_string, Difficulty};
+        /// use lib_table_top::common::rand::RngSeed;
+        ///
+        /// let puzzle = generate(RngSeed([0; 32]), Difficulty::Easy);
+        /// let game = puzzle.game();
+        /// assert_eq!(position_string(&game), puzzle.position);
+        /// assert!(game.apply_action((puzzle.to_move, puzzle.solution)).is_ok());
+        /// ```
+        pub fn game(&self) -> GameState {
+            let mut taken: EnumMap<Player, Vec<Position>> = EnumMap::default();
+
+            for (row, line) in Row::ALL.iter().zip(self.position.split('/')) {
+                for (col, square) in Col::ALL.iter().zip(line.chars()) {
+                    match square {
+                        'X' => taken[P1].push((*col, *row)),
+                        'O' => taken[P2].push((*col, *row)),
+                        _ => {}
+                    }
+                }
+            }
+
+            // P1 always goes first, so the moves alternate starting with P1
+            let mut history = Vector::new();
+
+            for (turn, &position) in taken[P1].iter().enumerate() {
+                history.push_back(position);
+                history.extend(taken[P2].get(turn).copied());
+            }
+
+            GameState {
+                history,
+                ..GameState::new()
+            }
+        }
+    }
+
+    /// The board as text, one row at a time from the top separated by `/`, with `X` for P1, `O`
+    /// for P2, and `.` for empty squares
+    /// ```
+    /// use lib_table_top::games::tic_tac_toe::{puzzles::position_string, GameState, Row::*, Col::*, Player::*};
+    ///
+    /// let game = GameState::new()
+    ///   .apply_action((P1, (Col1, Row1))).unwrap()
+    ///   .apply_action((P2, (Col2, Row0))).unwrap();
+    ///
+    /// assert_eq!(position_string(&game), "..O/.X./...");
+    /// ```
+    pub fn position_string(game: &GameState) -> String {
+        let board = game.board();
+
+        Row::ALL
+            .iter()
+            .map(|&row| {
+                Col::ALL
+                    .iter()
+                    .map(|&col| match board[col][row] {
+                        Some(P1) => 'X',
+                        Some(P2) => 'O',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+
+    /// Generates a puzzle by playing random games from the seed until one reaches a position of
+    /// the requested difficulty. The same seed and difficulty always make the same puzzle
+    pub fn generate(seed: RngSeed, difficulty: Difficulty) -> Puzzle {
+        let mut rng = seed.into_rng();
+
+        loop {
+            let mut game = GameState::new();
+
+            while game.status() == InProgress {
+                if let Some(solution) = solution(&game, difficulty) {
+                    return Puzzle {
+                        difficulty,
+                        position: position_string(&game),
+                        to_move: game.whose_turn(),
+                        solution,
+                    };
+                }
+
+                let actions: Vec<Action> = game.valid_actions().collect();
+                let action = *actions
+                    .choose(&mut rng)
+                    .expect("Games in progress have actions");
+                game = game.apply_action(action).unwrap();
+            }
+        }
+    }
+
+    /// The only correct move if the game is a puzzle of that difficulty
+    fn solution(game: &GameState, difficulty: Difficulty) -> Option<Position> {
+        let actions: Vec<Action> = game.valid_actions().collect();
+        let immediate_wins: Vec<Action> = actions
+            .iter()
+            .copied()
+            .filter(|&action| matches!(game.play(action).status(), Win { .. }))
+            .collect();
+
+        match difficulty {
+            Difficulty::Easy => only(&immediate_wins),
+            _ if !immediate_wins.is_empty() => None,
+            Difficulty::Medium if !is_threatened(game) => None,
+            Difficulty::Medium => only(&filter_by_score(game, &actions, |score| score >= 0)),
+            Difficulty::Hard => only(&filter_by_score(game, &actions, |score| score > WIN / 2)),
+        }
+    }
+
+    fn only(actions: &[Action]) -> Option<Position> {
+        match actions {
+            [(_, position)] => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// The actions whose solved score, for the player making them, passes the filter
+    fn filter_by_score(
+        game: &GameState,
+        actions: &[Action],
+        keep: impl Fn(i32) -> bool,
+    ) -> Vec<Action> {
+        actions
+            .iter()
+            .copied()
+            .filter(|&action| {
+                let next = game.play(action);
+                // Tic-Tac-Toe is small enough that the search always finishes
+                let score = -search_with_budget(&next, Duration::from_secs(60)).score;
+                keep(score)
+            })
+            .collect()
+    }
+
+    /// Whether the opponent could win on their next move
+    fn is_threatened(game: &GameState) -> bool {
+        let board = game.board();
+        let opponent = game.whose_turn().opponent();
+
+        POSSIBLE_WINS.iter().any(|line| {
+            let taken = line
+                .iter()
+                .filter(|&&(col, row)| board[col][row] == Some(opponent))
+                .count();
+            let empty = line
+                .iter()
+                .filter(|&&(col, row)| board[col][row].is_none())
+                .count();
+
+            taken == 2 && empty == 1
+        })
+    }
+}
+
+/// Generates games by playing up to 9 random valid actions, stopping early if the game is over
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for GameState {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+        use proptest::sample::Index;
+
+        proptest::collection::vec(any::<Index>(), 0..=9)
+            .prop_map(|choices| {
+                choices.iter().fold(GameState::new(), |game, choice| {
+                    if game.status() != InProgress {
+                        return game;
+                    }
+
+                    let actions: Vec<Action> = game.valid_actions().collect();
+                    game.apply_action(*choice.get(&actions)).unwrap()
+                })
+            })
+            .boxed()
+    }
+}
@@ -0,0 +1,1063 @@
+use enum_map::EnumMap;
+use im::Vector;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::common::deck::dealing::Pattern;
+use crate::common::deck::points::Scheme;
+use crate::common::deck::{Card, Rank, Suit, STANDARD_DECK};
+use crate::common::game::Play;
+use crate::common::rand::RngSeed;
+use crate::common::replay::Replayable;
+use crate::common::tricks::{must_match, RankOrder, RankOverride, TrickRules};
+use crate::common::views::{Perspective, Viewable};
+use crate::games::spades;
+
+/// The seats around the table, in the order play goes. When a game plays in partnerships,
+/// partners sit across from each other
+#[derive(
+    Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[repr(u8)]
+pub enum Player {
+    P1 = 1,
+    P2 = 2,
+    P3 = 3,
+    P4 = 4,
+}
+
+use Player::*;
+
+impl Player {
+    pub const ALL: [Self; 4] = [P1, P2, P3, P4];
+
+    /// The player to the left, who plays next
+    /// ```
+    /// use lib_table_top::games::trick_game_template::Player::*;
+    ///
+    /// assert_eq!(P1.next(), P2);
+    /// assert_eq!(P4.next(), P1);
+    /// ```
+    pub fn next(&self) -> Self {
+        match self {
+            P1 => P2,
+            P2 => P3,
+            P3 => P4,
+            P4 => P1,
+        }
+    }
+
+    /// The player across the table
+    pub fn partner(&self) -> Self {
+        self.next().next()
+    }
+}
+
+/// How the trump suit is picked for a round
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trump {
+    NoTrump,
+    /// The same suit every round, like spades in Spades
+    Fixed(Suit),
+    /// The last card dealt to the dealer is turned up and its suit is trump, like in Whist
+    TurnUp,
+}
+
+/// The restrictions on which cards can go on a trick
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowRules {
+    /// Players have to follow the led suit when they can
+    pub must_follow: bool,
+    /// A suit that can't be led until a card of it has been played, unless the leader has
+    /// nothing else
+    pub breaks: Option<Suit>,
+    /// A card that has to lead the first trick of a round. Whoever holds it leads instead of
+    /// the dealer's left
+    pub opening_lead: Option<Card>,
+}
+
+/// Whether the players bid how many tricks they'll take before playing
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bidding {
+    NoBidding,
+    /// Everyone bids a number of tricks in the range, starting to the dealer's left
+    Tricks {
+        min: u8,
+        max: u8,
+    },
+}
+
+/// When the game ends and who wins it
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Goal {
+    /// The game ends after a round where someone has at least this many points, as long as the
+    /// best score belongs to a single player or partnership
+    pub target: i32,
+    /// Whether the lowest score wins instead of the highest, like in Hearts
+    pub lowest_wins: bool,
+}
+
+/// How a round went, kept for score sheets and given to the scoring hook
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Round {
+    pub dealer: Player,
+    pub trump: Option<Suit>,
+    /// Empty for games without bidding
+    pub bids: BTreeMap<Player, u8>,
+    pub tricks: BTreeMap<Player, u8>,
+    /// The cards each player won in tricks
+    pub taken: BTreeMap<Player, Vec<Card>>,
+    /// The points each player scored in the round, empty while the round is being scored
+    pub points: BTreeMap<Player, i32>,
+}
+
+/// What a player knows when picking a card, given to the playable hook
+#[derive(Clone, Copy, Debug)]
+pub struct Situation<'a> {
+    pub player: Player,
+    pub hand: &'a [Card],
+    /// The cards played to the trick in progress, in order
+    pub trick: &'a [(Player, Card)],
+    /// The tricks finished so far this round
+    pub tricks_played: u8,
+    pub trump: Option<Suit>,
+}
+
+/// A hook that scores a round, given the round and the rounds before it. Players missing from
+/// the points score nothing. Partners should be given the same points
+pub type ScoreRound = fn(&Round, &[Round]) -> BTreeMap<Player, i32>;
+
+/// A hook that narrows the cards the follow rules allow, for restrictions they can't express.
+/// If it removes every card it's ignored, so a player always has something to play
+pub type PlayableHook = fn(&Situation, Vec<Card>) -> Vec<Card>;
+
+/// Everything that makes one whist family game different from another. The engine deals the
+/// whole deck to four players, runs the bidding if there is any, plays out the tricks, and uses
+/// the scoring hook after the last trick of each round
+/// ```
+/// use lib_table_top::common::rand::RngSeed;
+/// use lib_table_top::games::trick_game_template::{
+///     Bidding, Config, FollowRules, GameState, Goal, Player, Round, Trump, SPADES,
+/// };
+/// use std::collections::BTreeMap;
+///
+/// /// A side scores a point for every trick over six
+/// fn score_whist(round: &Round, _previous: &[Round]) -> BTreeMap<Player, i32> {
+///     Player::ALL
+///         .iter()
+///         .map(|&player| {
+///             let tricks = round.tricks[&player] + round.tricks[&player.partner()];
+///             (player, i32::from(tricks.saturating_sub(6)))
+///         })
+///         .collect()
+/// }
+///
+/// let whist = Config {
+///     name: "Whist",
+///     trump: Trump::TurnUp,
+///     follow: FollowRules { must_follow: true, breaks: None, opening_lead: None },
+///     bidding: Bidding::NoBidding,
+///     goal: Goal { target: 5, lowest_wins: false },
+///     score_round: score_whist,
+///     ..SPADES
+/// };
+/// let game = GameState::new(whist, RngSeed([0; 32]));
+/// assert!(game.trump().is_some());
+/// assert_eq!(game.valid_actions(Player::P1).len(), 13);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub name: &'static str,
+    /// The cards dealt each round, split evenly between the players. Cards that don't split
+    /// evenly are left out
+    pub deck: &'static [Card],
+    pub rank_order: RankOrder,
+    /// Changes the suits and powers of special cards, see
+    /// [`RankOverride`](type@crate::common::tricks::RankOverride)
+    pub rank_override: Option<RankOverride>,
+    pub trump: Trump,
+    pub follow: FollowRules,
+    pub bidding: Bidding,
+    /// Whether partners play as a team and win together
+    pub partnerships: bool,
+    pub goal: Goal,
+    pub score_round: ScoreRound,
+    pub playable: Option<PlayableHook>,
+}
+
+/// Hearts without passing. Hearts can't be led until one has been played, the two of clubs
+/// leads, no points can be played on the first trick, and the lowest score wins once someone
+/// reaches 100
+pub const HEARTS: Config = Config {
+    name: "Hearts",
+    deck: &STANDARD_DECK,
+    rank_order: RankOrder::AceHigh,
+    rank_override: None,
+    trump: Trump::NoTrump,
+    follow: FollowRules {
+        must_follow: true,
+        breaks: Some(Suit::Hearts),
+        opening_lead: Some(Card(Rank::Two, Suit::Clubs)),
+    },
+    bidding: Bidding::NoBidding,
+    partnerships: false,
+    goal: Goal {
+        target: 100,
+        lowest_wins: true,
+    },
+    score_round: score_hearts,
+    playable: Some(no_points_on_first_trick),
+};
+
+/// Spades with the default [`spades::Rules`](struct@spades::Rules), where a bid of zero is
+/// nil. There's no blind nil, see [`spades`](crate::games::spades) for the full game
+///
+/// The full game stays its own engine. Blind nil is bid before looking at the hand, rules
+/// like the bag limit are settings, and scores and bags are kept per partnership in its views
+/// and stored histories, none of which the template's trick bids and per player scores can
+/// express. Both score with [`spades::score_partnership`](fn@spades::score_partnership) and
+/// play tricks with [`TrickRules`](struct@TrickRules), so the rules can't drift apart
+pub const SPADES: Config = Config {
+    name: "Spades",
+    deck: &STANDARD_DECK,
+    rank_order: RankOrder::AceHigh,
+    rank_override: None,
+    trump: Trump::Fixed(Suit::Spades),
+    follow: FollowRules {
+        must_follow: true,
+        breaks: Some(Suit::Spades),
+        opening_lead: None,
+    },
+    bidding: Bidding::Tricks { min: 0, max: 13 },
+    partnerships: true,
+    goal: Goal {
+        target: 500,
+        lowest_wins: false,
+    },
+    score_round: score_spades,
+    playable: None,
+};
+
+/// Every heart taken is a point and the Queen of Spades is thirteen. Taking all of them shoots
+/// the moon, which gives everyone else the points instead
+/// ```
+/// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+/// use lib_table_top::games::trick_game_template::{score_hearts, Player::*, Round};
+/// use std::collections::BTreeMap;
+///
+/// let mut round = Round {
+///     dealer: P4,
+///     trump: None,
+///     bids: BTreeMap::new(),
+///     tricks: BTreeMap::new(),
+///     taken: vec![(P1, vec![Card(Queen, Spades), Card(Two, Hearts)]), (P2, vec![])]
+///         .into_iter()
+///         .collect(),
+///     points: BTreeMap::new(),
+/// };
+/// assert_eq!(score_hearts(&round, &[]), vec![(P1, 14), (P2, 0)].into_iter().collect());
+///
+/// round.taken.insert(P1, lib_table_top::common::deck::STANDARD_DECK.to_vec());
+/// assert_eq!(score_hearts(&round, &[]), vec![(P1, 0), (P2, 26)].into_iter().collect());
+/// ```
+pub fn score_hearts(round: &Round, _previous: &[Round]) -> BTreeMap<Player, i32> {
+    let points: BTreeMap<Player, i32> = round
+        .taken
+        .iter()
+        .map(|(&player, cards)| {
+            let points: u32 = cards.iter().map(|&card| Scheme::Hearts.value(card)).sum();
+            (player, points as i32)
+        })
+        .collect();
+
+    match points.iter().find(|(_, &points)| points == 26) {
+        Some((&moon, _)) => points
+            .keys()
+            .map(|&player| (player, if player == moon { 0 } else { 26 }))
+            .collect(),
+        None => points,
+    }
+}
+
+/// Scores each partnership with [`spades::score_partnership`](fn@spades::score_partnership),
+/// taking off the bag penalty whenever the bags across the rounds reach the limit
+pub fn score_spades(round: &Round, previous: &[Round]) -> BTreeMap<Player, i32> {
+    let rules = spades::Rules::default();
+    let mut points = BTreeMap::new();
+
+    for &player in [P1, P2].iter() {
+        let partners = [player, player.partner()];
+        let score = |round: &Round| {
+            spades::score_partnership(partners.map(|partner| {
+                let bid = match round.bids[&partner] {
+                    0 => spades::Bid::Nil,
+                    tricks => spades::Bid::Tricks(tricks),
+                };
+                (bid, round.tricks[&partner])
+            }))
+        };
+
+        let bags_before: u32 = previous.iter().map(|round| score(round).1).sum();
+        let (mut total, bags) = score(round);
+        let penalties = (bags_before + bags) / rules.bag_limit - bags_before / rules.bag_limit;
+        total -= rules.bag_penalty * penalties as i32;
+
+        for &partner in partners.iter() {
+            points.insert(partner, total);
+        }
+    }
+
+    points
+}
+
+/// Keeps points off the first trick of a round, unless the hand has nothing else that can be
+/// played
+pub fn no_points_on_first_trick(situation: &Situation, playable: Vec<Card>) -> Vec<Card> {
+    if situation.tricks_played > 0 {
+        return playable;
+    }
+
+    playable
+        .into_iter()
+        .filter(|&card| Scheme::Hearts.value(card) == 0)
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Bid(u8),
+    Play(Card),
+}
+
+/// Whether the players are bidding or playing out the tricks
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phase {
+    Bidding,
+    Playing,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    InProgress,
+    /// Someone reached the target and has the best score, see [`Goal`](struct@Goal). Both
+    /// partners win in a partnership game
+    Win {
+        players: Vec<Player>,
+        scores: BTreeMap<Player, i32>,
+    },
+}
+
+use Status::*;
+
+#[derive(Clone, Debug, Error, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionError {
+    #[error(
+        "It's {:?}'s turn and not {:?}'s turn",
+        correct_player,
+        attempted_player
+    )]
+    NotPlayerTurn {
+        attempted_player: Player,
+        correct_player: Player,
+    },
+    #[error("The game is already over")]
+    GameIsOver,
+    #[error("{:?} can't bid, this isn't the bidding", player)]
+    NotBidding { player: Player },
+    #[error("{:?} can't play a card until everyone has bid", player)]
+    StillBidding { player: Player },
+    #[error("{:?} isn't a bid in this game", bid)]
+    InvalidBid { bid: u8 },
+    #[error("Player {:?} does not have card {:?}", player, card)]
+    PlayerDoesNotHaveCard { player: Player, card: Card },
+    #[error("Player {:?} has to lead {:?}", player, card)]
+    MustLead { player: Player, card: Card },
+    #[error(
+        "Player {:?} has to follow {:?} and can't play {:?}",
+        player,
+        led,
+        card
+    )]
+    MustFollowSuit {
+        player: Player,
+        card: Card,
+        led: Suit,
+    },
+    #[error(
+        "Player {:?} can't lead {:?} until {:?} have been broken",
+        player,
+        card,
+        suit
+    )]
+    NotBroken {
+        player: Player,
+        card: Card,
+        suit: Suit,
+    },
+    #[error("Player {:?} can't play {:?} right now", player, card)]
+    NotAllowed { player: Player, card: Card },
+}
+
+use ActionError::*;
+
+impl ActionError {
+    /// A stable, machine readable code for the error
+    /// ```
+    /// use lib_table_top::games::trick_game_template::{ActionError::*, Player::*};
+    ///
+    /// let error = NotPlayerTurn { attempted_player: P1, correct_player: P2 };
+    /// assert_eq!(error.code(), "not_player_turn");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            NotPlayerTurn { .. } => "not_player_turn",
+            GameIsOver => "game_is_over",
+            NotBidding { .. } => "not_bidding",
+            StillBidding { .. } => "still_bidding",
+            InvalidBid { .. } => "invalid_bid",
+            PlayerDoesNotHaveCard { .. } => "player_does_not_have_card",
+            MustLead { .. } => "must_lead",
+            MustFollowSuit { .. } => "must_follow_suit",
+            NotBroken { .. } => "not_broken",
+            NotAllowed { .. } => "not_allowed",
+        }
+    }
+
+    /// The data of the error, for clients to build their own messages from
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            NotPlayerTurn {
+                attempted_player,
+                correct_player,
+            } => json!({
+                "attempted_player": attempted_player,
+                "correct_player": correct_player,
+            }),
+            GameIsOver => json!({}),
+            NotBidding { player } | StillBidding { player } => json!({ "player": player }),
+            InvalidBid { bid } => json!({ "bid": bid }),
+            PlayerDoesNotHaveCard { player, card }
+            | MustLead { player, card }
+            | NotAllowed { player, card } => json!({ "player": player, "card": card }),
+            MustFollowSuit { player, card, led } => {
+                json!({ "player": player, "card": card, "led": led })
+            }
+            NotBroken { player, card, suit } => {
+                json!({ "player": player, "card": card, "suit": suit })
+            }
+        }
+    }
+}
+
+/// The view that anyone can see, the totally non secret parts of the game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObserverView {
+    pub whose_turn: Player,
+    pub dealer: Player,
+    pub phase: Phase,
+    pub trump: Option<Suit>,
+    pub bids: HashMap<Player, u8>,
+    /// The tricks each player has taken this round
+    pub tricks: HashMap<Player, u8>,
+    /// The cards played to the trick in progress, in order
+    pub trick: Vec<(Player, Card)>,
+    pub last_trick: Option<Vec<(Player, Card)>>,
+    pub player_card_count: HashMap<Player, usize>,
+    /// Whether the suit that has to be broken has been, see
+    /// [`FollowRules::breaks`](FollowRules::breaks)
+    pub broken: bool,
+    pub scores: HashMap<Player, i32>,
+    pub rounds: Vec<Round>,
+}
+
+/// The view of a player, their hand along with everything an observer can see
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub player: Player,
+    pub hand: Vec<Card>,
+    /// The actions the player can take, worked out by the game because the hooks are code,
+    /// empty if it isn't their turn
+    pub valid_actions: Vec<Action>,
+    pub observer_view: ObserverView,
+}
+
+/// The view of everything in the game, including every hand. This shouldn't be shown to players
+/// while the game is in progress
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OmniscientView {
+    pub hands: HashMap<Player, Vec<Card>>,
+    pub observer_view: ObserverView,
+}
+
+/// A view of the game redacted for a [`Perspective`](enum@Perspective)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    Observer(ObserverView),
+    Player(PlayerView),
+    Omniscient(OmniscientView),
+}
+
+/// A game played with a [`Config`](struct@Config). Configs are code, so a stored game keeps
+/// its seed and actions, and is replayed with the config it was played with
+#[derive(Clone, Debug)]
+pub struct GameState {
+    config: Config,
+    seed: RngSeed,
+    history: Vector<(Player, Action)>,
+    rng: Arc<ChaCha20Rng>,
+    dealer: Player,
+    trump: Option<Suit>,
+    hands: EnumMap<Player, Vec<Card>>,
+    bids: EnumMap<Player, Option<u8>>,
+    tricks: EnumMap<Player, u8>,
+    taken: EnumMap<Player, Vec<Card>>,
+    /// The cards played to the trick in progress, in order
+    trick: Vec<(Player, Card)>,
+    last_trick: Option<Vec<(Player, Card)>>,
+    broken: bool,
+    /// Who leads the first trick of the round, once the bidding is over
+    leader: Player,
+    whose_turn: Player,
+    scores: EnumMap<Player, i32>,
+    rounds: Vector<Round>,
+}
+
+impl GameState {
+    /// Creates a new game and deals the first round. `P4` deals, so `P1` bids first and leads
+    /// the first trick, unless the config has an opening lead
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::trick_game_template::{Action, GameState, Phase, HEARTS};
+    ///
+    /// let game = GameState::new(HEARTS, RngSeed([0; 32]));
+    /// assert_eq!(game.phase(), Phase::Playing);
+    /// assert_eq!(game.trump(), None);
+    /// assert_eq!(
+    ///     game.valid_actions(game.whose_turn()),
+    ///     vec![Action::Play(Card(Two, Clubs))]
+    /// );
+    /// ```
+    pub fn new(config: Config, seed: RngSeed) -> Self {
+        let mut game = Self {
+            config,
+            seed,
+            history: Vector::new(),
+            rng: Arc::new(seed.into_rng()),
+            dealer: P4,
+            trump: None,
+            hands: EnumMap::default(),
+            bids: EnumMap::default(),
+            tricks: EnumMap::default(),
+            taken: EnumMap::default(),
+            trick: Vec::new(),
+            last_trick: None,
+            broken: false,
+            leader: P1,
+            whose_turn: P1,
+            scores: EnumMap::default(),
+            rounds: Vector::new(),
+        };
+        game.deal();
+        game
+    }
+
+    /// Shuffles and deals the deck, starting to the dealer's left
+    fn deal(&mut self) {
+        let mut cards = self.config.deck.to_vec();
+        cards.shuffle(Arc::make_mut(&mut self.rng));
+        let deal = Pattern::RoundRobin {
+            cards_per_player: cards.len() / 4,
+        }
+        .deal(cards, 4)
+        .expect("The hands are split from the deck");
+
+        let mut seat = self.dealer.next();
+        for hand in deal.hands {
+            self.hands[seat] = hand;
+            seat = seat.next();
+        }
+
+        self.trump = match self.config.trump {
+            Trump::NoTrump => None,
+            Trump::Fixed(suit) => Some(suit),
+            Trump::TurnUp => self.hands[self.dealer].last().map(Card::suit),
+        };
+        self.leader = self
+            .config
+            .follow
+            .opening_lead
+            .and_then(|card| {
+                Player::ALL
+                    .iter()
+                    .copied()
+                    .find(|&player| self.hands[player].contains(&card))
+            })
+            .unwrap_or_else(|| self.dealer.next());
+
+        self.bids = EnumMap::default();
+        self.tricks = EnumMap::default();
+        self.taken = EnumMap::default();
+        self.trick = Vec::new();
+        self.last_trick = None;
+        self.broken = false;
+        self.whose_turn = match self.config.bidding {
+            Bidding::NoBidding => self.leader,
+            Bidding::Tricks { .. } => self.dealer.next(),
+        };
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The actions taken so far, with who took them
+    pub fn history(&self) -> impl Iterator<Item = (Player, Action)> + '_ {
+        self.history.iter().copied()
+    }
+
+    pub fn whose_turn(&self) -> Player {
+        self.whose_turn
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = Player> + Clone {
+        Player::ALL.iter().copied()
+    }
+
+    /// The trump suit of the round being played
+    pub fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    pub fn phase(&self) -> Phase {
+        match self.config.bidding {
+            Bidding::Tricks { .. } if self.bids.values().any(Option::is_none) => Phase::Bidding,
+            _ => Phase::Playing,
+        }
+    }
+
+    /// A player's score across the rounds played
+    pub fn score(&self, player: Player) -> i32 {
+        self.scores[player]
+    }
+
+    pub fn rounds_played(&self) -> usize {
+        self.rounds.len()
+    }
+
+    pub fn status(&self) -> Status {
+        let goal = self.config.goal;
+        let scores: BTreeMap<Player, i32> = self.scores.iter().map(|(p, &s)| (p, s)).collect();
+
+        if scores.values().all(|&score| score < goal.target) {
+            return InProgress;
+        }
+
+        let best = if goal.lowest_wins {
+            scores.values().min()
+        } else {
+            scores.values().max()
+        };
+        let players: Vec<Player> = self
+            .players()
+            .filter(|player| Some(&scores[player]) == best)
+            .collect();
+
+        let one_side = match players.as_slice() {
+            [_] => true,
+            [a, b] => self.config.partnerships && a.partner() == *b,
+            _ => false,
+        };
+
+        if one_side {
+            Win { players, scores }
+        } else {
+            InProgress
+        }
+    }
+
+    /// The actions a player can take, empty if it isn't their turn
+    /// ```
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::trick_game_template::{Action, GameState, Player::*, SPADES};
+    ///
+    /// let game = GameState::new(SPADES, RngSeed([0; 32]));
+    /// let actions = game.valid_actions(P1);
+    /// assert_eq!(actions.len(), 14);
+    /// assert_eq!(actions[0], Action::Bid(0));
+    /// assert!(game.valid_actions(P2).is_empty());
+    /// ```
+    pub fn valid_actions(&self, player: Player) -> Vec<Action> {
+        if player != self.whose_turn || self.status() != InProgress {
+            return vec![];
+        }
+
+        match (self.phase(), self.config.bidding) {
+            (Phase::Bidding, Bidding::Tricks { min, max }) => {
+                (min..=max).map(Action::Bid).collect()
+            }
+            _ => self
+                .playable(player)
+                .into_iter()
+                .map(Action::Play)
+                .collect(),
+        }
+    }
+
+    pub fn observer_view(&self) -> ObserverView {
+        ObserverView {
+            whose_turn: self.whose_turn,
+            dealer: self.dealer,
+            phase: self.phase(),
+            trump: self.trump,
+            bids: self
+                .bids
+                .iter()
+                .filter_map(|(player, bid)| Some((player, (*bid)?)))
+                .collect(),
+            tricks: self.tricks.iter().map(|(p, &t)| (p, t)).collect(),
+            trick: self.trick.clone(),
+            last_trick: self.last_trick.clone(),
+            player_card_count: self.hands.iter().map(|(p, hand)| (p, hand.len())).collect(),
+            broken: self.broken,
+            scores: self.scores.iter().map(|(p, &s)| (p, s)).collect(),
+            rounds: self.rounds.iter().cloned().collect(),
+        }
+    }
+
+    pub fn player_view(&self, player: Player) -> PlayerView {
+        PlayerView {
+            player,
+            hand: self.hands[player].clone(),
+            valid_actions: self.valid_actions(player),
+            observer_view: self.observer_view(),
+        }
+    }
+
+    pub fn current_player_view(&self) -> PlayerView {
+        self.player_view(self.whose_turn)
+    }
+
+    pub fn omniscient_view(&self) -> OmniscientView {
+        OmniscientView {
+            hands: self
+                .hands
+                .iter()
+                .map(|(p, hand)| (p, hand.clone()))
+                .collect(),
+            observer_view: self.observer_view(),
+        }
+    }
+
+    /// Bids or plays a card. Once the fourth card of a trick is played the trick goes to its
+    /// winner, who leads the next one, and once the last trick is taken the round is scored and
+    /// the next round is dealt
+    /// ```
+    /// use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+    /// use lib_table_top::common::rand::RngSeed;
+    /// use lib_table_top::games::trick_game_template::{Action, ActionError, GameState, HEARTS};
+    ///
+    /// let game = GameState::new(HEARTS, RngSeed([0; 32]));
+    /// let player = game.whose_turn();
+    /// let card = game.player_view(player).hand[0];
+    /// assert_eq!(
+    ///   game.apply_action((player, Action::Bid(3))).err(),
+    ///   Some(ActionError::NotBidding { player })
+    /// );
+    /// if card != Card(Two, Clubs) {
+    ///     assert_eq!(
+    ///       game.apply_action((player, Action::Play(card))).err(),
+    ///       Some(ActionError::MustLead { player, card: Card(Two, Clubs) })
+    ///     );
+    /// }
+    ///
+    /// let game = game.apply_action((player, Action::Play(Card(Two, Clubs)))).unwrap();
+    /// assert_eq!(game.whose_turn(), player.next());
+    /// ```
+    pub fn apply_action(&self, (player, action): (Player, Action)) -> Result<Self, ActionError> {
+        if self.status() != InProgress {
+            return Err(GameIsOver);
+        }
+
+        if player != self.whose_turn {
+            return Err(NotPlayerTurn {
+                attempted_player: player,
+                correct_player: self.whose_turn,
+            });
+        }
+
+        match action {
+            Action::Bid(bid) => self.validate_bid(player, bid)?,
+            Action::Play(card) => self.validate_play(player, card)?,
+        }
+
+        let mut new_game = self.clone();
+
+        match action {
+            Action::Bid(bid) => {
+                new_game.bids[player] = Some(bid);
+                new_game.whose_turn = if new_game.phase() == Phase::Playing {
+                    new_game.leader
+                } else {
+                    player.next()
+                };
+            }
+            Action::Play(card) => new_game.play_card(player, card),
+        }
+
+        new_game.history.push_back((player, action));
+        Ok(new_game)
+    }
+
+    fn validate_bid(&self, player: Player, bid: u8) -> Result<(), ActionError> {
+        match (self.phase(), self.config.bidding) {
+            (Phase::Bidding, Bidding::Tricks { min, max }) if (min..=max).contains(&bid) => Ok(()),
+            (Phase::Bidding, _) => Err(InvalidBid { bid }),
+            (Phase::Playing, _) => Err(NotBidding { player }),
+        }
+    }
+
+    fn validate_play(&self, player: Player, card: Card) -> Result<(), ActionError> {
+        if self.phase() != Phase::Playing {
+            return Err(StillBidding { player });
+        }
+
+        let hand = &self.hands[player];
+        if !hand.contains(&card) {
+            return Err(PlayerDoesNotHaveCard { player, card });
+        }
+
+        if self.playable(player).contains(&card) {
+            return Ok(());
+        }
+
+        let rules = self.trick_rules();
+        let suit = |card: Card| rules.effective_suit(card);
+
+        match (self.opening_lead(player), self.trick.first()) {
+            (Some(opening), _) => Err(MustLead {
+                player,
+                card: opening,
+            }),
+            (None, Some(&(_, led)))
+                if self.config.follow.must_follow
+                    && suit(card) != suit(led)
+                    && hand.iter().any(|&held| suit(held) == suit(led)) =>
+            {
+                Err(MustFollowSuit {
+                    player,
+                    card,
+                    led: suit(led),
+                })
+            }
+            (None, None) if self.config.follow.breaks == Some(suit(card)) && !self.broken => {
+                Err(NotBroken {
+                    player,
+                    card,
+                    suit: suit(card),
+                })
+            }
+            _ => Err(NotAllowed { player, card }),
+        }
+    }
+
+    fn trick_rules(&self) -> TrickRules {
+        TrickRules {
+            trump: self.trump,
+            rank_order: self.config.rank_order,
+            rank_override: self.config.rank_override,
+        }
+    }
+
+    fn tricks_played(&self) -> u8 {
+        self.tricks.values().sum()
+    }
+
+    /// The card a player has to lead to open the round, if they hold it and it's time
+    fn opening_lead(&self, player: Player) -> Option<Card> {
+        let card = self.config.follow.opening_lead?;
+
+        if self.tricks_played() == 0 && self.trick.is_empty() && self.hands[player].contains(&card)
+        {
+            Some(card)
+        } else {
+            None
+        }
+    }
+
+    /// The cards a player can put on the trick, see [`FollowRules`](struct@FollowRules) and
+    /// [`PlayableHook`](type@PlayableHook)
+    fn playable(&self, player: Player) -> Vec<Card> {
+        let hand = &self.hands[player];
+        let rules = self.trick_rules();
+        let follow = self.config.follow;
+
+        let cards = match (self.opening_lead(player), self.trick.first()) {
+            (Some(opening), _) => vec![opening],
+            (None, Some(&(_, led))) if follow.must_follow => must_match(hand, |card| {
+                rules.effective_suit(card) == rules.effective_suit(led)
+            }),
+            (None, None) if !self.broken && follow.breaks.is_some() => must_match(hand, |card| {
+                Some(rules.effective_suit(card)) != follow.breaks
+            }),
+            _ => hand.to_vec(),
+        };
+
+        let hook = match self.config.playable {
+            Some(hook) => hook,
+            None => return cards,
+        };
+        let situation = Situation {
+            player,
+            hand,
+            trick: &self.trick,
+            tricks_played: self.tricks_played(),
+            trump: self.trump,
+        };
+
+        let allowed = hook(&situation, cards.clone());
+        if allowed.is_empty() {
+            cards
+        } else {
+            allowed
+        }
+    }
+
+    /// Plays a card to the trick, see `validate_play`
+    fn play_card(&mut self, player: Player, card: Card) {
+        self.hands[player].retain(|&held| held != card);
+        self.broken |= self.config.follow.breaks == Some(self.trick_rules().effective_suit(card));
+        self.trick.push((player, card));
+
+        let winner = match self.trick_rules().finished(&self.trick, Player::ALL.len()) {
+            Some(winner) => winner,
+            None => {
+                self.whose_turn = player.next();
+                return;
+            }
+        };
+        self.tricks[winner] += 1;
+        self.taken[winner].extend(self.trick.iter().map(|&(_, card)| card));
+        self.last_trick = Some(std::mem::take(&mut self.trick));
+        self.whose_turn = winner;
+
+        if self.hands.values().all(Vec::is_empty) {
+            self.finish_round();
+        }
+    }
+
+    /// Scores the round with the config's hook and deals the next one, unless the game is over
+    fn finish_round(&mut self) {
+        let mut round = Round {
+            dealer: self.dealer,
+            trump: self.trump,
+            bids: self
+                .bids
+                .iter()
+                .filter_map(|(player, bid)| Some((player, (*bid)?)))
+                .collect(),
+            tricks: self.tricks.iter().map(|(p, &t)| (p, t)).collect(),
+            taken: self
+                .taken
+                .iter()
+                .map(|(p, cards)| (p, cards.clone()))
+                .collect(),
+            points: BTreeMap::new(),
+        };
+
+        let previous: Vec<Round> = self.rounds.iter().cloned().collect();
+        round.points = (self.config.score_round)(&round, &previous);
+        for (&player, &points) in &round.points {
+            self.scores[player] += points;
+        }
+        self.rounds.push_back(round);
+
+        if self.status() == InProgress {
+            self.dealer = self.dealer.next();
+            self.deal();
+        }
+    }
+
+    /// The cards a perspective isn't allowed to see, for checking views with
+    /// [`leak_check`](fn@crate::common::views::leak_check)
+    pub fn hidden_cards(&self, perspective: Perspective<Player>) -> Vec<Card> {
+        self.players()
+            .filter(|&player| !perspective.can_see(player))
+            .flat_map(|player| self.hands[player].iter().copied())
+            .collect()
+    }
+}
+
+impl Viewable for GameState {
+    type Player = Player;
+    type View = View;
+
+    fn view(&self, perspective: Perspective<Player>) -> View {
+        match perspective {
+            Perspective::Observer => View::Observer(self.observer_view()),
+            Perspective::Player(player) => View::Player(self.player_view(player)),
+            Perspective::Omniscient => View::Omniscient(self.omniscient_view()),
+        }
+    }
+}
+
+impl Play for GameState {
+    type Action = (Player, Action);
+    type Player = Player;
+    type Status = Status;
+    type Error = ActionError;
+
+    fn apply_action(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+
+    fn valid_actions(&self) -> Vec<(Player, Action)> {
+        let player = self.whose_turn();
+        self.valid_actions(player)
+            .into_iter()
+            .map(|action| (player, action))
+            .collect()
+    }
+
+    fn players(&self) -> Vec<Player> {
+        self.players().collect()
+    }
+
+    fn whose_turn(&self) -> Player {
+        self.whose_turn()
+    }
+
+    fn status(&self) -> Status {
+        self.status()
+    }
+
+    fn is_over(&self) -> bool {
+        self.status() != InProgress
+    }
+
+    fn history(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+}
+
+impl Replayable for GameState {
+    type Action = (Player, Action);
+    type Error = ActionError;
+
+    fn initial(&self) -> Self {
+        Self::new(self.config, self.seed)
+    }
+
+    fn actions(&self) -> Vec<(Player, Action)> {
+        self.history().collect()
+    }
+
+    fn apply(&self, action: (Player, Action)) -> Result<Self, ActionError> {
+        self.apply_action(action)
+    }
+}
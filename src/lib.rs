@@ -15,5 +15,9 @@ extern crate im;
 extern crate rand;
 extern crate rand_chacha;
 
+#[cfg(feature = "testing")]
+extern crate proptest;
+
+#[macro_use]
 pub mod common;
 pub mod games;
@@ -1,16 +1,36 @@
+use itertools::iterate;
+use lib_table_top::common::clock::{ShowsClock, TurnClock};
+use lib_table_top::common::deck::points::{Hand, Scheme};
+use lib_table_top::common::deck::{
+    Card,
+    Rank::*,
+    Suit::{self, *},
+};
+use lib_table_top::common::game::Play;
+use lib_table_top::common::player_info::{PlayerInfo, PlayerInfoRegistry};
 use lib_table_top::common::rand::RngSeed;
+use lib_table_top::common::replay::Replay;
+use lib_table_top::common::results::Summarize;
+use lib_table_top::common::stats::Stats;
+use lib_table_top::common::views::{DelayedObserver, ViewMetadata};
 use lib_table_top::games::crazy_eights::{
-    GameHistory, GameState, NumberOfPlayers, PlayerView, Settings,
+    broadcast::BroadcastError,
+    stats::DrawsAndReshuffles,
+    Action::*,
+    ActionError,
+    ActionTag::*,
+    AnalyzedAction, Decks, DrawRule, EightOnEight, Event, GameHistory, GameState, NumberOfPlayers,
+    ObserverView,
+    Player::{self, *},
+    PlayerView, Rules, Settings, SettingsBuilder, Snapshot, StarterEight, Status,
 };
 use serde_json::json;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 #[test]
 fn test_serializing_crazy_eights_player_view() {
-    let settings = Settings {
-        seed: RngSeed([0; 32]),
-        number_of_players: NumberOfPlayers::Three,
-    };
+    let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
     let game = GameState::new(Arc::new(settings));
 
     let action = game.current_player_view().valid_actions().pop().unwrap();
@@ -32,6 +52,10 @@ fn test_serializing_crazy_eights_player_view() {
                 "P1": 4,
                 "P2": 4,
                 "P3": 5,
+            },
+            "suit_history": {
+                "suits": {"Diamonds": 1, "Hearts": 1},
+                "ranks": [[8, 1], [11, 1]],
             }
         },
         "player": "P3",
@@ -55,10 +79,7 @@ fn test_serializing_crazy_eights_player_view() {
 
 #[test]
 fn test_serializing_and_deserializing_crazy_eights_game_history() {
-    let settings = Settings {
-        seed: RngSeed([0; 32]),
-        number_of_players: NumberOfPlayers::Three,
-    };
+    let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
     let game = GameState::new(Arc::new(settings));
 
     let serialized = serde_json::to_value(game.game_history()).unwrap();
@@ -99,3 +120,1049 @@ fn test_serializing_and_deserializing_crazy_eights_game_history() {
     let deserialized: GameHistory = serde_json::from_value(serialized).unwrap();
     assert_eq!(&deserialized, game.game_history());
 }
+
+#[test]
+fn test_deserializing_settings_that_cant_deal() {
+    let history = |deal: serde_json::Value| {
+        serde_json::from_value::<GameHistory>(json!({
+            "settings": {
+                "seed": "0000000000000000000000000000000000000000000000000000000000000000",
+                "number_of_players": 8,
+                "deal": deal,
+            },
+            "history": []
+        }))
+    };
+
+    assert!(history(json!({ "cards_per_player": 6 })).is_ok());
+    for cards_per_player in [7, 0, 255] {
+        let error = history(json!({ "cards_per_player": cards_per_player })).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Can't deal {} cards to each of Eight players",
+                cards_per_player
+            )
+        );
+    }
+
+    let error = serde_json::from_value::<Settings>(json!({
+        "seed": "0000000000000000000000000000000000000000000000000000000000000000",
+        "number_of_players": 2,
+        "deal": { "cards_per_player": 8 },
+        "rules": Rules::tournament(),
+    }))
+    .unwrap_err();
+    assert_eq!(error.to_string(), "Tournament rules use the standard deal");
+}
+
+#[test]
+fn test_serializing_crazy_eights_player_view_with_player_info() {
+    let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    let game = GameState::new(Arc::new(settings));
+
+    let mut registry = PlayerInfoRegistry::new();
+    registry.insert(
+        P1,
+        PlayerInfo {
+            display_name: "Alice".to_string(),
+            avatar_id: Some("alice.png".to_string()),
+            rating: Some(1200),
+        },
+    );
+    registry.insert(P2, PlayerInfo::new("Bob"));
+
+    let view = game.player_view(P1).with_player_info(&registry);
+    let serialized = serde_json::to_value(&view).unwrap();
+    assert_eq!(
+        serialized["observer_view"]["player_info"],
+        json!({
+            "P1": {"display_name": "Alice", "avatar_id": "alice.png", "rating": 1200},
+            "P2": {"display_name": "Bob", "avatar_id": null, "rating": null},
+        })
+    );
+
+    let deserialized: PlayerView = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized, view);
+}
+
+#[test]
+fn test_serializing_and_deserializing_crazy_eights_snapshots() {
+    let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Two);
+    let game = GameState::new(Arc::new(settings));
+
+    let serialized = serde_json::to_value(game.snapshot()).unwrap();
+    assert_eq!(serialized["version"], json!("v1"));
+    assert_eq!(serialized["top_card"], json!([12, "Clubs"]));
+
+    let deserialized: Snapshot = serde_json::from_value(serialized).unwrap();
+    assert_eq!(GameState::from_snapshot(deserialized), game);
+}
+
+#[test]
+fn test_replaying_a_crazy_eights_game() {
+    let settings = Settings::new(RngSeed([1; 32]), NumberOfPlayers::Three);
+    let game = (0..20).fold(GameState::new(Arc::new(settings)), |game, _| {
+        let action = game.current_player_view().valid_actions().pop().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    });
+
+    let mut replay = Replay::new(&game).unwrap();
+    assert_eq!(replay.len(), 20);
+    assert_eq!(replay.current_state(), &GameState::new(Arc::new(settings)));
+
+    let mut forwards = vec![replay.current_state().clone()];
+    while let Some(state) = replay.step_forward() {
+        forwards.push(state.clone());
+    }
+    assert_eq!(replay.current_state(), &game);
+    assert_eq!(replay.last_action(), game.history().last().as_ref());
+
+    let mut backwards = vec![replay.current_state().clone()];
+    while let Some(state) = replay.step_back() {
+        backwards.push(state.clone());
+    }
+    backwards.reverse();
+    assert_eq!(forwards, backwards);
+
+    assert_eq!(replay.seek(21), None);
+    assert_eq!(replay.turn(), 0);
+    assert_eq!(replay.seek(20), Some(&game));
+}
+
+#[test]
+fn test_crazy_eights_winners_score_the_penalties_left_in_hand() {
+    let settings = Settings::new(RngSeed([0; 32]), NumberOfPlayers::Three);
+    let game = GameState::new(Arc::new(settings));
+
+    let game = iterate(game, |game| {
+        let action = game.current_player_view().valid_actions().pop().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .find(|game| game.status() != Status::InProgress)
+    .unwrap();
+
+    let hands = game.omniscient_view().hands;
+    let penalties: u32 = hands
+        .values()
+        .map(|hand| {
+            let hand: Vec<Card> = hand.iter().copied().collect();
+            hand.total(Scheme::CrazyEights).points
+        })
+        .sum();
+
+    let result = game.game_result().unwrap();
+    let scores: Vec<Option<i64>> = result.players.iter().map(|result| result.score).collect();
+    assert_eq!(scores, vec![Some(0), Some(i64::from(penalties)), Some(0)]);
+    assert_eq!(result.winners().collect::<Vec<_>>(), vec![&P2]);
+}
+
+#[test]
+fn test_crazy_eights_hints() {
+    let view_with_hand = |hand: serde_json::Value| -> PlayerView {
+        serde_json::from_value(json!({
+            "observer_view": {
+                "whose_turn": "P1",
+                "current_suit": "Hearts",
+                "top_card": [5, "Hearts"],
+                "discarded": [],
+                "draw_pile_remaining": 30,
+                "player_card_count": {"P1": 4, "P2": 5}
+            },
+            "player": "P1",
+            "hand": hand,
+        }))
+        .unwrap()
+    };
+
+    // Matching the rank of a card held twice keeps a way to change suit later, eights are held
+    let view = view_with_hand(json!([
+        [8, "Clubs"],
+        [9, "Hearts"],
+        [5, "Spades"],
+        [2, "Hearts"],
+        [5, "Clubs"]
+    ]));
+    assert_eq!(view.suggest_action(), Some(Play(Card(Five, Spades))));
+
+    // Eights are played when nothing else can be, picking the suit held the most
+    let view = view_with_hand(json!([
+        [13, "Spades"],
+        [8, "Clubs"],
+        [3, "Diamonds"],
+        [4, "Diamonds"]
+    ]));
+    assert_eq!(
+        view.suggest_action(),
+        Some(PlayEight(Card(Eight, Clubs), Diamonds))
+    );
+
+    let view = view_with_hand(json!([[13, "Spades"]]));
+    assert_eq!(view.suggest_action(), Some(Draw));
+
+    let view = PlayerView { player: P2, ..view };
+    assert_eq!(view.suggest_action(), None);
+}
+
+#[test]
+fn test_crazy_eights_analyzed_actions() {
+    let view: PlayerView = serde_json::from_value(json!({
+        "observer_view": {
+            "whose_turn": "P1",
+            "current_suit": "Hearts",
+            "top_card": [5, "Hearts"],
+            "discarded": [],
+            "draw_pile_remaining": 30,
+            "player_card_count": {"P1": 1, "P2": 5}
+        },
+        "player": "P1",
+        "hand": [[13, "Spades"]],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        view.analyzed_actions(),
+        vec![AnalyzedAction {
+            action: Draw,
+            tags: vec![ForcedDraw]
+        }]
+    );
+
+    let view = PlayerView {
+        hand: vec![Card(Eight, Clubs)].into(),
+        ..view
+    };
+    let analyzed = view.analyzed_actions();
+
+    assert_eq!(analyzed.len(), 4);
+    assert_eq!(
+        analyzed[0],
+        AnalyzedAction {
+            action: PlayEight(Card(Eight, Clubs), Clubs),
+            tags: vec![Wild, ChangesSuit, WinningMove]
+        }
+    );
+    assert_eq!(
+        analyzed[2],
+        AnalyzedAction {
+            action: PlayEight(Card(Eight, Clubs), Hearts),
+            tags: vec![Wild, WinningMove]
+        }
+    );
+}
+
+#[test]
+fn test_crazy_eights_deal_rules() {
+    let deal = |seed: RngSeed, starter_eight: StarterEight| {
+        SettingsBuilder::new()
+            .seed(seed)
+            .number_of_players(NumberOfPlayers::Three)
+            .starter_eight(starter_eight)
+            .build_game()
+            .unwrap()
+    };
+
+    let seed = (0..=255)
+        .map(|byte| RngSeed([byte; 32]))
+        .find(|&seed| {
+            deal(seed, StarterEight::Play)
+                .observer_view()
+                .top_card
+                .rank()
+                == Eight
+        })
+        .unwrap();
+
+    let view = deal(seed, StarterEight::Play).observer_view();
+    let starter = view.top_card;
+    assert_eq!(view.current_suit, starter.suit());
+    assert_eq!(view.draw_pile_remaining, 36);
+
+    let view = deal(seed, StarterEight::Bury).observer_view();
+    assert_ne!(view.top_card.rank(), Eight);
+    assert_eq!(view.current_suit, view.top_card.suit());
+    assert_eq!(view.draw_pile_remaining, 36);
+
+    let game = deal(seed, StarterEight::DealerPicksSuit);
+    let view = game.observer_view();
+    let held = |suit| {
+        game.player_view(P3)
+            .hand
+            .iter()
+            .filter(|card| card.suit() == suit)
+            .count()
+    };
+    assert_eq!(view.top_card, starter);
+    assert!(Suit::ALL
+        .iter()
+        .all(|&suit| held(suit) <= held(view.current_suit)));
+
+    let game = SettingsBuilder::new()
+        .seed(seed)
+        .number_of_players(NumberOfPlayers::Three)
+        .cards_per_player(3)
+        .build_game()
+        .unwrap();
+    assert_eq!(game.observer_view().draw_pile_remaining, 42);
+    assert!(game
+        .players()
+        .all(|player| game.player_view(player).hand.len() == 3));
+}
+
+#[test]
+fn test_crazy_eights_action_indices() {
+    for index in 0..GameState::action_space_size() {
+        let action = GameState::action_from_index(index).unwrap();
+        assert_eq!(GameState::action_index(action), index);
+    }
+
+    let game = iterate(
+        GameState::new(Arc::new(Settings::new(
+            RngSeed([3; 32]),
+            NumberOfPlayers::Three,
+        ))),
+        |game| {
+            let action = game.current_player_view().valid_actions()[0];
+            game.apply_action((game.whose_turn(), action)).unwrap()
+        },
+    )
+    .take(50)
+    .last()
+    .unwrap();
+
+    let legal: Vec<_> = game
+        .legal_action_mask()
+        .iter()
+        .enumerate()
+        .filter(|(_, &legal)| legal)
+        .map(|(index, _)| GameState::action_from_index(index).unwrap())
+        .collect();
+    let mut valid = game.current_player_view().valid_actions();
+    valid.sort_by_key(|&action| GameState::action_index(action));
+    assert_eq!(legal, valid);
+}
+
+#[test]
+fn test_delaying_crazy_eights_spectators() {
+    let game = GameState::new(Arc::new(Settings::new(
+        RngSeed([5; 32]),
+        NumberOfPlayers::Three,
+    )));
+    let games: Vec<GameState> = iterate(game, |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .collect();
+
+    let mut delayed = DelayedObserver::new(3);
+    for (turn, game) in games.iter().enumerate() {
+        let released = delayed.push(game.observer_view());
+
+        match turn.checked_sub(3) {
+            Some(earlier) => assert_eq!(released, Some(games[earlier].observer_view())),
+            None => assert_eq!(released, None),
+        }
+    }
+
+    let rest: Vec<_> = games[games.len() - 3..]
+        .iter()
+        .map(|game| game.observer_view())
+        .collect();
+    assert_eq!(delayed.flush(), rest);
+}
+
+#[test]
+fn test_driving_crazy_eights_generically() {
+    fn play_out<G: Play>(mut game: G) -> G {
+        while !game.is_over() {
+            let action = game.valid_actions().pop().unwrap();
+            game = Play::apply_action(&game, action).ok().unwrap();
+        }
+        game
+    }
+
+    let game = play_out(GameState::new(Arc::new(Settings::new(
+        RngSeed([6; 32]),
+        NumberOfPlayers::Two,
+    ))));
+
+    assert!(matches!(Play::status(&game), Status::Win { .. }));
+    assert!(game.valid_actions().is_empty());
+    assert_eq!(Play::history(&game), game.history().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_crazy_eights_views_show_the_clock() {
+    let game = GameState::new(Arc::new(Settings::new(
+        RngSeed([0; 32]),
+        NumberOfPlayers::Two,
+    )));
+    let started = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let clock = TurnClock::start(Duration::from_secs(20), started);
+
+    let view = game.player_view(P2);
+    assert_eq!(view.observer_view.deadline, None);
+    assert!(serde_json::to_value(&view.observer_view)
+        .unwrap()
+        .get("deadline")
+        .is_none());
+
+    let view = view.with_clock(&clock, started + Duration::from_secs(5));
+    assert_eq!(
+        view.observer_view.deadline,
+        Some(started + Duration::from_secs(20))
+    );
+    assert_eq!(
+        view.observer_view.time_remaining,
+        Some(Duration::from_secs(15))
+    );
+
+    let serialized = serde_json::to_value(&view).unwrap();
+    assert_eq!(
+        serialized["observer_view"]["time_remaining"],
+        json!({"secs": 15, "nanos": 0})
+    );
+    assert_eq!(
+        serde_json::from_value::<PlayerView>(serialized).unwrap(),
+        view
+    );
+}
+
+#[test]
+fn test_two_deck_tournament_games() {
+    let game = GameState::new(Arc::new(Settings::tournament(
+        RngSeed([4; 32]),
+        NumberOfPlayers::Six,
+    )));
+    assert_eq!(game.settings().rules().decks, Decks::TwoForSixOrMore);
+
+    let games: Vec<GameState> = iterate(game, |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .take(500)
+    .collect();
+
+    // Every card is still in play twice over, and playing one copy leaves the other in hand
+    for game in games.iter() {
+        let view = game.observer_view();
+        let in_hands: usize = view.player_card_count.values().sum();
+        assert_eq!(
+            in_hands + view.discarded.len() + 1 + view.draw_pile_remaining as usize,
+            104
+        );
+    }
+
+    let last = games.last().unwrap();
+    assert_eq!(last.game_history().game_state().as_ref(), Ok(last));
+}
+
+#[test]
+fn test_voluntary_draws() {
+    let game = |voluntary_draws| {
+        SettingsBuilder::new()
+            .seed(RngSeed([0; 32]))
+            .number_of_players(NumberOfPlayers::Two)
+            .rules(Rules {
+                voluntary_draws,
+                ..Default::default()
+            })
+            .build_game()
+            .unwrap()
+    };
+
+    let strict = game(false);
+    let actions = strict.current_player_view().valid_actions();
+    assert!(!actions.is_empty());
+    assert!(!actions.contains(&Draw));
+    assert!(strict.apply_action((P1, Draw)).is_err());
+
+    let relaxed = game(true);
+    let mut expected = actions;
+    expected.push(Draw);
+    assert_eq!(relaxed.current_player_view().valid_actions(), expected);
+    assert!(relaxed.observer_view().rules.voluntary_draws);
+
+    let drawn = relaxed.apply_action((P1, Draw)).unwrap();
+    assert_eq!(drawn.player_view(P1).hand.len(), 8);
+    assert_eq!(drawn.whose_turn(), P2);
+}
+
+#[test]
+fn test_player_metadata_stays_with_the_player() {
+    let game = GameState::new(Arc::new(Settings::new(
+        RngSeed([0; 32]),
+        NumberOfPlayers::Two,
+    )));
+
+    let view = game
+        .player_view(P1)
+        .with_metadata("hint", json!({"play": [7, "Clubs"]}));
+    let serialized = serde_json::to_value(&view).unwrap();
+
+    assert_eq!(serialized["metadata"]["hint"]["play"], json!([7, "Clubs"]));
+    assert!(serialized["observer_view"].get("metadata").is_none());
+    assert!(game.player_view(P2).metadata().is_empty());
+    assert_eq!(
+        serde_json::from_value::<PlayerView>(serialized).unwrap(),
+        view
+    );
+}
+
+#[test]
+fn test_reshuffles_are_replayed_and_resumed() {
+    let settings = Arc::new(Settings::new(RngSeed([4; 32]), NumberOfPlayers::Four));
+    let game = iterate(GameState::new(settings), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .find(|game| game.reshuffle_count() == 1 || game.status() != Status::InProgress)
+    .unwrap();
+    assert_eq!(game.reshuffle_count(), 1);
+
+    let reshuffled_on: Vec<usize> = game
+        .events()
+        .iter()
+        .filter_map(|event| match event {
+            Event::ReshuffleOccurred { turn } => Some(*turn),
+            Event::Action { .. } => None,
+        })
+        .collect();
+    assert_eq!(reshuffled_on, vec![game.history().count() - 1]);
+
+    let replayed = game.game_history().game_state().unwrap();
+    assert_eq!(replayed.events(), game.events());
+
+    let resumed = GameState::from_snapshot(game.snapshot());
+    assert_eq!(resumed, game);
+    assert_eq!(resumed.observer_view().reshuffle_count, 1);
+}
+
+#[test]
+fn test_match_play_redeals_until_the_target() {
+    let settings = Arc::new(Settings::with_rules(
+        RngSeed([2; 32]),
+        NumberOfPlayers::Three,
+        Rules {
+            match_target: Some(150),
+            ..Default::default()
+        },
+    ));
+    let game = iterate(GameState::new(settings), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .find(|game| game.status() != Status::InProgress)
+    .unwrap();
+
+    let (winner, scores) = match game.status() {
+        Status::MatchWin { player, scores } => (player, scores),
+        status => panic!("Expected a match win, got {:?}", status),
+    };
+    assert!(game.rounds_played() > 1);
+    assert!(scores[&winner] >= 150);
+    assert!(scores.values().filter(|&&score| score >= 150).count() == 1);
+    assert_eq!(game.observer_view().scores[&winner], scores[&winner]);
+
+    let result = game.game_result().unwrap();
+    assert_eq!(result.winners().collect::<Vec<_>>(), vec![&winner]);
+    assert_eq!(
+        result
+            .players
+            .iter()
+            .map(|result| result.score.unwrap())
+            .sum::<i64>(),
+        scores.values().map(|&score| i64::from(score)).sum::<i64>()
+    );
+
+    assert_eq!(game.game_history().game_state(), Ok(game.clone()));
+    assert_eq!(GameState::from_snapshot(game.snapshot()), game);
+}
+
+#[test]
+fn test_house_rules() {
+    let settings = Arc::new(Settings::with_rules(
+        RngSeed([3; 32]),
+        NumberOfPlayers::Four,
+        Rules {
+            stacking_twos: true,
+            queens_skip: true,
+            jacks_reverse: true,
+            ..Default::default()
+        },
+    ));
+    let games: Vec<GameState> = iterate(GameState::new(settings), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .collect();
+
+    // The player a number of seats away, going the way play is going
+    let seat = |from: Player, steps: usize, reversed: bool| {
+        let players = [P1, P2, P3, P4];
+        let index = players.iter().position(|&p| p == from).unwrap();
+        if reversed {
+            players[(index + 4 - steps) % 4]
+        } else {
+            players[(index + steps) % 4]
+        }
+    };
+
+    let mut seen = (false, false, false);
+    for pair in games.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        let (player, action) = after.last_action().unwrap();
+        let pending = before.observer_view().pending_draws;
+        let reversed = before.observer_view().reversed;
+
+        match action {
+            Play(Card(Two, _)) => {
+                seen.0 = true;
+                assert_eq!(after.observer_view().pending_draws, pending + 2);
+                assert_eq!(after.whose_turn(), seat(player, 1, reversed));
+            }
+            Play(Card(Queen, _)) => {
+                seen.1 = true;
+                assert_eq!(after.whose_turn(), seat(player, 2, reversed));
+            }
+            Play(Card(Jack, _)) => {
+                seen.2 = true;
+                assert_eq!(after.observer_view().reversed, !reversed);
+                assert_eq!(after.whose_turn(), seat(player, 1, !reversed));
+            }
+            Draw if pending > 0 => {
+                assert_eq!(
+                    after.player_view(player).hand.len(),
+                    before.player_view(player).hand.len() + usize::from(pending)
+                );
+                assert_eq!(after.observer_view().pending_draws, 0);
+                assert_eq!(after.whose_turn(), seat(player, 1, reversed));
+            }
+            _ => assert_eq!(after.whose_turn(), seat(player, 1, reversed)),
+        }
+
+        if pending > 0 {
+            assert!(before
+                .current_player_view()
+                .valid_actions()
+                .iter()
+                .all(|&action| matches!(action, Draw | Play(Card(Two, _)))));
+        }
+    }
+    assert_eq!(seen, (true, true, true));
+
+    let last = games.last().unwrap();
+    for (turn, game) in games.iter().enumerate().skip(1) {
+        assert_eq!(last.nth_turn(turn - 1), game.last_action());
+    }
+    let mut by_player: Vec<_> = [P1, P2, P3, P4]
+        .iter()
+        .flat_map(|&player| {
+            last.history_for_player(player)
+                .map(move |(turn, action)| (turn, player, action))
+        })
+        .collect();
+    by_player.sort_by_key(|&(turn, _, _)| turn);
+    assert!(by_player
+        .into_iter()
+        .map(|(_, player, action)| (player, action))
+        .eq(last.history()));
+
+    assert_eq!(last.game_history().game_state(), Ok(last.clone()));
+    assert_eq!(GameState::from_snapshot(last.snapshot()), *last);
+}
+
+#[test]
+fn test_drawing_until_playable() {
+    let settings = |draw| {
+        Arc::new(Settings::with_rules(
+            RngSeed([6; 32]),
+            NumberOfPlayers::Three,
+            Rules {
+                draw,
+                ..Default::default()
+            },
+        ))
+    };
+    let until_playable = DrawRule::DrawUntilPlayable { max: None };
+
+    // Finds a turn where the player has to draw
+    let game = iterate(GameState::new(settings(until_playable)), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .find(|game| game.forced_action() == Some(Draw))
+    .unwrap();
+    let player = game.whose_turn();
+    let hand = game.player_view(player).hand.len();
+
+    // With this seed the first seven cards drawn can't be played and the eighth can
+    let drawn = game.apply_action((player, Draw)).unwrap();
+    assert_eq!(drawn.whose_turn(), player);
+    assert_eq!(drawn.player_view(player).hand.len(), hand + 8);
+    assert!(drawn
+        .current_player_view()
+        .valid_actions()
+        .iter()
+        .all(|&action| action != Draw));
+
+    // Capping the draws passes the turn once the cap is hit without a playable card
+    let capped = settings(DrawRule::DrawUntilPlayable { max: Some(3) });
+    let replayed = game
+        .history()
+        .try_fold(GameState::new(capped), |replayed, action| {
+            replayed.apply_action(action)
+        })
+        .unwrap();
+    let drawn = replayed.apply_action((player, Draw)).unwrap();
+    assert_ne!(drawn.whose_turn(), player);
+    assert_eq!(drawn.player_view(player).hand.len(), hand + 3);
+}
+
+#[test]
+fn test_passing_once_every_card_is_dealt() {
+    // 25 cards each and the top card leave one card to draw
+    let game = SettingsBuilder::new()
+        .seed(RngSeed([2; 32]))
+        .number_of_players(NumberOfPlayers::Two)
+        .cards_per_player(25)
+        .rules(Rules {
+            voluntary_draws: true,
+            ..Default::default()
+        })
+        .build_game()
+        .unwrap();
+
+    // Drawing whenever possible keeps the draw and discard piles empty
+    let stuck = iterate(game, |game| {
+        let actions = game.current_player_view().valid_actions();
+        let action = if actions.contains(&Draw) {
+            Draw
+        } else {
+            actions[0]
+        };
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .find(|game| game.forced_action() == Some(Pass))
+    .unwrap();
+
+    let player = stuck.whose_turn();
+    assert_eq!(
+        stuck.apply_action((player, Draw)),
+        Err(ActionError::NoCardsToDraw { player })
+    );
+    let passed = stuck.apply_action((player, Pass)).unwrap();
+    assert_ne!(passed.whose_turn(), player);
+    assert_eq!(passed.history().last(), Some((player, Pass)));
+}
+
+/// A made up position in `game`, with `top_card` played and the current suit, hands, and draw pile
+/// set. Nothing is discarded and no one has scored
+fn position(
+    game: &GameState,
+    (top_card, current_suit): (Card, Suit),
+    hands: &[(Player, Vec<Card>)],
+    draw_pile: &[Card],
+) -> GameState {
+    match game.snapshot() {
+        Snapshot::V1 {
+            game_history,
+            rng_word_pos,
+            ..
+        } => GameState::from_snapshot(Snapshot::V1 {
+            game_history,
+            rng_word_pos,
+            discarded: Default::default(),
+            hands: hands.iter().cloned().collect(),
+            draw_pile: draw_pile.iter().copied().collect(),
+            top_card,
+            current_suit,
+            reshuffles: Default::default(),
+            scores: Default::default(),
+            rounds_played: 0,
+            turns: None,
+            reversed: false,
+            pending_draws: 0,
+            suit_history: Default::default(),
+        }),
+    }
+}
+
+#[test]
+fn test_blocked_games_end() {
+    // Eights can always be played, so only a game without them in hand can block
+    let game = GameState::new(Arc::new(Settings::tournament(
+        RngSeed([0; 32]),
+        NumberOfPlayers::Two,
+    )));
+    let blocked = position(
+        &game,
+        (Card(Five, Diamonds), Diamonds),
+        &[
+            (P1, vec![Card(Two, Clubs), Card(King, Hearts)]),
+            (P2, vec![Card(Ace, Spades)]),
+        ],
+        &[],
+    );
+
+    let scores = [(P1, 12), (P2, 1)].iter().copied().collect();
+    assert_eq!(blocked.status(), Status::Blocked { scores });
+    assert!(Play::is_over(&blocked));
+
+    let result = blocked.game_result().unwrap();
+    assert_eq!(result.winners().collect::<Vec<_>>(), vec![&P2]);
+    assert_eq!(result.players[0].score, Some(12));
+}
+
+#[test]
+fn test_draw_stats_count_every_card_and_reshuffle() {
+    let draws_and_reshuffles = |rules, action| {
+        let settings = Settings::with_rules(RngSeed([0; 32]), NumberOfPlayers::Two, rules);
+        // P1's play puts the five on the discard pile, so P2's draw takes the three and then
+        // reshuffles to get the five
+        let game = position(
+            &GameState::new(Arc::new(settings)),
+            (Card(Five, Clubs), Clubs),
+            &[
+                (P1, vec![action, Card(King, Hearts)]),
+                (P2, vec![Card(Ace, Hearts)]),
+            ],
+            &[Card(Three, Hearts)],
+        );
+        let played = game.apply_action((P1, Play(action))).unwrap();
+        let drawn = played.apply_action((P2, Draw)).unwrap();
+
+        let mut stats = Stats::new().with(DrawsAndReshuffles::default());
+        stats.record_action(&game, &(P1, Play(action)), &played, None);
+        stats.record_action(&played, &(P2, Draw), &drawn, None);
+        stats.record_game(&drawn);
+        let report = &stats.report()["draws_and_reshuffles"];
+        (
+            report["draws"]["total"].clone(),
+            report["reshuffles"]["total"].clone(),
+        )
+    };
+
+    let stacking_twos = Rules {
+        stacking_twos: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        draws_and_reshuffles(stacking_twos, Card(Two, Clubs)),
+        (json!(2), json!(1))
+    );
+
+    let draw_until_playable = Rules {
+        draw: DrawRule::DrawUntilPlayable { max: None },
+        ..Default::default()
+    };
+    assert_eq!(
+        draws_and_reshuffles(draw_until_playable, Card(Six, Clubs)),
+        (json!(2), json!(1))
+    );
+}
+
+#[test]
+fn test_broadcasting_observer_views() {
+    let settings = Arc::new(Settings::with_rules(
+        RngSeed([5; 32]),
+        NumberOfPlayers::Six,
+        Rules {
+            decks: Decks::TwoForSixOrMore,
+            voluntary_draws: true,
+            draw: DrawRule::DrawUntilPlayable { max: Some(3) },
+            match_target: Some(100),
+            stacking_twos: true,
+            queens_skip: true,
+            jacks_reverse: true,
+            eight_on_eight: EightOnEight::KeepSuit,
+        },
+    ));
+    let games: Vec<GameState> = iterate(GameState::new(settings), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .collect();
+
+    for game in &games {
+        let view = game.observer_view();
+        let bytes = view.broadcast_bytes();
+        assert_eq!(ObserverView::from_broadcast_bytes(&bytes), Ok(view));
+    }
+
+    let bytes = games.last().unwrap().observer_view().broadcast_bytes();
+    for end in 0..bytes.len() {
+        assert_eq!(
+            ObserverView::from_broadcast_bytes(&bytes[..end]),
+            Err(BroadcastError::Truncated)
+        );
+    }
+
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert_eq!(
+        ObserverView::from_broadcast_bytes(&extra),
+        Err(BroadcastError::TrailingBytes { extra: 1 })
+    );
+
+    // The second byte is whose turn it is, and P7 isn't seated at a table of six
+    let mut bad_turn = bytes.clone();
+    bad_turn[1] = 6;
+    assert_eq!(
+        ObserverView::from_broadcast_bytes(&bad_turn),
+        Err(BroadcastError::InvalidByte { offset: 1, byte: 6 })
+    );
+
+    // The byte after the hand counts is the top card, and there are only 52 cards
+    let mut bad_card = bytes;
+    let offset = 3 + 6;
+    bad_card[offset] = 52;
+    assert_eq!(
+        ObserverView::from_broadcast_bytes(&bad_card),
+        Err(BroadcastError::InvalidByte { offset, byte: 52 })
+    );
+}
+
+#[test]
+fn test_eight_on_eight_rules() {
+    let with_rule = |eight_on_eight| {
+        let settings = Settings::with_rules(
+            RngSeed([0; 32]),
+            NumberOfPlayers::Two,
+            Rules {
+                eight_on_eight,
+                ..Default::default()
+            },
+        );
+        position(
+            &GameState::new(Arc::new(settings)),
+            (Card(Eight, Clubs), Hearts),
+            &[
+                (P1, vec![Card(Eight, Spades), Card(King, Diamonds)]),
+                (P2, vec![Card(Ace, Spades)]),
+            ],
+            &[Card(Three, Clubs)],
+        )
+    };
+    let eights = |game: &GameState| {
+        game.player_view(P1)
+            .valid_actions()
+            .into_iter()
+            .filter(|action| matches!(action, PlayEight(..)))
+            .count()
+    };
+
+    let redeclare = with_rule(EightOnEight::Redeclare);
+    assert_eq!(eights(&redeclare), 4);
+    let game = redeclare
+        .apply_action((P1, PlayEight(Card(Eight, Spades), Diamonds)))
+        .unwrap();
+    assert_eq!(game.observer_view().current_suit, Diamonds);
+
+    let keep_suit = with_rule(EightOnEight::KeepSuit);
+    assert_eq!(
+        keep_suit.player_view(P1).valid_actions(),
+        vec![PlayEight(Card(Eight, Spades), Hearts)]
+    );
+    let error = keep_suit
+        .apply_action((P1, PlayEight(Card(Eight, Spades), Diamonds)))
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ActionError::MustKeepSuit {
+            player: P1,
+            suit: Diamonds,
+            current_suit: Hearts
+        }
+    );
+    assert_eq!(error.code(), "must_keep_suit");
+    let game = keep_suit
+        .apply_action((P1, PlayEight(Card(Eight, Spades), Hearts)))
+        .unwrap();
+    assert_eq!(game.observer_view().current_suit, Hearts);
+
+    // With nothing to follow hearts, the only option is to draw
+    let forbidden = with_rule(EightOnEight::Forbidden);
+    assert_eq!(forbidden.player_view(P1).valid_actions(), vec![Draw]);
+    assert!(matches!(
+        forbidden.apply_action((P1, PlayEight(Card(Eight, Spades), Hearts))),
+        Err(ActionError::CardCantBePlayed { .. })
+    ));
+    assert!(forbidden.apply_action((P1, Draw)).is_ok());
+}
+
+#[test]
+fn test_forbidden_eight_on_eight_starter() {
+    // This seed turns up the eight of hearts, and P1 holds the eight of clubs
+    let game = |eight_on_eight| {
+        let settings = Settings::with_rules(
+            RngSeed([105; 32]),
+            NumberOfPlayers::Two,
+            Rules {
+                eight_on_eight,
+                ..Default::default()
+            },
+        );
+        GameState::new(Arc::new(settings))
+    };
+
+    let forbidden = game(EightOnEight::Forbidden);
+    assert_eq!(forbidden.observer_view().top_card, Card(Eight, Hearts));
+    assert_eq!(forbidden.player_view(P1).valid_actions(), vec![Draw]);
+    assert!(matches!(
+        forbidden.apply_action((P1, PlayEight(Card(Eight, Clubs), Hearts))),
+        Err(ActionError::CardCantBePlayed { .. })
+    ));
+
+    let redeclare = game(EightOnEight::Redeclare);
+    assert!(redeclare
+        .apply_action((P1, PlayEight(Card(Eight, Clubs), Spades)))
+        .is_ok());
+}
+
+/// Stored seeds only replay to the same deals if the game draws the same numbers in the same
+/// order, so the draws of a whole match are pinned. If this fails the change breaks every stored
+/// game, and needs a new settings version instead
+#[cfg(debug_assertions)]
+#[test]
+fn test_rng_consumption_is_pinned() {
+    use lib_table_top::common::rand::{RngDraws, RngUse};
+
+    let settings = Arc::new(Settings::with_rules(
+        RngSeed([4; 32]),
+        NumberOfPlayers::Four,
+        Rules {
+            match_target: Some(100),
+            ..Default::default()
+        },
+    ));
+    let game = iterate(GameState::new(settings), |game| {
+        let action = game.current_player_view().suggest_action().unwrap();
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .find(|game| game.status() != Status::InProgress)
+    .unwrap();
+
+    let draws: Vec<(RngUse, u64)> = game
+        .rng_audit()
+        .iter()
+        .map(|&RngDraws { purpose, words }| (purpose, words))
+        .collect();
+    assert_eq!(game.reshuffle_count(), 1);
+    assert_eq!(
+        draws,
+        vec![
+            (RngUse::Shuffle, 85),
+            (RngUse::Reshuffle, 81),
+            (RngUse::Shuffle, 72),
+            (RngUse::Shuffle, 73),
+            (RngUse::Shuffle, 70),
+            (RngUse::Shuffle, 73),
+        ]
+    );
+}
@@ -1,7 +1,8 @@
 use itertools::iterate;
+use lib_table_top::common::results::{settings_hash, Summarize};
 use lib_table_top::games::marooned::{
-    Action, Col, Dimensions, GameState, Player::*, Position, Row, Settings, SettingsBuilder,
-    SettingsError::*, Status::*,
+    editor::BoardEditor, scenario::Scenario, Action, Col, Dimensions, GameState, ObserverView,
+    Player::*, Position, Row, Settings, SettingsBuilder, SettingsError::*, Status::*,
 };
 use serde_json::json;
 
@@ -115,6 +116,7 @@ fn test_a_full_game() {
                 vec![]
             );
         }
+        Draw => unreachable!("Games without draw rules are never drawn"),
     })
     .take_while(|game| game.status() == InProgress)
     .collect::<Vec<GameState>>();
@@ -204,3 +206,131 @@ fn test_serializing_game_state() {
     let deserialized: GameState = serde_json::from_value(serialized).unwrap();
     assert_eq!(game, deserialized);
 }
+
+#[test]
+fn test_serializing_observer_views() {
+    let game = SettingsBuilder::new()
+        .rows(3)
+        .cols(3)
+        .starting_removed(vec![(Col(0), Row(0))])
+        .build_game()
+        .unwrap();
+    let action = Action {
+        player: P1,
+        to: (Col(1), Row(1)),
+        remove: (Col(2), Row(2)),
+    };
+    let game = game.apply_action(action).unwrap();
+
+    let serialized = serde_json::to_value(game.observer_view()).unwrap();
+    assert_eq!(
+        serialized,
+        json!({
+            "dimensions": {"rows": 3, "cols": 3},
+            "p1": [1, 1],
+            "p2": [1, 2],
+            "removed": [[0, 0], [2, 2]],
+            "whose_turn": 2,
+            "last_action": {"player": 1, "to": [1, 1], "remove": [2, 2]},
+            "status": "InProgress",
+        })
+    );
+
+    let deserialized: ObserverView = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized, game.observer_view());
+}
+
+#[test]
+fn test_game_results() {
+    let game = SettingsBuilder::new().rows(1).cols(2).build_game().unwrap();
+    let result = game.game_result().unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&result.players).unwrap(),
+        json!([
+            {"player": 1, "outcome": "Loss", "score": null, "place": 2},
+            {"player": 2, "outcome": "Win", "score": null, "place": 1}
+        ])
+    );
+    assert_eq!(result.move_count, 0);
+    assert_eq!(result.settings_hash, settings_hash(game.settings()));
+    assert_ne!(
+        result.settings_hash,
+        settings_hash(&SettingsBuilder::new().build().unwrap())
+    );
+}
+
+#[test]
+fn test_serializing_scenarios() {
+    let scenario = Scenario {
+        dimensions: Dimensions::new(3, 3).unwrap(),
+        p1: (Col(2), Row(2)),
+        p2: (Col(0), Row(0)),
+        removed: vec![(Col(1), Row(1))],
+        to_move: P2,
+    };
+
+    let expected = json!({
+        "dimensions": {"rows": 3, "cols": 3},
+        "p1": [2, 2],
+        "p2": [0, 0],
+        "removed": [[1, 1]],
+        "to_move": 2,
+    });
+    assert_eq!(serde_json::to_value(&scenario).unwrap(), expected);
+    assert_eq!(
+        serde_json::from_value::<Scenario>(expected).unwrap(),
+        scenario
+    );
+
+    let game = scenario.game().unwrap();
+    assert_eq!(game.whose_turn(), P2);
+    assert_eq!(game.history().count(), 0);
+    assert_eq!(Scenario::from_game(&game), scenario);
+
+    let invalid = Scenario {
+        removed: vec![(Col(0), Row(0))],
+        ..scenario
+    };
+    assert_eq!(
+        invalid.game(),
+        Err(PlayerCantStartOnRemovedSquare {
+            player: P2,
+            position: (Col(0), Row(0))
+        })
+    );
+}
+
+#[test]
+fn test_serializing_edited_boards() {
+    let mut editor = BoardEditor::new(Dimensions::new(3, 3).unwrap());
+    editor.remove((Col(0), Row(2)));
+    editor.remove((Col(0), Row(1)));
+    editor.place_player(P2, (Col(2), Row(2)));
+
+    let expected = json!({
+        "dimensions": {"rows": 3, "cols": 3},
+        "p1": [1, 0],
+        "p2": [2, 2],
+        "removed": [[0, 1], [0, 2]],
+        "to_move": 1,
+    });
+    assert_eq!(serde_json::to_value(&editor).unwrap(), expected);
+    assert_eq!(
+        serde_json::from_value::<BoardEditor>(expected).unwrap(),
+        editor
+    );
+
+    let settings = editor.settings().unwrap();
+    assert_eq!(BoardEditor::from_settings(&settings), editor);
+    assert_eq!(editor.game().unwrap().settings(), &settings);
+
+    editor.place_player(P1, (Col(5), Row(5)));
+    assert_eq!(
+        editor.validate(),
+        Err(PlayersMustStartOnBoard {
+            player: P1,
+            position: (Col(5), Row(5))
+        })
+    );
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "testing")]
+
+use proptest::prelude::*;
+
+use lib_table_top::common::deck::Card;
+use lib_table_top::common::rand::RngSeed;
+use lib_table_top::common::views::{apply_patch, diff, leak_check, Perspective, Viewable};
+use lib_table_top::games::{crazy_eights, marooned, tic_tac_toe};
+use std::sync::Arc;
+
+proptest! {
+    #[test]
+    fn cards_and_seeds_round_trip_through_serde(card in any::<Card>(), seed in any::<RngSeed>()) {
+        let serialized = serde_json::to_value(card).unwrap();
+        prop_assert_eq!(serde_json::from_value::<Card>(serialized).unwrap(), card);
+
+        let serialized = serde_json::to_value(seed).unwrap();
+        prop_assert_eq!(serde_json::from_value::<RngSeed>(serialized).unwrap(), seed);
+    }
+
+    #[test]
+    fn tic_tac_toe_valid_actions_are_all_accepted(game in any::<tic_tac_toe::GameState>()) {
+        for action in game.valid_actions() {
+            prop_assert!(game.apply_action(action).is_ok());
+        }
+    }
+
+    #[test]
+    fn tic_tac_toe_history_replays_to_the_same_game(game in any::<tic_tac_toe::GameState>()) {
+        let replayed = game
+            .history()
+            .try_fold(tic_tac_toe::GameState::new(), |replayed, action| replayed.apply_action(action));
+
+        prop_assert_eq!(replayed, Ok(game));
+    }
+
+    #[test]
+    fn marooned_valid_actions_are_all_accepted(game in any::<marooned::GameState>()) {
+        for action in game.valid_actions() {
+            prop_assert!(game.apply_action(action).is_ok());
+        }
+    }
+
+    #[test]
+    fn marooned_history_replays_to_the_same_game(game in any::<marooned::GameState>()) {
+        let replayed = game.history().try_fold(
+            marooned::GameState::new(Arc::new(game.settings().clone())),
+            |replayed, &action| replayed.apply_action(action),
+        );
+
+        prop_assert_eq!(replayed, Ok(game));
+    }
+
+    #[test]
+    fn crazy_eights_valid_actions_are_all_accepted(game in any::<crazy_eights::GameState>()) {
+        let player = game.whose_turn();
+
+        for action in game.current_player_view().valid_actions() {
+            prop_assert!(game.apply_action((player, action)).is_ok());
+        }
+    }
+
+    #[test]
+    fn crazy_eights_game_history_rebuilds_the_same_game(game in any::<crazy_eights::GameState>()) {
+        prop_assert_eq!(game.game_history().game_state(), Ok(game));
+    }
+
+    #[test]
+    fn crazy_eights_fuzzing_never_panics(bytes in proptest::collection::vec(any::<u8>(), 0..500)) {
+        crazy_eights::fuzz::apply_raw(&bytes);
+    }
+
+    #[test]
+    fn marooned_fuzzing_never_panics(bytes in proptest::collection::vec(any::<u8>(), 0..500)) {
+        marooned::fuzz::apply_raw(&bytes);
+    }
+
+    #[test]
+    fn crazy_eights_snapshots_resume_the_same_game(
+        game in any::<crazy_eights::GameState>(),
+        moves in 0..100usize,
+    ) {
+        let serialized = serde_json::to_value(game.snapshot()).unwrap();
+        let snapshot: crazy_eights::Snapshot = serde_json::from_value(serialized).unwrap();
+        let resumed = crazy_eights::GameState::from_snapshot(snapshot);
+        prop_assert_eq!(&resumed, &game);
+
+        // Keep drawing and playing so that the resumed game has to reshuffle
+        let (game, resumed) = (0..moves).fold((game, resumed), |(game, resumed), _| {
+            match game.current_player_view().valid_actions().pop() {
+                Some(action) if game.status() == crazy_eights::Status::InProgress => {
+                    let player = game.whose_turn();
+                    (
+                        game.apply_action((player, action)).unwrap(),
+                        resumed.apply_action((player, action)).unwrap(),
+                    )
+                }
+                _ => (game, resumed),
+            }
+        });
+        prop_assert_eq!(resumed, game);
+    }
+
+    #[test]
+    fn marooned_and_tic_tac_toe_snapshots_resume_the_same_game(
+        marooned_game in any::<marooned::GameState>(),
+        tic_tac_toe_game in any::<tic_tac_toe::GameState>(),
+    ) {
+        prop_assert_eq!(
+            marooned::GameState::from_snapshot(marooned_game.snapshot()),
+            marooned_game
+        );
+        prop_assert_eq!(
+            tic_tac_toe::GameState::from_snapshot(tic_tac_toe_game.snapshot()),
+            tic_tac_toe_game
+        );
+    }
+
+    #[test]
+    fn crazy_eights_view_patches_round_trip(game in any::<crazy_eights::GameState>()) {
+        // Finished games are common enough that rejecting them would abort the test
+        if game.status() != crazy_eights::Status::InProgress {
+            return Ok(());
+        }
+
+        let player = game.whose_turn();
+        let action = game.current_player_view().valid_actions()[0];
+        let next = game.apply_action((player, action)).unwrap();
+
+        for &perspective in &[Perspective::Player(player), Perspective::Observer] {
+            let patch = diff(&game.view(perspective), &next.view(perspective));
+            prop_assert_eq!(apply_patch(&game.view(perspective), &patch), Ok(next.view(perspective)));
+        }
+    }
+
+    #[test]
+    fn crazy_eights_views_never_leak_hidden_cards(game in any::<crazy_eights::GameState>()) {
+        let perspectives = game
+            .players()
+            .map(Perspective::Player)
+            .chain(vec![Perspective::Observer, Perspective::Omniscient]);
+
+        for perspective in perspectives {
+            let leaks = leak_check(&game.view(perspective), game.hidden_cards(perspective));
+            prop_assert!(leaks.is_empty(), "{:?} can see {:?}", perspective, leaks);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use itertools::iterate;
+use lib_table_top::common::deck::{Card, Rank::*, RankOrder, Suit::*};
+use lib_table_top::common::game::Play;
+use lib_table_top::common::rand::RngSeed;
+use lib_table_top::common::results::Summarize;
+use lib_table_top::common::views::{leak_check, Perspective, Viewable};
+use lib_table_top::games::spades::{
+    Action, ActionError, Bid, GameHistory, GameState, Partnership::*, Phase, Player::*, PlayerView,
+    Rules, Settings, Status,
+};
+use std::sync::Arc;
+
+fn settings(seed: u8) -> Arc<Settings> {
+    Arc::new(Settings {
+        seed: RngSeed([seed; 32]),
+        rules: Default::default(),
+    })
+}
+
+/// Looks at a face down hand, bids the aces, kings, and long spades, and plays the first card it
+/// can
+fn simple_action(view: &PlayerView) -> Action {
+    match view.observer_view.phase {
+        Phase::Bidding if view.hand.is_empty() => Action::LookAtHand,
+        Phase::Bidding => {
+            let count = |matches: &dyn Fn(&Card) -> bool| {
+                view.hand.iter().filter(|card| matches(card)).count() as u8
+            };
+            let high = count(&|card| card.rank() == Ace || card.rank() == King);
+            let spades = count(&|card| card.suit() == Spades);
+            Action::Bid(Bid::Tricks((high + spades.saturating_sub(3)).max(1)))
+        }
+        Phase::Playing => view.valid_actions()[0],
+    }
+}
+
+fn play_out(settings: Arc<Settings>) -> Vec<GameState> {
+    // `iterate` makes the next state before `take_while` sees the last one is over
+    iterate(GameState::new(settings), |game| {
+        if Play::is_over(game) {
+            return game.clone();
+        }
+        let action = simple_action(&game.current_player_view());
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .take(100_000)
+    .collect()
+}
+
+#[test]
+fn test_playing_spades_to_the_target() {
+    let states = play_out(settings(1));
+    let last = states.last().unwrap();
+    let action = simple_action(&last.current_player_view());
+    let game = last.apply_action((last.whose_turn(), action)).unwrap();
+
+    let (winner, scores) = match game.status() {
+        Status::Win {
+            partnership,
+            scores,
+        } => (partnership, scores),
+        status => panic!("Expected a win, got {:?}", status),
+    };
+    assert!(scores[&winner] >= 500);
+    assert!(scores[&winner] > scores[&winner.opponents()]);
+    assert!(Play::valid_actions(&game).is_empty());
+
+    let view = game.observer_view();
+    assert_eq!(view.rounds.len(), game.rounds_played());
+    for partnership in [NorthSouth, EastWest].iter() {
+        let total: i32 = view
+            .rounds
+            .iter()
+            .map(|round| round.points[partnership])
+            .sum();
+        assert_eq!(total, game.score(*partnership));
+    }
+    for round in &view.rounds {
+        assert_eq!(round.tricks.values().sum::<u8>(), 13);
+    }
+
+    let result = game.game_result().unwrap();
+    let mut winners: Vec<_> = result.winners().copied().collect();
+    winners.sort();
+    assert_eq!(winners, winner.players().to_vec());
+
+    assert_eq!(game.game_history().game_state(), Ok(game.clone()));
+    let json = serde_json::to_string(game.game_history()).unwrap();
+    let history: GameHistory = serde_json::from_str(&json).unwrap();
+    assert_eq!(history.game_state(), Ok(game));
+}
+
+#[test]
+fn test_following_suit_and_breaking_spades() {
+    let states = play_out(settings(2));
+    let mut seen = (false, false);
+
+    for game in states.iter().filter(|game| game.phase() == Phase::Playing) {
+        let player = game.whose_turn();
+        let view = game.player_view(player);
+        let trick = &view.observer_view.trick;
+
+        for &card in &view.hand {
+            let result = game.apply_action((player, Action::Play(card)));
+            let follows = match trick.first() {
+                Some((_, led)) => {
+                    card.suit() == led.suit() || view.hand.iter().all(|c| c.suit() != led.suit())
+                }
+                None => {
+                    card.suit() != Spades
+                        || view.observer_view.spades_broken
+                        || view.hand.iter().all(|c| c.suit() == Spades)
+                }
+            };
+
+            match result {
+                Ok(_) => assert!(follows),
+                Err(ActionError::MustFollowSuit { .. }) => {
+                    seen.0 = true;
+                    assert!(!follows);
+                }
+                Err(ActionError::SpadesNotBroken { .. }) => {
+                    seen.1 = true;
+                    assert!(!follows && trick.is_empty());
+                }
+                Err(error) => panic!("Unexpected error {:?}", error),
+            }
+        }
+    }
+
+    assert_eq!(seen, (true, true));
+}
+
+#[test]
+fn test_tricks_go_to_the_highest_spade_or_the_highest_of_the_led_suit() {
+    let states = play_out(settings(3));
+
+    for pair in states.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        let trick = match &after.observer_view().last_trick {
+            Some(trick) if before.observer_view().trick.len() == 3 => trick.clone(),
+            _ => continue,
+        };
+
+        let led = trick[0].1.suit();
+        let spades = trick.iter().any(|(_, card)| card.suit() == Spades);
+        let suit = if spades { Spades } else { led };
+        let &(winner, _) = trick
+            .iter()
+            .filter(|(_, card)| card.suit() == suit)
+            .max_by_key(|(_, card)| card.rank().power(RankOrder::AceHigh))
+            .unwrap();
+        assert_eq!(after.whose_turn(), winner);
+    }
+}
+
+#[test]
+fn test_blind_nil_needs_a_deficit() {
+    let game = GameState::new(settings(0));
+    assert_eq!(
+        game.apply_action((P1, Action::Bid(Bid::BlindNil))),
+        Err(ActionError::BlindNilNotAllowed { player: P1 })
+    );
+
+    let anytime = Arc::new(Settings {
+        seed: RngSeed([0; 32]),
+        rules: Rules {
+            blind_nil_deficit: Some(0),
+            ..Default::default()
+        },
+    });
+    let game = GameState::new(anytime)
+        .apply_action((P1, Action::Bid(Bid::BlindNil)))
+        .unwrap();
+    assert_eq!(game.observer_view().bids[&P1], Bid::BlindNil);
+    assert_eq!(
+        game.apply_action((P2, Action::Bid(Bid::Tricks(0)))),
+        Err(ActionError::InvalidBid {
+            bid: Bid::Tricks(0)
+        })
+    );
+}
+
+#[test]
+fn test_blind_nil_is_bid_face_down() {
+    let anytime = Arc::new(Settings {
+        seed: RngSeed([0; 32]),
+        rules: Rules {
+            blind_nil_deficit: Some(0),
+            ..Default::default()
+        },
+    });
+    let game = GameState::new(anytime);
+    let hand = game.omniscient_view().hands[&P1].clone();
+
+    // Nothing in the bidding view gives the hand away, not even to its owner
+    let perspective = Perspective::Player(P1);
+    assert_eq!(game.hidden_cards(perspective).len(), 52);
+    assert!(leak_check(&game.view(perspective), game.hidden_cards(perspective)).is_empty());
+    assert!(game.player_view(P1).hand.is_empty());
+    let actions = game.player_view(P1).valid_actions();
+    assert_eq!(
+        actions[13..],
+        [
+            Action::Bid(Bid::Nil),
+            Action::Bid(Bid::BlindNil),
+            Action::LookAtHand
+        ]
+    );
+
+    let looked = game.apply_action((P1, Action::LookAtHand)).unwrap();
+    assert_eq!(looked.whose_turn(), P1);
+    assert_eq!(looked.player_view(P1).hand, hand);
+    assert!(!looked
+        .player_view(P1)
+        .valid_actions()
+        .contains(&Action::Bid(Bid::BlindNil)));
+    for action in [Action::Bid(Bid::BlindNil), Action::LookAtHand].iter() {
+        assert_eq!(
+            looked.apply_action((P1, *action)),
+            Err(ActionError::HandAlreadySeen { player: P1 })
+        );
+    }
+
+    // Once the bid is in the hand can be picked up
+    let bid = game.apply_action((P1, Action::Bid(Bid::BlindNil))).unwrap();
+    assert_eq!(bid.player_view(P1).hand, hand);
+    assert!(bid.player_view(P2).hand.is_empty());
+}
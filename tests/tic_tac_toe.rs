@@ -3,10 +3,14 @@ extern crate enum_map;
 #[macro_use]
 extern crate itertools;
 use serde_json::json;
+use std::time::Duration;
 
+use lib_table_top::common::rand::RngSeed;
+use lib_table_top::common::search::{search_with_budget, WIN};
+use lib_table_top::games::tic_tac_toe::puzzles::{generate, Difficulty};
 use lib_table_top::games::tic_tac_toe::{
-    Col, Col::*, Error::*, GameState, Player, Player::*, Position, Row, Row::*, Status,
-    POSSIBLE_WINS,
+    Col, Col::*, Error::*, GameState, ObserverView, Player, Player::*, Position, Row, Row::*,
+    Status, POSSIBLE_WINS,
 };
 
 #[test]
@@ -209,4 +213,76 @@ fn test_serializing_tic_tac_toe() {
 
     let deserialized: GameState = serde_json::from_value(serialized).unwrap();
     assert_eq!(deserialized, game);
+
+    let game = GameState::starting_with(P2)
+        .apply_action((P2, (Col0, Row0)))
+        .unwrap();
+
+    let serialized = serde_json::to_value(&game).unwrap();
+    assert_eq!(
+        serialized,
+        json!({ "history": [[0, 0]], "first_player": "P2" })
+    );
+
+    let deserialized: GameState = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized, game);
+}
+
+#[test]
+fn test_serializing_observer_views() {
+    let game = GameState::new()
+        .apply_action((P1, (Col0, Row0)))
+        .unwrap()
+        .apply_action((P2, (Col1, Row0)))
+        .unwrap();
+
+    let serialized = serde_json::to_value(game.observer_view()).unwrap();
+    assert_eq!(
+        serialized,
+        json!({
+            "board": [["P1", null, null], ["P2", null, null], [null, null, null]],
+            "whose_turn": "P1",
+            "last_action": ["P2", [1, 0]],
+            "status": "InProgress",
+        })
+    );
+
+    let deserialized: ObserverView = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized, game.observer_view());
+
+    let game = game
+        .apply_action((P1, (Col0, Row1)))
+        .unwrap()
+        .apply_action((P2, (Col1, Row1)))
+        .unwrap()
+        .apply_action((P1, (Col0, Row2)))
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(game.observer_view().status).unwrap(),
+        json!({"Win": {"player": "P1", "positions": [[0, 0], [0, 1], [0, 2]]}})
+    );
+}
+
+#[test]
+fn test_puzzles_have_a_single_solution() {
+    for &difficulty in &[Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let puzzle = generate(RngSeed([7; 32]), difficulty);
+        assert_eq!(puzzle, generate(RngSeed([7; 32]), difficulty));
+
+        let game = puzzle.game();
+        assert_eq!(game.whose_turn(), puzzle.to_move);
+        assert_eq!(game.status(), Status::InProgress);
+
+        let solved = game
+            .apply_action((puzzle.to_move, puzzle.solution))
+            .unwrap();
+        let score = -search_with_budget(&solved, Duration::from_secs(60)).score;
+
+        match difficulty {
+            Difficulty::Easy => assert!(matches!(solved.status(), Status::Win { .. })),
+            Difficulty::Medium => assert!(score >= 0),
+            Difficulty::Hard => assert!(score > WIN / 2),
+        }
+    }
 }
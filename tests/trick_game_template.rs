@@ -0,0 +1,202 @@
+use itertools::iterate;
+use lib_table_top::common::deck::points::Scheme;
+use lib_table_top::common::deck::{Card, Rank::*, Suit::*};
+use lib_table_top::common::game::Play;
+use lib_table_top::common::rand::RngSeed;
+use lib_table_top::common::replay::Replayable;
+use lib_table_top::games::spades;
+use lib_table_top::games::trick_game_template::{
+    Action, ActionError, GameState, Player::*, Status, HEARTS, SPADES,
+};
+use std::sync::Arc;
+
+fn play_out(game: GameState) -> Vec<GameState> {
+    // `iterate` makes the next state before `take_while` sees the last one is over
+    iterate(game, |game| {
+        if Play::is_over(game) {
+            return game.clone();
+        }
+        let action = game.current_player_view().valid_actions[0];
+        game.apply_action((game.whose_turn(), action)).unwrap()
+    })
+    .take_while(|game| game.status() == Status::InProgress)
+    .take(100_000)
+    .collect()
+}
+
+fn finish(states: &[GameState]) -> GameState {
+    let last = states.last().unwrap();
+    let action = last.current_player_view().valid_actions[0];
+    last.apply_action((last.whose_turn(), action)).unwrap()
+}
+
+#[test]
+fn test_playing_hearts_to_the_target() {
+    let states = play_out(GameState::new(HEARTS, RngSeed([1; 32])));
+    let game = finish(&states);
+
+    let (winners, scores) = match game.status() {
+        Status::Win { players, scores } => (players, scores),
+        status => panic!("Expected a win, got {:?}", status),
+    };
+    assert_eq!(winners.len(), 1);
+    assert!(scores.values().any(|&score| score >= 100));
+    assert_eq!(scores.values().min(), Some(&scores[&winners[0]]));
+
+    for round in &game.observer_view().rounds {
+        let points: i32 = round.points.values().sum();
+        assert!(points == 26 || points == 78, "{:?}", round.points);
+        assert_eq!(round.tricks.values().sum::<u8>(), 13);
+    }
+
+    for game in &states {
+        let view = game.current_player_view();
+        let first_trick = view
+            .observer_view
+            .tricks
+            .values()
+            .all(|&tricks| tricks == 0);
+
+        if first_trick && view.observer_view.trick.is_empty() {
+            assert_eq!(view.valid_actions, vec![Action::Play(Card(Two, Clubs))]);
+        } else if first_trick {
+            let points = |action: &Action| match action {
+                Action::Play(card) => Scheme::Hearts.value(*card) > 0,
+                Action::Bid(_) => false,
+            };
+            let safe = view.hand.iter().any(|&card| {
+                Scheme::Hearts.value(card) == 0 && view.valid_actions.contains(&Action::Play(card))
+            });
+            assert!(!safe || !view.valid_actions.iter().any(points));
+        }
+    }
+
+    let replayed = game
+        .actions()
+        .into_iter()
+        .try_fold(game.initial(), |game, action| game.apply_action(action))
+        .unwrap();
+    assert_eq!(replayed.omniscient_view(), game.omniscient_view());
+}
+
+#[test]
+fn test_hearts_explains_why_a_card_cant_be_played() {
+    let states = play_out(GameState::new(HEARTS, RngSeed([2; 32])));
+    let mut seen = Vec::new();
+
+    for game in states.iter().take(200) {
+        let player = game.whose_turn();
+        let view = game.player_view(player);
+
+        for &card in &view.hand {
+            match game.apply_action((player, Action::Play(card))) {
+                Ok(_) => assert!(view.valid_actions.contains(&Action::Play(card))),
+                Err(error) => {
+                    assert!(!view.valid_actions.contains(&Action::Play(card)));
+                    seen.push(error.code());
+                }
+            }
+        }
+    }
+
+    for code in &["must_lead", "must_follow_suit", "not_broken"] {
+        assert!(seen.contains(code), "{}", code);
+    }
+
+    let game = &states[0];
+    assert_eq!(
+        game.apply_action((game.whose_turn().next(), Action::Play(Card(Ace, Spades))))
+            .err(),
+        Some(ActionError::NotPlayerTurn {
+            attempted_player: game.whose_turn().next(),
+            correct_player: game.whose_turn(),
+        })
+    );
+}
+
+/// Looks at a face down hand, bids the aces, kings, and long spades, and plays the first card it
+/// can
+fn spades_action(view: &spades::PlayerView) -> spades::Action {
+    match view.observer_view.phase {
+        spades::Phase::Bidding if view.hand.is_empty() => spades::Action::LookAtHand,
+        spades::Phase::Bidding => {
+            let count = |matches: &dyn Fn(&Card) -> bool| {
+                view.hand.iter().filter(|card| matches(card)).count() as u8
+            };
+            let high = count(&|card| card.rank() == Ace || card.rank() == King);
+            let long = count(&|card| card.suit() == Spades).saturating_sub(3);
+            match high + long {
+                0 => spades::Action::Bid(spades::Bid::Nil),
+                tricks => spades::Action::Bid(spades::Bid::Tricks(tricks)),
+            }
+        }
+        spades::Phase::Playing => view.valid_actions()[0],
+    }
+}
+
+#[test]
+fn test_the_spades_config_plays_like_spades() {
+    let seed = RngSeed([3; 32]);
+    let mut game = spades::GameState::new(Arc::new(spades::Settings {
+        seed,
+        rules: Default::default(),
+    }));
+    let mut template = GameState::new(SPADES, seed);
+
+    while game.status() == spades::Status::InProgress {
+        let view = game.current_player_view();
+        let action = spades_action(&view);
+        let mirrored = match action {
+            spades::Action::Bid(bid) => Action::Bid(bid.tricks()),
+            spades::Action::Play(card) => Action::Play(card),
+            // The template never deals face down, so there's nothing to mirror
+            spades::Action::LookAtHand => {
+                game = game.apply_action((game.whose_turn(), action)).unwrap();
+                continue;
+            }
+        };
+
+        let expected: Vec<Action> = match view.observer_view.phase {
+            spades::Phase::Bidding => (0..=13).map(Action::Bid).collect(),
+            spades::Phase::Playing => view
+                .valid_actions()
+                .into_iter()
+                .map(|action| match action {
+                    spades::Action::Play(card) => Action::Play(card),
+                    spades::Action::Bid(_) | spades::Action::LookAtHand => unreachable!(),
+                })
+                .collect(),
+        };
+        assert_eq!(template.current_player_view().valid_actions, expected);
+
+        game = game.apply_action((game.whose_turn(), action)).unwrap();
+        template = template
+            .apply_action((template.whose_turn(), mirrored))
+            .unwrap();
+    }
+
+    for &(player, partnership) in &[
+        (P1, spades::Partnership::NorthSouth),
+        (P2, spades::Partnership::EastWest),
+    ] {
+        assert_eq!(template.score(player), game.score(partnership));
+        assert_eq!(template.score(player.partner()), game.score(partnership));
+    }
+    assert_eq!(template.rounds_played(), game.rounds_played());
+
+    let winners = match (template.status(), game.status()) {
+        (Status::Win { players, .. }, spades::Status::Win { partnership, .. }) => {
+            let seats = partnership.players().map(|player| player as u8);
+            assert_eq!(
+                players
+                    .iter()
+                    .map(|&player| player as u8)
+                    .collect::<Vec<_>>(),
+                seats
+            );
+            players
+        }
+        statuses => panic!("Expected both games to be won, got {:?}", statuses),
+    };
+    assert_eq!(winners.len(), 2);
+}